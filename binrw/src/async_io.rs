@@ -0,0 +1,512 @@
+//! Asynchronous reading and writing using [Tokio](https://tokio.rs) streams.
+//!
+//! The traits in this module are implemented for every [`BinRead`] and
+//! [`BinWrite`] type, including types using the derive macros, so the same
+//! directives are used to read and write data from asynchronous streams.
+//!
+//! # Limitations
+//!
+//! Parsing is performed synchronously on an in-memory buffer. By default, the
+//! rest of the asynchronous stream is read into the buffer and then parsed
+//! once, so streams which do not end, like sockets, will wait forever.
+//!
+//! Primitive types and structs using the
+//! [`async`](crate::docs::attribute#async) directive avoid this. Primitive
+//! types only read their own size, and `async` structs are read one field at
+//! a time, so only the field being read is parsed from a buffer.
+//!
+//! [`read_options_incremental_async`](AsyncBinRead::read_options_incremental_async)
+//! can be used to opt in to filling the buffer from the stream on demand
+//! instead. If the parser tries to read past the end of the buffer before the
+//! end of the stream was reached, more data is read from the stream and
+//! parsing is restarted from the beginning, so parsers with side effects will
+//! observe these retries.
+//!
+//! Writing is performed synchronously into an in-memory buffer which is then
+//! written to the asynchronous stream all at once. Any bytes skipped over by
+//! the writer (for example, by [`seek_before`](crate::docs::attribute#padding-and-alignment))
+//! are written as zeroes.
+//!
+//! In both cases, seeking to a position before the position of the stream at
+//! the start of the operation is an error.
+
+use crate::{
+    __private::Required,
+    io::{Cursor, Error, ErrorKind, Read, Seek, SeekFrom, Write},
+    meta::{ReadEndian, WriteEndian},
+    BinRead, BinResult, BinWrite, Endian,
+};
+use alloc::vec::Vec;
+use core::future::Future;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt};
+
+/// The minimum number of bytes to read from the stream when the read buffer
+/// needs to grow.
+const MIN_READ_SIZE: usize = 64;
+
+/// Extension methods for reading [`BinRead`] objects from asynchronous
+/// streams.
+///
+/// This trait is implemented for every type that implements [`BinRead`].
+///
+/// # Examples
+///
+/// ```
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// use binrw::{binread, AsyncBinRead};
+///
+/// #[binread]
+/// #[br(big, magic = b"PNT")]
+/// # #[derive(Debug, PartialEq)]
+/// struct Point {
+///     x: u16,
+///     y: u16,
+/// }
+///
+/// let mut stream = std::io::Cursor::new(b"PNT\0\x01\0\x02");
+/// let point = Point::read_async(&mut stream).await.unwrap();
+/// assert_eq!(point, Point { x: 1, y: 2 });
+/// # });
+/// ```
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "tokio")))]
+pub trait AsyncBinRead: BinRead {
+    /// Reads `Self` from the asynchronous reader using default arguments.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    fn read_async<'a, R>(reader: &'a mut R) -> impl Future<Output = BinResult<Self>> + Send + 'a
+    where
+        R: AsyncRead + AsyncSeek + Unpin + Send + ?Sized,
+        Self: ReadEndian + Send + 'a,
+        Self::Args<'a>: Required + Clone + Send,
+    {
+        Self::read_args_async(reader, Self::Args::args())
+    }
+
+    /// Reads `Self` from the asynchronous reader using the given arguments.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    fn read_args_async<'a, R>(
+        reader: &'a mut R,
+        args: Self::Args<'a>,
+    ) -> impl Future<Output = BinResult<Self>> + Send + 'a
+    where
+        R: AsyncRead + AsyncSeek + Unpin + Send + ?Sized,
+        Self: ReadEndian + Send + 'a,
+        Self::Args<'a>: Clone + Send,
+    {
+        Self::read_options_async(reader, Endian::Little, args)
+    }
+
+    /// Reads `Self` from the asynchronous reader using default arguments and
+    /// assuming big-endian byte order.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    fn read_be_async<'a, R>(reader: &'a mut R) -> impl Future<Output = BinResult<Self>> + Send + 'a
+    where
+        R: AsyncRead + AsyncSeek + Unpin + Send + ?Sized,
+        Self: Send + 'a,
+        Self::Args<'a>: Required + Clone + Send,
+    {
+        Self::read_options_async(reader, Endian::Big, Self::Args::args())
+    }
+
+    /// Reads `Self` from the asynchronous reader using default arguments and
+    /// assuming little-endian byte order.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    fn read_le_async<'a, R>(reader: &'a mut R) -> impl Future<Output = BinResult<Self>> + Send + 'a
+    where
+        R: AsyncRead + AsyncSeek + Unpin + Send + ?Sized,
+        Self: Send + 'a,
+        Self::Args<'a>: Required + Clone + Send,
+    {
+        Self::read_options_async(reader, Endian::Little, Self::Args::args())
+    }
+
+    /// Reads `Self` from the asynchronous reader using the given [`Endian`]
+    /// and arguments.
+    ///
+    /// The endianness is only used if `Self` does not specify its own
+    /// endianness.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    fn read_options_async<'a, R>(
+        reader: &'a mut R,
+        endian: Endian,
+        args: Self::Args<'a>,
+    ) -> impl Future<Output = BinResult<Self>> + Send + 'a
+    where
+        R: AsyncRead + AsyncSeek + Unpin + Send + ?Sized,
+        Self: Send + 'a,
+        Self::Args<'a>: Clone + Send,
+    {
+        Self::__read_options_async(reader, endian, args)
+    }
+
+    /// Reads `Self` from the asynchronous reader using the given [`Endian`]
+    /// and arguments, filling the buffer from the stream on demand.
+    ///
+    /// Unlike [`read_options_async`](Self::read_options_async), this does not
+    /// wait for the end of the stream. Instead, whenever the parser tries to
+    /// read past the end of the data read so far, more data is read from the
+    /// stream and parsing is restarted from the beginning. Parsers with side
+    /// effects will observe these retries.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    fn read_options_incremental_async<'a, R>(
+        reader: &'a mut R,
+        endian: Endian,
+        args: Self::Args<'a>,
+    ) -> impl Future<Output = BinResult<Self>> + Send + 'a
+    where
+        R: AsyncRead + AsyncSeek + Unpin + Send + ?Sized,
+        Self: Send + 'a,
+        Self::Args<'a>: Clone + Send,
+    {
+        read_incremental(reader, endian, args)
+    }
+}
+
+impl<T: BinRead> AsyncBinRead for T {}
+
+/// Extension methods for writing [`BinWrite`] objects to asynchronous
+/// streams.
+///
+/// This trait is implemented for every type that implements [`BinWrite`].
+///
+/// # Examples
+///
+/// ```
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// use binrw::{binwrite, AsyncBinWrite};
+///
+/// #[binwrite]
+/// #[bw(big, magic = b"PNT")]
+/// struct Point {
+///     x: u16,
+///     y: u16,
+/// }
+///
+/// let mut stream = std::io::Cursor::new(Vec::new());
+/// Point { x: 1, y: 2 }.write_async(&mut stream).await.unwrap();
+/// assert_eq!(stream.into_inner(), b"PNT\0\x01\0\x02");
+/// # });
+/// ```
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "tokio")))]
+pub trait AsyncBinWrite: BinWrite {
+    /// Writes `self` to the asynchronous writer using default arguments.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    fn write_async<'a, W>(
+        &'a self,
+        writer: &'a mut W,
+    ) -> impl Future<Output = BinResult<()>> + Send + 'a
+    where
+        W: AsyncWrite + AsyncSeek + Unpin + Send + ?Sized,
+        Self: WriteEndian + Sync,
+        Self::Args<'a>: Required + Send,
+    {
+        self.write_args_async(writer, Self::Args::args())
+    }
+
+    /// Writes `self` to the asynchronous writer using the given arguments.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    fn write_args_async<'a, W>(
+        &'a self,
+        writer: &'a mut W,
+        args: Self::Args<'a>,
+    ) -> impl Future<Output = BinResult<()>> + Send + 'a
+    where
+        W: AsyncWrite + AsyncSeek + Unpin + Send + ?Sized,
+        Self: WriteEndian + Sync,
+        Self::Args<'a>: Send,
+    {
+        self.write_options_async(writer, Endian::Little, args)
+    }
+
+    /// Writes `self` to the asynchronous writer using default arguments and
+    /// assuming big-endian byte order.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    fn write_be_async<'a, W>(
+        &'a self,
+        writer: &'a mut W,
+    ) -> impl Future<Output = BinResult<()>> + Send + 'a
+    where
+        W: AsyncWrite + AsyncSeek + Unpin + Send + ?Sized,
+        Self: Sync,
+        Self::Args<'a>: Required + Send,
+    {
+        self.write_options_async(writer, Endian::Big, Self::Args::args())
+    }
+
+    /// Writes `self` to the asynchronous writer using default arguments and
+    /// assuming little-endian byte order.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    fn write_le_async<'a, W>(
+        &'a self,
+        writer: &'a mut W,
+    ) -> impl Future<Output = BinResult<()>> + Send + 'a
+    where
+        W: AsyncWrite + AsyncSeek + Unpin + Send + ?Sized,
+        Self: Sync,
+        Self::Args<'a>: Required + Send,
+    {
+        self.write_options_async(writer, Endian::Little, Self::Args::args())
+    }
+
+    /// Writes `self` to the asynchronous writer using the given [`Endian`] and
+    /// arguments.
+    ///
+    /// The endianness is only used if `Self` does not specify its own
+    /// endianness.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    fn write_options_async<'a, W>(
+        &'a self,
+        writer: &'a mut W,
+        endian: Endian,
+        args: Self::Args<'a>,
+    ) -> impl Future<Output = BinResult<()>> + Send + 'a
+    where
+        W: AsyncWrite + AsyncSeek + Unpin + Send + ?Sized,
+        Self: Sync,
+        Self::Args<'a>: Send,
+    {
+        async move {
+            let start = writer.stream_position().await?;
+            let mut buffer = Window::new(start);
+            self.write_options(&mut buffer, endian, args)?;
+            let end = buffer.stream_position()?;
+            writer.write_all(buffer.inner.get_ref()).await?;
+            writer.seek(SeekFrom::Start(end)).await?;
+            Ok(())
+        }
+    }
+}
+
+impl<T: BinWrite + ?Sized> AsyncBinWrite for T {}
+
+/// Reads `T` by parsing an in-memory copy of the rest of the asynchronous
+/// reader.
+pub(crate) fn read_buffered<'a, T, R>(
+    reader: &'a mut R,
+    endian: Endian,
+    args: T::Args<'a>,
+) -> impl Future<Output = BinResult<T>> + Send + 'a
+where
+    R: AsyncRead + AsyncSeek + Unpin + Send + ?Sized,
+    T: BinRead + Send + 'a,
+    T::Args<'a>: Clone + Send,
+{
+    read_sized(reader, endian, args, usize::MAX)
+}
+
+/// Reads `T` by parsing an in-memory copy of the next `len` bytes of the
+/// asynchronous reader.
+// An `async fn` cannot be used here because its future would only be `Send`
+// if the arguments were `Send` for every lifetime
+#[allow(clippy::manual_async_fn)]
+pub(crate) fn read_sized<'a, T, R>(
+    reader: &'a mut R,
+    endian: Endian,
+    args: T::Args<'a>,
+    len: usize,
+) -> impl Future<Output = BinResult<T>> + Send + 'a
+where
+    R: AsyncRead + AsyncSeek + Unpin + Send + ?Sized,
+    T: BinRead + Send + 'a,
+    T::Args<'a>: Clone + Send,
+{
+    async move {
+        let start = reader.stream_position().await?;
+        let mut buffer = Window::new(start);
+        buffer.fill_from(reader, len).await?;
+        let mut value = T::read_options(&mut buffer, endian, args.clone())?;
+        value.after_parse(&mut buffer, endian, args)?;
+        let end = buffer.stream_position()?;
+        reader.seek(SeekFrom::Start(end)).await?;
+        Ok(value)
+    }
+}
+
+/// Reads `T` by parsing an in-memory copy of the asynchronous reader which is
+/// filled on demand.
+#[allow(clippy::manual_async_fn)]
+fn read_incremental<'a, T, R>(
+    reader: &'a mut R,
+    endian: Endian,
+    args: T::Args<'a>,
+) -> impl Future<Output = BinResult<T>> + Send + 'a
+where
+    R: AsyncRead + AsyncSeek + Unpin + Send + ?Sized,
+    T: BinRead + Send + 'a,
+    T::Args<'a>: Clone + Send,
+{
+    async move {
+        let start = reader.stream_position().await?;
+        let mut buffer = Window::new(start);
+        loop {
+            buffer.rewind();
+            let result =
+                T::read_options(&mut buffer, endian, args.clone()).and_then(|mut value| {
+                    value.after_parse(&mut buffer, endian, args.clone())?;
+                    Ok(value)
+                });
+
+            // The result is only trustworthy if the parser never looked past the
+            // end of the buffer, or if the buffer already holds everything up to
+            // the end of the stream
+            if buffer.hit_end && !buffer.complete {
+                let len = buffer.len().max(MIN_READ_SIZE);
+                if buffer.fill_from(reader, len).await? != 0 {
+                    continue;
+                }
+            }
+
+            let value = result?;
+            let end = buffer.stream_position()?;
+            reader.seek(SeekFrom::Start(end)).await?;
+            return Ok(value);
+        }
+    }
+}
+
+/// Calls [`BinRead::after_parse`] on a value read by a struct using the
+/// `async` directive.
+///
+/// Derived types do not read anything in `after_parse`, so no data is
+/// buffered for it. If it tries to read anyway, an error is returned.
+#[allow(clippy::manual_async_fn)]
+pub(crate) fn after_parse_async<'a, T, R>(
+    value: &'a mut T,
+    reader: &'a mut R,
+    endian: Endian,
+    args: T::Args<'a>,
+) -> impl Future<Output = BinResult<()>> + Send + 'a
+where
+    R: AsyncRead + AsyncSeek + Unpin + Send + ?Sized,
+    T: BinRead + Send,
+    T::Args<'a>: Send,
+{
+    async move {
+        let start = reader.stream_position().await?;
+        let mut buffer = Window::new(start);
+        value.after_parse(&mut buffer, endian, args)?;
+        if buffer.hit_end || buffer.stream_position()? != start {
+            return Err(crate::Error::AssertFail {
+                pos: start,
+                message: "`after_parse` cannot read from an asynchronous stream".into(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// An in-memory stream whose positions are offset by the position of the
+/// asynchronous stream it mirrors.
+struct Window {
+    inner: Cursor<Vec<u8>>,
+    base: u64,
+    /// Whether the current parse attempt tried to read past the end of the
+    /// buffer or seeked relative to it.
+    hit_end: bool,
+    /// Whether the buffer holds everything up to the end of the stream.
+    complete: bool,
+}
+
+impl Window {
+    fn new(base: u64) -> Self {
+        Self {
+            inner: Cursor::new(Vec::new()),
+            base,
+            hit_end: false,
+            complete: false,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.inner.get_ref().len()
+    }
+
+    fn rewind(&mut self) {
+        self.inner.set_position(0);
+        self.hit_end = false;
+    }
+
+    async fn fill_from<R>(&mut self, reader: &mut R, len: usize) -> BinResult<usize>
+    where
+        R: AsyncRead + Unpin + ?Sized,
+    {
+        let buffer = self.inner.get_mut();
+        let n = (&mut *reader)
+            .take(len.try_into().unwrap_or(u64::MAX))
+            .read_to_end(buffer)
+            .await?;
+        self.complete = n < len;
+        Ok(n)
+    }
+}
+
+impl Read for Window {
+    fn read(&mut self, buf: &mut [u8]) -> crate::io::Result<usize> {
+        let n = Read::read(&mut self.inner, buf)?;
+        self.hit_end |= n < buf.len();
+        Ok(n)
+    }
+}
+
+impl Write for Window {
+    fn write(&mut self, buf: &[u8]) -> crate::io::Result<usize> {
+        Write::write(&mut self.inner, buf)
+    }
+
+    fn flush(&mut self) -> crate::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for Window {
+    fn seek(&mut self, pos: SeekFrom) -> crate::io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(pos) => {
+                SeekFrom::Start(pos.checked_sub(self.base).ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        "seek before the start of an asynchronous read or write",
+                    )
+                })?)
+            }
+            SeekFrom::End(pos) => {
+                self.hit_end = true;
+                SeekFrom::End(pos)
+            }
+            pos @ SeekFrom::Current(_) => pos,
+        };
+        Ok(Seek::seek(&mut self.inner, pos)? + self.base)
+    }
+}
@@ -1,8 +1,14 @@
 use crate::{
-    io::{self, Read, Seek, SeekFrom},
+    io::{Read, Seek, SeekFrom},
     BinRead, BinResult, Endian, Error, NamedArgs,
 };
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    rc::Rc,
+    vec::Vec,
+};
 use core::num::{
     NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
     NonZeroU32, NonZeroU64, NonZeroU8,
@@ -31,6 +37,20 @@ macro_rules! binread_impl {
                         }
                     })
                 }
+
+                #[cfg(feature = "tokio")]
+                fn __read_options_async<'a, R>(
+                    reader: &'a mut R,
+                    endian: Endian,
+                    args: Self::Args<'a>,
+                ) -> impl core::future::Future<Output = BinResult<Self>> + Send + 'a
+                where
+                    R: tokio::io::AsyncRead + tokio::io::AsyncSeek + Unpin + Send + ?Sized,
+                    Self: Send + 'a,
+                    Self::Args<'a>: Clone + Send,
+                {
+                    crate::async_io::read_sized(reader, endian, args, core::mem::size_of::<$type_name>())
+                }
             }
         )*
     }
@@ -38,11 +58,15 @@ macro_rules! binread_impl {
 
 binread_impl!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
 
-fn unexpected_zero_num() -> Error {
-    Error::Io(io::Error::new(
-        io::ErrorKind::InvalidData,
-        "unexpected zero found",
-    ))
+#[cfg(feature = "half")]
+binread_impl!(half::f16);
+
+fn unexpected_zero_num<R: Seek, T>(reader: &mut R, pos: u64, type_name: &str) -> BinResult<T> {
+    reader.seek(SeekFrom::Start(pos))?;
+    Err(Error::AssertFail {
+        pos,
+        message: alloc::format!("expected a non-zero {type_name}, found zero").into(),
+    })
 }
 
 macro_rules! binread_nonzero_impl {
@@ -56,9 +80,10 @@ macro_rules! binread_nonzero_impl {
                     endian: Endian,
                     _: Self::Args<'_>,
                 ) -> BinResult<Self> {
+                    let pos = reader.stream_position()?;
                     match <$Ty>::new(<$Int>::read_options(reader, endian, ())?) {
                         Some(x) => Ok(x),
-                        None => Err(unexpected_zero_num()),
+                        None => unexpected_zero_num(reader, pos, stringify!($Int)),
                     }
                 }
             }
@@ -165,6 +190,207 @@ where
     }
 }
 
+/// Reads `count` key-value pairs into a map.
+///
+/// Each key is immediately followed by its value. If a key occurs more than
+/// once, the last value is kept. The `inner` arguments are a tuple of the
+/// arguments for the key and value types.
+fn read_map<'a, R, K, V, M>(
+    reader: &mut R,
+    endian: Endian,
+    args: VecArgs<(K::Args<'a>, V::Args<'a>)>,
+) -> BinResult<M>
+where
+    R: Read + Seek,
+    K: BinRead,
+    V: BinRead,
+    K::Args<'a>: Clone,
+    V::Args<'a>: Clone,
+    M: FromIterator<(K, V)> + 'static,
+{
+    crate::helpers::count_with(
+        args.count,
+        |reader, endian, (key_args, value_args): (K::Args<'a>, V::Args<'a>)| {
+            // Keys cannot be changed once they are in the map, so they are
+            // finalised immediately instead of in `after_parse`
+            let mut key = K::read_options(reader, endian, key_args.clone())?;
+            key.after_parse(reader, endian, key_args)?;
+            Ok((key, V::read_options(reader, endian, value_args)?))
+        },
+    )(reader, endian, args.inner)
+}
+
+impl<K, V> BinRead for BTreeMap<K, V>
+where
+    K: BinRead + Ord + 'static,
+    V: BinRead + 'static,
+    for<'a> K::Args<'a>: Clone,
+    for<'a> V::Args<'a>: Clone,
+{
+    type Args<'a> = VecArgs<(K::Args<'a>, V::Args<'a>)>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        read_map(reader, endian, args)
+    }
+
+    fn after_parse<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        for val in self.values_mut() {
+            val.after_parse(reader, endian, args.inner.1.clone())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> BinRead for std::collections::HashMap<K, V, S>
+where
+    K: BinRead + Eq + core::hash::Hash + 'static,
+    V: BinRead + 'static,
+    S: core::hash::BuildHasher + Default + 'static,
+    for<'a> K::Args<'a>: Clone,
+    for<'a> V::Args<'a>: Clone,
+{
+    type Args<'a> = VecArgs<(K::Args<'a>, V::Args<'a>)>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        read_map(reader, endian, args)
+    }
+
+    fn after_parse<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        for val in self.values_mut() {
+            val.after_parse(reader, endian, args.inner.1.clone())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads `count` values into a set, or another collection which does not allow
+/// its values to be changed, such as a heap.
+///
+/// Values cannot be changed once they are in the collection, so each value is
+/// finalised immediately instead of in `after_parse`.
+fn read_set<'a, R, T, C>(reader: &mut R, endian: Endian, args: VecArgs<T::Args<'a>>) -> BinResult<C>
+where
+    R: Read + Seek,
+    T: BinRead,
+    T::Args<'a>: Clone,
+    C: FromIterator<T> + 'static,
+{
+    crate::helpers::count_with(args.count, |reader, endian, args: T::Args<'a>| {
+        let mut value = T::read_options(reader, endian, args.clone())?;
+        value.after_parse(reader, endian, args)?;
+        Ok(value)
+    })(reader, endian, args.inner)
+}
+
+impl<T> BinRead for BTreeSet<T>
+where
+    T: BinRead + Ord + 'static,
+    for<'a> T::Args<'a>: Clone,
+{
+    type Args<'a> = VecArgs<T::Args<'a>>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        read_set(reader, endian, args)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> BinRead for std::collections::HashSet<T, S>
+where
+    T: BinRead + Eq + core::hash::Hash + 'static,
+    S: core::hash::BuildHasher + Default + 'static,
+    for<'a> T::Args<'a>: Clone,
+{
+    type Args<'a> = VecArgs<T::Args<'a>>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        read_set(reader, endian, args)
+    }
+}
+
+impl<T> BinRead for BinaryHeap<T>
+where
+    T: BinRead + Ord + 'static,
+    for<'a> T::Args<'a>: Clone,
+{
+    type Args<'a> = VecArgs<T::Args<'a>>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        read_set(reader, endian, args)
+    }
+}
+
+macro_rules! binread_sequence_impl {
+    ($($Ty:ident)+) => {$(
+        impl<T> BinRead for $Ty<T>
+        where
+            T: BinRead + 'static,
+            for<'a> T::Args<'a>: Clone,
+        {
+            type Args<'a> = VecArgs<T::Args<'a>>;
+
+            fn read_options<R: Read + Seek>(
+                reader: &mut R,
+                endian: Endian,
+                args: Self::Args<'_>,
+            ) -> BinResult<Self> {
+                crate::helpers::count_with(args.count, T::read_options)(reader, endian, args.inner)
+            }
+
+            fn after_parse<R>(
+                &mut self,
+                reader: &mut R,
+                endian: Endian,
+                args: Self::Args<'_>,
+            ) -> BinResult<()>
+            where
+                R: Read + Seek,
+            {
+                for val in self.iter_mut() {
+                    val.after_parse(reader, endian, args.inner.clone())?;
+                }
+
+                Ok(())
+            }
+        }
+    )+}
+}
+
+binread_sequence_impl!(VecDeque LinkedList);
+
 impl<B, const N: usize> BinRead for [B; N]
 where
     B: BinRead,
@@ -257,6 +483,94 @@ impl<T: BinRead> BinRead for Box<T> {
     }
 }
 
+impl<B> BinRead for Box<[B]>
+where
+    B: BinRead + 'static,
+    for<'a> B::Args<'a>: Clone,
+{
+    type Args<'a> = VecArgs<B::Args<'a>>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Vec::<B>::read_options(reader, endian, args).map(Vec::into_boxed_slice)
+    }
+
+    fn after_parse<R>(
+        &mut self,
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()>
+    where
+        R: Read + Seek,
+    {
+        for val in self.iter_mut() {
+            val.after_parse(reader, endian, args.inner.clone())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: BinRead> BinRead for Rc<T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(Rc::new(T::read_options(reader, endian, args)?))
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T: BinRead> BinRead for alloc::sync::Arc<T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(alloc::sync::Arc::new(T::read_options(
+            reader, endian, args,
+        )?))
+    }
+}
+
+/// Always reads an owned value, since there is nothing to borrow from.
+impl<T> BinRead for Cow<'static, T>
+where
+    T: ToOwned + ?Sized,
+    T::Owned: BinRead,
+{
+    type Args<'a> = <T::Owned as BinRead>::Args<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(Cow::Owned(T::Owned::read_options(reader, endian, args)?))
+    }
+
+    fn after_parse<R>(
+        &mut self,
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()>
+    where
+        R: Read + Seek,
+    {
+        self.to_mut().after_parse(reader, endian, args)
+    }
+}
+
 impl<T: BinRead> BinRead for Option<T> {
     type Args<'a> = T::Args<'a>;
 
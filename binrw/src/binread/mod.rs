@@ -1,12 +1,14 @@
 mod impls;
+mod read_iter;
 
 use crate::{
-    io::{Read, Seek},
+    io::{Read, Seek, SeekFrom},
     BinResult, Endian,
     __private::Required,
     meta::ReadEndian,
 };
 pub use impls::VecArgs;
+pub use read_iter::ReadIter;
 
 /// The `BinRead` trait reads data from streams and converts it into objects.
 ///
@@ -149,6 +151,30 @@ pub trait BinRead: Sized {
         Self::read_options(reader, Endian::NATIVE, args)
     }
 
+    /// Read `Self` from the file at the given path using default arguments.
+    ///
+    /// The file is read through a [`BufReader`](crate::io::BufReader), which
+    /// keeps its buffer when seeking.
+    ///
+    /// # Errors
+    ///
+    /// If opening the file or reading fails, an [`Error`](crate::Error)
+    /// variant will be returned with the path of the file added to its
+    /// backtrace.
+    #[cfg(feature = "std")]
+    #[cfg_attr(all(doc, nightly), doc(cfg(feature = "std")))]
+    fn read_from_path<P: AsRef<std::path::Path>>(path: P) -> BinResult<Self>
+    where
+        Self: ReadEndian,
+        for<'a> Self::Args<'a>: Required,
+    {
+        let path = path.as_ref();
+        let result = std::fs::File::open(path)
+            .map_err(Into::into)
+            .and_then(|file| Self::read(&mut crate::io::BufReader::new(file)));
+        crate::error::with_path(result, "reading", path)
+    }
+
     /// Read `Self` from the reader using the given [`Endian`] and
     /// arguments.
     ///
@@ -176,6 +202,27 @@ pub trait BinRead: Sized {
     ) -> BinResult<()> {
         Ok(())
     }
+
+    /// Reads `Self` from the asynchronous reader.
+    ///
+    /// This is the implementation of
+    /// [`AsyncBinRead::read_options_async`](crate::AsyncBinRead::read_options_async).
+    /// It is overridden by types using `#[br(async)]` and should not be
+    /// implemented or called directly.
+    #[cfg(feature = "tokio")]
+    #[doc(hidden)]
+    fn __read_options_async<'a, R>(
+        reader: &'a mut R,
+        endian: Endian,
+        args: Self::Args<'a>,
+    ) -> impl core::future::Future<Output = BinResult<Self>> + Send + 'a
+    where
+        R: tokio::io::AsyncRead + tokio::io::AsyncSeek + Unpin + Send + ?Sized,
+        Self: Send + 'a,
+        Self::Args<'a>: Clone + Send,
+    {
+        crate::async_io::read_buffered(reader, endian, args)
+    }
 }
 
 /// Extension methods for reading [`BinRead`] objects directly from a reader.
@@ -309,6 +356,141 @@ pub trait BinReaderExt: Read + Seek + Sized {
     {
         self.read_type_args(Endian::NATIVE, args)
     }
+
+    /// Returns an iterator which reads `T` from the reader until the end of
+    /// the stream, using the byte order of `T` and default arguments.
+    ///
+    /// See [`ReadIter`] for more information.
+    #[inline]
+    fn read_iter<'a, T>(&mut self) -> ReadIter<'_, Self, T, T::Args<'a>>
+    where
+        T: BinRead + ReadEndian,
+        T::Args<'a>: Required + Clone,
+    {
+        ReadIter::new(self, Endian::Little, T::Args::args())
+    }
+
+    /// Returns an iterator which reads `T` from the reader until the end of
+    /// the stream, using the given byte order and default arguments.
+    ///
+    /// See [`ReadIter`] for more information.
+    #[inline]
+    fn read_type_iter<'a, T>(&mut self, endian: Endian) -> ReadIter<'_, Self, T, T::Args<'a>>
+    where
+        T: BinRead,
+        T::Args<'a>: Required + Clone,
+    {
+        ReadIter::new(self, endian, T::Args::args())
+    }
+
+    /// Returns an iterator which reads `T` from the reader until the end of
+    /// the stream, using the given byte order and arguments. The same
+    /// arguments are used to read every object.
+    ///
+    /// See [`ReadIter`] for more information.
+    #[inline]
+    fn read_type_iter_args<'a, T>(
+        &mut self,
+        endian: Endian,
+        args: T::Args<'a>,
+    ) -> ReadIter<'_, Self, T, T::Args<'a>>
+    where
+        T: BinRead,
+        T::Args<'a>: Clone,
+    {
+        ReadIter::new(self, endian, args)
+    }
+
+    /// Read `T` from the given position in the reader with the given byte
+    /// order, then restore the original position of the reader.
+    ///
+    /// # Errors
+    ///
+    /// If seeking or reading fails, an [`Error`](crate::Error) variant will be
+    /// returned. The original position is restored even if reading fails.
+    #[inline]
+    fn read_type_at<'a, T>(&mut self, pos: SeekFrom, endian: Endian) -> BinResult<T>
+    where
+        T: BinRead,
+        T::Args<'a>: Required + Clone,
+    {
+        self.read_type_args_at(pos, endian, T::Args::args())
+    }
+
+    /// Read `T` from the given absolute offset in the reader, assuming
+    /// big-endian byte order, then restore the original position of the
+    /// reader.
+    ///
+    /// # Errors
+    ///
+    /// If seeking or reading fails, an [`Error`](crate::Error) variant will be
+    /// returned. The original position is restored even if reading fails.
+    #[inline]
+    fn read_be_at<'a, T>(&mut self, offset: u64) -> BinResult<T>
+    where
+        T: BinRead,
+        T::Args<'a>: Required + Clone,
+    {
+        self.read_type_at(SeekFrom::Start(offset), Endian::Big)
+    }
+
+    /// Read `T` from the given absolute offset in the reader, assuming
+    /// little-endian byte order, then restore the original position of the
+    /// reader.
+    ///
+    /// # Errors
+    ///
+    /// If seeking or reading fails, an [`Error`](crate::Error) variant will be
+    /// returned. The original position is restored even if reading fails.
+    #[inline]
+    fn read_le_at<'a, T>(&mut self, offset: u64) -> BinResult<T>
+    where
+        T: BinRead,
+        T::Args<'a>: Required + Clone,
+    {
+        self.read_type_at(SeekFrom::Start(offset), Endian::Little)
+    }
+
+    /// Read `T` from the given position in the reader with the given byte
+    /// order and arguments, then restore the original position of the reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binrw::{BinReaderExt, Endian, VecArgs, io::{Cursor, Seek, SeekFrom}};
+    ///
+    /// let mut reader = Cursor::new(b"\x04\0\0\0\x01\x02\x03");
+    /// let offset: u32 = reader.read_le().unwrap();
+    /// let args = VecArgs { count: 3, inner: () };
+    /// let data: Vec<u8> = reader
+    ///     .read_type_args_at(SeekFrom::Start(offset.into()), Endian::Little, args)
+    ///     .unwrap();
+    /// assert_eq!(data, [1, 2, 3]);
+    /// assert_eq!(reader.stream_position().unwrap(), 4);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If seeking or reading fails, an [`Error`](crate::Error) variant will be
+    /// returned. The original position is restored even if reading fails.
+    fn read_type_args_at<'a, T>(
+        &mut self,
+        pos: SeekFrom,
+        endian: Endian,
+        args: T::Args<'a>,
+    ) -> BinResult<T>
+    where
+        T: BinRead,
+        T::Args<'a>: Clone,
+    {
+        let restore_pos = self.stream_position()?;
+        let result = self
+            .seek(pos)
+            .map_err(Into::into)
+            .and_then(|_| self.read_type_args(endian, args));
+        self.seek(SeekFrom::Start(restore_pos))?;
+        result
+    }
 }
 
 impl<R: Read + Seek + Sized> BinReaderExt for R {}
@@ -0,0 +1,103 @@
+use super::{BinRead, BinReaderExt};
+use crate::{
+    __private::is_eof,
+    io::{Read, Seek},
+    BinResult, Endian,
+};
+use core::marker::PhantomData;
+
+/// An iterator which reads a sequence of objects from a reader until the end
+/// of the stream.
+///
+/// This struct is created by [`BinReaderExt::read_iter`] and its variants.
+///
+/// A new object is read each time the iterator is advanced, so records can be
+/// processed one at a time without loading the whole stream into memory. The
+/// iterator ends cleanly when the stream ends exactly where the next object
+/// would start. If the stream ends part way through an object instead, the
+/// iterator yields the error, which will return `true` from
+/// [`Error::is_eof`](crate::Error::is_eof). The iterator also ends after any
+/// error.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{binread, io::Cursor, BinReaderExt};
+///
+/// #[binread]
+/// #[derive(Debug)]
+/// #[br(little)]
+/// struct Record {
+///     #[br(temp)]
+///     len: u8,
+///     #[br(count = len)]
+///     data: Vec<u8>,
+/// }
+///
+/// let mut reader = Cursor::new(b"\x02ab\x01c");
+/// let records = reader
+///     .read_iter::<Record>()
+///     .map(|record| record.map(|record| record.data))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(records, [b"ab".to_vec(), b"c".to_vec()]);
+///
+/// let mut reader = Cursor::new(b"\x02ab\x02c");
+/// let mut records = reader.read_iter::<Record>();
+/// assert!(records.next().unwrap().is_ok());
+/// assert!(records.next().unwrap().unwrap_err().is_eof());
+/// assert!(records.next().is_none());
+/// ```
+pub struct ReadIter<'r, R, T, Args> {
+    reader: &'r mut R,
+    endian: Endian,
+    args: Args,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'r, R, T, Args> ReadIter<'r, R, T, Args> {
+    pub(super) fn new(reader: &'r mut R, endian: Endian, args: Args) -> Self {
+        Self {
+            reader,
+            endian,
+            args,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, R, T> Iterator for ReadIter<'_, R, T, T::Args<'a>>
+where
+    R: Read + Seek,
+    T: BinRead,
+    T::Args<'a>: Clone,
+{
+    type Item = BinResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = match is_eof(self.reader) {
+            Ok(true) => {
+                self.done = true;
+                return None;
+            }
+            Ok(false) => self.reader.read_type_args(self.endian, self.args.clone()),
+            Err(err) => Err(err),
+        };
+        self.done = result.is_err();
+        Some(result)
+    }
+}
+
+impl<'a, R, T> core::iter::FusedIterator for ReadIter<'_, R, T, T::Args<'a>>
+where
+    R: Read + Seek,
+    T: BinRead,
+    T::Args<'a>: Clone,
+{
+}
@@ -0,0 +1,111 @@
+use super::BinWrite;
+use crate::{
+    __private::Required,
+    io::{Seek, Write},
+    BinResult, Endian,
+};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// Write adapter which appends objects to the end of an underlying writer and
+/// records where each one was written.
+///
+/// This is useful for formats which grow by adding new records instead of
+/// rewriting the whole file, like journals and logs. Every object is written
+/// starting at the end of the stream, even if the writer was moved somewhere
+/// else in the meantime, and the byte range of each object is recorded in
+/// [`extents`](Self::extents) so that an index can be written later.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{binwrite, io::Cursor, AppendWriter, Endian};
+///
+/// #[binwrite]
+/// struct Entry {
+///     #[bw(calc = text.len() as u8)]
+///     len: u8,
+///     text: Vec<u8>,
+/// }
+///
+/// let mut log = AppendWriter::new(Cursor::new(b"LOG".to_vec()));
+/// log.append(&Entry { text: b"hi".to_vec() }, Endian::Little).unwrap();
+/// log.append(&Entry { text: b"bye".to_vec() }, Endian::Little).unwrap();
+/// assert_eq!(log.extents(), [3..6, 6..10]);
+/// assert_eq!(log.into_inner().into_inner(), b"LOG\x02hi\x03bye");
+/// ```
+#[derive(Debug)]
+pub struct AppendWriter<T> {
+    inner: T,
+    extents: Vec<Range<u64>>,
+}
+
+impl<T> AppendWriter<T> {
+    /// Creates a new `AppendWriter` which appends to `inner`.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            extents: Vec::new(),
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns the byte ranges of the objects which have been appended, in
+    /// order.
+    pub fn extents(&self) -> &[Range<u64>] {
+        &self.extents
+    }
+
+    /// Forgets all of the recorded extents.
+    pub fn clear(&mut self) {
+        self.extents.clear();
+    }
+}
+
+impl<T: Write + Seek> AppendWriter<T> {
+    /// Appends `value` with the given byte order, returning the range of bytes
+    /// that were written.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    /// Nothing is recorded for an object which fails to write.
+    pub fn append<B: BinWrite>(&mut self, value: &B, endian: Endian) -> BinResult<Range<u64>>
+    where
+        for<'a> B::Args<'a>: Required,
+    {
+        self.append_args(value, endian, B::Args::args())
+    }
+
+    /// Appends `value` with the given byte order and arguments, returning the
+    /// range of bytes that were written.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    /// Nothing is recorded for an object which fails to write.
+    pub fn append_args<B: BinWrite>(
+        &mut self,
+        value: &B,
+        endian: Endian,
+        args: B::Args<'_>,
+    ) -> BinResult<Range<u64>> {
+        let extent = value.append_options(&mut self.inner, endian, args)?;
+        self.extents.push(extent.clone());
+        Ok(extent)
+    }
+}
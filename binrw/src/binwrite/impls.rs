@@ -1,8 +1,15 @@
 use crate::{
+    __private::size_hint_add,
     io::{Seek, Write},
     BinResult, BinWrite, Endian,
 };
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    rc::Rc,
+    vec::Vec,
+};
 use core::{
     any::Any,
     marker::PhantomData,
@@ -29,6 +36,10 @@ macro_rules! binwrite_num_impl {
                         Endian::Little => self.to_le_bytes(),
                     }).map_err(Into::into)
                 }
+
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    (core::mem::size_of::<$type_name>(), Some(core::mem::size_of::<$type_name>()))
+                }
             }
         )*
     };
@@ -36,6 +47,9 @@ macro_rules! binwrite_num_impl {
 
 binwrite_num_impl!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
 
+#[cfg(feature = "half")]
+binwrite_num_impl!(half::f16);
+
 macro_rules! binwrite_nonzero_num_impl {
     ($($non_zero_type:ty => $type_name:ty),*$(,)?) => {
         $(
@@ -55,6 +69,10 @@ macro_rules! binwrite_nonzero_num_impl {
                         Endian::Little => num.to_le_bytes(),
                     }).map_err(Into::into)
                 }
+
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    (core::mem::size_of::<$type_name>(), Some(core::mem::size_of::<$type_name>()))
+                }
             }
         )*
     };
@@ -96,6 +114,14 @@ where
 
         Ok(())
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if <dyn Any>::is::<[u8; N]>(self) {
+            (N, Some(N))
+        } else {
+            self[..].size_hint()
+        }
+    }
 }
 
 impl<T> BinWrite for [T]
@@ -117,6 +143,234 @@ where
 
         Ok(())
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter().fold((0, Some(0)), |hint, item| {
+            size_hint_add(hint, item.size_hint())
+        })
+    }
+}
+
+/// Writes the key-value pairs of a map in iteration order, with each key
+/// immediately followed by its value.
+fn write_map<'a, 'b, K, V, W>(
+    entries: impl Iterator<Item = (&'b K, &'b V)>,
+    writer: &mut W,
+    endian: Endian,
+    (key_args, value_args): (K::Args<'a>, V::Args<'a>),
+) -> BinResult<()>
+where
+    K: BinWrite + 'b,
+    V: BinWrite + 'b,
+    K::Args<'a>: Clone,
+    V::Args<'a>: Clone,
+    W: Write + Seek,
+{
+    for (key, value) in entries {
+        key.write_options(writer, endian, key_args.clone())?;
+        value.write_options(writer, endian, value_args.clone())?;
+    }
+
+    Ok(())
+}
+
+fn map_size_hint<'b, K, V>(entries: impl Iterator<Item = (&'b K, &'b V)>) -> (usize, Option<usize>)
+where
+    K: BinWrite + 'b,
+    V: BinWrite + 'b,
+{
+    entries.fold((0, Some(0)), |hint, (key, value)| {
+        size_hint_add(size_hint_add(hint, key.size_hint()), value.size_hint())
+    })
+}
+
+impl<K, V> BinWrite for BTreeMap<K, V>
+where
+    K: BinWrite,
+    V: BinWrite,
+    for<'a> K::Args<'a>: Clone,
+    for<'a> V::Args<'a>: Clone,
+{
+    type Args<'a> = (K::Args<'a>, V::Args<'a>);
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        write_map(self.iter(), writer, endian, args)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        map_size_hint(self.iter())
+    }
+}
+
+/// Hash maps are written in ascending key order, like [`BTreeMap`], so that the
+/// output does not depend on the hasher.
+#[cfg(feature = "std")]
+impl<K, V, S> BinWrite for std::collections::HashMap<K, V, S>
+where
+    K: BinWrite + Ord,
+    V: BinWrite,
+    for<'a> K::Args<'a>: Clone,
+    for<'a> V::Args<'a>: Clone,
+{
+    type Args<'a> = (K::Args<'a>, V::Args<'a>);
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let mut entries = self.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(k, _)| *k);
+        write_map(entries.into_iter(), writer, endian, args)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        map_size_hint(self.iter())
+    }
+}
+
+impl<T> BinWrite for BTreeSet<T>
+where
+    T: BinWrite,
+    for<'a> T::Args<'a>: Clone,
+{
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        write_items(self.iter(), writer, endian, args)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        items_size_hint(self.iter())
+    }
+}
+
+/// Hash sets are written in ascending order, like [`BTreeSet`], so that the
+/// output does not depend on the hasher.
+#[cfg(feature = "std")]
+impl<T, S> BinWrite for std::collections::HashSet<T, S>
+where
+    T: BinWrite + Ord,
+    for<'a> T::Args<'a>: Clone,
+{
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let mut items = self.iter().collect::<Vec<_>>();
+        items.sort_unstable();
+        write_items(items, writer, endian, args)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        items_size_hint(self.iter())
+    }
+}
+
+macro_rules! binwrite_sequence_impl {
+    ($($Ty:ident)+) => {$(
+        impl<T> BinWrite for $Ty<T>
+        where
+            T: BinWrite,
+            for<'a> T::Args<'a>: Clone,
+        {
+            type Args<'a> = T::Args<'a>;
+
+            fn write_options<W: Write + Seek>(
+                &self,
+                writer: &mut W,
+                endian: Endian,
+                args: Self::Args<'_>,
+            ) -> BinResult<()> {
+                write_items(self.iter(), writer, endian, args)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                items_size_hint(self.iter())
+            }
+        }
+    )+}
+}
+
+binwrite_sequence_impl!(VecDeque LinkedList);
+
+/// Heaps are written in ascending order, which is the same order as
+/// [`BinaryHeap::into_sorted_vec`], so that the output does not depend on how
+/// the heap was built.
+impl<T> BinWrite for BinaryHeap<T>
+where
+    T: BinWrite + Ord,
+    for<'a> T::Args<'a>: Clone,
+{
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let mut items = self.iter().collect::<Vec<_>>();
+        items.sort_unstable();
+        write_items(items, writer, endian, args)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        items_size_hint(self.iter())
+    }
+}
+
+/// Writes the items of a collection in iteration order.
+fn write_items<'a, 'b, T, W>(
+    items: impl IntoIterator<Item = &'b T>,
+    writer: &mut W,
+    endian: Endian,
+    args: T::Args<'a>,
+) -> BinResult<()>
+where
+    T: BinWrite + 'b,
+    T::Args<'a>: Clone,
+    W: Write + Seek,
+{
+    for item in items {
+        item.write_options(writer, endian, args.clone())?;
+    }
+
+    Ok(())
+}
+
+fn items_size_hint<'b, T>(items: impl Iterator<Item = &'b T>) -> (usize, Option<usize>)
+where
+    T: BinWrite + 'b,
+{
+    items.fold((0, Some(0)), |hint, item| {
+        size_hint_add(hint, item.size_hint())
+    })
+}
+
+macro_rules! vec_fast_write {
+    (try ($($Ty:ty)+) using ($list:expr, $writer:expr, $endian:expr) else { $($else:tt)* }) => {
+        $(if let Some(list) = <dyn Any>::downcast_ref::<Vec<$Ty>>($list) {
+            crate::helpers::write_bulk($writer, list, $endian)
+        } else)* {
+            $($else)*
+        }
+    }
 }
 
 impl<T> BinWrite for Vec<T>
@@ -132,17 +386,21 @@ where
         endian: Endian,
         args: Self::Args<'_>,
     ) -> BinResult<()> {
-        if let Some(this) = <dyn Any>::downcast_ref::<Vec<u8>>(self) {
-            writer.write_all(this)?;
-        } else if let Some(this) = <dyn Any>::downcast_ref::<Vec<i8>>(self) {
-            writer.write_all(bytemuck::cast_slice(this.as_slice()))?;
-        } else {
+        vec_fast_write!(try (u8 i8 u16 i16 u32 i32 u64 i64 u128 i128 f32 f64) using (self, writer, endian) else {
             for item in self {
                 T::write_options(item, writer, endian, args.clone())?;
             }
-        }
 
-        Ok(())
+            Ok(())
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if <dyn Any>::is::<Vec<u8>>(self) || <dyn Any>::is::<Vec<i8>>(self) {
+            (self.len(), Some(self.len()))
+        } else {
+            self.as_slice().size_hint()
+        }
     }
 }
 
@@ -157,6 +415,10 @@ impl<T: BinWrite + ?Sized> BinWrite for &T {
     ) -> BinResult<()> {
         (**self).write_options(writer, endian, args)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
 }
 
 impl<T: BinWrite + ?Sized + 'static> BinWrite for Box<T> {
@@ -176,6 +438,62 @@ impl<T: BinWrite + ?Sized + 'static> BinWrite for Box<T> {
 
         Ok(())
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+}
+
+impl<T: BinWrite + ?Sized> BinWrite for Rc<T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        (**self).write_options(writer, endian, args)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T: BinWrite + ?Sized> BinWrite for alloc::sync::Arc<T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        (**self).write_options(writer, endian, args)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+}
+
+impl<T: BinWrite + ToOwned + ?Sized> BinWrite for Cow<'_, T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        (**self).write_options(writer, endian, args)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
 }
 
 impl<T: BinWrite> BinWrite for Option<T> {
@@ -192,6 +510,13 @@ impl<T: BinWrite> BinWrite for Option<T> {
             None => Ok(()),
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Some(inner) => inner.size_hint(),
+            None => (0, Some(0)),
+        }
+    }
 }
 
 impl<T: BinWrite> BinWrite for PhantomData<T> {
@@ -205,6 +530,10 @@ impl<T: BinWrite> BinWrite for PhantomData<T> {
     ) -> BinResult<()> {
         Ok(())
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
 }
 
 impl BinWrite for () {
@@ -218,6 +547,10 @@ impl BinWrite for () {
     ) -> BinResult<()> {
         Ok(())
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
 }
 
 macro_rules! binwrite_tuple_impl {
@@ -245,6 +578,18 @@ macro_rules! binwrite_tuple_impl {
 
                 Ok(())
             }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let ($type1, $(
+                    $types
+                ),*) = self;
+
+                let hint = $type1.size_hint();
+                $(
+                    let hint = size_hint_add(hint, $types.size_hint());
+                )*
+                hint
+            }
         }
 
         binwrite_tuple_impl!($($types),*);
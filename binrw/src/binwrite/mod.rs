@@ -1,10 +1,15 @@
+mod append;
 mod impls;
+mod size;
 
 use crate::{
-    io::{Seek, Write},
+    io::{Seek, SeekFrom, Write, WriteSeek},
     BinResult, Endian,
     __private::Required,
 };
+pub use append::AppendWriter;
+use core::ops::Range;
+pub use size::BinSize;
 
 /// The `BinWrite` trait serialises objects and writes them to streams.
 ///
@@ -129,6 +134,35 @@ pub trait BinWrite {
         self.write_options(writer, Endian::Little, args)
     }
 
+    /// Write `Self` to the file at the given path using default arguments.
+    ///
+    /// The file is created if it does not exist and truncated if it does. Data
+    /// is written through a [`BufWriter`](std::io::BufWriter) which is flushed
+    /// before returning.
+    ///
+    /// # Errors
+    ///
+    /// If creating the file or writing fails, an [`Error`](crate::Error)
+    /// variant will be returned with the path of the file added to its
+    /// backtrace.
+    #[cfg(feature = "std")]
+    #[cfg_attr(all(doc, nightly), doc(cfg(feature = "std")))]
+    fn write_to_path<P: AsRef<std::path::Path>>(&self, path: P) -> BinResult<()>
+    where
+        Self: crate::meta::WriteEndian,
+        for<'a> Self::Args<'a>: Required,
+    {
+        let path = path.as_ref();
+        let result = std::fs::File::create(path)
+            .map_err(Into::into)
+            .and_then(|file| {
+                let mut writer = std::io::BufWriter::new(file);
+                self.write(&mut writer)?;
+                writer.flush().map_err(Into::into)
+            });
+        crate::error::with_path(result, "writing", path)
+    }
+
     /// Write `Self` to the writer using the given [`Endian`] and
     /// arguments.
     ///
@@ -141,6 +175,154 @@ pub trait BinWrite {
         endian: Endian,
         args: Self::Args<'_>,
     ) -> BinResult<()>;
+
+    /// Write `Self` to the end of the writer using default arguments,
+    /// returning the range of bytes that were written.
+    ///
+    /// This is useful for formats which grow by adding new records instead of
+    /// rewriting the whole file, like journals and logs. To keep track of
+    /// every appended record, use an [`AppendWriter`].
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binrw::{binwrite, io::Cursor, BinWrite};
+    ///
+    /// #[binwrite]
+    /// #[bw(little)]
+    /// struct Entry {
+    ///     id: u16,
+    /// }
+    ///
+    /// let mut journal = Cursor::new(b"\x01\0".to_vec());
+    /// assert_eq!(Entry { id: 2 }.append_to(&mut journal).unwrap(), 2..4);
+    /// assert_eq!(journal.into_inner(), b"\x01\0\x02\0");
+    /// ```
+    #[inline]
+    fn append_to<W: Write + Seek>(&self, writer: &mut W) -> BinResult<Range<u64>>
+    where
+        Self: crate::meta::WriteEndian,
+        for<'a> Self::Args<'a>: Required,
+    {
+        self.append_options(writer, Endian::Little, Self::Args::args())
+    }
+
+    /// Write `Self` to the end of the writer using the given [`Endian`] and
+    /// arguments, returning the range of bytes that were written.
+    ///
+    /// The writer is left positioned at the new end of the stream.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    fn append_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Range<u64>> {
+        let start = writer.seek(SeekFrom::End(0))?;
+        self.write_options(writer, endian, args)?;
+        let end = writer.seek(SeekFrom::End(0))?;
+        Ok(start..end)
+    }
+
+    /// Returns the bounds on the number of bytes that [`write_options()`]
+    /// will write for `self`.
+    ///
+    /// Like [`Iterator::size_hint`], this returns a tuple where the first
+    /// element is the lower bound and the second element is the upper bound,
+    /// or `None` if there is no known upper bound. Hints are intended for
+    /// optimisations like preallocating buffers and must not be trusted for
+    /// memory safety.
+    ///
+    /// When `BinWrite` is derived, the hint is the sum of the hints of each
+    /// field and magic value. Fields whose size cannot be known without
+    /// writing them, like fields with [`map`], [`calc`], [`args`], or
+    /// [padding] directives, have no upper bound. If any field moves the
+    /// writer, like fields with [`seek_before`], [`seek_after`],
+    /// [`seek_to_saved`], or [`restore_position`] directives, the hint is
+    /// `(0, None)`. To calculate the exact size, use
+    /// [`BinSize`](crate::BinSize) instead.
+    ///
+    /// The default implementation returns `(0, None)`.
+    ///
+    /// [`args`]: crate::docs::attribute#arguments
+    /// [`calc`]: crate::docs::attribute#calculations
+    /// [`map`]: crate::docs::attribute#map
+    /// [padding]: crate::docs::attribute#padding-and-alignment
+    /// [`restore_position`]: crate::docs::attribute#restore-position
+    /// [`seek_after`]: crate::docs::attribute#padding-and-alignment
+    /// [`seek_before`]: crate::docs::attribute#padding-and-alignment
+    /// [`seek_to_saved`]: crate::docs::attribute#saved-positions
+    /// [`write_options()`]: Self::write_options
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binrw::{binwrite, BinWrite};
+    ///
+    /// #[binwrite]
+    /// #[bw(magic = b"HDR")]
+    /// struct Header {
+    ///     version: u16,
+    ///     flags: Option<u32>,
+    /// }
+    ///
+    /// let header = Header { version: 1, flags: None };
+    /// assert_eq!(header.size_hint(), (5, Some(5)));
+    /// ```
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+/// An object-safe version of [`BinWrite`] for writing trait objects.
+///
+/// `BinWrite` cannot be used as a trait object because its methods are
+/// generic over the writer. `DynBinWrite` is implemented for every `BinWrite`
+/// type that does not require arguments, so making it a supertrait of a custom
+/// trait allows values of type `Box<dyn MyTrait>` to be written with the
+/// [`dyn_with`] directive.
+///
+/// [`dyn_with`]: crate::docs::attribute#trait-objects
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{binwrite, io::Cursor, DynBinWrite, Endian};
+///
+/// #[binwrite]
+/// struct Point(u8, u8);
+///
+/// let point: Box<dyn DynBinWrite> = Box::new(Point(1, 2));
+/// let mut writer = Cursor::new(Vec::new());
+/// point.dyn_write_options(&mut writer, Endian::Little).unwrap();
+/// assert_eq!(writer.into_inner(), [1, 2]);
+/// ```
+pub trait DynBinWrite {
+    /// Write `Self` to the writer using the given [`Endian`] and default
+    /// arguments.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    fn dyn_write_options(&self, writer: &mut dyn WriteSeek, endian: Endian) -> BinResult<()>;
+}
+
+impl<T> DynBinWrite for T
+where
+    T: BinWrite,
+    for<'a> T::Args<'a>: Required,
+{
+    fn dyn_write_options(&self, mut writer: &mut dyn WriteSeek, endian: Endian) -> BinResult<()> {
+        self.write_options(&mut writer, endian, T::Args::args())
+    }
 }
 
 /// Extension methods for writing [`BinWrite`] objects directly to a writer.
@@ -253,6 +435,100 @@ pub trait BinWriterExt: Write + Seek + Sized {
     fn write_ne_args<T: BinWrite>(&mut self, value: &T, args: T::Args<'_>) -> BinResult<()> {
         self.write_type_args(value, Endian::NATIVE, args)
     }
+
+    /// Write `T` to the given position in the writer with the given byte
+    /// order, then restore the original position of the writer.
+    ///
+    /// # Errors
+    ///
+    /// If seeking or writing fails, an [`Error`](crate::Error) variant will be
+    /// returned. The original position is restored even if writing fails.
+    #[inline]
+    fn write_type_at<T: BinWrite>(
+        &mut self,
+        value: &T,
+        pos: SeekFrom,
+        endian: Endian,
+    ) -> BinResult<()>
+    where
+        for<'a> T::Args<'a>: Required,
+    {
+        self.write_type_args_at(value, pos, endian, T::Args::args())
+    }
+
+    /// Write `T` to the given absolute offset in the writer, assuming
+    /// big-endian byte order, then restore the original position of the
+    /// writer.
+    ///
+    /// # Errors
+    ///
+    /// If seeking or writing fails, an [`Error`](crate::Error) variant will be
+    /// returned. The original position is restored even if writing fails.
+    #[inline]
+    fn write_be_at<T: BinWrite>(&mut self, value: &T, offset: u64) -> BinResult<()>
+    where
+        for<'a> T::Args<'a>: Required,
+    {
+        self.write_type_at(value, SeekFrom::Start(offset), Endian::Big)
+    }
+
+    /// Write `T` to the given absolute offset in the writer, assuming
+    /// little-endian byte order, then restore the original position of the
+    /// writer.
+    ///
+    /// # Errors
+    ///
+    /// If seeking or writing fails, an [`Error`](crate::Error) variant will be
+    /// returned. The original position is restored even if writing fails.
+    #[inline]
+    fn write_le_at<T: BinWrite>(&mut self, value: &T, offset: u64) -> BinResult<()>
+    where
+        for<'a> T::Args<'a>: Required,
+    {
+        self.write_type_at(value, SeekFrom::Start(offset), Endian::Little)
+    }
+
+    /// Write `T` to the given position in the writer with the given byte order
+    /// and arguments, then restore the original position of the writer.
+    ///
+    /// This is useful for going back to fill in a header field, like a length
+    /// or an offset, once the data it describes has been written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binrw::{BinWriterExt, Endian, io::{Cursor, Seek, SeekFrom}};
+    ///
+    /// let mut writer = Cursor::new(Vec::new());
+    /// writer.write_le(&0_u16).unwrap();
+    /// writer.write_le(&[1_u8, 2, 3]).unwrap();
+    /// let len = writer.stream_position().unwrap() as u16 - 2;
+    /// writer
+    ///     .write_type_args_at(&len, SeekFrom::Start(0), Endian::Little, ())
+    ///     .unwrap();
+    /// assert_eq!(writer.stream_position().unwrap(), 5);
+    /// assert_eq!(writer.into_inner(), b"\x03\0\x01\x02\x03");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If seeking or writing fails, an [`Error`](crate::Error) variant will be
+    /// returned. The original position is restored even if writing fails.
+    fn write_type_args_at<T: BinWrite>(
+        &mut self,
+        value: &T,
+        pos: SeekFrom,
+        endian: Endian,
+        args: T::Args<'_>,
+    ) -> BinResult<()> {
+        let restore_pos = self.stream_position()?;
+        let result = self
+            .seek(pos)
+            .map_err(Into::into)
+            .and_then(|_| self.write_type_args(value, endian, args));
+        self.seek(SeekFrom::Start(restore_pos))?;
+        result
+    }
 }
 
 impl<W: Write + Seek + Sized> BinWriterExt for W {}
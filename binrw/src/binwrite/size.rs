@@ -0,0 +1,124 @@
+use super::BinWrite;
+use crate::{
+    io::{self, Seek, SeekFrom, Write},
+    BinResult, Endian,
+};
+
+/// Extension methods for calculating the serialised size of [`BinWrite`]
+/// objects.
+///
+/// Sizes are calculated by running the normal [`write_options()`] for the
+/// object against a stream which counts bytes instead of storing them, so
+/// every directive that affects the output (like [`count`], [`pad_size_to`],
+/// [`align_after`], or [`magic`]) is accounted for exactly as it would be when
+/// writing. This trait is implemented automatically for every `BinWrite`
+/// type.
+///
+/// Because the size is found by performing a full write, calculating it costs
+/// as much as writing the object, and any side effects of writing, like
+/// [pre-write hooks], happen again. For a cheap estimate which does not write
+/// anything, use [`BinWrite::size_hint`] instead.
+///
+/// [`write_options()`]: BinWrite::write_options
+/// [`count`]: crate::docs::attribute#count
+/// [`pad_size_to`]: crate::docs::attribute#padding-and-alignment
+/// [`align_after`]: crate::docs::attribute#padding-and-alignment
+/// [`magic`]: crate::docs::attribute#magic
+/// [pre-write hooks]: crate::docs::attribute#pre-write-hooks
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{binwrite, BinSize};
+///
+/// #[binwrite]
+/// #[bw(big, magic = b"PNT")]
+/// struct Points {
+///     #[bw(calc = points.len() as u8)]
+///     count: u8,
+///     #[bw(pad_size_to = 8)]
+///     points: Vec<(u8, u8)>,
+/// }
+///
+/// let points = Points { points: vec![(1, 2), (3, 4)] };
+/// assert_eq!(points.binary_size(()).unwrap(), 12);
+/// ```
+pub trait BinSize: BinWrite {
+    /// Returns the number of bytes that would be written by
+    /// [`write_args()`](BinWrite::write_args) with the given arguments.
+    ///
+    /// # Errors
+    ///
+    /// If writing would fail, an [`Error`](crate::Error) variant will be
+    /// returned.
+    #[inline]
+    fn binary_size(&self, args: Self::Args<'_>) -> BinResult<u64>
+    where
+        Self: crate::meta::WriteEndian,
+    {
+        self.binary_size_options(Endian::Little, args)
+    }
+
+    /// Returns the number of bytes that would be written by
+    /// [`write_options()`](BinWrite::write_options) with the given [`Endian`]
+    /// and arguments.
+    ///
+    /// This calls `write_options()` with a writer which counts bytes instead of
+    /// storing them, so it does all of the work of writing the object.
+    ///
+    /// # Errors
+    ///
+    /// If writing would fail, an [`Error`](crate::Error) variant will be
+    /// returned.
+    fn binary_size_options(&self, endian: Endian, args: Self::Args<'_>) -> BinResult<u64> {
+        let mut counter = SizeCounter::default();
+        self.write_options(&mut counter, endian, args)?;
+        Ok(counter.len)
+    }
+}
+
+impl<T: BinWrite + ?Sized> BinSize for T {}
+
+/// A stream which discards written data and records the furthest position
+/// written to.
+#[derive(Default)]
+struct SizeCounter {
+    pos: u64,
+    len: u64,
+}
+
+impl Write for SizeCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pos += buf.len() as u64;
+        self.len = self.len.max(self.pos);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for SizeCounter {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => {
+                self.pos = n;
+                return Ok(n);
+            }
+            SeekFrom::End(n) => (self.len, n),
+            SeekFrom::Current(n) => (self.pos, n),
+        };
+
+        match base.checked_add_signed(offset) {
+            Some(n) => {
+                self.pos = n;
+                Ok(n)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! Type definitions for bit-packed arrays of flags.
+//!
+//! Bits are packed eight to a byte, starting from the first byte. Since the
+//! data is a sequence of bytes, it is not affected by the endianness of the
+//! stream. A bit mask stored as a little-endian integer with bit 0 as its least
+//! significant bit can be read using [`BitOrder::Lsb0`].
+
+use crate::{
+    io::{Read, Seek, Write},
+    BinRead, BinResult, BinWrite, Endian, NamedArgs,
+};
+use alloc::vec::Vec;
+
+/// The order of bits within each byte of a bit-packed array.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BitOrder {
+    /// The first bit is the most significant bit of each byte.
+    #[default]
+    Msb0,
+
+    /// The first bit is the least significant bit of each byte.
+    Lsb0,
+}
+
+impl BitOrder {
+    fn mask(self, index: usize) -> u8 {
+        match self {
+            Self::Msb0 => 0x80 >> (index % 8),
+            Self::Lsb0 => 1 << (index % 8),
+        }
+    }
+
+    fn unpack(self, byte: u8, bits: &mut [bool]) {
+        for (index, bit) in bits.iter_mut().enumerate() {
+            *bit = byte & self.mask(index) != 0;
+        }
+    }
+
+    fn pack(self, bits: &[bool]) -> u8 {
+        bits.iter()
+            .enumerate()
+            .filter(|(_, bit)| **bit)
+            .fold(0, |byte, (index, _)| byte | self.mask(index))
+    }
+}
+
+fn write_bits<W: Write>(writer: &mut W, bits: &[bool], order: BitOrder) -> BinResult<()> {
+    let bytes = bits
+        .chunks(8)
+        .map(|chunk| order.pack(chunk))
+        .collect::<Vec<_>>();
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+/// Named arguments for the [`BinRead::read_options()`] implementation of
+/// [`PackedBits`].
+#[derive(Clone, NamedArgs)]
+pub struct PackedBitsArgs {
+    /// The number of bits to read.
+    pub count: usize,
+
+    /// The order of bits within each byte.
+    #[named_args(default = BitOrder::Msb0)]
+    pub order: BitOrder,
+}
+
+/// A variable-length bit-packed list of flags.
+///
+/// When reading, `count` bits are unpacked from the next `count / 8` bytes,
+/// rounded up. Any unused bits in the last byte are ignored. When writing,
+/// every flag in the list is packed, and any unused bits in the last byte are
+/// set to zero.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{binrw, io::Cursor, BinRead, BinWrite, BitOrder, PackedBits};
+///
+/// #[binrw]
+/// #[brw(little)]
+/// struct Inventory {
+///     #[br(temp)]
+///     #[bw(calc = slots.len() as u8)]
+///     count: u8,
+///     #[br(args { count: count.into(), order: BitOrder::Lsb0 })]
+///     #[bw(args_raw = BitOrder::Lsb0)]
+///     slots: PackedBits,
+/// }
+///
+/// let data = b"\x0a\x05\x02";
+/// let inventory = Inventory::read(&mut Cursor::new(data)).unwrap();
+/// assert_eq!(
+///     *inventory.slots,
+///     [true, false, true, false, false, false, false, false, false, true]
+/// );
+///
+/// let mut out = Cursor::new(Vec::new());
+/// inventory.write(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), data);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PackedBits(
+    /// The unpacked flags.
+    pub Vec<bool>,
+);
+
+impl PackedBits {
+    /// Consumes this wrapper, returning the unpacked flags.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<bool> {
+        self.0
+    }
+}
+
+impl BinRead for PackedBits {
+    type Args<'a> = PackedBitsArgs;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        _: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        // The list grows as data is read so that an untrusted count cannot
+        // cause a large allocation up front
+        let mut bits = Vec::new();
+        while bits.len() < args.count {
+            let mut byte = [0];
+            reader.read_exact(&mut byte)?;
+            let start = bits.len();
+            bits.resize((start + 8).min(args.count), false);
+            args.order.unpack(byte[0], &mut bits[start..]);
+        }
+        Ok(Self(bits))
+    }
+}
+
+impl BinWrite for PackedBits {
+    type Args<'a> = BitOrder;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        _: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        write_bits(writer, &self.0, args)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.0.len().div_ceil(8);
+        (size, Some(size))
+    }
+}
+
+impl core::ops::Deref for PackedBits {
+    type Target = Vec<bool>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl core::ops::DerefMut for PackedBits {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Vec<bool>> for PackedBits {
+    fn from(bits: Vec<bool>) -> Self {
+        Self(bits)
+    }
+}
+
+impl From<PackedBits> for Vec<bool> {
+    fn from(bits: PackedBits) -> Self {
+        bits.0
+    }
+}
+
+/// A fixed-size bit-packed array of `N` flags.
+///
+/// The array is read from and written to `N / 8` bytes, rounded up, using the
+/// [`BitOrder`] given in the arguments. Any unused bits in the last byte are
+/// ignored when reading and set to zero when writing.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{io::Cursor, BinRead, BinWrite, BitArray, BitOrder};
+///
+/// let mask = BitArray::<12>::read_le(&mut Cursor::new(b"\x81\x20")).unwrap();
+/// assert!(mask[0] && mask[7] && mask[10]);
+/// assert_eq!(mask.iter().filter(|bit| **bit).count(), 3);
+///
+/// let mask = BitArray::<4>::read_le_args(&mut Cursor::new(b"\x09"), BitOrder::Lsb0).unwrap();
+/// assert_eq!(*mask, [true, false, false, true]);
+///
+/// let mut out = Cursor::new(Vec::new());
+/// mask.write_le_args(&mut out, BitOrder::Msb0).unwrap();
+/// assert_eq!(out.into_inner(), b"\x90");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BitArray<const N: usize>(
+    /// The unpacked flags.
+    pub [bool; N],
+);
+
+impl<const N: usize> BitArray<N> {
+    /// Consumes this wrapper, returning the unpacked flags.
+    #[must_use]
+    pub fn into_inner(self) -> [bool; N] {
+        self.0
+    }
+}
+
+impl<const N: usize> Default for BitArray<N> {
+    fn default() -> Self {
+        Self([false; N])
+    }
+}
+
+impl<const N: usize> BinRead for BitArray<N> {
+    type Args<'a> = BitOrder;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        _: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let mut bits = [false; N];
+        for chunk in bits.chunks_mut(8) {
+            let mut byte = [0];
+            reader.read_exact(&mut byte)?;
+            args.unpack(byte[0], chunk);
+        }
+        Ok(Self(bits))
+    }
+}
+
+impl<const N: usize> BinWrite for BitArray<N> {
+    type Args<'a> = BitOrder;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        _: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        write_bits(writer, &self.0, args)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (N.div_ceil(8), Some(N.div_ceil(8)))
+    }
+}
+
+impl<const N: usize> core::ops::Deref for BitArray<N> {
+    type Target = [bool; N];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const N: usize> core::ops::DerefMut for BitArray<N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> From<[bool; N]> for BitArray<N> {
+    fn from(bits: [bool; N]) -> Self {
+        Self(bits)
+    }
+}
+
+impl<const N: usize> From<BitArray<N>> for [bool; N] {
+    fn from(bits: BitArray<N>) -> Self {
+        bits.0
+    }
+}
@@ -0,0 +1,201 @@
+//! Stable building blocks for code generated by third-party macros.
+//!
+//! The code generated by the binrw derive macros calls hidden helper functions
+//! which may change in any release. Other macros which generate [`BinRead`]
+//! and [`BinWrite`] implementations, like a derive macro for a specific file
+//! format, should use the functions in this module instead. They behave the
+//! same way as the code generated by binrw, so errors and stream positions are
+//! consistent with derived types, and they follow the normal semver rules.
+//!
+//! Generated code should refer to these functions by their absolute path, e.g.
+//! `::binrw::codegen::read_magic`.
+//!
+//! # Examples
+//!
+//! A macro might expand a struct with a magic number and two fields into:
+//!
+//! ```
+//! use binrw::{codegen, io::{Read, Seek}, BinRead, BinResult, Endian};
+//!
+//! struct Header {
+//!     version: u16,
+//!     count: u32,
+//! }
+//!
+//! impl BinRead for Header {
+//!     type Args<'a> = ();
+//!
+//!     fn read_options<R: Read + Seek>(
+//!         reader: &mut R,
+//!         endian: Endian,
+//!         _: Self::Args<'_>,
+//!     ) -> BinResult<Self> {
+//!         codegen::rewind_on_error(reader, |reader, pos| {
+//!             codegen::read_magic(reader, *b"HDR", endian)?;
+//!             let version = codegen::field_context(
+//!                 reader,
+//!                 "Header",
+//!                 "version",
+//!                 |reader| u16::read_options(reader, endian, ()),
+//!             )?;
+//!             // Version 2 files are big-endian, older files are little-endian
+//!             let endian = codegen::conditional_endian(version >= 2, Endian::Big);
+//!             let count = codegen::field_context(
+//!                 reader,
+//!                 "Header",
+//!                 "count",
+//!                 |reader| u32::read_options(reader, endian, ()),
+//!             )?;
+//!             codegen::assert(count != 0, pos, || "count must not be zero".into())?;
+//!             Ok(Header { version, count })
+//!         })
+//!     }
+//! }
+//!
+//! let header = Header::read_le(&mut binrw::io::Cursor::new(b"HDR\x02\0\0\0\0\x04")).unwrap();
+//! assert_eq!((header.version, header.count), (2, 4));
+//! ```
+
+use crate::{
+    error::{BacktraceFrame, ContextExt},
+    io::{Read, Seek, SeekFrom, Write},
+    BinRead, BinResult, BinWrite, Endian, Error,
+};
+use alloc::string::String;
+
+/// Reads a magic number and checks that it matches the expected value.
+///
+/// This is the same check as the [`magic`](crate::docs::attribute#magic)
+/// directive.
+///
+/// # Errors
+///
+/// If reading fails, an [`Error`] variant will be returned. If the magic
+/// number does not match, a [`BadMagic`](Error::BadMagic) error is returned
+/// with the position of the magic number.
+pub fn read_magic<R, B>(reader: &mut R, expected: B, endian: Endian) -> BinResult<()>
+where
+    B: for<'a> BinRead<Args<'a> = ()>
+        + core::fmt::Debug
+        + PartialEq
+        + Sync
+        + Send
+        + Clone
+        + Copy
+        + 'static,
+    R: Read + Seek,
+{
+    crate::__private::magic(reader, expected, endian)
+}
+
+/// Writes a magic number.
+///
+/// # Errors
+///
+/// If writing fails, an [`Error`] variant will be returned.
+pub fn write_magic<W, B>(writer: &mut W, magic: &B, endian: Endian) -> BinResult<()>
+where
+    B: for<'a> BinWrite<Args<'a> = ()>,
+    W: Write + Seek,
+{
+    magic.write_options(writer, endian, ())
+}
+
+/// Returns `endian` if `condition` is true, or the opposite byte order of
+/// `endian` otherwise.
+///
+/// This is the same logic as the
+/// [`is_big` and `is_little`](crate::docs::attribute#byte-order) directives.
+#[must_use]
+pub fn conditional_endian(condition: bool, endian: Endian) -> Endian {
+    match (condition, endian) {
+        (true, endian) => endian,
+        (false, Endian::Big) => Endian::Little,
+        (false, Endian::Little) => Endian::Big,
+    }
+}
+
+/// Runs `read`, restoring the position of the reader if it fails.
+///
+/// `read` receives the reader and its starting position.
+///
+/// Derived implementations of [`BinRead`] always leave the reader where it
+/// was when an error occurs, so that callers like enums and
+/// [`try`](crate::docs::attribute#try) can attempt another read from the same
+/// position.
+///
+/// # Errors
+///
+/// If `read` or getting the position of the reader fails, an [`Error`]
+/// variant will be returned. If restoring the position fails, that error is
+/// returned instead of the original error.
+pub fn rewind_on_error<R, T, F>(reader: &mut R, read: F) -> BinResult<T>
+where
+    R: Read + Seek,
+    F: FnOnce(&mut R, u64) -> BinResult<T>,
+{
+    let pos = reader.stream_position()?;
+    read(reader, pos).or_else(|error| {
+        reader.seek(SeekFrom::Start(pos))?;
+        Err(error)
+    })
+}
+
+/// Runs `read`, adding a backtrace frame for the given field of the given type
+/// to any error.
+///
+/// This is the same frame that derived implementations add, so errors from
+/// generated code display like errors from derived types. The frame records
+/// the location of the caller.
+///
+/// # Errors
+///
+/// If `read` fails, its error is returned with the additional frame.
+#[track_caller]
+pub fn field_context<R, T, F>(
+    reader: &mut R,
+    type_name: &'static str,
+    field: &'static str,
+    read: F,
+) -> BinResult<T>
+where
+    R: Read + Seek,
+    F: FnOnce(&mut R) -> BinResult<T>,
+{
+    let caller = core::panic::Location::caller();
+    read(reader).map_err(|error| {
+        error.with_context(BacktraceFrame::Field {
+            code: None,
+            type_name,
+            field,
+            pos: reader.stream_position().ok(),
+            file: caller.file(),
+            line: caller.line(),
+        })
+    })
+}
+
+/// Checks that a condition is true.
+///
+/// This is the same check as the [`assert`](crate::docs::attribute#assert)
+/// directive with a message.
+///
+/// # Errors
+///
+/// If `condition` is false, an [`AssertFail`](Error::AssertFail) error is
+/// returned with the given position and message. Derived types use the
+/// position of the start of the type, which is passed to the function given
+/// to [`rewind_on_error`].
+pub fn assert<F>(condition: bool, pos: u64, message: F) -> BinResult<()>
+where
+    F: FnOnce() -> String,
+{
+    if condition {
+        Ok(())
+    } else {
+        Err(Error::AssertFail {
+            pos,
+            message: message().into(),
+        })
+    }
+}
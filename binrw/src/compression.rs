@@ -0,0 +1,258 @@
+//! Type definitions for compressed regions which share a dictionary.
+//!
+//! binrw does not include any compression algorithms. Instead, a [`Codec`]
+//! is implemented for the algorithm used by a format, usually by calling into
+//! a compression crate. The codec and the dictionary used by a format are
+//! bundled together in a [`CompressionContext`], which is passed down to
+//! nested types using [arguments](crate::docs::attribute#arguments) like any
+//! other state. This means a dictionary read from a file header can be used by
+//! every compressed region in the file without global state.
+//!
+//! # Examples
+//!
+//! ```
+//! use binrw::{
+//!     binrw, io::{self, Cursor}, BinRead, BinWrite, Codec, Compressed,
+//!     CompressionContext,
+//! };
+//!
+//! /// A toy codec which XORs data with the dictionary.
+//! struct Xor;
+//!
+//! impl Codec for Xor {
+//!     fn compress(&self, data: &[u8], dictionary: Option<&[u8]>) -> io::Result<Vec<u8>> {
+//!         self.decompress(data, dictionary)
+//!     }
+//!
+//!     fn decompress(&self, data: &[u8], dictionary: Option<&[u8]>) -> io::Result<Vec<u8>> {
+//!         let key = dictionary.unwrap_or(&[0]);
+//!         Ok(data.iter().zip(key.iter().cycle()).map(|(a, b)| a ^ b).collect())
+//!     }
+//! }
+//!
+//! #[binrw]
+//! #[brw(little, import(context: CompressionContext<'_>))]
+//! struct Archive {
+//!     #[br(temp)]
+//!     #[bw(calc = entries.len() as u8)]
+//!     count: u8,
+//!     #[br(count = count, args { inner: (context,) })]
+//!     #[bw(args(context))]
+//!     entries: Vec<Entry>,
+//! }
+//!
+//! #[binrw]
+//! #[brw(little, import(context: CompressionContext<'_>))]
+//! struct Entry {
+//!     #[br(temp)]
+//!     #[bw(calc = 2)]
+//!     size: u8,
+//!     #[br(args { context, size: size.into() })]
+//!     #[bw(args { context })]
+//!     value: Compressed<u16>,
+//! }
+//!
+//! // The dictionary would usually be read from the header of the file
+//! let dictionary = [0x0f, 0xf0];
+//! let context = CompressionContext::new(&Xor).with_dictionary(&dictionary);
+//!
+//! let data = b"\x02\x02\x0e\xf2\x02\x0b\xf4";
+//! let archive = Archive::read_args(&mut Cursor::new(data), (context,)).unwrap();
+//! assert_eq!(*archive.entries[0].value, 0x0201);
+//! assert_eq!(*archive.entries[1].value, 0x0404);
+//!
+//! let mut out = Cursor::new(Vec::new());
+//! archive.write_args(&mut out, (context,)).unwrap();
+//! assert_eq!(out.into_inner(), data);
+//! ```
+
+use crate::{
+    io::{self, Cursor, Read, Seek, Write},
+    BinRead, BinResult, BinWrite, Endian, Error, NamedArgs,
+};
+use alloc::vec::Vec;
+
+/// A compression algorithm which can use a shared dictionary.
+pub trait Codec {
+    /// Compresses `data`, using the given dictionary if there is one.
+    ///
+    /// # Errors
+    ///
+    /// If the data cannot be compressed, an [`io::Error`] should be returned.
+    fn compress(&self, data: &[u8], dictionary: Option<&[u8]>) -> io::Result<Vec<u8>>;
+
+    /// Decompresses `data`, using the given dictionary if there is one.
+    ///
+    /// # Errors
+    ///
+    /// If the data is not valid compressed data, an [`io::Error`] should be
+    /// returned.
+    fn decompress(&self, data: &[u8], dictionary: Option<&[u8]>) -> io::Result<Vec<u8>>;
+}
+
+/// A codec and the dictionary to use with it.
+///
+/// The context is cheap to copy, so it can be passed to any number of nested
+/// compressed regions as an argument.
+#[derive(Clone, Copy)]
+pub struct CompressionContext<'a> {
+    /// The codec used to compress and decompress data.
+    pub codec: &'a dyn Codec,
+
+    /// The dictionary shared by every compressed region, if any.
+    pub dictionary: Option<&'a [u8]>,
+}
+
+impl<'a> CompressionContext<'a> {
+    /// Creates a context for the given codec without a dictionary.
+    #[must_use]
+    pub fn new(codec: &'a dyn Codec) -> Self {
+        Self {
+            codec,
+            dictionary: None,
+        }
+    }
+
+    /// Sets the dictionary to use with the codec.
+    #[must_use]
+    pub fn with_dictionary(self, dictionary: &'a [u8]) -> Self {
+        Self {
+            dictionary: Some(dictionary),
+            ..self
+        }
+    }
+
+    /// Compresses `data` using the codec and dictionary of this context.
+    ///
+    /// # Errors
+    ///
+    /// If the codec fails, an [`Error::Io`] variant will be returned.
+    pub fn compress(&self, data: &[u8]) -> BinResult<Vec<u8>> {
+        Ok(self.codec.compress(data, self.dictionary)?)
+    }
+
+    /// Decompresses `data` using the codec and dictionary of this context.
+    ///
+    /// # Errors
+    ///
+    /// If the codec fails, an [`Error::Io`] variant will be returned.
+    pub fn decompress(&self, data: &[u8]) -> BinResult<Vec<u8>> {
+        Ok(self.codec.decompress(data, self.dictionary)?)
+    }
+}
+
+/// Named arguments for the [`BinRead::read_options()`] implementation of
+/// [`Compressed`].
+///
+/// The `inner` field can be omitted completely if the inner type doesn’t
+/// require arguments, in which case a default value will be used.
+#[derive(Clone, NamedArgs)]
+pub struct CompressedArgs<'a, Inner> {
+    /// The codec and dictionary used to decompress the data.
+    pub context: CompressionContext<'a>,
+
+    /// The size of the compressed data, in bytes.
+    pub size: u64,
+
+    /// The [arguments](crate::BinRead::Args) for the inner type.
+    #[named_args(try_optional)]
+    pub inner: Inner,
+}
+
+/// Named arguments for the [`BinWrite::write_options()`] implementation of
+/// [`Compressed`].
+///
+/// The `inner` field can be omitted completely if the inner type doesn’t
+/// require arguments, in which case a default value will be used.
+#[derive(Clone, NamedArgs)]
+pub struct CompressedWriteArgs<'a, Inner> {
+    /// The codec and dictionary used to compress the data.
+    pub context: CompressionContext<'a>,
+
+    /// The [arguments](crate::BinWrite::Args) for the inner type.
+    #[named_args(try_optional)]
+    pub inner: Inner,
+}
+
+/// A value stored in a compressed region.
+///
+/// When reading, `size` bytes of compressed data are read and decompressed,
+/// and the value is read from the decompressed data. When writing, the value
+/// is written to a buffer, which is then compressed and written. The size of
+/// the compressed data is not written, so it must be stored elsewhere.
+///
+/// The positions in errors from reading the value are offsets into the
+/// decompressed data. Data left over after reading the value is ignored.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Compressed<T>(
+    /// The decompressed value.
+    pub T,
+);
+
+impl<T> Compressed<T> {
+    /// Consumes this wrapper, returning the decompressed value.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: BinRead> BinRead for Compressed<T> {
+    type Args<'a> = CompressedArgs<'a, T::Args<'a>>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        // The buffer grows as data is read so that an untrusted size cannot
+        // cause a large allocation up front
+        let mut data = Vec::new();
+        reader.take(args.size).read_to_end(&mut data)?;
+        if data.len() as u64 != args.size {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "not enough bytes in reader",
+            )));
+        }
+
+        let data = args.context.decompress(&data)?;
+        T::read_options(&mut Cursor::new(data), endian, args.inner).map(Self)
+    }
+}
+
+impl<T: BinWrite> BinWrite for Compressed<T> {
+    type Args<'a> = CompressedWriteArgs<'a, T::Args<'a>>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let mut data = Cursor::new(Vec::new());
+        self.0.write_options(&mut data, endian, args.inner)?;
+        writer.write_all(&args.context.compress(&data.into_inner())?)?;
+        Ok(())
+    }
+}
+
+impl<T> core::ops::Deref for Compressed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> core::ops::DerefMut for Compressed<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Compressed<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
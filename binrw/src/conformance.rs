@@ -0,0 +1,210 @@
+//! Helpers for testing readers and writers against tables of test vectors.
+//!
+//! The [`conformance!`](crate::conformance!) macro generates one test for each
+//! row of a table of inputs and expected results. The functions in this module
+//! do the actual checking, and can also be called directly from hand-written
+//! tests. Each function panics with a description of the input on failure.
+
+use crate::{io::Cursor, BinRead, BinReaderExt, BinWrite, Endian, Error};
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+/// Generates tests which check a type against a table of test vectors.
+///
+/// The first line gives the type under test and the byte order to use,
+/// optionally followed by the arguments to use when reading and writing. If
+/// arguments are not given, the default arguments are used.
+///
+/// Each following row generates a `#[test]` function with the given name. Rows
+/// are separated by commas:
+///
+/// * `round_trip name: bytes => value,` checks that `bytes` reads as `value`
+///   and that `value` writes as `bytes`.
+/// * `read name: bytes => value,` checks that `bytes` reads as `value`.
+/// * `write name: value => bytes,` checks that `value` writes as `bytes`.
+/// * `error name: bytes => pattern,` checks that reading `bytes` fails with an
+///   error whose [root cause](Error::root_cause) matches `pattern`.
+///
+/// Reads must consume every byte of the input to pass.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{binrw, Endian, Error};
+///
+/// #[binrw]
+/// #[derive(Debug, PartialEq)]
+/// #[brw(magic = b"PT")]
+/// struct Point(i16, i16);
+///
+/// binrw::conformance! {
+///     Point, Endian::Little;
+///     round_trip origin: b"PT\0\0\0\0" => Point(0, 0),
+///     round_trip negative: b"PT\xff\xff\x01\0" => Point(-1, 1),
+///     error bad_magic: b"XX\0\0\0\0" => Error::BadMagic { pos: 0, .. },
+///     error truncated: b"PT\0" => Error::Io(_),
+/// }
+/// ```
+#[macro_export]
+macro_rules! conformance {
+    (@args) => { ::core::default::Default::default() };
+    (@args $args:expr) => { $args };
+
+    (@rows [$($ctx:tt)*]) => {};
+    (@rows [$ty:ty, $endian:expr, [$($read_args:expr)?], [$($write_args:expr)?]]
+        round_trip $name:ident: $data:expr => $value:expr $(, $($rest:tt)*)?
+    ) => {
+        #[test]
+        fn $name() {
+            $crate::conformance::assert_round_trip::<$ty>(
+                $data,
+                $endian,
+                $crate::conformance!(@args $($read_args)?),
+                $crate::conformance!(@args $($write_args)?),
+                &$value,
+            );
+        }
+        $crate::conformance!(@rows [$ty, $endian, [$($read_args)?], [$($write_args)?]] $($($rest)*)?);
+    };
+    (@rows [$ty:ty, $endian:expr, [$($read_args:expr)?], [$($write_args:expr)?]]
+        read $name:ident: $data:expr => $value:expr $(, $($rest:tt)*)?
+    ) => {
+        #[test]
+        fn $name() {
+            $crate::conformance::assert_read::<$ty>(
+                $data,
+                $endian,
+                $crate::conformance!(@args $($read_args)?),
+                &$value,
+            );
+        }
+        $crate::conformance!(@rows [$ty, $endian, [$($read_args)?], [$($write_args)?]] $($($rest)*)?);
+    };
+    (@rows [$ty:ty, $endian:expr, [$($read_args:expr)?], [$($write_args:expr)?]]
+        write $name:ident: $value:expr => $data:expr $(, $($rest:tt)*)?
+    ) => {
+        #[test]
+        fn $name() {
+            $crate::conformance::assert_write::<$ty>(
+                &$value,
+                $endian,
+                $crate::conformance!(@args $($write_args)?),
+                $data,
+            );
+        }
+        $crate::conformance!(@rows [$ty, $endian, [$($read_args)?], [$($write_args)?]] $($($rest)*)?);
+    };
+    (@rows [$ty:ty, $endian:expr, [$($read_args:expr)?], [$($write_args:expr)?]]
+        error $name:ident: $data:expr => $error:pat $(, $($rest:tt)*)?
+    ) => {
+        #[test]
+        fn $name() {
+            let error = $crate::conformance::read_error::<$ty>(
+                $data,
+                $endian,
+                $crate::conformance!(@args $($read_args)?),
+            );
+            assert!(
+                matches!(error.root_cause(), $error),
+                "unexpected error reading {:02x?}: {:?}",
+                &$data[..],
+                error
+            );
+        }
+        $crate::conformance!(@rows [$ty, $endian, [$($read_args)?], [$($write_args)?]] $($($rest)*)?);
+    };
+
+    ($ty:ty, $endian:expr
+        $(, read_args = $read_args:expr)?
+        $(, write_args = $write_args:expr)?;
+        $($rows:tt)*
+    ) => {
+        $crate::conformance!(@rows [$ty, $endian, [$($read_args)?], [$($write_args)?]] $($rows)*);
+    };
+}
+
+/// Checks that `data` reads as `expected`, consuming every byte.
+///
+/// # Panics
+///
+/// Panics if reading fails, if the value read is not equal to `expected`, or
+/// if any bytes are left over.
+#[track_caller]
+pub fn assert_read<'a, T>(data: &[u8], endian: Endian, args: T::Args<'a>, expected: &T)
+where
+    T: BinRead + Debug + PartialEq,
+    T::Args<'a>: Clone,
+{
+    let mut reader = Cursor::new(data);
+    match reader.read_type_args::<T>(endian, args) {
+        Ok(value) => assert_eq!(&value, expected, "wrong value read from {data:02x?}"),
+        Err(error) => panic!("reading {data:02x?} failed: {error}"),
+    }
+
+    let pos = reader.position();
+    assert!(
+        pos == data.len() as u64,
+        "only {pos} of {} bytes were read from {data:02x?}",
+        data.len()
+    );
+}
+
+/// Checks that `value` writes as `expected`.
+///
+/// # Panics
+///
+/// Panics if writing fails or if the bytes written are not equal to
+/// `expected`.
+#[track_caller]
+pub fn assert_write<T>(value: &T, endian: Endian, args: T::Args<'_>, expected: &[u8])
+where
+    T: BinWrite + Debug,
+{
+    let mut writer = Cursor::new(Vec::new());
+    if let Err(error) = value.write_options(&mut writer, endian, args) {
+        panic!("writing {value:?} failed: {error}");
+    }
+    assert_eq!(
+        writer.into_inner(),
+        expected,
+        "wrong bytes written for {value:?}"
+    );
+}
+
+/// Checks that `data` reads as `expected`, and that `expected` writes as
+/// `data`.
+///
+/// # Panics
+///
+/// Panics if either [`assert_read`] or [`assert_write`] would panic.
+#[track_caller]
+pub fn assert_round_trip<'a, T>(
+    data: &[u8],
+    endian: Endian,
+    read_args: <T as BinRead>::Args<'a>,
+    write_args: <T as BinWrite>::Args<'_>,
+    expected: &T,
+) where
+    T: BinRead + BinWrite + Debug + PartialEq,
+    <T as BinRead>::Args<'a>: Clone,
+{
+    assert_read(data, endian, read_args, expected);
+    assert_write(expected, endian, write_args, data);
+}
+
+/// Reads `data`, expecting it to fail, and returns the error.
+///
+/// # Panics
+///
+/// Panics if reading succeeds.
+#[track_caller]
+pub fn read_error<'a, T>(data: &[u8], endian: Endian, args: T::Args<'a>) -> Error
+where
+    T: BinRead + Debug,
+    T::Args<'a>: Clone,
+{
+    match Cursor::new(data).read_type_args::<T>(endian, args) {
+        Ok(value) => panic!("reading {data:02x?} succeeded with {value:?}, expected an error"),
+        Err(error) => error,
+    }
+}
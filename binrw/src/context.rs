@@ -0,0 +1,105 @@
+//! Type definitions for sharing values with deeply nested types.
+//!
+//! Some values, like the version of a file format or a string table, are
+//! needed by types which are nested deep inside other types. Passing these
+//! values as [arguments](crate::docs::attribute#arguments) means that every
+//! type in between has to accept and forward them, even if it does not use
+//! them. Instead, a value can be made available for the duration of a read or
+//! write with [`with`], and then retrieved by its type with the
+//! [`context`](crate::docs::attribute#context-values) directive, or with [`get`]
+//! from any directive.
+//!
+//! # Limitations
+//!
+//! [`BinRead::read_options`](crate::BinRead::read_options) and
+//! [`BinWrite::write_options`](crate::BinWrite::write_options) only receive
+//! an endianness and arguments, so the context is not attached to a read or
+//! write. It is stored per thread instead, which means:
+//!
+//! * Values are not visible to work which is moved to other threads, like the
+//!   workers of `FilePtr::parse_all_parallel`. Values which those reads need
+//!   must be made available again on each worker, or passed as arguments.
+//! * Values are only visible to async reads while the future is polled inside
+//!   [`with`] on the same thread, like with a current-thread runtime, because
+//!   [`with`] only covers synchronous code.
+//!
+//! This module is only available with the `std` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use binrw::{binread, context, io::Cursor, BinRead};
+//!
+//! #[derive(Clone, Copy)]
+//! struct FormatVersion(u8);
+//!
+//! #[binread]
+//! #[br(little)]
+//! struct Entry {
+//!     #[br(temp, context)]
+//!     version: FormatVersion,
+//!     id: u8,
+//!     // Version 2 added a size to every entry
+//!     #[br(if(version.0 >= 2))]
+//!     size: Option<u16>,
+//! }
+//!
+//! #[derive(BinRead)]
+//! #[br(little)]
+//! struct Directory {
+//!     count: u8,
+//!     // `Directory` does not need to know about the version
+//!     #[br(count = count)]
+//!     entries: Vec<Entry>,
+//! }
+//!
+//! let data = b"\x01\x05\x20\x00";
+//! let directory = context::with(FormatVersion(2), || {
+//!     Directory::read(&mut Cursor::new(data))
+//! })
+//! .unwrap();
+//! assert_eq!(directory.entries[0].size, Some(32));
+//! ```
+
+use alloc::{rc::Rc, vec::Vec};
+use core::{any::Any, cell::RefCell};
+
+std::thread_local! {
+    static CONTEXT: RefCell<Vec<Rc<dyn Any>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Makes `value` available to [`get`] on this thread while `f` runs.
+///
+/// Calls can be nested. If a value of the same type was already made
+/// available by an outer call, it is hidden until `f` returns.
+pub fn with<T: 'static, R>(value: T, f: impl FnOnce() -> R) -> R {
+    /// Removes the value again even if `f` panics.
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            // The value is dropped after the borrow ends in case its
+            // destructor uses the context
+            let value = CONTEXT.with_borrow_mut(Vec::pop);
+            drop(value);
+        }
+    }
+
+    CONTEXT.with_borrow_mut(|context| context.push(Rc::new(value)));
+    let _guard = Guard;
+    f()
+}
+
+/// Returns a copy of the innermost value of type `T` which was made available
+/// on this thread by [`with`], or `None` if there is no such value.
+///
+/// Values which are expensive to clone, like string tables, can be shared by
+/// wrapping them in an [`Rc`].
+#[must_use]
+pub fn get<T: Clone + 'static>() -> Option<T> {
+    // The value is cloned after the borrow ends in case its `Clone`
+    // implementation uses the context
+    let value = CONTEXT
+        .with_borrow(|context| context.iter().rev().find(|value| value.is::<T>()).cloned())?;
+    value.downcast_ref::<T>().cloned()
+}
@@ -0,0 +1,97 @@
+//! Cyclic redundancy checks for the [`crc`](crate::docs::attribute#crc)
+//! directive.
+//!
+//! Each function computes the checksum of a complete byte slice, so they can
+//! also be used directly with the
+//! [`checksum` and `verify`](crate::docs::attribute#checksums) directives.
+
+/// Computes a CRC-16/ARC checksum (also known as CRC-16/IBM).
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(binrw::crc::crc16(b"123456789"), 0xbb3d);
+/// ```
+#[must_use]
+pub fn crc16(data: &[u8]) -> u16 {
+    static TABLE: [u16; 256] = table16(0xa001);
+    data.iter().fold(0, |crc, &byte| {
+        (crc >> 8) ^ TABLE[usize::from(crc.to_le_bytes()[0] ^ byte)]
+    })
+}
+
+/// Computes a CRC-32/ISO-HDLC checksum, as used by zlib, PNG, and Ethernet.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(binrw::crc::crc32(b"123456789"), 0xcbf4_3926);
+/// ```
+#[must_use]
+pub fn crc32(data: &[u8]) -> u32 {
+    static TABLE: [u32; 256] = table32(0xedb8_8320);
+    update32(&TABLE, data)
+}
+
+/// Computes a CRC-32C checksum (Castagnoli), as used by iSCSI and ext4.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(binrw::crc::crc32c(b"123456789"), 0xe306_9283);
+/// ```
+#[must_use]
+pub fn crc32c(data: &[u8]) -> u32 {
+    static TABLE: [u32; 256] = table32(0x82f6_3b78);
+    update32(&TABLE, data)
+}
+
+fn update32(table: &[u32; 256], data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |crc, &byte| {
+        (crc >> 8) ^ table[usize::from(crc.to_le_bytes()[0] ^ byte)]
+    })
+}
+
+// Lint: Truncation is intentional since the table index is always < 256.
+#[allow(clippy::cast_possible_truncation)]
+const fn table16(poly: u16) -> [u16; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < table.len() {
+        let mut crc = i as u16;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ poly
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+// Lint: Truncation is intentional since the table index is always < 256.
+#[allow(clippy::cast_possible_truncation)]
+const fn table32(poly: u32) -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < table.len() {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ poly
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
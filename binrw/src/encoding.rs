@@ -0,0 +1,208 @@
+//! Type definitions for strings in legacy text encodings.
+//!
+//! This module is only available with the `encoding_rs` feature.
+
+use crate::{
+    io::{Read, Seek, Write},
+    BinRead, BinResult, BinWrite, Endian, Error, NamedArgs, VecArgs,
+};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+#[doc(no_inline)]
+pub use encoding_rs::{self, Encoding};
+
+/// Named arguments for the [`BinRead::read_options()`] and
+/// [`BinWrite::write_options()`] implementations of [`EncodedString`].
+#[derive(Clone, NamedArgs)]
+pub struct EncodedStringArgs {
+    /// The text encoding.
+    pub encoding: &'static Encoding,
+
+    /// The size of the string, in bytes.
+    ///
+    /// If `None`, the string is terminated by a null byte. Otherwise, the
+    /// string is padded with null bytes to this size when writing, and any
+    /// trailing null bytes are removed when reading.
+    #[named_args(default = None)]
+    pub count: Option<usize>,
+}
+
+/// A string stored in a legacy text encoding like Shift JIS or
+/// Windows-1252.
+///
+/// The string is decoded to UTF-8 when reading and encoded when writing using
+/// the [`Encoding`] given in the arguments. Only encodings which use a single
+/// null byte as a terminator are supported, so UTF-16 encodings should be
+/// read using [`NullWideString`](crate::NullWideString) instead.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{
+///     binrw, encoding::{encoding_rs::SHIFT_JIS, EncodedString}, io::Cursor, BinRead,
+///     BinWrite,
+/// };
+///
+/// #[binrw]
+/// struct Save {
+///     #[brw(args { encoding: SHIFT_JIS })]
+///     name: EncodedString,
+///     #[brw(args { encoding: SHIFT_JIS, count: Some(8) })]
+///     title: EncodedString,
+/// }
+///
+/// let data = b"\x83\x8a\x83\x93\x83\x4e\0\x8e\xe5\x90\x6c\x8c\xf6\0\0";
+/// let save = Save::read_le(&mut Cursor::new(data)).unwrap();
+/// assert_eq!(*save.name, "リンク");
+/// assert_eq!(*save.title, "主人公");
+///
+/// let mut out = Cursor::new(Vec::new());
+/// save.write_le(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), data);
+/// ```
+///
+/// # Errors
+///
+/// If the data cannot be decoded, or if the string contains characters which
+/// cannot be represented in the encoding, an [`Error::Custom`] containing an
+/// [`EncodingError`] will be returned.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct EncodedString(
+    /// The decoded string.
+    pub String,
+);
+
+impl EncodedString {
+    /// Consumes this wrapper, returning the decoded string.
+    #[must_use]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl BinRead for EncodedString {
+    type Args<'a> = EncodedStringArgs;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = reader.stream_position()?;
+        let bytes = if let Some(count) = args.count {
+            let mut bytes = Vec::<u8>::read_options(reader, endian, VecArgs { count, inner: () })?;
+            let len = bytes
+                .iter()
+                .rposition(|b| *b != 0)
+                .map_or(0, |last| last + 1);
+            bytes.truncate(len);
+            bytes
+        } else {
+            crate::NullString::read_options(reader, endian, ())?.0
+        };
+
+        args.encoding
+            .decode_without_bom_handling_and_without_replacement(&bytes)
+            .map(|value| Self(value.into_owned()))
+            .ok_or_else(|| Error::Custom {
+                pos,
+                err: alloc::boxed::Box::new(EncodingError::Decode(args.encoding)),
+            })
+    }
+}
+
+impl BinWrite for EncodedString {
+    type Args<'a> = EncodedStringArgs;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        _: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let pos = writer.stream_position()?;
+        let error = |err| Error::Custom {
+            pos,
+            err: alloc::boxed::Box::new(err),
+        };
+
+        let (bytes, encoding, had_errors) = args.encoding.encode(&self.0);
+        if had_errors || encoding != args.encoding {
+            return Err(error(EncodingError::Encode(args.encoding)));
+        }
+
+        let padding = match args.count {
+            Some(count) => count
+                .checked_sub(bytes.len())
+                .ok_or_else(|| error(EncodingError::TooLong(count)))?,
+            None => 1,
+        };
+
+        writer.write_all(&bytes)?;
+        crate::__private::write_zeroes(writer, padding as u64)
+    }
+}
+
+impl core::ops::Deref for EncodedString {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl core::ops::DerefMut for EncodedString {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<&str> for EncodedString {
+    fn from(s: &str) -> Self {
+        Self(s.into())
+    }
+}
+
+impl From<String> for EncodedString {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+impl From<EncodedString> for String {
+    fn from(s: EncodedString) -> Self {
+        s.0
+    }
+}
+
+impl fmt::Display for EncodedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// An error which occurred while converting an [`EncodedString`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EncodingError {
+    /// The data is not valid in the encoding.
+    Decode(&'static Encoding),
+
+    /// The string contains characters which cannot be represented in the
+    /// encoding.
+    Encode(&'static Encoding),
+
+    /// The encoded string is longer than the given number of bytes.
+    TooLong(usize),
+}
+
+impl fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode(encoding) => write!(f, "invalid {} data", encoding.name()),
+            Self::Encode(encoding) => {
+                write!(f, "string cannot be encoded as {}", encoding.name())
+            }
+            Self::TooLong(count) => write!(f, "encoded string is longer than {count} bytes"),
+        }
+    }
+}
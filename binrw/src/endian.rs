@@ -1,7 +1,6 @@
 //! Type definitions for byte order handling.
 
 use crate::BinResult;
-use alloc::boxed::Box;
 pub use Endian::{Big as BE, Little as LE};
 
 /// Defines the order of bytes in a multi-byte type.
@@ -35,7 +34,7 @@ impl Endian {
             REVERSE_BOM => Ok(Self::Big),
             _ => Err(crate::Error::BadMagic {
                 pos: u64::MAX,
-                found: Box::new("Invalid UTF-16 BOM"),
+                found: crate::error::found("Invalid UTF-16 BOM"),
             }),
         }
     }
@@ -1,5 +1,11 @@
 use super::{ContextExt, CustomError, Error};
-use alloc::{borrow::Cow, boxed::Box, format, string::ToString, vec::Vec};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
 use core::fmt::{self, Write};
 
 #[cfg(feature = "verbose-backtrace")]
@@ -48,6 +54,33 @@ impl Backtrace {
         }
     }
 
+    /// Returns the path to the field which caused the error, starting from the
+    /// outermost type, e.g. `Outer.inner.value`.
+    ///
+    /// Only [`BacktraceFrame::Field`] frames are included in the path. If there
+    /// are no such frames, an empty string is returned.
+    #[must_use]
+    pub fn field_path(&self) -> String {
+        let mut fields = self.frames.iter().rev().filter_map(|frame| match frame {
+            BacktraceFrame::Field {
+                type_name, field, ..
+            } => Some((*type_name, *field)),
+            _ => None,
+        });
+
+        let mut path = String::new();
+        if let Some((type_name, field)) = fields.next() {
+            path.push_str(type_name);
+            path.push('.');
+            path.push_str(field);
+        }
+        for (_, field) in fields {
+            path.push('.');
+            path.push_str(field);
+        }
+        path
+    }
+
     fn fmt_no_bars(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut frames = self.frames.iter();
 
@@ -135,6 +168,30 @@ pub enum BacktraceFrame {
         line: u32,
     },
 
+    /// A frame for a field of a derived type.
+    Field {
+        /// The code at the location where the frame was generated.
+        code: Option<&'static str>,
+
+        /// The name of the type containing the field. For an enum variant,
+        /// this is `Enum::Variant`.
+        type_name: &'static str,
+
+        /// The name of the field.
+        field: &'static str,
+
+        /// The position of the reader after the field failed to parse. Because
+        /// a failed read rewinds the reader, this is usually the start of the
+        /// field.
+        pos: Option<u64>,
+
+        /// The origin filename.
+        file: &'static str,
+
+        /// The origin line number.
+        line: u32,
+    },
+
     /// A message-only frame.
     Message(Cow<'static, str>),
 
@@ -152,6 +209,9 @@ impl BacktraceFrame {
         match self {
             BacktraceFrame::Full {
                 code, file, line, ..
+            }
+            | BacktraceFrame::Field {
+                code, file, line, ..
             } => {
                 writeln!(
                     f,
@@ -175,6 +235,15 @@ impl BacktraceFrame {
     fn message(&self) -> Cow<'_, str> {
         match self {
             BacktraceFrame::Full { message: msg, .. } | BacktraceFrame::Message(msg) => msg.clone(),
+            BacktraceFrame::Field {
+                type_name,
+                field,
+                pos: Some(pos),
+                ..
+            } => format!("While parsing field '{field}' in {type_name} at {pos:#x}").into(),
+            BacktraceFrame::Field {
+                type_name, field, ..
+            } => format!("While parsing field '{field}' in {type_name}").into(),
             BacktraceFrame::Custom(context) => context.to_string().into(),
         }
     }
@@ -195,7 +264,14 @@ impl fmt::Display for FirstErrorFmt<'_> {
                 pos,
                 variant_errors,
             } => {
-                writeln!(f, "no variants matched at {pos:#x?}...{BOLD_CLOSE}")?;
+                if super::all_failed_assertions(variant_errors) {
+                    writeln!(
+                        f,
+                        "no variants matched at {pos:#x?} because every variant failed an assertion...{BOLD_CLOSE}"
+                    )?;
+                } else {
+                    writeln!(f, "no variants matched at {pos:#x?}...{BOLD_CLOSE}")?;
+                }
 
                 for (i, (name, err)) in variant_errors.iter().enumerate() {
                     if i != 0 {
@@ -256,7 +332,7 @@ impl fmt::Display for NoBars<'_> {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, not(feature = "compact-errors")))]
 mod tests {
     use super::*;
 
@@ -270,7 +346,7 @@ mod tests {
 
         let error = Error::AssertFail {
             pos: 4,
-            message: ERR0.to_string(),
+            message: ERR0.into(),
         };
 
         let (line1, error) = (line!(), Err::<(), _>(error.with_message(ERR1)));
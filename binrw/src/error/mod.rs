@@ -3,9 +3,15 @@
 mod backtrace;
 
 use crate::{
-    alloc::{borrow::Cow, boxed::Box, string::String, vec, vec::Vec},
-    io, BinResult,
+    __private::Required,
+    alloc::{borrow::Cow, boxed::Box, vec::Vec},
+    io::{self, Read, Seek},
+    meta::ReadEndian,
+    BinRead, BinResult, Endian,
 };
+#[cfg(not(feature = "compact-errors"))]
+use alloc::string::String;
+use alloc::vec;
 pub use backtrace::*;
 use core::{any::Any, fmt};
 
@@ -13,6 +19,9 @@ use core::{any::Any, fmt};
 ///
 /// This is used to add tracking information to errors that bubble up from an
 /// inner field.
+///
+/// The `compact-errors` feature stops derived parsers from adding context to
+/// their errors, but these methods always add the context they are given.
 pub trait ContextExt {
     /// Adds a new context frame to the error, consuming the original error.
     #[must_use]
@@ -160,7 +169,100 @@ impl dyn CustomError {
     }
 }
 
-/// The error type used by [`BinRead`](crate::BinRead).
+#[cfg(not(feature = "compact-errors"))]
+pub(crate) fn message(message: Cow<'static, str>) -> String {
+    message.into_owned()
+}
+
+#[cfg(feature = "compact-errors")]
+pub(crate) fn message(message: Cow<'static, str>) -> Cow<'static, str> {
+    message
+}
+
+#[cfg(not(feature = "compact-errors"))]
+pub(crate) fn found<T: fmt::Debug + Send + Sync + 'static>(
+    value: T,
+) -> Box<dyn fmt::Debug + Send + Sync> {
+    Box::new(value)
+}
+
+#[cfg(feature = "compact-errors")]
+pub(crate) fn found<T: fmt::Debug>(value: T) -> FoundMagic {
+    FoundMagic::new(&value)
+}
+
+/// The debug representation of an unexpected
+/// [magic number](crate::docs::attribute#magic) in an [`Error::BadMagic`],
+/// stored without allocating.
+///
+/// Representations longer than [`FoundMagic::CAPACITY`] bytes are truncated.
+///
+/// This type is only available with the `compact-errors` feature.
+#[cfg(feature = "compact-errors")]
+pub struct FoundMagic(InlineDebug);
+
+#[cfg(feature = "compact-errors")]
+impl FoundMagic {
+    /// The maximum number of bytes of the debug representation which are
+    /// stored.
+    pub const CAPACITY: usize = 64;
+
+    /// Stores the debug representation of the given value.
+    #[must_use]
+    pub fn new<T: fmt::Debug + ?Sized>(value: &T) -> Self {
+        let mut repr = InlineDebug {
+            buf: [0; Self::CAPACITY],
+            len: 0,
+            truncated: false,
+        };
+        // The writer never fails; it truncates instead
+        let _ = fmt::write(&mut repr, format_args!("{value:?}"));
+        Self(repr)
+    }
+}
+
+#[cfg(feature = "compact-errors")]
+impl fmt::Debug for FoundMagic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "compact-errors")]
+struct InlineDebug {
+    buf: [u8; FoundMagic::CAPACITY],
+    len: usize,
+    truncated: bool,
+}
+
+#[cfg(feature = "compact-errors")]
+impl fmt::Write for InlineDebug {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let available = FoundMagic::CAPACITY - self.len;
+        let mut end = s.len().min(available);
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.buf[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+        self.len += end;
+        self.truncated |= end < s.len();
+        Ok(())
+    }
+}
+
+#[cfg(feature = "compact-errors")]
+impl fmt::Debug for InlineDebug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The buffer is only ever filled from whole characters
+        f.write_str(core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default())?;
+        if self.truncated {
+            f.write_str("…")?;
+        }
+        Ok(())
+    }
+}
+
+/// The error type used by [`BinRead`].
 #[non_exhaustive]
 pub enum Error {
     /// An expected [magic number](crate::docs::attribute#magic) was not found.
@@ -169,7 +271,12 @@ pub enum Error {
         pos: u64,
 
         /// The value which was actually read.
+        #[cfg(not(feature = "compact-errors"))]
         found: Box<dyn fmt::Debug + Send + Sync>,
+
+        /// The debug representation of the value which was actually read.
+        #[cfg(feature = "compact-errors")]
+        found: FoundMagic,
     },
 
     /// An assertion failed.
@@ -186,13 +293,34 @@ pub enum Error {
         pos: u64,
 
         /// The failure message.
+        #[cfg(not(feature = "compact-errors"))]
         message: String,
+
+        /// The failure message, which is borrowed if it is a string literal.
+        #[cfg(feature = "compact-errors")]
+        message: Cow<'static, str>,
     },
 
     /// An error occurred in the underlying reader while reading or seeking to
     /// data.
     Io(io::Error),
 
+    /// An offset pointed before the start or past the end of the stream.
+    ///
+    /// This variant is used by [`FilePtr`](crate::FilePtr) when its pointer,
+    /// plus its [`offset`](crate::docs::attribute#offset), does not point to
+    /// a position in the stream.
+    OffsetOutOfBounds {
+        /// The byte position in the reader immediately after the pointer.
+        pos: u64,
+
+        /// The absolute position which the pointer pointed to.
+        offset: i128,
+
+        /// The length of the stream.
+        len: u64,
+    },
+
     /// A user-generated error.
     ///
     /// This variant is used for [`assert`] directives which use an error object
@@ -288,21 +416,141 @@ impl From<io::Error> for Error {
     }
 }
 
+/// Types which report read errors using their own error type.
+///
+/// This trait is automatically defined on derived types with an
+/// [`error` directive](crate::docs::attribute#custom-error-types).
+pub trait ReadError: BinRead {
+    /// The error type returned by the `try_read` functions.
+    type Error: From<Error> + CustomError + 'static;
+
+    /// Read `Self` from the reader using default arguments, returning
+    /// [`Self::Error`](ReadError::Error) on failure.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, the converted error is returned.
+    fn try_read<R: Read + Seek>(reader: &mut R) -> Result<Self, Self::Error>
+    where
+        Self: ReadEndian,
+        for<'a> Self::Args<'a>: Required,
+    {
+        Self::read(reader).map_err(Self::convert_error)
+    }
+
+    /// Read `Self` from the reader using the given arguments, returning
+    /// [`Self::Error`](ReadError::Error) on failure.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, the converted error is returned.
+    fn try_read_args<R: Read + Seek>(
+        reader: &mut R,
+        args: Self::Args<'_>,
+    ) -> Result<Self, Self::Error>
+    where
+        Self: ReadEndian,
+    {
+        Self::read_args(reader, args).map_err(Self::convert_error)
+    }
+
+    /// Read `Self` from the reader using the given [`Endian`] and arguments,
+    /// returning [`Self::Error`](ReadError::Error) on failure.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, the converted error is returned.
+    fn try_read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> Result<Self, Self::Error> {
+        Self::read_options(reader, endian, args).map_err(Self::convert_error)
+    }
+
+    /// Converts a [`binrw::Error`] into [`Self::Error`](ReadError::Error).
+    ///
+    /// If the [root cause](Error::root_cause) of the error is a
+    /// [`Custom`](Error::Custom) error which is already a `Self::Error`, such
+    /// as an error raised by an [`assert`](crate::docs::attribute#assert), it
+    /// is returned as-is. Otherwise, the error is converted using [`From`].
+    #[must_use]
+    fn convert_error(error: Error) -> Self::Error {
+        let error = match error {
+            Error::Backtrace(backtrace)
+                if backtrace.error.custom_err::<Self::Error>().is_some() =>
+            {
+                *backtrace.error
+            }
+            error => error,
+        };
+
+        match error {
+            Error::Custom { pos, err } => match err.downcast() {
+                Ok(err) => *err,
+                Err(err) => Error::Custom { pos, err }.into(),
+            },
+            error => error.into(),
+        }
+    }
+}
+
+/// Returns true if every variant of an enum was rejected by an `assert` or
+/// `pre_assert` directive.
+pub(crate) fn all_failed_assertions(variant_errors: &[(&'static str, Error)]) -> bool {
+    !variant_errors.is_empty()
+        && variant_errors
+            .iter()
+            .all(|(_, err)| matches!(err.root_cause(), Error::AssertFail { .. }))
+}
+
+/// Adds the path of the file which was being read or written to an error.
+#[cfg(feature = "std")]
+pub(crate) fn with_path<T>(
+    result: BinResult<T>,
+    action: &str,
+    path: &std::path::Path,
+) -> BinResult<T> {
+    result.with_context(BacktraceFrame::Message(
+        alloc::format!("While {action} file '{}'", path.display()).into(),
+    ))
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::BadMagic { pos, found } => write!(f, "bad magic at 0x{pos:x}: {found:?}"),
             Self::AssertFail { pos, message } => write!(f, "{message} at 0x{pos:x}"),
             Self::Io(err) => fmt::Display::fmt(err, f),
+            Self::OffsetOutOfBounds { pos, offset, len } => {
+                let sign = if offset.is_negative() { "-" } else { "" };
+                write!(
+                    f,
+                    "offset {sign}0x{:x} is out of bounds for stream of length 0x{len:x} at 0x{pos:x}",
+                    offset.unsigned_abs()
+                )
+            }
             Self::Custom { pos, err } => write!(f, "{err} at 0x{pos:x}"),
             Self::NoVariantMatch { pos } => write!(f, "no variants matched at 0x{pos:x}"),
             Self::EnumErrors {
                 pos,
                 variant_errors,
             } => {
-                write!(f, "no variants matched at 0x{pos:x}:")?;
-                for (name, err) in variant_errors {
-                    write!(f, "\n  {name}: {err}")?;
+                if all_failed_assertions(variant_errors) {
+                    // The failed assertions are what decide which variant is
+                    // chosen, so they are shown without any other context
+                    write!(
+                        f,
+                        "no variants matched at 0x{pos:x} because every variant failed an assertion:"
+                    )?;
+                    for (name, err) in variant_errors {
+                        write!(f, "\n  {name}: {}", err.root_cause())?;
+                    }
+                } else {
+                    write!(f, "no variants matched at 0x{pos:x}:")?;
+                    for (name, err) in variant_errors {
+                        write!(f, "\n  {name}: {err}")?;
+                    }
                 }
                 Ok(())
             }
@@ -3,9 +3,11 @@
 
 use crate::NamedArgs;
 use crate::{
-    io::{Read, Seek, SeekFrom},
-    BinRead, BinResult, Endian,
+    io::{Cursor, Read, Seek, SeekFrom, Write, WriteSeek},
+    BinRead, BinResult, BinWrite, Endian, Error,
 };
+use alloc::{collections::VecDeque, vec::Vec};
+use core::cell::{OnceCell, RefCell};
 use core::fmt;
 use core::num::{
     NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
@@ -46,6 +48,14 @@ pub type NonZeroFilePtr128<T> = FilePtr<NonZeroU128, T>;
 /// When deriving `BinRead`, [offset](crate::docs::attribute#offset) directives
 /// can be used to adjust the offset before the pointed-to value is read.
 ///
+/// When writing, the pointed-to value is added to a [`FilePtrQueue`] and a
+/// placeholder offset is written. Calling [`FilePtrQueue::flush()`] writes the
+/// queued values and patches each offset to point to its value. Each value is
+/// serialized once, as though it started at position 0, so values which use
+/// [`align_before`](crate::docs::attribute#padding-and-alignment) or similar
+/// directives need the [`align`](FilePtrWriteArgs::align) argument to make
+/// sure that they are written at a position with the same alignment.
+///
 /// [dereferencing]: core::ops::Deref
 ///
 /// # Examples
@@ -69,6 +79,32 @@ pub type NonZeroFilePtr128<T> = FilePtr<NonZeroU128, T>;
 ///           [pointer]           [value]
 /// 00000000: 0000 0008 0000 0000 ff                   ............
 /// ```
+///
+/// Writing pointers:
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor, FilePtr32, file_ptr::FilePtrQueue};
+/// #
+/// #[binwrite]
+/// #[bw(big, import(queue: &FilePtrQueue))]
+/// struct Test {
+///     #[bw(args { queue })]
+///     first: FilePtr32<u8>,
+///     #[bw(args { queue })]
+///     second: FilePtr32<u16>,
+/// }
+///
+/// let test = Test {
+///     first: FilePtr32 { ptr: 0, value: Some(0xff) },
+///     second: FilePtr32 { ptr: 0, value: Some(0x1234) },
+/// };
+///
+/// let queue = FilePtrQueue::new();
+/// let mut writer = Cursor::new(Vec::new());
+/// test.write_args(&mut writer, (&queue,)).unwrap();
+/// queue.flush(&mut writer).unwrap();
+/// assert_eq!(writer.into_inner(), b"\0\0\0\x08\0\0\0\x09\xff\x12\x34");
+/// ```
 pub struct FilePtr<Ptr: IntoSeekFrom, T> {
     /// The raw offset to the value.
     pub ptr: Ptr,
@@ -113,6 +149,7 @@ where
         self.after_parse_with_parser(
             Value::read_options,
             Value::after_parse,
+            None,
             reader,
             endian,
             args,
@@ -120,6 +157,71 @@ where
     }
 }
 
+impl<Ptr, Value> BinWrite for FilePtr<Ptr, Value>
+where
+    Ptr: for<'a> BinWrite<Args<'a> = ()> + IntoSeekFrom + TryFrom<u64>,
+    Value: BinWrite,
+{
+    type Args<'a> = FilePtrWriteArgs<'a, Value::Args<'a>>;
+
+    /// Writes a placeholder offset to the writer and adds the pointed-to value
+    /// to the queue.
+    ///
+    /// The offset will not be correct until [`FilePtrQueue::flush()`] is
+    /// called. If there is no pointed-to value, the current offset is written
+    /// unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If writing the pointed-to value fails, or if the
+    /// [`align`](FilePtrWriteArgs::align) argument is not a power of two, an
+    /// [`Error`] variant will be returned.
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let ptr_pos = writer.stream_position()?;
+        self.ptr.write_options(writer, endian, ())?;
+
+        if let Some(value) = &self.value {
+            if !args.align.is_power_of_two() {
+                return Err(Error::AssertFail {
+                    pos: ptr_pos,
+                    message: alloc::format!("alignment {} is not a power of two", args.align)
+                        .into(),
+                });
+            }
+
+            // The final position of the value is not known until the queue is
+            // flushed, so it is serialized as though it were at position 0 and
+            // later placed at a multiple of its alignment
+            let queue = args.queue;
+            let mark = queue.pending.borrow().len();
+            let mut data = Cursor::new(Vec::new());
+            value.write_options(&mut data, endian, args.inner)?;
+            let children = queue.pending.borrow_mut().split_off(mark);
+
+            queue.pending.borrow_mut().push(PendingValue {
+                ptr_pos,
+                base: args.offset,
+                endian,
+                align: args.align,
+                data: data.into_inner(),
+                children,
+                patch: patch_ptr::<Ptr>,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ptr.size_hint()
+    }
+}
+
 impl<Ptr, Value> FilePtr<Ptr, Value>
 where
     Ptr: for<'a> BinRead<Args<'a> = ()> + IntoSeekFrom,
@@ -127,6 +229,7 @@ where
     fn read_with_parser<R, Parser, AfterParse, Args>(
         parser: Parser,
         after_parse: AfterParse,
+        translate: Option<&dyn Fn(u64) -> Option<u64>>,
         reader: &mut R,
         endian: Endian,
         args: FilePtrArgs<Args>,
@@ -141,7 +244,7 @@ where
             ptr: Ptr::read_options(reader, endian, ())?,
             value: None,
         };
-        file_ptr.after_parse_with_parser(parser, after_parse, reader, endian, args)?;
+        file_ptr.after_parse_with_parser(parser, after_parse, translate, reader, endian, args)?;
         Ok(file_ptr)
     }
 
@@ -149,6 +252,7 @@ where
         &mut self,
         parser: Parser,
         after_parse: AfterParse,
+        translate: Option<&dyn Fn(u64) -> Option<u64>>,
         reader: &mut R,
         endian: Endian,
         args: FilePtrArgs<Args>,
@@ -161,10 +265,60 @@ where
     {
         let relative_to = args.offset;
         let before = reader.stream_position()?;
-        reader.seek(SeekFrom::Start(relative_to))?;
-        reader.seek(self.ptr.into_seek_from())?;
+        // The length of the stream is only needed to report bad offsets, so
+        // it is not looked up for valid pointers
+        let stream_len = |reader: &mut R| -> BinResult<u64> {
+            let len = reader.seek(SeekFrom::End(0))?;
+            reader.seek(SeekFrom::Start(before))?;
+            Ok(len)
+        };
 
-        let mut inner: Value = parser(reader, endian, args.inner.clone())?;
+        let offset = match self.ptr.into_seek_from() {
+            SeekFrom::Start(address) => i128::from(address),
+            SeekFrom::Current(offset) => i128::from(relative_to) + i128::from(offset),
+            SeekFrom::End(offset) => i128::from(stream_len(reader)?) + i128::from(offset),
+        };
+        let offset = if let Some(translate) = translate {
+            let address = u64::try_from(offset)
+                .ok()
+                .and_then(translate)
+                .ok_or_else(|| Error::AssertFail {
+                    pos: before,
+                    message: alloc::format!(
+                        "pointer {:?} does not map to a position in the stream",
+                        self.ptr
+                    )
+                    .into(),
+                })?;
+            i128::from(address)
+        } else {
+            offset
+        };
+        let Ok(pos) = u64::try_from(offset) else {
+            return Err(Error::OffsetOutOfBounds {
+                pos: before,
+                offset,
+                len: stream_len(reader)?,
+            });
+        };
+        reader.seek(SeekFrom::Start(pos))?;
+
+        let mut inner: Value = match parser(reader, endian, args.inner.clone()) {
+            Ok(inner) => inner,
+            Err(err) if err.is_eof() => {
+                let len = stream_len(reader)?;
+                return Err(if pos > len {
+                    Error::OffsetOutOfBounds {
+                        pos: before,
+                        offset,
+                        len,
+                    }
+                } else {
+                    err
+                });
+            }
+            Err(err) => return Err(err),
+        };
 
         after_parse(&mut inner, reader, endian, args.inner)?;
         reader.seek(SeekFrom::Start(before))?;
@@ -180,9 +334,9 @@ where
     ///
     /// # Errors
     ///
-    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
-    #[binrw::parser(reader, endian)]
-    pub fn parse<Args>(args: FilePtrArgs<Args>, ...) -> BinResult<Value>
+    /// If reading fails, an [`Error`] variant will be returned.
+    #[binrw::parser(reader, endian, args_raw)]
+    pub fn parse<Args>(args: FilePtrArgs<Args>) -> BinResult<Value>
     where
         Args: Clone,
         Value: for<'a> BinRead<Args<'a> = Args>,
@@ -190,6 +344,7 @@ where
         Ok(Self::read_with_parser(
             Value::read_options,
             Value::after_parse,
+            None,
             reader,
             endian,
             args,
@@ -204,7 +359,7 @@ where
     ///
     /// # Errors
     ///
-    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    /// If reading fails, an [`Error`] variant will be returned.
     pub fn parse_with<R, F, Args>(
         parser: F,
     ) -> impl Fn(&mut R, Endian, FilePtrArgs<Args>) -> BinResult<Value>
@@ -215,7 +370,10 @@ where
     {
         move |reader, endian, args| {
             let after_parse = |_: &mut Value, _: &mut R, _: Endian, _: Args| Ok(());
-            Ok(Self::read_with_parser(&parser, after_parse, reader, endian, args)?.into_inner())
+            Ok(
+                Self::read_with_parser(&parser, after_parse, None, reader, endian, args)?
+                    .into_inner(),
+            )
         }
     }
 
@@ -226,7 +384,7 @@ where
     ///
     /// # Errors
     ///
-    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    /// If reading fails, an [`Error`] variant will be returned.
     pub fn with<R, F, Args>(
         parser: F,
     ) -> impl Fn(&mut R, Endian, FilePtrArgs<Args>) -> BinResult<Self>
@@ -237,10 +395,224 @@ where
     {
         move |reader, endian, args| {
             let after_parse = |_: &mut Value, _: &mut R, _: Endian, _: Args| Ok(());
-            Self::read_with_parser(&parser, after_parse, reader, endian, args)
+            Self::read_with_parser(&parser, after_parse, None, reader, endian, args)
         }
     }
 
+    /// Custom parser for use with the
+    /// [`parse_with`](crate::docs::attribute#custom-parserswriters) directive that reads and then
+    /// immediately finalizes a [`FilePtr`] whose offset is a format address
+    /// instead of a stream position, returning the pointed-to value as the
+    /// result.
+    ///
+    /// The `translate` function receives the absolute address of the value
+    /// (the [`offset`](FilePtrArgs::offset) argument plus the pointer) and
+    /// returns the corresponding position in the stream, or `None` if the
+    /// address is not mapped. This allows pointers like relative virtual
+    /// addresses in PE files or virtual addresses in ELF files to be read
+    /// without translating each one manually.
+    ///
+    /// # Errors
+    ///
+    /// If the address cannot be translated or reading fails, an
+    /// [`Error`] variant will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use binrw::{prelude::*, io::Cursor, FilePtr32};
+    /// #[derive(BinRead)]
+    /// #[br(big)]
+    /// struct Image {
+    ///     // Addresses start at 0x1000 in memory, but the data starts at 8 in the
+    ///     // file
+    ///     base: u32,
+    ///     #[br(parse_with = FilePtr32::parse_translated(|address| {
+    ///         address.checked_sub(u64::from(base)).map(|offset| offset + 8)
+    ///     }))]
+    ///     entry: u16,
+    /// }
+    ///
+    /// let image: Image = Cursor::new(b"\0\0\x10\0\0\0\x10\x02\0\0\x12\x34").read_be().unwrap();
+    /// assert_eq!(image.entry, 0x1234);
+    /// ```
+    pub fn parse_translated<R, T, Args>(
+        translate: T,
+    ) -> impl Fn(&mut R, Endian, FilePtrArgs<Args>) -> BinResult<Value>
+    where
+        R: Read + Seek,
+        Args: Clone,
+        T: Fn(u64) -> Option<u64>,
+        Value: for<'a> BinRead<Args<'a> = Args>,
+    {
+        move |reader, endian, args| {
+            Ok(Self::with_translation(&translate)(reader, endian, args)?.into_inner())
+        }
+    }
+
+    /// Custom parser for use with the
+    /// [`parse_with`](crate::docs::attribute#custom-parserswriters) directive that reads and then
+    /// immediately finalizes a [`FilePtr`] whose offset is a format address
+    /// instead of a stream position, returning the [`FilePtr`] as the result.
+    ///
+    /// See [`parse_translated()`](Self::parse_translated) for details.
+    ///
+    /// # Errors
+    ///
+    /// If the address cannot be translated or reading fails, an
+    /// [`Error`] variant will be returned.
+    pub fn with_translation<R, T, Args>(
+        translate: T,
+    ) -> impl Fn(&mut R, Endian, FilePtrArgs<Args>) -> BinResult<Self>
+    where
+        R: Read + Seek,
+        Args: Clone,
+        T: Fn(u64) -> Option<u64>,
+        Value: for<'a> BinRead<Args<'a> = Args>,
+    {
+        move |reader, endian, args| {
+            Self::read_with_parser(
+                Value::read_options,
+                Value::after_parse,
+                Some(&translate),
+                reader,
+                endian,
+                args,
+            )
+        }
+    }
+
+    /// Custom parser for use with the
+    /// [`parse_with`](crate::docs::attribute#custom-parserswriters) directive that reads and then
+    /// immediately finalizes an optional [`FilePtr`], returning `None` if the
+    /// pointer is equal to `null`.
+    ///
+    /// This is usually used with a `null` of `0`, but formats which use
+    /// another sentinel value like `u32::MAX` are also supported. Use
+    /// [`write_nullable()`](Self::write_nullable) with the same `null` to
+    /// write the pointer back.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`] variant will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use binrw::{prelude::*, io::Cursor, file_ptr::FilePtrQueue, FilePtr8};
+    /// #[binrw]
+    /// #[brw(big)]
+    /// #[bw(import(queue: &FilePtrQueue))]
+    /// struct Node {
+    ///     value: u8,
+    ///     #[br(parse_with = FilePtr8::with_nullable(0))]
+    ///     #[bw(write_with = FilePtr8::write_nullable(0), args { queue })]
+    ///     next: Option<FilePtr8<u8>>,
+    /// }
+    ///
+    /// let node = Node::read(&mut Cursor::new(b"\x01\0")).unwrap();
+    /// assert!(node.next.is_none());
+    ///
+    /// let node = Node::read(&mut Cursor::new(b"\x01\x02\x03")).unwrap();
+    /// assert_eq!(node.next.as_deref(), Some(&3));
+    ///
+    /// let queue = FilePtrQueue::new();
+    /// let mut output = Cursor::new(Vec::new());
+    /// node.write_args(&mut output, (&queue,)).unwrap();
+    /// queue.flush(&mut output).unwrap();
+    /// assert_eq!(output.into_inner(), b"\x01\x02\x03");
+    /// ```
+    pub fn with_nullable<R, Args>(
+        null: Ptr,
+    ) -> impl Fn(&mut R, Endian, FilePtrArgs<Args>) -> BinResult<Option<Self>>
+    where
+        R: Read + Seek,
+        Args: Clone,
+        Ptr: PartialEq,
+        Value: for<'a> BinRead<Args<'a> = Args>,
+    {
+        move |reader, endian, args| {
+            let ptr = Ptr::read_options(reader, endian, ())?;
+            if ptr == null {
+                return Ok(None);
+            }
+
+            let mut file_ptr = Self { ptr, value: None };
+            file_ptr.after_parse_with_parser(
+                Value::read_options,
+                Value::after_parse,
+                None,
+                reader,
+                endian,
+                args,
+            )?;
+            Ok(Some(file_ptr))
+        }
+    }
+
+    /// Reads the values pointed to by a table of pointers which have already
+    /// been read, using the [rayon](https://docs.rs/rayon) thread pool.
+    ///
+    /// The pointers are split between worker threads, and each worker reads
+    /// from its own clone of `reader`, so this is only useful with readers
+    /// which are cheap to clone, like a [`Cursor`] over a byte slice. The values are
+    /// returned in the same order as `ptrs`. If reading any value fails, the
+    /// error for the earliest pointer is returned.
+    ///
+    /// This function is only available with the `parallel` feature.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`] variant will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use binrw::{args, io::Cursor, BinReaderExt, Endian, FilePtr8};
+    /// let data = b"\x03\x05\x07\0\x01\0\x02\0\x03";
+    /// let mut reader = Cursor::new(&data[..]);
+    /// let ptrs: [u8; 3] = reader.read_le().unwrap();
+    ///
+    /// let values =
+    ///     FilePtr8::<u16>::parse_all_parallel(&reader, Endian::Big, &ptrs, args! {}).unwrap();
+    /// assert_eq!(values, [1, 2, 3]);
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn parse_all_parallel<R, Args>(
+        reader: &R,
+        endian: Endian,
+        ptrs: &[Ptr],
+        args: FilePtrArgs<Args>,
+    ) -> BinResult<Vec<Value>>
+    where
+        R: Read + Seek + Clone + Send,
+        Args: Clone + Send,
+        Ptr: Sync,
+        Value: for<'a> BinRead<Args<'a> = Args> + Send,
+    {
+        use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+        ptrs.par_iter()
+            .map_with((reader.clone(), args), |(reader, args), ptr| {
+                let mut file_ptr = Self {
+                    ptr: *ptr,
+                    value: None,
+                };
+                file_ptr.after_parse_with_parser(
+                    Value::read_options,
+                    Value::after_parse,
+                    None,
+                    reader,
+                    endian,
+                    args.clone(),
+                )?;
+                Ok(file_ptr.into_inner())
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .collect()
+    }
+
     /// Consumes this object, returning the pointed-to value.
     ///
     /// # Panics
@@ -252,6 +624,39 @@ where
     }
 }
 
+impl<Ptr, Value> FilePtr<Ptr, Value>
+where
+    Ptr: for<'a> BinWrite<Args<'a> = ()> + IntoSeekFrom + TryFrom<u64>,
+{
+    /// Custom writer for use with the
+    /// [`write_with`](crate::docs::attribute#custom-parserswriters) directive that writes an
+    /// optional [`FilePtr`], writing `null` as the pointer if there is no
+    /// [`FilePtr`].
+    ///
+    /// See [`with_nullable()`](Self::with_nullable) for details.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`] variant will be returned.
+    pub fn write_nullable<W>(
+        null: Ptr,
+    ) -> impl for<'a> Fn(
+        &Option<Self>,
+        &mut W,
+        Endian,
+        FilePtrWriteArgs<'a, Value::Args<'a>>,
+    ) -> BinResult<()>
+    where
+        W: Write + Seek,
+        Value: BinWrite,
+    {
+        move |file_ptr, writer, endian, args| match file_ptr {
+            Some(file_ptr) => file_ptr.write_options(writer, endian, args),
+            None => null.write_options(writer, endian, ()),
+        }
+    }
+}
+
 /// Dereferences the value.
 ///
 /// # Panics
@@ -309,6 +714,244 @@ where
     }
 }
 
+/// A wrapper type like [`FilePtr`] which only reads the pointed-to value when
+/// it is requested.
+///
+/// Reading a `LazyFilePtr` reads only the pointer. The byte order and
+/// arguments used to read the pointer are stored so that the value can be read
+/// later by calling [`get()`](Self::get) with a reader for the same stream. The
+/// value is read the first time it is requested and cached after that.
+///
+/// This is useful for large files with an offset table where only a few
+/// entries are needed. Since the arguments are stored, arguments for the
+/// pointed-to type cannot borrow any data.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor, file_ptr::LazyFilePtr};
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Archive {
+///     count: u8,
+///     #[br(count = count)]
+///     entries: Vec<LazyFilePtr<u8, u16>>,
+/// }
+///
+/// let mut reader = Cursor::new(b"\x02\x03\x05\x12\x34\xab\xcd");
+/// let archive = Archive::read(&mut reader).unwrap();
+/// assert!(archive.entries[0].value().is_none());
+/// assert_eq!(*archive.entries[1].get(&mut reader).unwrap(), 0xabcd);
+/// assert!(archive.entries[0].value().is_none());
+/// ```
+pub struct LazyFilePtr<Ptr: IntoSeekFrom, Value: BinRead> {
+    /// The raw offset to the value.
+    pub ptr: Ptr,
+
+    endian: Endian,
+    args: FilePtrArgs<Value::Args<'static>>,
+    value: OnceCell<Value>,
+}
+
+impl<Ptr, Value> BinRead for LazyFilePtr<Ptr, Value>
+where
+    Ptr: for<'a> BinRead<Args<'a> = ()> + IntoSeekFrom,
+    Value: BinRead,
+    for<'a> Value::Args<'a>: Clone,
+{
+    type Args<'a> = FilePtrArgs<Value::Args<'static>>;
+
+    /// Reads the offset of the value from the reader.
+    ///
+    /// The actual value will not be read until [`get()`](Self::get) is
+    /// called.
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(Self {
+            ptr: Ptr::read_options(reader, endian, ())?,
+            endian,
+            args,
+            value: OnceCell::new(),
+        })
+    }
+}
+
+impl<Ptr, Value> LazyFilePtr<Ptr, Value>
+where
+    Ptr: for<'a> BinRead<Args<'a> = ()> + IntoSeekFrom,
+    Value: BinRead,
+    for<'a> Value::Args<'a>: Clone,
+{
+    /// Returns the pointed-to value, reading it from `reader` if it has not
+    /// been read yet.
+    ///
+    /// The position of `reader` is restored after the value is read.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`] variant will be returned.
+    /// The value is read again on the next call.
+    pub fn get<R: Read + Seek>(&self, reader: &mut R) -> BinResult<&Value> {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        let mut file_ptr = FilePtr::<Ptr, Value> {
+            ptr: self.ptr,
+            value: None,
+        };
+        file_ptr.after_parse(reader, self.endian, self.args.clone())?;
+        Ok(self.value.get_or_init(|| file_ptr.into_inner()))
+    }
+}
+
+impl<Ptr: IntoSeekFrom, Value: BinRead> LazyFilePtr<Ptr, Value> {
+    /// Returns the pointed-to value if it has already been read.
+    pub fn value(&self) -> Option<&Value> {
+        self.value.get()
+    }
+
+    /// Consumes this object, returning the pointed-to value if it has already
+    /// been read.
+    pub fn into_value(self) -> Option<Value> {
+        self.value.into_inner()
+    }
+}
+
+impl<Ptr, Value> fmt::Debug for LazyFilePtr<Ptr, Value>
+where
+    Ptr: fmt::Debug + IntoSeekFrom,
+    Value: BinRead + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(value) = self.value.get() {
+            fmt::Debug::fmt(value, f)
+        } else {
+            f.debug_tuple("UnreadPointer").field(&self.ptr).finish()
+        }
+    }
+}
+
+/// A queue of pointed-to values waiting to be written by [`FilePtr`].
+///
+/// Writing a `FilePtr` writes a placeholder offset and adds its value to the
+/// queue. Once everything else has been written, [`flush()`](Self::flush)
+/// writes all of the queued values and patches each placeholder with the
+/// actual offset of its value.
+///
+/// Values which contain more `FilePtr`s can use the same queue; their values
+/// are written after all of the values from the previous level.
+#[derive(Default)]
+pub struct FilePtrQueue {
+    pending: RefCell<Vec<PendingValue>>,
+}
+
+impl FilePtrQueue {
+    /// Creates a new empty queue.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if there are no values waiting to be written.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pending.borrow().is_empty()
+    }
+
+    /// Returns the number of values waiting to be written.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pending.borrow().len()
+    }
+
+    /// Writes all queued values starting at the current position of the
+    /// writer and patches their offsets.
+    ///
+    /// Zero bytes are written before values which need to be aligned.
+    ///
+    /// The writer is left positioned after the last written value.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, or if an offset does not fit in its pointer type, an
+    /// [`Error`] variant will be returned.
+    pub fn flush<W: Write + Seek>(&self, writer: &mut W) -> BinResult<()> {
+        let mut pending = VecDeque::from(core::mem::take(&mut *self.pending.borrow_mut()));
+        let mut pos = writer.stream_position()?;
+
+        while let Some(value) = pending.pop_front() {
+            let padding = pos.next_multiple_of(value.align) - pos;
+            crate::__private::write_zeroes(writer, padding)?;
+            pos += padding;
+            writer.write_all(&value.data)?;
+            let end = pos + value.data.len() as u64;
+
+            let offset = pos
+                .checked_sub(value.base)
+                .ok_or_else(|| Error::AssertFail {
+                    pos: value.ptr_pos,
+                    message: alloc::format!(
+                        "value at {pos:#x} is before the pointer base {:#x}",
+                        value.base
+                    )
+                    .into(),
+                })?;
+            writer.seek(SeekFrom::Start(value.ptr_pos))?;
+            (value.patch)(writer, value.endian, value.ptr_pos, offset)?;
+            writer.seek(SeekFrom::Start(end))?;
+
+            pending.extend(value.children.into_iter().map(|mut child| {
+                child.ptr_pos += pos;
+                child
+            }));
+            pos = end;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for FilePtrQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FilePtrQueue")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+struct PendingValue {
+    /// The position of the placeholder offset, relative to the start of the
+    /// parent value if this is a child.
+    ptr_pos: u64,
+    base: u64,
+    endian: Endian,
+    /// The alignment of the position of the value.
+    align: u64,
+    data: Vec<u8>,
+    children: Vec<PendingValue>,
+    patch: fn(&mut dyn WriteSeek, Endian, u64, u64) -> BinResult<()>,
+}
+
+fn patch_ptr<Ptr>(
+    mut writer: &mut dyn WriteSeek,
+    endian: Endian,
+    ptr_pos: u64,
+    offset: u64,
+) -> BinResult<()>
+where
+    Ptr: for<'a> BinWrite<Args<'a> = ()> + TryFrom<u64>,
+{
+    let ptr = Ptr::try_from(offset).map_err(|_| Error::AssertFail {
+        pos: ptr_pos,
+        message: alloc::format!("offset {offset:#x} does not fit in the pointer type").into(),
+    })?;
+    ptr.write_options(&mut writer, endian, ())
+}
+
 /// A trait to convert from an integer into
 /// [`SeekFrom::Current`](crate::io::SeekFrom::Current).
 pub trait IntoSeekFrom: Copy + fmt::Debug {
@@ -316,19 +959,40 @@ pub trait IntoSeekFrom: Copy + fmt::Debug {
     fn into_seek_from(self) -> SeekFrom;
 }
 
+// Offsets which do not fit in an `i64` saturate instead of panicking, since
+// they may come from untrusted data. Seeking to a saturated offset fails
+// normally.
 macro_rules! impl_into_seek_from {
     ($($t:ty),*) => {
         $(
             impl IntoSeekFrom for $t {
                 fn into_seek_from(self) -> SeekFrom {
-                    SeekFrom::Current(TryInto::try_into(self).unwrap())
+                    SeekFrom::Current(TryInto::try_into(self).unwrap_or(i64::MAX))
                 }
             }
         )*
     };
 }
 
-impl_into_seek_from!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+impl_into_seek_from!(u8, u16, u32, u64, u128);
+
+macro_rules! impl_into_seek_from_signed {
+    ($($t:ty),*) => {
+        $(
+            impl IntoSeekFrom for $t {
+                fn into_seek_from(self) -> SeekFrom {
+                    SeekFrom::Current(TryInto::try_into(self).unwrap_or(if self.is_negative() {
+                        i64::MIN
+                    } else {
+                        i64::MAX
+                    }))
+                }
+            }
+        )*
+    };
+}
+
+impl_into_seek_from_signed!(i8, i16, i32, i64, i128);
 
 macro_rules! impl_into_seek_from_for_non_zero {
     ($($t:ty),*) => {
@@ -370,3 +1034,32 @@ pub struct FilePtrArgs<Inner> {
     #[named_args(try_optional)]
     pub inner: Inner,
 }
+
+/// Named arguments for the [`BinWrite::write_options()`] implementation of
+/// [`FilePtr`].
+///
+/// The `inner` field can be omitted completely if the inner type doesn’t
+/// require arguments, in which case a default value will be used.
+#[derive(Clone, NamedArgs)]
+pub struct FilePtrWriteArgs<'a, Inner> {
+    /// The queue which receives the pointed-to value.
+    pub queue: &'a FilePtrQueue,
+
+    /// An absolute offset subtracted from the position of the pointed-to value
+    /// to calculate the [`FilePtr::ptr`](crate::FilePtr::ptr) offset.
+    #[named_args(default = 0)]
+    pub offset: u64,
+
+    /// The alignment of the position where the pointed-to value is written,
+    /// which must be a power of two.
+    ///
+    /// The value is serialized as though it were written at position 0, so
+    /// this must be at least the largest alignment the value uses
+    /// internally.
+    #[named_args(default = 1)]
+    pub align: u64,
+
+    /// The [arguments](crate::BinWrite::Args) for the inner type.
+    #[named_args(try_optional)]
+    pub inner: Inner,
+}
@@ -0,0 +1,202 @@
+//! Type definitions for fixed-point numbers.
+
+use crate::{
+    io::{Read, Seek, Write},
+    BinRead, BinResult, BinWrite, Endian,
+};
+use core::fmt;
+
+/// A binary fixed-point number with `FRAC` fractional bits, stored as the
+/// integer `I`.
+///
+/// The value of the number is the stored integer divided by 2<sup>`FRAC`</sup>.
+/// For example, the Q16.16 format used by many audio and 3D formats is
+/// `FixedPoint<i32, 16>`, and the unsigned Q8.8 format is
+/// `FixedPoint<u16, 8>`. `FRAC` must not be greater than the number of bits
+/// in `I`, which is checked at compile time:
+///
+/// ```compile_fail
+/// # use binrw::fixed_point::FixedPoint;
+/// let _ = FixedPoint::<u8, 9>(1).to_f64();
+/// ```
+///
+/// Use [`map`](crate::docs::attribute#map) to read a fixed-point number into
+/// a floating-point field, or [`FixedPoint::from_f32`] and
+/// [`FixedPoint::from_f64`] to convert a floating-point number back.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{fixed_point::FixedPoint, io::Cursor, BinRead, BinWrite};
+///
+/// #[derive(BinRead, BinWrite)]
+/// struct Vertex {
+///     x: FixedPoint<i32, 16>,
+///     #[br(map = |y: FixedPoint<i16, 8>| y.to_f32())]
+///     #[bw(map = |y: &f32| FixedPoint::<i16, 8>::from_f32(*y).unwrap())]
+///     y: f32,
+/// }
+///
+/// let data = b"\x00\x80\xfe\xff\x40\x01";
+/// let vertex = Vertex::read_le(&mut Cursor::new(data)).unwrap();
+/// assert_eq!(vertex.x.to_f64(), -1.5);
+/// assert_eq!(vertex.y, 1.25);
+///
+/// let mut out = Cursor::new(Vec::new());
+/// vertex.write_le(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), data);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct FixedPoint<I, const FRAC: u32>(
+    /// The raw stored integer.
+    pub I,
+);
+
+macro_rules! impl_fixed_point {
+    ($($ty:ty),*) => {
+        $(
+            impl<const FRAC: u32> FixedPoint<$ty, FRAC> {
+                /// The value of one unit of the stored integer.
+                const SCALE: f64 = {
+                    assert!(
+                        FRAC <= <$ty>::BITS,
+                        concat!("too many fractional bits for ", stringify!($ty))
+                    );
+                    // Lint: Powers of two are exactly representable
+                    #[allow(clippy::cast_precision_loss)]
+                    let scale = (1_u128 << FRAC) as f64;
+                    scale
+                };
+
+                /// Converts the number to an [`f64`].
+                ///
+                /// The result is rounded to the nearest [`f64`] if the stored
+                /// integer has more significant bits than an [`f64`] can
+                /// represent.
+                #[must_use]
+                pub fn to_f64(self) -> f64 {
+                    // Lint: Rounding is documented
+                    #[allow(clippy::cast_precision_loss, clippy::cast_lossless)]
+                    let value = self.0 as f64;
+                    value / Self::SCALE
+                }
+
+                /// Converts the number to an [`f32`].
+                ///
+                /// The result is rounded to the nearest [`f32`].
+                #[must_use]
+                pub fn to_f32(self) -> f32 {
+                    // Lint: Rounding is documented
+                    #[allow(clippy::cast_possible_truncation)]
+                    let value = self.to_f64() as f32;
+                    value
+                }
+
+                /// Converts an [`f64`] to a fixed-point number, rounding to
+                /// the nearest representable value.
+                ///
+                /// Returns `None` if the value is not finite or is out of
+                /// range.
+                #[must_use]
+                pub fn from_f64(value: f64) -> Option<Self> {
+                    let scaled = value * Self::SCALE;
+                    if !scaled.is_finite() {
+                        return None;
+                    }
+
+                    // Lint: Casts saturate, and saturated values are out of
+                    // range of every supported type
+                    #[allow(clippy::cast_possible_truncation)]
+                    let bits = round(scaled) as i128;
+                    <$ty>::try_from(bits).ok().map(Self)
+                }
+
+                /// Converts an [`f32`] to a fixed-point number, rounding to
+                /// the nearest representable value.
+                ///
+                /// Returns `None` if the value is not finite or is out of
+                /// range.
+                #[must_use]
+                pub fn from_f32(value: f32) -> Option<Self> {
+                    Self::from_f64(value.into())
+                }
+            }
+
+            impl<const FRAC: u32> fmt::Display for FixedPoint<$ty, FRAC> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(&self.to_f64(), f)
+                }
+            }
+
+            impl<const FRAC: u32> From<FixedPoint<$ty, FRAC>> for f64 {
+                fn from(value: FixedPoint<$ty, FRAC>) -> Self {
+                    value.to_f64()
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_point!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+/// Rounds to the nearest integer, with ties away from zero.
+#[cfg(feature = "std")]
+fn round(value: f64) -> f64 {
+    value.round()
+}
+
+/// Rounds to the nearest integer, with ties away from zero.
+///
+/// `f64::round` is not available in `no_std`.
+#[cfg(not(feature = "std"))]
+fn round(value: f64) -> f64 {
+    // Every `f64` at least this large is already an integer
+    const MIN_INTEGRAL: f64 = 4_503_599_627_370_496.0;
+    if !(-MIN_INTEGRAL < value && value < MIN_INTEGRAL) {
+        return value;
+    }
+
+    // Lint: The value is in range, and removing the integer part of a float
+    // is exact
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    let trunc = value as i64 as f64;
+    let frac = value - trunc;
+    if frac >= 0.5 {
+        trunc + 1.0
+    } else if frac <= -0.5 {
+        trunc - 1.0
+    } else {
+        trunc
+    }
+}
+
+impl<I, const FRAC: u32> BinRead for FixedPoint<I, FRAC>
+where
+    I: for<'a> BinRead<Args<'a> = ()>,
+{
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        I::read_options(reader, endian, ()).map(Self)
+    }
+}
+
+impl<I, const FRAC: u32> BinWrite for FixedPoint<I, FRAC>
+where
+    I: for<'a> BinWrite<Args<'a> = ()>,
+{
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.0.write_options(writer, endian, ())
+    }
+}
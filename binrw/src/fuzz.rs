@@ -0,0 +1,230 @@
+//! Helpers for fuzzing readers and writers.
+//!
+//! [`roundtrip`] reads a value from arbitrary bytes, writes it back out, and
+//! reads the written bytes again, reporting a [`Divergence`] if the value
+//! cannot be written or does not survive the round trip. This can be called
+//! directly from a `cargo-fuzz` harness:
+//!
+//! ```ignore
+//! #![no_main]
+//! libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+//!     binrw::fuzz::roundtrip::<my_format::Header>(data).unwrap();
+//! });
+//! ```
+//!
+//! For harnesses which generate structured values instead of bytes,
+//! [`roundtrip_value`] writes a value and checks that it reads back the same.
+//! With the `arbitrary` feature, `roundtrip_arbitrary` does this for a value
+//! generated from the fuzzer's bytes by the
+//! [`arbitrary`](https://docs.rs/arbitrary) crate:
+//!
+//! ```ignore
+//! #![no_main]
+//! libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+//!     binrw::fuzz::roundtrip_arbitrary::<my_format::Header>(data).unwrap();
+//! });
+//! ```
+
+use crate::{io::Cursor, BinRead, BinReaderExt, BinWrite, Endian, Error};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Reads `data` as a `T`, writes it, and reads the written bytes again, using
+/// little-endian byte order for types which do not set their own and default
+/// arguments.
+///
+/// Returns the value read, or `None` if `data` is not a valid `T`.
+///
+/// # Errors
+///
+/// If the value cannot be written, or the written bytes do not read as an
+/// equal value, a [`Divergence`] is returned.
+pub fn roundtrip<T>(data: &[u8]) -> Result<Option<T>, Divergence<T>>
+where
+    T: BinRead + BinWrite + PartialEq,
+    for<'a> <T as BinRead>::Args<'a>: Clone + Default,
+    for<'a> <T as BinWrite>::Args<'a>: Default,
+{
+    roundtrip_with(data, Endian::Little, Default::default(), Default::default())
+}
+
+/// Like [`roundtrip`], but with the given byte order and arguments.
+///
+/// # Errors
+///
+/// If the value cannot be written, or the written bytes do not read as an
+/// equal value, a [`Divergence`] is returned.
+pub fn roundtrip_with<'a, T>(
+    data: &[u8],
+    endian: Endian,
+    read_args: <T as BinRead>::Args<'a>,
+    write_args: <T as BinWrite>::Args<'_>,
+) -> Result<Option<T>, Divergence<T>>
+where
+    T: BinRead + BinWrite + PartialEq,
+    <T as BinRead>::Args<'a>: Clone,
+{
+    let Ok(value) = Cursor::new(data).read_type_args::<T>(endian, read_args.clone()) else {
+        return Ok(None);
+    };
+
+    let mut writer = Cursor::new(Vec::new());
+    if let Err(error) = value.write_options(&mut writer, endian, write_args) {
+        return Err(Divergence::Write { value, error });
+    }
+    let written = writer.into_inner();
+
+    match Cursor::new(&written).read_type_args::<T>(endian, read_args) {
+        Ok(reread) if reread == value => Ok(Some(value)),
+        Ok(reread) => Err(Divergence::Value {
+            first: value,
+            second: reread,
+            written,
+        }),
+        Err(error) => Err(Divergence::Reread {
+            value,
+            written,
+            error,
+        }),
+    }
+}
+
+/// Generates a `T` from `data` using its [`Arbitrary`](arbitrary::Arbitrary)
+/// implementation, writes it, and checks that the written bytes read as an
+/// equal value, using little-endian byte order for types which do not set
+/// their own and default arguments.
+///
+/// Returns the generated value, or `None` if `data` cannot generate a `T`.
+/// Like [`roundtrip_value`], values which cannot be written are not
+/// considered a divergence.
+///
+/// This function is only available with the `arbitrary` feature.
+///
+/// # Errors
+///
+/// If the written bytes do not read as an equal value, a [`Divergence`] is
+/// returned.
+#[cfg(feature = "arbitrary")]
+pub fn roundtrip_arbitrary<'a, T>(data: &'a [u8]) -> Result<Option<T>, Divergence<T>>
+where
+    T: arbitrary::Arbitrary<'a> + BinRead + BinWrite + Clone + PartialEq,
+    for<'b> <T as BinRead>::Args<'b>: Clone + Default,
+    for<'b> <T as BinWrite>::Args<'b>: Default,
+{
+    let Ok(value) = T::arbitrary_take_rest(arbitrary::Unstructured::new(data)) else {
+        return Ok(None);
+    };
+    roundtrip_value(
+        &value,
+        Endian::Little,
+        Default::default(),
+        Default::default(),
+    )?;
+    Ok(Some(value))
+}
+
+/// Writes `value` and checks that the written bytes read as an equal value.
+///
+/// Values which cannot be written, such as those which fail an assertion, are
+/// not considered a divergence.
+///
+/// # Errors
+///
+/// If the written bytes do not read as an equal value, a [`Divergence`] is
+/// returned.
+pub fn roundtrip_value<'a, T>(
+    value: &T,
+    endian: Endian,
+    read_args: <T as BinRead>::Args<'a>,
+    write_args: <T as BinWrite>::Args<'_>,
+) -> Result<(), Divergence<T>>
+where
+    T: BinRead + BinWrite + Clone + PartialEq,
+    <T as BinRead>::Args<'a>: Clone,
+{
+    let mut writer = Cursor::new(Vec::new());
+    if value
+        .write_options(&mut writer, endian, write_args)
+        .is_err()
+    {
+        return Ok(());
+    }
+    let written = writer.into_inner();
+
+    match Cursor::new(&written).read_type_args::<T>(endian, read_args) {
+        Ok(reread) if reread == *value => Ok(()),
+        Ok(reread) => Err(Divergence::Value {
+            first: value.clone(),
+            second: reread,
+            written,
+        }),
+        Err(error) => Err(Divergence::Reread {
+            value: value.clone(),
+            written,
+            error,
+        }),
+    }
+}
+
+/// A difference between a value and the result of writing and reading it
+/// again.
+pub enum Divergence<T> {
+    /// The value could not be written.
+    Write {
+        /// The value.
+        value: T,
+        /// The error returned by the writer.
+        error: Error,
+    },
+    /// The written bytes could not be read.
+    Reread {
+        /// The value.
+        value: T,
+        /// The bytes written for the value.
+        written: Vec<u8>,
+        /// The error returned by the reader.
+        error: Error,
+    },
+    /// The written bytes were read as a different value.
+    Value {
+        /// The original value.
+        first: T,
+        /// The value read from the written bytes.
+        second: T,
+        /// The bytes written for the original value.
+        written: Vec<u8>,
+    },
+}
+
+impl<T: fmt::Debug> fmt::Display for Divergence<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Write { value, error } => write!(f, "writing {value:?} failed: {error}"),
+            Self::Reread {
+                value,
+                written,
+                error,
+            } => write!(
+                f,
+                "reading {written:02x?} written for {value:?} failed: {error}"
+            ),
+            Self::Value {
+                first,
+                second,
+                written,
+            } => write!(
+                f,
+                "{first:?} was written as {written:02x?}, which reads as {second:?}"
+            ),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Divergence<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> std::error::Error for Divergence<T> {}
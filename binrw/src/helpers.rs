@@ -1,10 +1,11 @@
 //! Helper functions for reading data.
 
 use crate::{
-    io::{self, Read, Seek},
-    BinRead, BinResult, Endian, Error,
+    __private::Required,
+    io::{self, Read, Seek, SeekFrom, Write},
+    BinRead, BinResult, BinWrite, Endian, Error,
 };
-use alloc::vec::Vec;
+use alloc::{collections::BTreeSet, vec::Vec};
 use core::iter::from_fn;
 
 /// Creates a parser that reads items into a collection until a condition is
@@ -451,14 +452,14 @@ where
     move |reader, endian, args| {
         let mut container = core::iter::empty::<T>().collect::<Ret>();
 
-        vec_fast_int!(try (i8 i16 u16 i32 u32 i64 u64 i128 u128) using (container, reader, endian, n) else {
+        vec_fast_int!(try (i8 i16 u16 i32 u32 i64 u64 i128 u128 f32 f64) using (container, reader, endian, n) else {
             // This extra branch for `Vec<u8>` makes it faster than
             // `vec_fast_int`, but *only* because `vec_fast_int` is not allowed
             // to use unsafe code to eliminate the unnecessary zero-fill.
             // Otherwise, performance would be identical and it could be
             // deleted.
             if let Some(bytes) = <dyn core::any::Any>::downcast_mut::<Vec<u8>>(&mut container) {
-                bytes.reserve_exact(n);
+                bytes.reserve_exact(crate::untrusted::preallocation::<u8>(n));
                 let byte_count = reader
                     .take(n.try_into().map_err(not_enough_bytes)?)
                     .read_to_end(bytes)?;
@@ -477,8 +478,514 @@ where
     }
 }
 
-#[binrw::parser(reader, endian)]
-fn default_reader<'a, T: BinRead>(args: T::Args<'a>, ...) -> BinResult<T>
+/// Creates a parser that reads items into a collection until exactly N bytes
+/// have been consumed.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`].
+///
+/// # Errors
+///
+/// If an item ends past the end of the N-byte region, or reading an item does
+/// not advance the stream, an [`Error::AssertFail`] variant will be
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::count_bytes, io::Cursor, BinReaderExt};
+/// #[derive(BinRead)]
+/// struct CountBytes {
+///     size: u8,
+///
+///     #[br(parse_with = count_bytes(size.into()))]
+///     data: Vec<u16>,
+/// }
+///
+/// # let mut x = Cursor::new(b"\x04\0\x01\0\x02");
+/// # let x: CountBytes = x.read_be().unwrap();
+/// # assert_eq!(x.data, &[1, 2]);
+/// ```
+pub fn count_bytes<R, T, Arg, Ret>(n: u64) -> impl Fn(&mut R, Endian, Arg) -> BinResult<Ret>
+where
+    T: for<'a> BinRead<Args<'a> = Arg>,
+    R: Read + Seek,
+    Arg: Clone,
+    Ret: FromIterator<T>,
+{
+    count_bytes_with(n, default_reader)
+}
+
+/// Creates a parser that uses a given function to read items into a collection
+/// until exactly N bytes have been consumed.
+///
+/// The given `read` function should return one item each time it is called.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`].
+///
+/// # Errors
+///
+/// If an item ends past the end of the N-byte region, or reading an item does
+/// not advance the stream, an [`Error::AssertFail`] variant will be
+/// returned.
+///
+/// # Examples
+///
+/// Reading a two-dimensional `VecDeque` by combining [`count_bytes_with`] and
+/// [`count`]:
+///
+/// ```
+/// # use binrw::{BinRead, helpers::{count, count_bytes_with}, io::Cursor, BinReaderExt};
+/// # use std::collections::VecDeque;
+/// #[derive(BinRead)]
+/// struct CountBytes {
+///     size: u8,
+///
+///     #[br(parse_with = count_bytes_with(size.into(), count(2)))]
+///     data: VecDeque<VecDeque<u8>>,
+/// }
+///
+/// # let mut x = Cursor::new(b"\x04\x01\x02\x03\x04");
+/// # let x: CountBytes = x.read_be().unwrap();
+/// # assert_eq!(x.data, &[[1, 2], [3, 4]]);
+/// ```
+pub fn count_bytes_with<R, T, Arg, ReadFn, Ret>(
+    n: u64,
+    read: ReadFn,
+) -> impl Fn(&mut R, Endian, Arg) -> BinResult<Ret>
+where
+    R: Read + Seek,
+    Arg: Clone,
+    ReadFn: Fn(&mut R, Endian, Arg) -> BinResult<T>,
+    Ret: FromIterator<T>,
+{
+    move |reader, endian, args| {
+        let start = reader.stream_position()?;
+        let end = start.checked_add(n).ok_or_else(|| Error::AssertFail {
+            pos: start,
+            message: alloc::format!("byte count {n} overflows the stream position").into(),
+        })?;
+        let mut last = start;
+        from_fn(|| {
+            if last == end {
+                return None;
+            }
+
+            Some(read(reader, endian, args.clone()).and_then(|value| {
+                let pos = reader.stream_position()?;
+                if pos > end {
+                    Err(Error::AssertFail {
+                        pos: last,
+                        message: alloc::format!(
+                            "item ended {} byte(s) past the end of a {n}-byte region",
+                            pos - end
+                        )
+                        .into(),
+                    })
+                } else if pos <= last {
+                    Err(Error::AssertFail {
+                        pos: last,
+                        message: "item did not advance the stream".into(),
+                    })
+                } else {
+                    last = pos;
+                    Ok(value)
+                }
+            }))
+        })
+        .fuse()
+        .collect()
+    }
+}
+
+/// A source of lengths for collections whose sizes are described by
+/// out-of-band metadata, like an index file which describes the records in a
+/// separate data file.
+///
+/// This trait is implemented for slices and [`Vec`]s of integers, where each
+/// item is the length of one collection. It can be implemented for other
+/// types which store lengths in a different form.
+pub trait LengthSource {
+    /// Returns the number of collections described by this source.
+    fn count(&self) -> usize;
+
+    /// Returns the length of the collection at `index`, or `None` if the
+    /// length does not fit in a `usize`.
+    fn length(&self, index: usize) -> Option<usize>;
+}
+
+impl<T: Copy + TryInto<usize>> LengthSource for [T] {
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn length(&self, index: usize) -> Option<usize> {
+        self.get(index).and_then(|length| (*length).try_into().ok())
+    }
+}
+
+impl<T: Copy + TryInto<usize>> LengthSource for Vec<T> {
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn length(&self, index: usize) -> Option<usize> {
+        self.as_slice().length(index)
+    }
+}
+
+/// Creates a parser that reads one collection for each length in an external
+/// [`LengthSource`].
+///
+/// This is useful when the sizes of the collections in a stream are not
+/// stored in the stream itself, but are instead passed as
+/// [arguments](crate::docs::attribute#arguments) from some other source.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::count_from, io::Cursor};
+/// #[derive(BinRead)]
+/// #[br(import(lengths: &[u8]))]
+/// struct Records {
+///     #[br(parse_with = count_from(lengths))]
+///     records: Vec<Vec<u8>>,
+/// }
+///
+/// let lengths = [2, 0, 1];
+/// let records = Records::read_le_args(&mut Cursor::new(b"\x01\x02\x03"), (&lengths,)).unwrap();
+/// assert_eq!(records.records, [vec![1, 2], vec![], vec![3]]);
+/// ```
+pub fn count_from<'a, R, T, Arg, Ret, L>(
+    lengths: &'a L,
+) -> impl Fn(&mut R, Endian, Arg) -> BinResult<Vec<Ret>> + 'a
+where
+    T: for<'b> BinRead<Args<'b> = Arg>,
+    R: Read + Seek,
+    Arg: Clone,
+    Ret: FromIterator<T> + 'static,
+    L: LengthSource + ?Sized,
+{
+    move |reader, endian, args| {
+        (0..lengths.count())
+            .map(|index| {
+                let pos = reader.stream_position()?;
+                let length = lengths.length(index).ok_or_else(|| Error::AssertFail {
+                    pos,
+                    message: alloc::format!("external length {index} is out of range").into(),
+                })?;
+                count(length)(reader, endian, args.clone())
+            })
+            .collect()
+    }
+}
+
+/// Creates a parser that reads the nodes of a linked list stored in the
+/// stream, returning them as a collection.
+///
+/// The first node is read from the current position. The `next` function is
+/// then called with each node to get the absolute position of the following
+/// node, or `None` if it is the last node. After reading, the reader is moved
+/// back to the end of the first node.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`].
+///
+/// # Errors
+///
+/// If a node links back to a node which has already been read, or if the list
+/// is longer than `max` nodes, an [`Error::AssertFail`] will be returned. If
+/// reading fails, an [`Error`] variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::follow_links, io::Cursor};
+/// #[derive(BinRead)]
+/// struct Block {
+///     next: u8,
+///     value: u8,
+/// }
+///
+/// #[derive(BinRead)]
+/// struct Chain {
+///     #[br(parse_with = follow_links(16, |block: &Block| {
+///         (block.next != 0).then(|| block.next.into())
+///     }))]
+///     blocks: Vec<Block>,
+///     after: u8,
+/// }
+///
+/// let chain = Chain::read_le(&mut Cursor::new(b"\x04\x01\xff\0\x06\x02\0\x03")).unwrap();
+/// let values = chain.blocks.iter().map(|block| block.value).collect::<Vec<_>>();
+/// assert_eq!(values, [1, 2, 3]);
+/// assert_eq!(chain.after, 0xff);
+///
+/// let error = Chain::read_le(&mut Cursor::new(b"\x02\x01\x02\x02")).err().unwrap();
+/// assert!(error.to_string().contains("cycle"));
+/// ```
+pub fn follow_links<R, T, Arg, NextFn, Ret>(
+    max: usize,
+    next: NextFn,
+) -> impl Fn(&mut R, Endian, Arg) -> BinResult<Ret>
+where
+    T: for<'a> BinRead<Args<'a> = Arg>,
+    R: Read + Seek,
+    Arg: Clone,
+    NextFn: Fn(&T) -> Option<u64>,
+    Ret: FromIterator<T>,
+{
+    move |reader, endian, args| {
+        let mut visited = BTreeSet::new();
+        let mut nodes = Vec::new();
+        let mut pos = reader.stream_position()?;
+        let mut end_of_first = None;
+        loop {
+            if nodes.len() == max {
+                return Err(Error::AssertFail {
+                    pos,
+                    message: alloc::format!("linked list is longer than {max} nodes").into(),
+                });
+            }
+
+            reader.seek(SeekFrom::Start(pos))?;
+            let node = default_reader(reader, endian, args.clone())?;
+            end_of_first.get_or_insert(reader.stream_position()?);
+            visited.insert(pos);
+
+            let Some(next_pos) = next(&node) else {
+                nodes.push(node);
+                break;
+            };
+            nodes.push(node);
+
+            if visited.contains(&next_pos) {
+                return Err(Error::AssertFail {
+                    pos,
+                    message: alloc::format!("linked list contains a cycle back to {next_pos:#x}")
+                        .into(),
+                });
+            }
+            pos = next_pos;
+        }
+
+        if let Some(end_of_first) = end_of_first {
+            reader.seek(SeekFrom::Start(end_of_first))?;
+        }
+        Ok(nodes.into_iter().collect())
+    }
+}
+
+/// Reads an index from one reader, then reads data from a second reader using
+/// the index as an argument.
+///
+/// This coordinates the two readers of formats which store metadata
+/// separately from bulk data, like an index file which describes the records
+/// in a data file. The data type receives a reference to the index as its
+/// only argument, so any of its directives (like `count`) can use values from
+/// the index.
+///
+/// # Errors
+///
+/// If reading either the index or the data fails, an [`Error`] variant will
+/// be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::{count_from, read_with_index}, io::Cursor, Endian};
+/// #[derive(BinRead)]
+/// struct Index {
+///     count: u8,
+///     #[br(count = count)]
+///     lengths: Vec<u8>,
+/// }
+///
+/// #[derive(BinRead)]
+/// #[br(import(index: &Index))]
+/// struct Data {
+///     #[br(parse_with = count_from(&index.lengths))]
+///     records: Vec<Vec<u8>>,
+/// }
+///
+/// let mut index_file = Cursor::new(b"\x02\x01\x03");
+/// let mut data_file = Cursor::new(b"\x01\x02\x03\x04");
+/// let (index, data) =
+///     read_with_index::<Index, Data, _, _>(&mut index_file, &mut data_file, Endian::Little)
+///         .unwrap();
+/// assert_eq!(index.count, 2);
+/// assert_eq!(data.records, [vec![1], vec![2, 3, 4]]);
+/// ```
+pub fn read_with_index<I, T, IndexReader, DataReader>(
+    index_reader: &mut IndexReader,
+    data_reader: &mut DataReader,
+    endian: Endian,
+) -> BinResult<(I, T)>
+where
+    I: for<'a> BinRead<Args<'a> = ()>,
+    T: for<'a> BinRead<Args<'a> = (&'a I,)>,
+    IndexReader: Read + Seek,
+    DataReader: Read + Seek,
+{
+    let index = I::read_options(index_reader, endian, ())?;
+    let data = T::read_options(data_reader, endian, (&index,))?;
+    Ok((index, data))
+}
+
+/// Creates a parser that borrows `len` bytes from `source` instead of copying
+/// them.
+///
+/// The reader must be reading from `source` itself (e.g. using
+/// [`Cursor::new(source)`](crate::io::Cursor)), since the current position
+/// of the reader is used as the offset into `source`. The reader is advanced
+/// past the borrowed bytes.
+///
+/// To borrow from the input buffer, pass it to each type which needs it as an
+/// [argument](crate::docs::attribute#arguments) using the lifetime of the
+/// type.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::borrow_bytes, io::Cursor};
+/// #[derive(BinRead)]
+/// #[br(import(source: &'a [u8]))]
+/// struct Packet<'a> {
+///     len: u8,
+///
+///     #[br(parse_with = borrow_bytes(source, len.into()))]
+///     data: &'a [u8],
+/// }
+///
+/// let source = b"\x03\x01\x02\x03";
+/// let packet = Packet::read_be_args(&mut Cursor::new(source), (source,)).unwrap();
+/// assert_eq!(packet.data, &[1, 2, 3]);
+/// ```
+pub fn borrow_bytes<'a, R>(
+    source: &'a [u8],
+    len: usize,
+) -> impl Fn(&mut R, Endian, ()) -> BinResult<&'a [u8]>
+where
+    R: Read + Seek,
+{
+    move |reader, _, ()| {
+        let pos = reader.stream_position()?;
+        let bytes = usize::try_from(pos)
+            .ok()
+            .and_then(|start| source.get(start..start.checked_add(len)?))
+            .ok_or_else(|| not_enough_bytes(()))?;
+        reader.seek(io::SeekFrom::Start(pos + bytes.len() as u64))?;
+        Ok(bytes)
+    }
+}
+
+/// Creates a parser that borrows a UTF-8 string of `len` bytes from `source`
+/// instead of copying it.
+///
+/// This works the same way as [`borrow_bytes`], and additionally returns an
+/// error if the borrowed bytes are not valid UTF-8.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::borrow_str, io::Cursor};
+/// #[derive(BinRead)]
+/// #[br(import(source: &'a [u8]))]
+/// struct Name<'a> {
+///     len: u8,
+///
+///     #[br(parse_with = borrow_str(source, len.into()))]
+///     name: &'a str,
+/// }
+///
+/// let source = b"\x04Rudy";
+/// let name = Name::read_be_args(&mut Cursor::new(source), (source,)).unwrap();
+/// assert_eq!(name.name, "Rudy");
+/// ```
+pub fn borrow_str<'a, R>(
+    source: &'a [u8],
+    len: usize,
+) -> impl Fn(&mut R, Endian, ()) -> BinResult<&'a str>
+where
+    R: Read + Seek,
+{
+    let read_bytes = borrow_bytes(source, len);
+    move |reader, endian, ()| {
+        let pos = reader.stream_position()?;
+        core::str::from_utf8(read_bytes(reader, endian, ())?).map_err(|err| Error::Custom {
+            pos,
+            err: alloc::boxed::Box::new(err),
+        })
+    }
+}
+
+/// The [arguments](BinRead::Args) type used to read a `T`.
+///
+/// This is a shorter way to write `<T as BinRead>::Args<'a>`.
+pub type ArgsOf<'a, T> = <T as BinRead>::Args<'a>;
+
+/// The [arguments](BinWrite::Args) type used to write a `T`.
+///
+/// This is a shorter way to write `<T as BinWrite>::Args<'a>`.
+pub type WriteArgsOf<'a, T> = <T as BinWrite>::Args<'a>;
+
+/// Returns the default arguments for reading a `T`.
+///
+/// This only compiles if the arguments of `T` have a default value, so it can
+/// also be used to require that a type can be read without arguments.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{helpers::{args_of, ArgsOf}, io::{Cursor, Read, Seek}, BinRead, BinResult, Endian};
+/// fn read_pair<R, T>(reader: &mut R, args: ArgsOf<'_, T>) -> BinResult<(T, u16)>
+/// where
+///     R: Read + Seek,
+///     T: BinRead,
+/// {
+///     let value = T::read_options(reader, Endian::Little, args)?;
+///     let tag = u16::read_options(reader, Endian::Little, args_of::<u16>())?;
+///     Ok((value, tag))
+/// }
+///
+/// let mut data = Cursor::new(b"\x01\x02\0");
+/// let (value, tag) = read_pair::<_, u8>(&mut data, args_of::<u8>()).unwrap();
+/// assert_eq!((value, tag), (1, 2));
+/// ```
+#[must_use]
+pub fn args_of<'a, T>() -> ArgsOf<'a, T>
+where
+    T: BinRead,
+    ArgsOf<'a, T>: Required,
+{
+    <ArgsOf<'a, T> as Required>::args()
+}
+
+/// Returns the default arguments for writing a `T`.
+///
+/// This only compiles if the arguments of `T` have a default value.
+#[must_use]
+pub fn write_args_of<'a, T>() -> WriteArgsOf<'a, T>
+where
+    T: BinWrite,
+    WriteArgsOf<'a, T>: Required,
+{
+    <WriteArgsOf<'a, T> as Required>::args()
+}
+
+/// Returns the name of the [arguments](BinRead::Args) type used to read a
+/// `T`.
+///
+/// Like [`core::any::type_name`], the returned name is only meant for
+/// diagnostics.
+#[must_use]
+pub fn args_type_name<T: BinRead>() -> &'static str {
+    core::any::type_name::<ArgsOf<'static, T>>()
+}
+
+#[binrw::parser(reader, endian, args_raw)]
+fn default_reader<'a, T: BinRead>(args: T::Args<'a>) -> BinResult<T>
 where
     T::Args<'a>: Clone,
 {
@@ -494,6 +1001,56 @@ fn not_enough_bytes<T>(_: T) -> Error {
     ))
 }
 
+/// A primitive type whose lists can be read and written in bulk by
+/// reinterpreting them as bytes.
+pub(crate) trait BulkPrimitive: bytemuck::Pod {
+    /// Returns the value with the order of its bytes reversed.
+    fn byte_swapped(self) -> Self;
+}
+
+macro_rules! bulk_primitive_impl {
+    (ints ($($Ty:ty)+) floats ($($Float:ty)+)) => {
+        $(impl BulkPrimitive for $Ty {
+            fn byte_swapped(self) -> Self {
+                self.swap_bytes()
+            }
+        })+
+
+        $(impl BulkPrimitive for $Float {
+            fn byte_swapped(self) -> Self {
+                <$Float>::from_bits(self.to_bits().swap_bytes())
+            }
+        })+
+    }
+}
+
+bulk_primitive_impl!(ints (u8 i8 u16 i16 u32 i32 u64 i64 u128 i128) floats (f32 f64));
+
+/// Writes a list of primitives in bulk, byte swapping them first if `endian`
+/// is not the native byte order.
+pub(crate) fn write_bulk<T, W>(writer: &mut W, values: &[T], endian: Endian) -> BinResult<()>
+where
+    T: BulkPrimitive,
+    W: Write,
+{
+    if core::mem::size_of::<T>() == 1 || endian == Endian::NATIVE {
+        writer.write_all(bytemuck::cast_slice(values))?;
+    } else {
+        // Swapped values are written through a bounded buffer so that large
+        // lists do not need to be copied in full
+        const CHUNK_BYTES: usize = 4096;
+        let chunk_len = CHUNK_BYTES / core::mem::size_of::<T>();
+        let mut buffer = Vec::with_capacity(values.len().min(chunk_len));
+        for chunk in values.chunks(chunk_len) {
+            buffer.clear();
+            buffer.extend(chunk.iter().map(|value| value.byte_swapped()));
+            writer.write_all(bytemuck::cast_slice(&buffer))?;
+        }
+    }
+
+    Ok(())
+}
+
 macro_rules! vec_fast_int {
     (try ($($Ty:ty)+) using ($list:expr, $reader:expr, $endian:expr, $count:expr) else { $($else:tt)* }) => {
         $(if let Some(list) = <dyn core::any::Any>::downcast_mut::<Vec<$Ty>>(&mut $list) {
@@ -516,7 +1073,7 @@ macro_rules! vec_fast_int {
                 // In benchmarks, this resize decreases performance by 27–40%
                 // relative to using `unsafe` to write directly to uninitialised
                 // memory, but nobody ever got fired for buying IBM
-                list.resize(end, 0);
+                list.resize(end, bytemuck::Zeroable::zeroed());
                 $reader.read_exact(&mut bytemuck::cast_slice_mut::<_, u8>(&mut list[start..end]))?;
 
                 remaining -= items_to_read;
@@ -531,7 +1088,7 @@ macro_rules! vec_fast_int {
                 )
             {
                 for value in list.iter_mut() {
-                    *value = value.swap_bytes();
+                    *value = BulkPrimitive::byte_swapped(*value);
                 }
             }
             Ok($list)
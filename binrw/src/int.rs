@@ -0,0 +1,247 @@
+//! Type definitions for integers with sizes that are not a power of two.
+//!
+//! These types store their values in the next largest primitive integer type,
+//! but are read and written using only the number of bytes in their names. For
+//! example, a [`U24`] is read from three bytes and stored in a `u32`.
+
+use crate::{
+    io::{Read, Seek, Write},
+    BinRead, BinResult, BinWrite, Endian,
+};
+use core::fmt;
+
+/// The error type returned when a checked conversion to an integer type with
+/// an unusual size fails.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TryFromIntError(());
+
+impl fmt::Display for TryFromIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("out of range integral type conversion attempted")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryFromIntError {}
+
+macro_rules! int_type {
+    (
+        $(#[$meta:meta])*
+        $name:ident($inner:ty, $unsigned:ty, $bytes:literal),
+        from($($from:ty),*),
+        into($($into:ty),*)
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        pub struct $name($inner);
+
+        impl $name {
+            /// The size of this integer type in bytes.
+            pub const BYTES: usize = $bytes;
+
+            /// The size of this integer type in bits.
+            // Lint: `BYTES` is at most 7
+            #[allow(clippy::cast_possible_truncation)]
+            pub const BITS: u32 = ($bytes * 8) as u32;
+
+            /// The smallest value that can be represented by this integer
+            /// type.
+            pub const MIN: Self = Self(<$inner>::MIN >> (<$inner>::BITS - Self::BITS));
+
+            /// The largest value that can be represented by this integer type.
+            pub const MAX: Self = Self(<$inner>::MAX >> (<$inner>::BITS - Self::BITS));
+
+            /// Creates a new integer, or returns `None` if `value` is out of
+            /// range.
+            #[must_use]
+            pub const fn new(value: $inner) -> Option<Self> {
+                if value >= Self::MIN.0 && value <= Self::MAX.0 {
+                    Some(Self(value))
+                } else {
+                    None
+                }
+            }
+
+            /// Creates a new integer, discarding any bits of `value` which are
+            /// out of range.
+            #[must_use]
+            pub const fn new_truncated(value: $inner) -> Self {
+                let shift = <$inner>::BITS - Self::BITS;
+                Self((value << shift) >> shift)
+            }
+
+            /// Returns the value as a primitive integer.
+            #[must_use]
+            pub const fn get(self) -> $inner {
+                self.0
+            }
+        }
+
+        impl BinRead for $name {
+            type Args<'a> = ();
+
+            fn read_options<R: Read + Seek>(
+                reader: &mut R,
+                endian: Endian,
+                (): Self::Args<'_>,
+            ) -> BinResult<Self> {
+                let mut bytes = [0; $bytes];
+                reader.read_exact(&mut bytes)?;
+                if endian == Endian::Little {
+                    bytes.reverse();
+                }
+                let value = bytes
+                    .iter()
+                    .fold(0, |value: $unsigned, byte| (value << 8) | <$unsigned>::from(*byte));
+                // Lint: Reinterpreting the bits and sign-extending them is
+                // intended
+                #[allow(clippy::cast_possible_wrap)]
+                Ok(Self::new_truncated(value as $inner))
+            }
+        }
+
+        impl BinWrite for $name {
+            type Args<'a> = ();
+
+            fn write_options<W: Write + Seek>(
+                &self,
+                writer: &mut W,
+                endian: Endian,
+                (): Self::Args<'_>,
+            ) -> BinResult<()> {
+                // Lint: Only the bytes which fit are written
+                #[allow(clippy::cast_sign_loss)]
+                let value = self.0 as $unsigned;
+                let mut bytes = [0; $bytes];
+                for (index, byte) in bytes.iter_mut().rev().enumerate() {
+                    // Lint: Masked to a single byte
+                    #[allow(clippy::cast_possible_truncation)]
+                    {
+                        *byte = (value >> (index * 8)) as u8;
+                    }
+                }
+                if endian == Endian::Little {
+                    bytes.reverse();
+                }
+                writer.write_all(&bytes)?;
+                Ok(())
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                ($bytes, Some($bytes))
+            }
+        }
+
+        impl TryFrom<$inner> for $name {
+            type Error = TryFromIntError;
+
+            fn try_from(value: $inner) -> Result<Self, Self::Error> {
+                Self::new(value).ok_or(TryFromIntError(()))
+            }
+        }
+
+        $(
+            impl From<$from> for $name {
+                fn from(value: $from) -> Self {
+                    Self(value.into())
+                }
+            }
+        )*
+
+        $(
+            impl From<$name> for $into {
+                fn from(value: $name) -> Self {
+                    // Lint: The value is always in range of the target type
+                    #[allow(clippy::cast_lossless, clippy::cast_possible_wrap)]
+                    {
+                        value.0 as Self
+                    }
+                }
+            }
+        )*
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    };
+}
+
+int_type! {
+    /// A 24-bit unsigned integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binrw::{io::Cursor, BinReaderExt, BinWriterExt, U24};
+    ///
+    /// let value: U24 = Cursor::new(b"\x01\x02\x03").read_be().unwrap();
+    /// assert_eq!(u32::from(value), 0x01_0203);
+    ///
+    /// let mut out = Cursor::new(Vec::new());
+    /// out.write_le(&U24::new(0x01_0203).unwrap()).unwrap();
+    /// assert_eq!(out.into_inner(), b"\x03\x02\x01");
+    /// ```
+    U24(u32, u32, 3),
+    from(u8, u16),
+    into(u32, u64, u128, i32, i64, i128)
+}
+
+int_type! {
+    /// A 40-bit unsigned integer.
+    U40(u64, u64, 5),
+    from(u8, u16, u32),
+    into(u64, u128, i64, i128)
+}
+
+int_type! {
+    /// A 48-bit unsigned integer.
+    U48(u64, u64, 6),
+    from(u8, u16, u32),
+    into(u64, u128, i64, i128)
+}
+
+int_type! {
+    /// A 56-bit unsigned integer.
+    U56(u64, u64, 7),
+    from(u8, u16, u32),
+    into(u64, u128, i64, i128)
+}
+
+int_type! {
+    /// A 24-bit signed integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binrw::{io::Cursor, BinReaderExt, I24};
+    ///
+    /// let value: I24 = Cursor::new(b"\xff\xff\xfe").read_be().unwrap();
+    /// assert_eq!(i32::from(value), -2);
+    /// ```
+    I24(i32, u32, 3),
+    from(u8, i8, u16, i16),
+    into(i32, i64, i128)
+}
+
+int_type! {
+    /// A 40-bit signed integer.
+    I40(i64, u64, 5),
+    from(u8, i8, u16, i16, u32, i32),
+    into(i64, i128)
+}
+
+int_type! {
+    /// A 48-bit signed integer.
+    I48(i64, u64, 6),
+    from(u8, i8, u16, i16, u32, i32),
+    into(i64, i128)
+}
+
+int_type! {
+    /// A 56-bit signed integer.
+    I56(i64, u64, 7),
+    from(u8, i8, u16, i16, u32, i32),
+    into(i64, i128)
+}
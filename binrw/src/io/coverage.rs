@@ -0,0 +1,148 @@
+//! A reader adapter which records which bytes of the underlying reader were
+//! read.
+
+use super::{Read, Result, Seek, SeekFrom};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// Read adapter which records the byte ranges consumed from an underlying
+/// reader.
+///
+/// This is useful for finding data which a type definition silently skips:
+/// after parsing, [`holes`](Self::holes) returns every range of the stream
+/// which was never read.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, io::{Cursor, CoverageReader}};
+///
+/// #[derive(BinRead)]
+/// #[br(little)]
+/// struct Header {
+///     magic: u16,
+///     #[br(pad_before = 2)]
+///     len: u32,
+/// }
+///
+/// let mut reader = CoverageReader::new(Cursor::new(b"\x01\x02\x03\x04\x05\x06\x07\x08\x09"));
+/// Header::read(&mut reader).unwrap();
+/// assert_eq!(reader.ranges(), [0..2, 4..8]);
+/// assert_eq!(reader.holes(9), [2..4, 8..9]);
+/// ```
+#[derive(Debug)]
+pub struct CoverageReader<T> {
+    inner: T,
+    pos: u64,
+    ranges: Vec<Range<u64>>,
+}
+
+impl<T: Seek> CoverageReader<T> {
+    /// Creates a new `CoverageReader` which records reads from `inner`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position of the underlying reader cannot be retrieved.
+    pub fn new(mut inner: T) -> Self {
+        let pos = inner
+            .stream_position()
+            .expect("cannot get position for `CoverageReader`");
+
+        Self {
+            inner,
+            pos,
+            ranges: Vec::new(),
+        }
+    }
+}
+
+impl<T> CoverageReader<T> {
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying reader as doing so may corrupt the recorded ranges of this
+    /// `CoverageReader`.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns the byte ranges which have been read, in order.
+    ///
+    /// Overlapping and adjacent ranges are merged.
+    pub fn ranges(&self) -> &[Range<u64>] {
+        &self.ranges
+    }
+
+    /// Returns the byte ranges between the start of the stream and `end`
+    /// which have not been read, in order.
+    pub fn holes(&self, end: u64) -> Vec<Range<u64>> {
+        let mut holes = Vec::new();
+        let mut pos = 0;
+        for range in &self.ranges {
+            if range.start >= end {
+                break;
+            }
+            if range.start > pos {
+                holes.push(pos..range.start);
+            }
+            pos = pos.max(range.end);
+        }
+        if pos < end {
+            holes.push(pos..end);
+        }
+        holes
+    }
+
+    /// Forgets all of the recorded ranges.
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    fn record(&mut self, range: Range<u64>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        if first == last {
+            self.ranges.insert(first, range);
+        } else {
+            let start = range.start.min(self.ranges[first].start);
+            let end = range.end.max(self.ranges[last - 1].end);
+            self.ranges.drain(first + 1..last);
+            self.ranges[first] = start..end;
+        }
+    }
+}
+
+impl<T: Read> Read for CoverageReader<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        let start = self.pos;
+        self.pos += n as u64;
+        self.record(start..self.pos);
+        Ok(n)
+    }
+}
+
+impl<T: Seek> Seek for CoverageReader<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.pos = self.inner.seek(pos)?;
+        Ok(self.pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
@@ -6,6 +6,7 @@
 
 #[cfg(feature = "std")]
 mod bufreader;
+mod coverage;
 #[cfg(not(feature = "std"))]
 mod no_std;
 pub mod prelude;
@@ -17,9 +18,18 @@ pub use bufreader::BufReader;
 #[cfg(all(doc, not(feature = "std")))]
 #[doc(hidden)]
 pub struct BufReader;
+pub use coverage::CoverageReader;
 #[cfg(not(feature = "std"))]
 pub use no_std::*;
 pub use seek::NoSeek;
 #[cfg(feature = "std")]
 pub use std::io::{Bytes, Cursor, Error, ErrorKind, Read, Result, Seek, SeekFrom, Write};
 pub use take_seek::*;
+
+/// A combined [`Write`] + [`Seek`] trait which can be used as a trait object.
+///
+/// This trait is implemented automatically for every type which implements
+/// both [`Write`] and [`Seek`].
+pub trait WriteSeek: Write + Seek {}
+
+impl<T: Write + Seek + ?Sized> WriteSeek for T {}
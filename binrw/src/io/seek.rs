@@ -9,17 +9,30 @@ use alloc::{string::String, vec::Vec};
 ///
 /// This is useful when reading or writing from unseekable streams where binrw
 /// does not *actually* need to seek to successfully parse or write the data.
+///
+/// Seeking forward is emulated by skipping data: when reading, the skipped
+/// bytes are read and discarded, and when writing, the skipped bytes are
+/// filled with zeroes. Skipping happens lazily on the next read, write, or
+/// flush. Seeking backward, or relative to the end of the stream, returns an
+/// error.
 pub struct NoSeek<T> {
     /// The original stream.
     inner: T,
     /// The virtual position of the seekable stream.
     pos: u64,
+    /// The number of bytes which have been seeked over but not yet skipped
+    /// in the original stream.
+    skip: u64,
 }
 
 impl<T> NoSeek<T> {
     /// Creates a new seekable wrapper for the given value.
     pub fn new(inner: T) -> Self {
-        NoSeek { inner, pos: 0 }
+        NoSeek {
+            inner,
+            pos: 0,
+            skip: 0,
+        }
     }
 
     /// Gets a mutable reference to the underlying value.
@@ -33,18 +46,71 @@ impl<T> NoSeek<T> {
     }
 
     /// Consumes this wrapper, returning the underlying value.
+    ///
+    /// Any pending forward seek which has not been skipped yet is discarded.
     pub fn into_inner(self) -> T {
         self.inner
     }
 }
 
+impl<T: super::Read> NoSeek<T> {
+    /// Reads and discards any bytes which were seeked over.
+    fn skip_read(&mut self) -> super::Result<()> {
+        let mut buf = [0; 256];
+        while self.skip != 0 {
+            let len = buf
+                .len()
+                .min(usize::try_from(self.skip).unwrap_or(usize::MAX));
+            match self.inner.read(&mut buf[..len]) {
+                Ok(0) => {
+                    return Err(Error::new(
+                        ErrorKind::UnexpectedEof,
+                        "seek past the end of an unseekable stream",
+                    ))
+                }
+                Ok(n) => self.skip -= n as u64,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: super::Write> NoSeek<T> {
+    /// Writes zeroes for any bytes which were seeked over.
+    fn skip_write(&mut self) -> super::Result<()> {
+        const ZEROES: [u8; 256] = [0; 256];
+        while self.skip != 0 {
+            let len = ZEROES
+                .len()
+                .min(usize::try_from(self.skip).unwrap_or(usize::MAX));
+            self.inner.write_all(&ZEROES[..len])?;
+            self.skip -= len as u64;
+        }
+        Ok(())
+    }
+}
+
 impl<T> super::Seek for NoSeek<T> {
     fn seek(&mut self, pos: SeekFrom) -> super::Result<u64> {
-        match pos {
-            SeekFrom::Start(n) if self.pos == n => Ok(n),
-            SeekFrom::Current(n) if n == 0 => Ok(self.pos),
+        let target = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::Current(n) => self.pos.checked_add_signed(n),
+            SeekFrom::End(_) => None,
+        };
+
+        match target {
+            Some(target) if target >= self.pos => {
+                self.skip += target - self.pos;
+                self.pos = target;
+                Ok(target)
+            }
             // https://github.com/rust-lang/rust/issues/86442
-            _ => Err(Error::new(ErrorKind::Other, "seek on unseekable file")),
+            _ => Err(Error::new(
+                ErrorKind::Other,
+                "backward or end-relative seek on unseekable stream",
+            )),
         }
     }
 
@@ -55,6 +121,7 @@ impl<T> super::Seek for NoSeek<T> {
 
 impl<T: super::Read> super::Read for NoSeek<T> {
     fn read(&mut self, buf: &mut [u8]) -> super::Result<usize> {
+        self.skip_read()?;
         let n = self.inner.read(buf)?;
         self.pos += n as u64;
         Ok(n)
@@ -62,24 +129,28 @@ impl<T: super::Read> super::Read for NoSeek<T> {
 
     #[cfg(feature = "std")]
     fn read_vectored(&mut self, bufs: &mut [std::io::IoSliceMut<'_>]) -> super::Result<usize> {
+        self.skip_read()?;
         let n = self.inner.read_vectored(bufs)?;
         self.pos += n as u64;
         Ok(n)
     }
 
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> super::Result<usize> {
+        self.skip_read()?;
         let n = self.inner.read_to_end(buf)?;
         self.pos += n as u64;
         Ok(n)
     }
 
     fn read_to_string(&mut self, buf: &mut String) -> super::Result<usize> {
+        self.skip_read()?;
         let n = self.inner.read_to_string(buf)?;
         self.pos += n as u64;
         Ok(n)
     }
 
     fn read_exact(&mut self, buf: &mut [u8]) -> super::Result<()> {
+        self.skip_read()?;
         self.inner.read_exact(buf)?;
         self.pos += buf.len() as u64;
         Ok(())
@@ -88,23 +159,27 @@ impl<T: super::Read> super::Read for NoSeek<T> {
 
 impl<T: super::Write> super::Write for NoSeek<T> {
     fn write(&mut self, buf: &[u8]) -> super::Result<usize> {
+        self.skip_write()?;
         let n = self.inner.write(buf)?;
         self.pos += n as u64;
         Ok(n)
     }
 
     fn flush(&mut self) -> super::Result<()> {
+        self.skip_write()?;
         self.inner.flush()
     }
 
     #[cfg(feature = "std")]
     fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> super::Result<usize> {
+        self.skip_write()?;
         let n = self.inner.write_vectored(bufs)?;
         self.pos += n as u64;
         Ok(n)
     }
 
     fn write_all(&mut self, buf: &[u8]) -> super::Result<()> {
+        self.skip_write()?;
         self.inner.write_all(buf)?;
         self.pos += buf.len() as u64;
         Ok(())
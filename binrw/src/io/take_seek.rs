@@ -1,20 +1,23 @@
 //! Types for seekable reader adapters which limit the number of bytes read from
 //! the underlying reader.
 
-use super::{Read, Result, Seek, SeekFrom};
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom};
 
 /// Read adapter which limits the bytes read from an underlying reader, with
 /// seek support.
 ///
 /// This struct is generally created by importing the [`TakeSeekExt`] extension
-/// and calling [`take_seek`] on a reader.
+/// and calling [`take_seek`] or [`take_window`] on a reader.
 ///
 /// [`take_seek`]: TakeSeekExt::take_seek
+/// [`take_window`]: TakeSeekExt::take_window
 #[derive(Debug)]
 pub struct TakeSeek<T> {
     inner: T,
+    start: u64,
     pos: u64,
     end: u64,
+    clamp: bool,
 }
 
 impl<T> TakeSeek<T> {
@@ -47,6 +50,11 @@ impl<T> TakeSeek<T> {
     pub fn limit(&self) -> u64 {
         self.end.saturating_sub(self.pos)
     }
+
+    /// Returns the position of the end of the readable region.
+    pub(crate) fn end(&self) -> u64 {
+        self.end
+    }
 }
 
 impl<T: Seek> TakeSeek<T> {
@@ -59,6 +67,7 @@ impl<T: Seek> TakeSeek<T> {
             .inner
             .stream_position()
             .expect("cannot get position for `set_limit`");
+        self.start = pos;
         self.pos = pos;
         self.end = pos + limit;
     }
@@ -86,7 +95,26 @@ impl<T: Read> Read for TakeSeek<T> {
 
 impl<T: Seek> Seek for TakeSeek<T> {
     fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
-        self.pos = self.inner.seek(pos)?;
+        if !self.clamp {
+            self.pos = self.inner.seek(pos)?;
+            return Ok(self.pos);
+        }
+
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.end.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+        }
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        self.pos = self
+            .inner
+            .seek(SeekFrom::Start(target.clamp(self.start, self.end)))?;
         Ok(self.pos)
     }
 
@@ -95,13 +123,47 @@ impl<T: Seek> Seek for TakeSeek<T> {
     }
 }
 
-/// An extension trait that implements `take_seek()` for compatible streams.
+/// An extension trait that implements `take_seek()` and `take_window()` for
+/// compatible streams.
 pub trait TakeSeekExt {
     /// Creates an adapter which will read at most `limit` bytes from the
     /// wrapped stream.
+    ///
+    /// Seeking is passed through to the wrapped stream, so the adapter can be
+    /// moved outside of the readable region.
     fn take_seek(self, limit: u64) -> TakeSeek<Self>
     where
         Self: Sized;
+
+    /// Creates an adapter which will read at most `limit` bytes from the
+    /// wrapped stream, and which cannot be moved outside of those bytes.
+    ///
+    /// Positions are still absolute positions in the wrapped stream, but any
+    /// seek outside of the window is clamped to its nearest edge, and
+    /// [`SeekFrom::End`] is relative to the end of the window. This makes it
+    /// safe to pass the adapter to a parser which may seek, without it
+    /// reaching any data which follows the window.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binrw::io::{Cursor, Read, Seek, SeekFrom, TakeSeekExt};
+    ///
+    /// let mut data = Cursor::new(b"hello world");
+    /// data.seek(SeekFrom::Start(2)).unwrap();
+    /// let mut window = data.take_window(3);
+    /// assert_eq!(window.seek(SeekFrom::End(-1)).unwrap(), 4);
+    /// assert_eq!(window.seek(SeekFrom::Start(0)).unwrap(), 2);
+    /// assert_eq!(window.seek(SeekFrom::Current(10)).unwrap(), 5);
+    ///
+    /// let mut buf = Vec::new();
+    /// window.seek(SeekFrom::Start(2)).unwrap();
+    /// window.read_to_end(&mut buf).unwrap();
+    /// assert_eq!(buf, b"llo");
+    /// ```
+    fn take_window(self, limit: u64) -> TakeSeek<Self>
+    where
+        Self: Sized;
 }
 
 impl<T: Read + Seek> TakeSeekExt for T {
@@ -115,8 +177,20 @@ impl<T: Read + Seek> TakeSeekExt for T {
 
         TakeSeek {
             inner: self,
+            start: pos,
             pos,
             end: pos + limit,
+            clamp: false,
+        }
+    }
+
+    fn take_window(self, limit: u64) -> TakeSeek<Self>
+    where
+        Self: Sized,
+    {
+        TakeSeek {
+            clamp: true,
+            ..self.take_seek(limit)
         }
     }
 }
@@ -7,6 +7,9 @@
 #![warn(rust_2018_idioms)]
 // Lint: This is not beneficial for code organisation.
 #![allow(clippy::module_name_repetitions)]
+// Lint: Error messages are converted with `into` because their type depends on
+// the `compact-errors` feature.
+#![cfg_attr(not(feature = "compact-errors"), allow(clippy::useless_conversion))]
 
 extern crate alloc;
 // This extern crate declaration is required to use binrw_derive macros like
@@ -20,35 +23,75 @@ extern crate std;
 #[doc(hidden)]
 #[path = "private.rs"]
 pub mod __private;
+#[cfg(feature = "tokio")]
+pub mod async_io;
 mod binread;
 mod binwrite;
+pub mod bits;
+pub mod codegen;
+pub mod compression;
+pub mod conformance;
+#[cfg(feature = "std")]
+pub mod context;
+#[cfg(all(doc, not(feature = "std")))]
+#[doc(hidden)]
+pub mod context {}
+pub mod crc;
 pub mod docs;
+#[cfg(feature = "encoding_rs")]
+pub mod encoding;
 pub mod endian;
 pub mod error;
 pub mod file_ptr;
+pub mod fixed_point;
+pub mod fuzz;
 pub mod helpers;
+pub mod int;
 pub mod io;
+#[doc(hidden)]
+pub mod magic;
 pub mod meta;
 mod named_args;
 #[doc(hidden)]
 pub mod pos_value;
 pub mod punctuated;
 #[doc(hidden)]
+pub mod rle;
+pub mod schema;
+pub mod session;
+#[doc(hidden)]
 pub mod strings;
+pub mod timestamp;
+pub mod untrusted;
+#[cfg(feature = "uuid")]
+pub mod uuid;
+#[cfg(all(doc, not(feature = "uuid")))]
+#[doc(hidden)]
+pub mod uuid {}
+pub mod varint;
 
 #[cfg(all(doc, not(feature = "std")))]
 use alloc::vec::Vec;
+#[cfg(feature = "tokio")]
+#[doc(inline)]
+pub use async_io::{AsyncBinRead, AsyncBinWrite};
 #[doc(inline)]
 pub use {
     binread::*,
     binwrite::*,
+    bits::{BitArray, BitOrder, PackedBits, PackedBitsArgs},
+    compression::{Codec, Compressed, CompressionContext},
     endian::Endian,
     error::Error,
     file_ptr::{FilePtr, FilePtr128, FilePtr16, FilePtr32, FilePtr64, FilePtr8},
     helpers::{count, until, until_eof, until_exclusive},
+    int::{I24, I40, I48, I56, U24, U40, U48, U56},
+    magic::Magic,
     named_args::NamedArgs,
     pos_value::PosValue,
-    strings::{NullString, NullWideString},
+    rle::{Rle, RleArgs, RleBytes, RleScheme},
+    strings::{NullString, NullWideString, PrefixedWideString, WithBom},
+    varint::{Leb128, Vlq},
 };
 
 /// Derive macro generating an impl of the trait [`BinRead`].
@@ -109,6 +152,10 @@ pub use binrw_derive::binrw;
 /// # Field options
 ///
 /// * `#[named_args(default = $expr)]`: Sets the default value for a field.
+/// * `#[named_args(try_optional)]`: Makes a field optional only when its type
+///   implements [`Default`]. Otherwise, the field is required. This is useful
+///   for forwarding the arguments of a generic inner type, which may or may
+///   not require arguments. Only one field per struct may use this option.
 ///
 /// # Examples
 ///
@@ -143,6 +190,41 @@ pub use binrw_derive::binrw;
 /// #     vec![0x42, 0x69]
 /// # );
 /// ```
+///
+/// Named arguments for a custom parser function:
+///
+/// ```
+/// use binrw::{binread, io::Read, BinResult, NamedArgs};
+///
+/// #[derive(Clone, NamedArgs)]
+/// struct ChunkArgs {
+///     len: usize,
+///     #[named_args(default = 0)]
+///     key: u8,
+/// }
+///
+/// #[binrw::parser(reader, args_raw)]
+/// fn chunk(args: ChunkArgs) -> BinResult<Vec<u8>> {
+///     let mut data = vec![0; args.len];
+///     reader.read_exact(&mut data)?;
+///     Ok(data.into_iter().map(|b| b ^ args.key).collect())
+/// }
+///
+/// #[binread]
+/// struct Chunks {
+///     #[br(temp)]
+///     len: u8,
+///     #[br(parse_with = chunk, args { len: len.into() })]
+///     plain: Vec<u8>,
+///     #[br(parse_with = chunk, args { len: len.into(), key: 0xff })]
+///     masked: Vec<u8>,
+/// }
+///
+/// # use binrw::BinRead;
+/// # let chunks = Chunks::read_le(&mut binrw::io::Cursor::new(b"\x02\x01\x02\xfe\xfd")).unwrap();
+/// # assert_eq!(chunks.plain, [1, 2]);
+/// # assert_eq!(chunks.masked, [1, 2]);
+/// ```
 pub use binrw_derive::NamedArgs;
 
 /// Attribute macro used to generate
@@ -156,6 +238,8 @@ pub use binrw_derive::NamedArgs;
 ///   stream to the function. If no variable name is given, `reader` is used.
 /// * `#[parser(endian)]` or `#[parser(endian: $ident)]`: Exposes the endianness
 ///   to the function. If no variable name is given, `endian` is used.
+/// * `#[parser(args_raw)]`: Uses the function parameter as
+///   [raw arguments](#raw-arguments).
 ///
 /// Options are comma-separated.
 ///
@@ -195,13 +279,13 @@ pub use binrw_derive::NamedArgs;
 ///
 /// ## Raw arguments
 ///
-/// Use a *variadic* function signature with a single parameter. The name and
-/// type of the parameter will be used as the raw argument. For example:
+/// Use the `args_raw` option with a single parameter. The name and type of the
+/// parameter will be used as the raw argument. For example:
 ///
 /// ```
 /// # struct ArgsType;
-/// #[binrw::parser]
-/// fn custom_parser(args: ArgsType, ...) -> binrw::BinResult<()> {
+/// #[binrw::parser(args_raw)]
+/// fn custom_parser(args: ArgsType) -> binrw::BinResult<()> {
 ///     Ok(())
 /// }
 /// # custom_parser(&mut binrw::io::Cursor::new(b""), binrw::Endian::Little, ArgsType).unwrap();
@@ -239,6 +323,8 @@ pub use binrw_derive::parser;
 ///   stream to the function. If no variable name is given, `writer` is used.
 /// * `#[writer(endian)]` or `#[writer(endian: $ident)]`: Exposes the endianness
 ///   to the function. If no variable name is given, `endian` is used.
+/// * `#[writer(args_raw)]`: Uses the second function parameter as
+///   [raw arguments](#raw-arguments).
 ///
 /// Options are comma-separated.
 ///
@@ -284,14 +370,14 @@ pub use binrw_derive::parser;
 ///
 /// ## Raw arguments
 ///
-/// Use a *variadic* function signature with a second parameter. The name and
-/// type of the second parameter will be used as the raw argument. For example:
+/// Use the `args_raw` option with a second parameter. The name and type of
+/// the second parameter will be used as the raw argument. For example:
 ///
 /// ```
 /// # struct Object;
 /// # struct ArgsType;
-/// #[binrw::writer]
-/// fn custom_writer(obj: &Object, args: ArgsType, ...) -> binrw::BinResult<()> {
+/// #[binrw::writer(args_raw)]
+/// fn custom_writer(obj: &Object, args: ArgsType) -> binrw::BinResult<()> {
 ///     Ok(())
 /// }
 /// # custom_writer(&Object, &mut binrw::io::Cursor::new(vec![]), binrw::Endian::Little, ArgsType).unwrap();
@@ -334,6 +420,6 @@ pub mod prelude {
     //! ```
 
     pub use crate::{
-        binread, binrw, binwrite, BinRead, BinReaderExt, BinResult, BinWrite, BinWriterExt,
+        binread, binrw, binwrite, BinRead, BinReaderExt, BinResult, BinSize, BinWrite, BinWriterExt,
     };
 }
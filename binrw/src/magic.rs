@@ -0,0 +1,147 @@
+//! Type definitions for magic number markers.
+
+use crate::{
+    io::{Read, Seek, SeekFrom, Write},
+    meta::{ReadMagic, WriteMagic},
+    BinRead, BinResult, BinWrite, Endian,
+};
+use core::{fmt, marker::PhantomData};
+
+/// A zero-sized marker which reads and writes the magic number of `M`.
+///
+/// This is the same as using a [`magic`](crate::docs::attribute#magic)
+/// directive, but the magic number is carried in the type instead of an
+/// attribute, so it can be used in generic code and in types which do not use
+/// the derive macros. The magic number is defined by implementing
+/// [`ReadMagic`] and [`WriteMagic`] on a marker type `M`. Types which are
+/// derived with a `magic` directive already implement these traits.
+///
+/// If the magic number does not match, the reader is returned to its original
+/// position before the error is returned. This makes it possible to probe for a
+/// signature using [`try`](crate::docs::attribute#try):
+///
+/// ```
+/// use binrw::{
+///     binrw, io::Cursor, meta::{ReadMagic, WriteMagic}, BinRead, BinWrite, Magic,
+/// };
+///
+/// struct Png;
+///
+/// impl ReadMagic for Png {
+///     type MagicType = [u8; 8];
+///     const MAGIC: Self::MagicType = *b"\x89PNG\r\n\x1a\n";
+/// }
+///
+/// impl WriteMagic for Png {
+///     type MagicType = [u8; 8];
+///     const MAGIC: Self::MagicType = *b"\x89PNG\r\n\x1a\n";
+/// }
+///
+/// #[binrw]
+/// #[brw(big)]
+/// struct Image {
+///     #[br(try)]
+///     signature: Option<Magic<Png>>,
+///     width: u16,
+/// }
+///
+/// let image = Image::read(&mut Cursor::new(b"\x89PNG\r\n\x1a\n\0\x10")).unwrap();
+/// assert!(image.signature.is_some());
+/// assert_eq!(image.width, 16);
+///
+/// let image = Image::read(&mut Cursor::new(b"\0\x10")).unwrap();
+/// assert!(image.signature.is_none());
+/// assert_eq!(image.width, 16);
+///
+/// let mut out = Cursor::new(Vec::new());
+/// Image { signature: Some(Magic::new()), width: 16 }.write(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), b"\x89PNG\r\n\x1a\n\0\x10");
+/// ```
+pub struct Magic<M>(PhantomData<M>);
+
+impl<M> Magic<M> {
+    /// Creates a new magic number marker.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<M> BinRead for Magic<M>
+where
+    M: ReadMagic,
+    M::MagicType: for<'a> BinRead<Args<'a> = ()>
+        + fmt::Debug
+        + PartialEq
+        + Sync
+        + Send
+        + Clone
+        + Copy
+        + 'static,
+{
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = reader.stream_position()?;
+        match crate::__private::magic(reader, M::MAGIC, endian) {
+            Ok(()) => Ok(Self::new()),
+            Err(err) => {
+                reader.seek(SeekFrom::Start(pos))?;
+                Err(err)
+            }
+        }
+    }
+}
+
+impl<M> BinWrite for Magic<M>
+where
+    M: WriteMagic,
+    M::MagicType: for<'a> BinWrite<Args<'a> = ()>,
+{
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        M::MAGIC.write_options(writer, endian, ())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        M::MAGIC.size_hint()
+    }
+}
+
+impl<M> Clone for Magic<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for Magic<M> {}
+
+impl<M> Default for Magic<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M> PartialEq for Magic<M> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<M> Eq for Magic<M> {}
+
+impl<M> fmt::Debug for Magic<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Magic<{}>", core::any::type_name::<M>())
+    }
+}
@@ -1,13 +1,20 @@
 //! Traits representing basic properties of types.
 
 use crate::Endian;
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    rc::Rc,
+    vec::Vec,
+};
 use core::marker::PhantomData;
 
 /// Types that require a magic number when parsed.
 ///
 /// This trait is automatically defined on derived types with a
-/// [magic directive](crate::docs::attribute#magic).
+/// [magic directive](crate::docs::attribute#magic), unless the directive
+/// accepts several alternative magic numbers.
 pub trait ReadMagic {
     /// The type of the magic number.
     type MagicType;
@@ -46,6 +53,24 @@ pub trait WriteEndian {
     const ENDIAN: EndianKind;
 }
 
+/// Types with audited read code.
+///
+/// This trait is automatically defined on derived types with an
+/// [audit directive](crate::docs::attribute#audit).
+pub trait ReadAudit {
+    /// The number of tokens in the generated `read_options` function body.
+    const CODE_SIZE: usize;
+}
+
+/// Types with audited write code.
+///
+/// This trait is automatically defined on derived types with an
+/// [audit directive](crate::docs::attribute#audit).
+pub trait WriteAudit {
+    /// The number of tokens in the generated `write_options` function body.
+    const CODE_SIZE: usize;
+}
+
 /// The kind of endianness used by a type.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum EndianKind {
@@ -84,6 +109,8 @@ macro_rules! endian_impl {
 }
 
 endian_impl!(() i8 u8 core::num::NonZeroU8 core::num::NonZeroI8 crate::strings::NullString => EndianKind::None);
+#[cfg(feature = "uuid")]
+endian_impl!(crate::uuid::Uuid => EndianKind::None);
 
 impl<T: ReadEndian + ?Sized> ReadEndian for Box<T> {
     const ENDIAN: EndianKind = <T as ReadEndian>::ENDIAN;
@@ -93,6 +120,36 @@ impl<T: WriteEndian + ?Sized> WriteEndian for Box<T> {
     const ENDIAN: EndianKind = <T as WriteEndian>::ENDIAN;
 }
 
+impl<T: ReadEndian + ?Sized> ReadEndian for Rc<T> {
+    const ENDIAN: EndianKind = <T as ReadEndian>::ENDIAN;
+}
+
+impl<T: WriteEndian + ?Sized> WriteEndian for Rc<T> {
+    const ENDIAN: EndianKind = <T as WriteEndian>::ENDIAN;
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T: ReadEndian + ?Sized> ReadEndian for alloc::sync::Arc<T> {
+    const ENDIAN: EndianKind = <T as ReadEndian>::ENDIAN;
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T: WriteEndian + ?Sized> WriteEndian for alloc::sync::Arc<T> {
+    const ENDIAN: EndianKind = <T as WriteEndian>::ENDIAN;
+}
+
+impl<T> ReadEndian for Cow<'_, T>
+where
+    T: ToOwned + ?Sized,
+    T::Owned: ReadEndian,
+{
+    const ENDIAN: EndianKind = <T::Owned as ReadEndian>::ENDIAN;
+}
+
+impl<T: WriteEndian + ToOwned + ?Sized> WriteEndian for Cow<'_, T> {
+    const ENDIAN: EndianKind = <T as WriteEndian>::ENDIAN;
+}
+
 impl<T: ReadEndian> ReadEndian for [T] {
     const ENDIAN: EndianKind = <T as ReadEndian>::ENDIAN;
 }
@@ -121,7 +178,35 @@ macro_rules! endian_generic_impl {
     )+}
 }
 
-endian_generic_impl!(Option Vec PhantomData);
+endian_generic_impl!(Option Vec PhantomData BTreeSet VecDeque LinkedList BinaryHeap);
+
+#[cfg(feature = "std")]
+impl<T: ReadEndian, S> ReadEndian for std::collections::HashSet<T, S> {
+    const ENDIAN: EndianKind = <T as ReadEndian>::ENDIAN;
+}
+
+#[cfg(feature = "std")]
+impl<T: WriteEndian, S> WriteEndian for std::collections::HashSet<T, S> {
+    const ENDIAN: EndianKind = <T as WriteEndian>::ENDIAN;
+}
+
+impl<K: ReadEndian, V: ReadEndian> ReadEndian for BTreeMap<K, V> {
+    const ENDIAN: EndianKind = EndianKind::Mixed;
+}
+
+impl<K: WriteEndian, V: WriteEndian> WriteEndian for BTreeMap<K, V> {
+    const ENDIAN: EndianKind = EndianKind::Mixed;
+}
+
+#[cfg(feature = "std")]
+impl<K: ReadEndian, V: ReadEndian, S> ReadEndian for std::collections::HashMap<K, V, S> {
+    const ENDIAN: EndianKind = EndianKind::Mixed;
+}
+
+#[cfg(feature = "std")]
+impl<K: WriteEndian, V: WriteEndian, S> WriteEndian for std::collections::HashMap<K, V, S> {
+    const ENDIAN: EndianKind = EndianKind::Mixed;
+}
 
 macro_rules! endian_tuple_impl {
     ($type1:ident $(, $types:ident)*) => {
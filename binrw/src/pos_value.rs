@@ -1,11 +1,16 @@
 use crate::{
-    io::{Read, Seek},
-    BinRead, BinResult, Endian,
+    io::{Read, Seek, Write},
+    BinRead, BinResult, BinWrite, Endian,
 };
 use core::fmt;
 
 /// A wrapper that stores a value’s position alongside the value.
 ///
+/// When written, only the inner value is written. To also record where the
+/// value was written, use [`write_options_updating_pos`].
+///
+/// [`write_options_updating_pos`]: Self::write_options_updating_pos
+///
 /// # Examples
 ///
 /// ```
@@ -55,6 +60,54 @@ impl<T: BinRead> BinRead for PosValue<T> {
     }
 }
 
+impl<T: BinWrite> BinWrite for PosValue<T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.val.write_options(writer, endian, args)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.val.size_hint()
+    }
+}
+
+impl<T: BinWrite> PosValue<T> {
+    /// Writes the inner value and sets `pos` to the position it was written
+    /// at.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binrw::{Endian, PosValue, io::Cursor};
+    ///
+    /// let mut value = PosValue { val: 1u16, pos: 0 };
+    /// let mut out = Cursor::new(vec![0; 2]);
+    /// out.set_position(2);
+    /// value.write_options_updating_pos(&mut out, Endian::Big, ()).unwrap();
+    /// assert_eq!(value.pos, 2);
+    /// assert_eq!(out.into_inner(), b"\0\0\0\x01");
+    /// ```
+    pub fn write_options_updating_pos<W: Write + Seek>(
+        &mut self,
+        writer: &mut W,
+        endian: Endian,
+        args: T::Args<'_>,
+    ) -> BinResult<()> {
+        self.pos = writer.stream_position()?;
+        self.val.write_options(writer, endian, args)
+    }
+}
+
 impl<T> core::ops::Deref for PosValue<T> {
     type Target = T;
 
@@ -1,9 +1,10 @@
 use crate::{
     error::CustomError,
-    io::{Read, Seek, Write},
-    BinRead, BinResult, BinWrite, Endian, Error,
+    io::{Read, Seek, SeekFrom, TakeSeek, TakeSeekExt, Write},
+    BinRead, BinResult, BinWrite, DynBinWrite, Endian, Error,
 };
-use alloc::{boxed::Box, string::String};
+use alloc::{borrow::Cow, boxed::Box};
+use core::num::FpCategory;
 
 pub use crate::named_args::{
     builder_helper, passthrough_helper, Needed, Optional, Satisfied, SatisfiedOrOptional,
@@ -43,7 +44,7 @@ pub fn assert<MsgFn, Msg, ErrorFn, Err>(
 ) -> BinResult<()>
 where
     MsgFn: Fn() -> Msg,
-    Msg: Into<String> + Sized,
+    Msg: Into<Cow<'static, str>> + Sized,
     ErrorFn: Fn() -> Err,
     Err: CustomError + 'static,
 {
@@ -53,7 +54,7 @@ where
         Err(match error_fn {
             AssertErrorFn::Message(error_fn) => Error::AssertFail {
                 pos,
-                message: error_fn().into(),
+                message: crate::error::message(error_fn().into()),
             },
             AssertErrorFn::Error(error_fn) => Error::Custom {
                 pos,
@@ -63,6 +64,77 @@ where
     }
 }
 
+// These traits use autoref specialisation so that the operands of a failed
+// comparison assertion are only included in the error message if their types
+// implement `Debug`.
+pub struct AssertValue<'a, T: ?Sized>(pub &'a T);
+
+pub trait AssertValueDebug {
+    fn debug_value(&self) -> Option<&dyn core::fmt::Debug>;
+}
+
+impl<T: core::fmt::Debug + ?Sized> AssertValueDebug for AssertValue<'_, T> {
+    fn debug_value(&self) -> Option<&dyn core::fmt::Debug> {
+        Some(&self.0)
+    }
+}
+
+pub trait AssertValueNoDebug {
+    fn debug_value(&self) -> Option<&dyn core::fmt::Debug> {
+        None
+    }
+}
+
+impl<T: ?Sized> AssertValueNoDebug for &AssertValue<'_, T> {}
+
+#[cfg(not(feature = "compact-errors"))]
+#[must_use]
+pub fn assert_message(
+    message: &'static str,
+    left: Option<&dyn core::fmt::Debug>,
+    right: Option<&dyn core::fmt::Debug>,
+) -> Cow<'static, str> {
+    match (left, right) {
+        (Some(left), Some(right)) => {
+            alloc::format!("{message} (left: `{left:?}`, right: `{right:?}`)").into()
+        }
+        _ => message.into(),
+    }
+}
+
+#[cfg(feature = "compact-errors")]
+#[must_use]
+pub fn assert_message(
+    message: &'static str,
+    _: Option<&dyn core::fmt::Debug>,
+    _: Option<&dyn core::fmt::Debug>,
+) -> Cow<'static, str> {
+    message.into()
+}
+
+/// Adds a backtrace frame to an error from a derived implementation.
+///
+/// With the `compact-errors` feature, derived implementations do not collect
+/// backtraces, so the error is returned unchanged.
+#[cfg(not(feature = "compact-errors"))]
+#[must_use]
+pub fn with_context<Frame: Into<crate::error::BacktraceFrame>>(
+    error: Error,
+    frame: Frame,
+) -> Error {
+    crate::error::ContextExt::with_context(error, frame)
+}
+
+/// Adds a backtrace frame to an error from a derived implementation.
+///
+/// With the `compact-errors` feature, derived implementations do not collect
+/// backtraces, so the error is returned unchanged.
+#[cfg(feature = "compact-errors")]
+#[must_use]
+pub fn with_context<Frame: Into<crate::error::BacktraceFrame>>(error: Error, _: Frame) -> Error {
+    error
+}
+
 // This validates the map function return value by trying to coerce it into
 // a function with the expected return type. If this is not done, the
 // compiler will emit the diagnostic on the `#[derive]`d attribute instead of
@@ -95,11 +167,167 @@ where
     } else {
         Err(Error::BadMagic {
             pos,
-            found: Box::new(val) as _,
+            found: crate::error::found(val),
+        })
+    }
+}
+
+pub fn magic_any<R, B, const N: usize>(
+    reader: &mut R,
+    expected: [B; N],
+    endian: Endian,
+) -> BinResult<B>
+where
+    B: for<'a> BinRead<Args<'a> = ()>
+        + core::fmt::Debug
+        + PartialEq
+        + Sync
+        + Send
+        + Clone
+        + Copy
+        + 'static,
+    R: Read + Seek,
+{
+    let pos = reader.stream_position()?;
+    let val = B::read_options(reader, endian, ())?;
+    if expected.contains(&val) {
+        Ok(val)
+    } else {
+        Err(Error::BadMagic {
+            pos,
+            found: crate::error::found(val),
+        })
+    }
+}
+
+#[cfg(feature = "tokio")]
+pub use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt};
+
+#[cfg(feature = "tokio")]
+pub fn after_parse_async<'a, T, R>(
+    value: &'a mut T,
+    reader: &'a mut R,
+    endian: Endian,
+    args: T::Args<'a>,
+) -> impl core::future::Future<Output = BinResult<()>> + Send + 'a
+where
+    R: AsyncRead + AsyncSeek + Unpin + Send + ?Sized,
+    T: BinRead + Send,
+    T::Args<'a>: Send,
+{
+    crate::async_io::after_parse_async(value, reader, endian, args)
+}
+
+#[cfg(feature = "tokio")]
+pub async fn magic_async<R, B>(reader: &mut R, expected: B, endian: Endian) -> BinResult<()>
+where
+    B: for<'a> BinRead<Args<'a> = ()>
+        + core::fmt::Debug
+        + PartialEq
+        + Sync
+        + Send
+        + Clone
+        + Copy
+        + 'static,
+    R: AsyncRead + AsyncSeek + Unpin + Send + ?Sized,
+{
+    let pos = AsyncSeekExt::stream_position(reader).await?;
+    let val = <B as crate::AsyncBinRead>::read_options_async(reader, endian, ()).await?;
+    if val == expected {
+        Ok(())
+    } else {
+        Err(Error::BadMagic {
+            pos,
+            found: crate::error::found(val),
         })
     }
 }
 
+pub fn bad_magic<T>(pos: u64, found: T) -> Error
+where
+    T: core::fmt::Debug + Send + Sync + 'static,
+{
+    Error::BadMagic {
+        pos,
+        found: crate::error::found(found),
+    }
+}
+
+pub trait MagicMask: Copy {
+    #[must_use]
+    fn mask(self, mask: Self) -> Self;
+}
+
+macro_rules! magic_mask_impl {
+    ($($Int:ty),+) => {
+        $(
+            impl MagicMask for $Int {
+                fn mask(self, mask: Self) -> Self {
+                    self & mask
+                }
+            }
+        )+
+    };
+}
+
+magic_mask_impl!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+impl<const N: usize> MagicMask for [u8; N] {
+    fn mask(mut self, mask: Self) -> Self {
+        for (byte, mask) in self.iter_mut().zip(mask) {
+            *byte &= mask;
+        }
+        self
+    }
+}
+
+pub fn magic_masked<R, B, const N: usize>(
+    reader: &mut R,
+    expected: [B; N],
+    mask: B,
+    endian: Endian,
+) -> BinResult<B>
+where
+    B: for<'a> BinRead<Args<'a> = ()>
+        + core::fmt::Debug
+        + MagicMask
+        + PartialEq
+        + Sync
+        + Send
+        + 'static,
+    R: Read + Seek,
+{
+    let pos = reader.stream_position()?;
+    let val = B::read_options(reader, endian, ())?;
+    if expected
+        .iter()
+        .any(|expected| expected.mask(mask) == val.mask(mask))
+    {
+        Ok(val)
+    } else {
+        Err(Error::BadMagic {
+            pos,
+            found: crate::error::found(val),
+        })
+    }
+}
+
+pub fn is_eof<R: Read + Seek>(reader: &mut R) -> BinResult<bool> {
+    let pos = reader.stream_position()?;
+    let is_eof = reader.read(&mut [0])? == 0;
+    if !is_eof {
+        reader.seek(SeekFrom::Start(pos))?;
+    }
+    Ok(is_eof)
+}
+
+pub fn finally_fn_type_hint<T, F>(f: F) -> F
+where
+    F: FnOnce(&mut T, u64) -> BinResult<()>,
+{
+    f
+}
+
 pub fn parse_fn_type_hint<Ret, ParseFn, R, Args>(f: ParseFn) -> ParseFn
 where
     R: Read + Seek,
@@ -108,7 +336,7 @@ where
     f
 }
 
-pub fn parse_function_args_type_hint<R, Res, Args, F>(_: F, a: Args) -> Args
+pub fn parse_function_args_type_hint<R, Res, Args, F>(_: &F, a: Args) -> Args
 where
     R: Read + Seek,
     F: FnOnce(&mut R, Endian, Args) -> BinResult<Res>,
@@ -116,7 +344,7 @@ where
     a
 }
 
-pub fn write_function_args_type_hint<T, W, Args, F>(_: F, a: Args) -> Args
+pub fn write_function_args_type_hint<T, W, Args, F>(_: &F, a: Args) -> Args
 where
     W: Write + Seek,
     F: FnOnce(&T, &mut W, Endian, Args) -> BinResult<()>,
@@ -213,29 +441,332 @@ where
 }
 
 pub fn write_zeroes<W: Write>(writer: &mut W, count: u64) -> BinResult<()> {
+    write_fill(writer, count, 0)
+}
+
+pub fn write_fill<W: Write>(writer: &mut W, count: u64, fill: u8) -> BinResult<()> {
     const BUF_SIZE: u16 = 0x20;
-    const ZEROES: [u8; BUF_SIZE as usize] = [0u8; BUF_SIZE as usize];
+    let buf = [fill; BUF_SIZE as usize];
 
     if count <= BUF_SIZE.into() {
         // Lint: `count` is guaranteed to be <= BUF_SIZE
         #[allow(clippy::cast_possible_truncation)]
-        writer.write_all(&ZEROES[..count as usize])?;
+        writer.write_all(&buf[..count as usize])?;
     } else {
         let full_chunks = count / u64::from(BUF_SIZE);
         let remaining = count % u64::from(BUF_SIZE);
 
         for _ in 0..full_chunks {
-            writer.write_all(&ZEROES)?;
+            writer.write_all(&buf)?;
         }
 
         // Lint: `remaining` is guaranteed to be < BUF_SIZE
         #[allow(clippy::cast_possible_truncation)]
-        writer.write_all(&ZEROES[..remaining as usize])?;
+        writer.write_all(&buf[..remaining as usize])?;
     }
 
     Ok(())
 }
 
+pub fn skip_fill<R: Read + Seek>(reader: &mut R, count: i64, fill: u8) -> BinResult<()> {
+    const BUF_SIZE: usize = 0x20;
+
+    // Seeking backwards cannot be verified, so it is left to the reader
+    let Ok(mut remaining) = u64::try_from(count) else {
+        reader.seek(SeekFrom::Current(count))?;
+        return Ok(());
+    };
+
+    let mut buf = [0; BUF_SIZE];
+    while remaining != 0 {
+        let pos = reader.stream_position()?;
+        // Lint: The chunk size is guaranteed to be <= BUF_SIZE
+        #[allow(clippy::cast_possible_truncation)]
+        let buf = &mut buf[..remaining.min(BUF_SIZE as u64) as usize];
+        reader.read_exact(buf)?;
+        if let Some(index) = buf.iter().position(|byte| *byte != fill) {
+            return Err(Error::AssertFail {
+                pos: pos + index as u64,
+                message: alloc::format!(
+                    "expected padding byte {fill:#04x}, found {:#04x}",
+                    buf[index]
+                )
+                .into(),
+            });
+        }
+        remaining -= buf.len() as u64;
+    }
+
+    Ok(())
+}
+
+pub fn read_rest<R, T>(reader: &mut R, _: Endian, _: ()) -> BinResult<T>
+where
+    R: Read + Seek,
+    T: From<alloc::vec::Vec<u8>>,
+{
+    let mut data = alloc::vec::Vec::new();
+    reader.read_to_end(&mut data)?;
+    Ok(data.into())
+}
+
+#[cfg(feature = "std")]
+pub fn read_context<R, T>(reader: &mut R, _: Endian, _: ()) -> BinResult<T>
+where
+    R: Seek,
+    T: Clone + 'static,
+{
+    match crate::context::get() {
+        Some(value) => Ok(value),
+        None => Err(Error::AssertFail {
+            pos: reader.stream_position()?,
+            message: alloc::format!(
+                "no `{}` value is available from `binrw::context`",
+                core::any::type_name::<T>()
+            )
+            .into(),
+        }),
+    }
+}
+
+pub struct CountOutOfRange {
+    count: usize,
+    ty: &'static str,
+}
+
+impl core::fmt::Debug for CountOutOfRange {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
+    }
+}
+
+impl core::fmt::Display for CountOutOfRange {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "count {} out of range of {}", self.count, self.ty)
+    }
+}
+
+pub fn count_of<T: TryFrom<usize>>(count: usize) -> Result<T, CountOutOfRange> {
+    T::try_from(count).map_err(|_| CountOutOfRange {
+        count,
+        ty: core::any::type_name::<T>(),
+    })
+}
+
+pub trait FloatField: Sized {
+    fn find_float(&self, reject: &mut impl FnMut(FpCategory) -> bool) -> Option<FpCategory>;
+    #[must_use]
+    fn canonical_nan(&self) -> Self;
+}
+
+macro_rules! float_field_impl {
+    ($($ty:ty),+) => {$(
+        impl FloatField for $ty {
+            fn find_float(
+                &self,
+                reject: &mut impl FnMut(FpCategory) -> bool,
+            ) -> Option<FpCategory> {
+                let category = self.classify();
+                reject(category).then_some(category)
+            }
+
+            fn canonical_nan(&self) -> Self {
+                if self.is_nan() {
+                    <$ty>::NAN
+                } else {
+                    *self
+                }
+            }
+        }
+    )+};
+}
+
+float_field_impl!(f32, f64);
+
+impl<T: FloatField> FloatField for Option<T> {
+    fn find_float(&self, reject: &mut impl FnMut(FpCategory) -> bool) -> Option<FpCategory> {
+        self.as_ref().and_then(|value| value.find_float(reject))
+    }
+
+    fn canonical_nan(&self) -> Self {
+        self.as_ref().map(T::canonical_nan)
+    }
+}
+
+impl<T: FloatField, const N: usize> FloatField for [T; N] {
+    fn find_float(&self, reject: &mut impl FnMut(FpCategory) -> bool) -> Option<FpCategory> {
+        self.iter().find_map(|value| value.find_float(reject))
+    }
+
+    fn canonical_nan(&self) -> Self {
+        core::array::from_fn(|index| self[index].canonical_nan())
+    }
+}
+
+impl<T: FloatField> FloatField for alloc::vec::Vec<T> {
+    fn find_float(&self, reject: &mut impl FnMut(FpCategory) -> bool) -> Option<FpCategory> {
+        self.iter().find_map(|value| value.find_float(reject))
+    }
+
+    fn canonical_nan(&self) -> Self {
+        self.iter().map(T::canonical_nan).collect()
+    }
+}
+
+pub fn check_float<T: FloatField>(
+    value: T,
+    pos: u64,
+    finite: bool,
+    no_subnormal: bool,
+) -> BinResult<T> {
+    let found = value.find_float(&mut |category| match category {
+        FpCategory::Nan | FpCategory::Infinite => finite,
+        FpCategory::Subnormal => no_subnormal,
+        FpCategory::Zero | FpCategory::Normal => false,
+    });
+
+    match found {
+        None => Ok(value),
+        Some(category) => Err(Error::AssertFail {
+            pos,
+            message: alloc::format!(
+                "expected a {} float, found {}",
+                match (finite, no_subnormal) {
+                    (true, true) => "finite, normal",
+                    (true, false) => "finite",
+                    _ => "normal",
+                },
+                match category {
+                    FpCategory::Nan => "NaN",
+                    FpCategory::Infinite => "an infinite value",
+                    _ => "a subnormal value",
+                }
+            )
+            .into(),
+        }),
+    }
+}
+
+pub fn write_dyn<P, W>(value: &P, writer: &mut W, endian: Endian, _: ()) -> BinResult<()>
+where
+    P: core::ops::Deref,
+    P::Target: DynBinWrite,
+    W: Write + Seek,
+{
+    value.deref().dyn_write_options(writer, endian)
+}
+
+pub type SizeHint = (usize, Option<usize>);
+
+#[must_use]
+pub fn size_hint_add(a: SizeHint, b: SizeHint) -> SizeHint {
+    (
+        a.0.saturating_add(b.0),
+        a.1.zip(b.1).and_then(|(a, b)| a.checked_add(b)),
+    )
+}
+
+#[must_use]
+pub fn size_hint_either(a: SizeHint, b: SizeHint) -> SizeHint {
+    (a.0.min(b.0), a.1.zip(b.1).map(|(a, b)| a.max(b)))
+}
+
+pub struct UntrustedGuard(());
+
+impl Drop for UntrustedGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        crate::untrusted::DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+pub fn untrusted_enter(pos: u64) -> BinResult<UntrustedGuard> {
+    #[cfg(feature = "std")]
+    crate::untrusted::DEPTH.with(|depth| {
+        let next = depth.get() + 1;
+        let max_depth = crate::untrusted::max_depth();
+        if next > max_depth {
+            return Err(Error::AssertFail {
+                pos,
+                message: alloc::format!(
+                    "nesting depth exceeds the untrusted input limit of {max_depth}"
+                )
+                .into(),
+            });
+        }
+        depth.set(next);
+        Ok(())
+    })?;
+
+    #[cfg(not(feature = "std"))]
+    let _ = pos;
+
+    Ok(UntrustedGuard(()))
+}
+
+/// Checks an untrusted `count` against the current limit and the bytes
+/// remaining in the stream, where each item takes at least `min_size` bytes.
+pub fn untrusted_count<R: Read + Seek>(
+    reader: &mut R,
+    count: usize,
+    min_size: usize,
+) -> BinResult<usize> {
+    let pos = reader.stream_position()?;
+    let max_count = crate::untrusted::max_count();
+    if count > max_count {
+        return Err(Error::AssertFail {
+            pos,
+            message: alloc::format!(
+                "count {count} exceeds the untrusted input limit of {max_count}"
+            )
+            .into(),
+        });
+    }
+
+    let end = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(pos))?;
+    let remaining = end.saturating_sub(pos);
+    let needed = count
+        .checked_mul(min_size)
+        .and_then(|needed| u64::try_from(needed).ok());
+    if needed.is_none_or(|needed| needed > remaining) {
+        return Err(Error::AssertFail {
+            pos,
+            message: alloc::format!(
+                "count {count} needs more than the {remaining} byte(s) remaining in the stream"
+            )
+            .into(),
+        });
+    }
+
+    Ok(count)
+}
+
+pub fn untrusted_align(pos: u64, align: i64) -> BinResult<i64> {
+    let error = || Error::AssertFail {
+        pos,
+        message: alloc::format!("invalid alignment {align}").into(),
+    };
+    let pos = i64::try_from(pos).map_err(|_| error())?;
+    if align <= 0 {
+        return Err(error());
+    }
+    Ok((align - pos % align) % align)
+}
+
+pub fn untrusted_size(pos: u64, start: u64) -> BinResult<i64> {
+    pos.checked_sub(start)
+        .and_then(|size| i64::try_from(size).ok())
+        .ok_or_else(|| Error::AssertFail {
+            pos,
+            message: alloc::format!("stream moved before the start of the field at {start:#x}")
+                .into(),
+        })
+}
+
+#[cfg(feature = "tracing")]
+pub use tracing;
+
 #[cfg(feature = "std")]
 pub use std::eprintln;
 
@@ -250,3 +781,203 @@ macro_rules! eprintln {
 
 #[cfg(not(feature = "std"))]
 pub use crate::eprintln;
+
+fn checksum_range(
+    pos: u64,
+    base: u64,
+    field_pos: u64,
+    range: Option<core::ops::Range<u64>>,
+) -> BinResult<core::ops::Range<u64>> {
+    let range = range.unwrap_or(0..field_pos.saturating_sub(base));
+    match (base.checked_add(range.start), base.checked_add(range.end)) {
+        (Some(start), Some(end)) if start <= end => Ok(start..end),
+        _ => Err(Error::AssertFail {
+            pos,
+            message: alloc::format!("invalid checksum range {range:?}").into(),
+        }),
+    }
+}
+
+fn checksum_mismatch<T: core::fmt::Debug>(
+    pos: u64,
+    range: &core::ops::Range<u64>,
+    expected: &T,
+    found: &T,
+) -> Error {
+    Error::AssertFail {
+        pos,
+        message: alloc::format!(
+            "checksum mismatch over bytes {:#x}..{:#x}: expected {expected:#x?}, found {found:#x?}",
+            range.start,
+            range.end
+        )
+        .into(),
+    }
+}
+
+pub fn verify_checksum<R, T, F>(
+    reader: &mut R,
+    base: u64,
+    field_pos: u64,
+    range: Option<core::ops::Range<u64>>,
+    value: &T,
+    checksum: F,
+) -> BinResult<()>
+where
+    R: Read + Seek,
+    T: PartialEq + core::fmt::Debug,
+    F: FnOnce(&[u8]) -> T,
+{
+    let range = checksum_range(field_pos, base, field_pos, range)?;
+    let pos = reader.stream_position()?;
+    reader.seek(SeekFrom::Start(range.start))?;
+    let mut data = alloc::vec::Vec::new();
+    reader
+        .by_ref()
+        .take(range.end - range.start)
+        .read_to_end(&mut data)?;
+    reader.seek(SeekFrom::Start(pos))?;
+
+    if u64::try_from(data.len()).ok() != Some(range.end - range.start) {
+        return Err(Error::Io(crate::io::Error::new(
+            crate::io::ErrorKind::UnexpectedEof,
+            "checksum range extends past the end of the stream",
+        )));
+    }
+
+    let expected = checksum(&data);
+    if *value == expected {
+        Ok(())
+    } else {
+        Err(checksum_mismatch(field_pos, &range, &expected, value))
+    }
+}
+
+/// A writer which keeps a copy of everything written to it so that checksums
+/// can be calculated over data which has already been written.
+pub struct ChecksumWriter<'a, W> {
+    inner: &'a mut W,
+    start: u64,
+    pos: u64,
+    data: alloc::vec::Vec<u8>,
+}
+
+impl<'a, W: Seek> ChecksumWriter<'a, W> {
+    pub fn new(inner: &'a mut W, start: u64) -> Self {
+        Self {
+            inner,
+            start,
+            pos: start,
+            data: alloc::vec::Vec::new(),
+        }
+    }
+}
+
+impl<W: Write> Write for ChecksumWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> crate::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        if let Some(offset) = self
+            .pos
+            .checked_sub(self.start)
+            .and_then(|offset| usize::try_from(offset).ok())
+        {
+            let end = offset + n;
+            if self.data.len() < end {
+                self.data.resize(end, 0);
+            }
+            self.data[offset..end].copy_from_slice(&buf[..n]);
+        }
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> crate::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Seek> Seek for ChecksumWriter<'_, W> {
+    fn seek(&mut self, pos: SeekFrom) -> crate::io::Result<u64> {
+        self.pos = self.inner.seek(pos)?;
+        Ok(self.pos)
+    }
+}
+
+pub fn size_of_value<T: TryFrom<u64>>(pos: u64, size: u64) -> BinResult<T> {
+    T::try_from(size).map_err(|_| Error::AssertFail {
+        pos,
+        message: alloc::format!("size {size:#x} does not fit in the `size_of` field").into(),
+    })
+}
+
+pub fn size_region<R, S>(reader: &mut R, size: S) -> BinResult<TakeSeek<&mut R>>
+where
+    R: Read + Seek,
+    S: TryInto<u64> + Copy + core::fmt::Debug,
+{
+    let pos = reader.stream_position()?;
+    match size.try_into() {
+        Ok(size) if pos.checked_add(size).is_some() => Ok(reader.take_seek(size)),
+        _ => Err(Error::AssertFail {
+            pos,
+            message: alloc::format!("invalid region size {size:?}").into(),
+        }),
+    }
+}
+
+pub fn size_region_end<R: Seek>(mut region: TakeSeek<&mut R>, error: bool) -> BinResult<&mut R> {
+    let pos = region.stream_position()?;
+    let end = region.end();
+    let reader = region.into_inner();
+    if pos < end && error {
+        return Err(Error::AssertFail {
+            pos,
+            message: alloc::format!("{} unread byte(s) at the end of a sized region", end - pos)
+                .into(),
+        });
+    }
+
+    if pos != end {
+        reader.seek(SeekFrom::Start(end))?;
+    }
+
+    Ok(reader)
+}
+
+pub fn size_region_capture<R: Read + Seek>(
+    mut region: TakeSeek<&mut R>,
+) -> BinResult<(&mut R, alloc::vec::Vec<u8>)> {
+    let mut rest = alloc::vec::Vec::new();
+    region.read_to_end(&mut rest)?;
+    size_region_end(region, false).map(|reader| (reader, rest))
+}
+
+pub fn write_checksum<W, T, F>(
+    writer: &mut ChecksumWriter<'_, W>,
+    range: Option<core::ops::Range<u64>>,
+    checksum: F,
+) -> BinResult<T>
+where
+    F: FnOnce(&[u8]) -> T,
+{
+    let pos = writer.pos;
+    let range = checksum_range(pos, writer.start, pos, range)?;
+    let start = range.start - writer.start;
+    let end = range.end - writer.start;
+    match usize::try_from(start)
+        .ok()
+        .zip(usize::try_from(end).ok())
+        .and_then(|(start, end)| writer.data.get(start..end))
+    {
+        Some(data) => Ok(checksum(data)),
+        None => Err(Error::AssertFail {
+            pos,
+            message: alloc::format!(
+                "checksum range {:#x}..{:#x} has not been written yet",
+                range.start,
+                range.end
+            )
+            .into(),
+        }),
+    }
+}
@@ -1,7 +1,11 @@
 //! Type definitions for wrappers which parse interleaved data.
 
-use crate::{BinRead, BinResult, VecArgs};
-use alloc::vec::Vec;
+use crate::{
+    io::{Seek, Write},
+    untrusted::preallocation,
+    BinRead, BinResult, BinWrite, Endian, Error, VecArgs,
+};
+use alloc::{format, vec::Vec};
 use core::fmt;
 
 /// A parser for data which consists of values of type `T` interleaved with
@@ -14,6 +18,10 @@ use core::fmt;
 /// [`separated_trailing()`]: Self::separated_trailing
 /// [`parse_with`]: crate::docs::attribute#custom-parserswriters
 ///
+/// When written, each value is followed by its separator. If there is one
+/// separator for every value, the last value is followed by a trailing
+/// separator; otherwise, there must be one fewer separator than values.
+///
 /// Consider using a `Vec<(T, P)>` or `(Vec<(T, P)>, Option<T>>)` instead if you
 /// do not need the parsed data to be transformed into a structure of arrays.
 ///
@@ -55,7 +63,7 @@ where
     ///
     /// # Errors
     ///
-    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    /// If reading fails, an [`Error`] variant will be returned.
     ///
     /// # Example
     ///
@@ -75,13 +83,13 @@ where
     /// # assert_eq!(*y.x, vec![3, 2, 1]);
     /// # assert_eq!(y.x.separators, vec![0, 1]);
     /// ```
-    #[crate::parser(reader, endian)]
-    pub fn separated<'a>(args: VecArgs<T::Args<'a>>, ...) -> BinResult<Self>
+    #[crate::parser(reader, endian, args_raw)]
+    pub fn separated<'a>(args: VecArgs<T::Args<'a>>) -> BinResult<Self>
     where
         T::Args<'a>: Clone,
     {
-        let mut data = Vec::with_capacity(args.count);
-        let mut separators = Vec::with_capacity(args.count.max(1) - 1);
+        let mut data = Vec::with_capacity(preallocation::<T>(args.count));
+        let mut separators = Vec::with_capacity(preallocation::<P>(args.count.max(1) - 1));
 
         for i in 0..args.count {
             data.push(T::read_options(reader, endian, args.inner.clone())?);
@@ -100,14 +108,14 @@ where
     ///
     /// # Errors
     ///
-    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
-    #[crate::parser(reader, endian)]
-    pub fn separated_trailing<'a>(args: VecArgs<T::Args<'a>>, ...) -> BinResult<Self>
+    /// If reading fails, an [`Error`] variant will be returned.
+    #[crate::parser(reader, endian, args_raw)]
+    pub fn separated_trailing<'a>(args: VecArgs<T::Args<'a>>) -> BinResult<Self>
     where
         T::Args<'a>: Clone,
     {
-        let mut data = Vec::with_capacity(args.count);
-        let mut separators = Vec::with_capacity(args.count);
+        let mut data = Vec::with_capacity(preallocation::<T>(args.count));
+        let mut separators = Vec::with_capacity(preallocation::<P>(args.count));
 
         for _ in 0..args.count {
             data.push(T::read_options(reader, endian, args.inner.clone())?);
@@ -131,6 +139,113 @@ where
     }
 }
 
+impl<T, P> Punctuated<T, P>
+where
+    T: BinRead + BinWrite,
+    P: BinRead + for<'a> BinWrite<Args<'a> = ()>,
+{
+    /// Writes values of type `T` separated by values of type `P` without a
+    /// trailing separator value, even if one was read.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, or if there are too few separators, an
+    /// [`Error`] variant will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use binrw::{prelude::*, io::Cursor};
+    /// use binrw::punctuated::Punctuated;
+    ///
+    /// #[binrw]
+    /// struct MyList {
+    ///     #[br(parse_with = Punctuated::separated_trailing, count = 3)]
+    ///     #[bw(write_with = Punctuated::write_separated)]
+    ///     x: Punctuated<u16, u8>,
+    /// }
+    ///
+    /// let mut x = Cursor::new(b"\0\x03\0\0\x02\x01\0\x01\x02");
+    /// let list = MyList::read_be(&mut x).unwrap();
+    ///
+    /// let mut out = Cursor::new(Vec::new());
+    /// list.write_be(&mut out).unwrap();
+    /// assert_eq!(out.into_inner(), b"\0\x03\0\0\x02\x01\0\x01");
+    /// ```
+    #[crate::writer(writer, endian, args_raw)]
+    pub fn write_separated<'a>(value: &Self, args: <T as BinWrite>::Args<'a>) -> BinResult<()>
+    where
+        <T as BinWrite>::Args<'a>: Clone,
+    {
+        value.write_interleaved(writer, endian, args, value.data.len().saturating_sub(1))
+    }
+
+    fn write_interleaved<'a, W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: <T as BinWrite>::Args<'a>,
+        count: usize,
+    ) -> BinResult<()>
+    where
+        <T as BinWrite>::Args<'a>: Clone,
+    {
+        if self.separators.len() < count {
+            return Err(Error::AssertFail {
+                pos: writer.stream_position()?,
+                message: format!(
+                    "expected {count} separators for {} values, found {}",
+                    self.data.len(),
+                    self.separators.len()
+                )
+                .into(),
+            });
+        }
+
+        for (index, value) in self.data.iter().enumerate() {
+            value.write_options(writer, endian, args.clone())?;
+            if index < count {
+                self.separators[index].write_options(writer, endian, ())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, P> BinWrite for Punctuated<T, P>
+where
+    T: BinRead + BinWrite,
+    P: BinRead + for<'a> BinWrite<Args<'a> = ()>,
+    for<'a> <T as BinWrite>::Args<'a>: Clone,
+{
+    type Args<'a> = <T as BinWrite>::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let count = self.data.len();
+        if self.separators.len() == count {
+            self.write_interleaved(writer, endian, args, count)
+        } else if self.separators.len() + 1 == count {
+            self.write_interleaved(writer, endian, args, count - 1)
+        } else {
+            Err(Error::AssertFail {
+                pos: writer.stream_position()?,
+                message: format!(
+                    "expected {} or {count} separators for {count} values, found {}",
+                    count.saturating_sub(1),
+                    self.separators.len()
+                )
+                .into(),
+            })
+        }
+    }
+}
+
 impl<T: BinRead + fmt::Debug, P: BinRead> fmt::Debug for Punctuated<T, P> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.data.fmt(f)
@@ -0,0 +1,214 @@
+//! Type definitions for run-length encoded data.
+
+use crate::{
+    io::{Read, Seek, Write},
+    BinRead, BinResult, BinWrite, Endian, Error, NamedArgs,
+};
+use alloc::vec::Vec;
+
+/// A run-length encoding scheme.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RleScheme {
+    /// Each run is a `u8` repeat count followed by a single value.
+    ///
+    /// A count of zero is an empty run. Runs longer than 255 values are
+    /// written as multiple runs.
+    #[default]
+    Pairs,
+
+    /// The [PackBits](https://en.wikipedia.org/wiki/PackBits) scheme.
+    ///
+    /// Each packet starts with an `i8` header `n`. If `n` is between 0 and
+    /// 127, the next `n + 1` values are copied as-is. If `n` is between -127
+    /// and -1, the next value is repeated `1 - n` times. A header of -128 is
+    /// ignored.
+    PackBits,
+}
+
+/// Named arguments for the [`BinRead::read_options()`] implementation of
+/// [`Rle`].
+#[derive(Clone, NamedArgs)]
+pub struct RleArgs {
+    /// The number of decoded values to read.
+    pub count: usize,
+
+    /// The encoding scheme.
+    #[named_args(default = RleScheme::Pairs)]
+    pub scheme: RleScheme,
+}
+
+/// A run-length encoded list of values.
+///
+/// The data is decoded when reading and encoded when writing, using the
+/// [`RleScheme`] given in the arguments. Reading stops once `count` values
+/// have been decoded.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{binrw, io::Cursor, BinRead, BinWrite, RleBytes, RleScheme};
+///
+/// #[binrw]
+/// #[brw(little)]
+/// struct Sprite {
+///     #[br(temp)]
+///     #[bw(calc = pixels.len() as u16)]
+///     len: u16,
+///     #[br(args { count: len.into(), scheme: RleScheme::PackBits })]
+///     #[bw(args_raw = RleScheme::PackBits)]
+///     pixels: RleBytes,
+/// }
+///
+/// let data = b"\x06\0\xfd\x07\x01\x01\x02";
+/// let sprite = Sprite::read(&mut Cursor::new(data)).unwrap();
+/// assert_eq!(*sprite.pixels, [7, 7, 7, 7, 1, 2]);
+///
+/// let mut out = Cursor::new(Vec::new());
+/// sprite.write(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), data);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Rle<T>(
+    /// The decoded values.
+    pub Vec<T>,
+);
+
+/// A run-length encoded byte string.
+pub type RleBytes = Rle<u8>;
+
+impl<T> Rle<T> {
+    /// Consumes this wrapper, returning the decoded values.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> BinRead for Rle<T>
+where
+    T: for<'a> BinRead<Args<'a> = ()> + Clone,
+{
+    type Args<'a> = RleArgs;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let mut values = Vec::new();
+        while values.len() < args.count {
+            let pos = reader.stream_position()?;
+            let (repeat, literal) = match args.scheme {
+                RleScheme::Pairs => (usize::from(u8::read_options(reader, endian, ())?), 0),
+                RleScheme::PackBits => match i8::read_options(reader, endian, ())? {
+                    -128 => continue,
+                    header @ 0.. => (0, usize::from(header.unsigned_abs()) + 1),
+                    header => (usize::from(header.unsigned_abs()) + 1, 0),
+                },
+            };
+
+            if values.len() + repeat.max(literal) > args.count {
+                return Err(Error::AssertFail {
+                    pos,
+                    message: alloc::format!(
+                        "run-length encoded data decodes to more than {} values",
+                        args.count
+                    )
+                    .into(),
+                });
+            }
+
+            if repeat != 0 {
+                let value = T::read_options(reader, endian, ())?;
+                values.resize(values.len() + repeat, value);
+            }
+            for _ in 0..literal {
+                values.push(T::read_options(reader, endian, ())?);
+            }
+        }
+
+        Ok(Self(values))
+    }
+}
+
+impl<T> BinWrite for Rle<T>
+where
+    T: for<'a> BinWrite<Args<'a> = ()> + PartialEq,
+{
+    type Args<'a> = RleScheme;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let (max_run, min_repeat) = match args {
+            RleScheme::Pairs => (255, 1),
+            RleScheme::PackBits => (128, 2),
+        };
+
+        let mut rest = &self.0[..];
+        while let Some(value) = rest.first() {
+            let repeat = rest
+                .iter()
+                .take(max_run)
+                .take_while(|other| *other == value)
+                .count();
+
+            if repeat >= min_repeat {
+                match args {
+                    // `repeat` is at most 255
+                    #[allow(clippy::cast_possible_truncation)]
+                    RleScheme::Pairs => (repeat as u8).write_options(writer, endian, ())?,
+                    // `repeat` is between 2 and 128
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+                    RleScheme::PackBits => {
+                        ((1 - repeat as i16) as i8).write_options(writer, endian, ())?;
+                    }
+                }
+                value.write_options(writer, endian, ())?;
+                rest = &rest[repeat..];
+            } else {
+                let literal = rest
+                    .windows(2)
+                    .position(|pair| pair[0] == pair[1])
+                    .unwrap_or(rest.len())
+                    .min(max_run);
+                // `literal` is between 1 and 128
+                #[allow(clippy::cast_possible_truncation)]
+                (literal as u8 - 1).write_options(writer, endian, ())?;
+                rest[..literal].write_options(writer, endian, ())?;
+                rest = &rest[literal..];
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> core::ops::Deref for Rle<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> core::ops::DerefMut for Rle<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<Vec<T>> for Rle<T> {
+    fn from(values: Vec<T>) -> Self {
+        Self(values)
+    }
+}
+
+impl<T> From<Rle<T>> for Vec<T> {
+    fn from(rle: Rle<T>) -> Self {
+        rle.0
+    }
+}
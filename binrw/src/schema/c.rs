@@ -0,0 +1,407 @@
+//! Export of schemas as C headers.
+//!
+//! [`header`] generates packed C structs from the [read schemas](super::ReadSchema)
+//! of derived types, so that firmware or other C code can use the same wire
+//! format. Each struct is followed by a `static_assert` which checks its size.
+//!
+//! Only types with a fixed layout can be exported. A type is rejected if it
+//! has a field with a condition, a count or padding which is not an integer
+//! literal, or a runtime byte order, or if it is an enum with fields or a type
+//! converted by a `map` function. Byte order is given in comments, since C
+//! structs do not have one.
+//!
+//! Alignment is calculated relative to the start of the struct. Seeks are not
+//! described by schemas, so types which use them must not be exported.
+//!
+//! # Example
+//!
+//! ```
+//! # use binrw::{prelude::*, schema::{c, ReadSchema}};
+//! #[derive(BinRead)]
+//! #[br(little, magic = b"HDR", schema)]
+//! struct Header {
+//!     version: u16,
+//!     #[br(align_before = 4)]
+//!     len: u32,
+//! }
+//!
+//! let header = c::header(&[Header::SCHEMA]).unwrap();
+//! assert!(header.contains("uint8_t _pad0[3];"));
+//! assert!(header.contains("static_assert(sizeof(Header) == 12"));
+//! ```
+
+use super::{Endianness, Field, Layout, Schema, Variant};
+use crate::Endian;
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+use core::fmt;
+
+/// Generates a C header with a packed struct for each of the given schemas.
+///
+/// Schemas must be given in dependency order. See the
+/// [module documentation](self) for limitations.
+///
+/// # Errors
+///
+/// If a type cannot be represented as a C struct, an [`Error`] is returned.
+pub fn header(schemas: &[Schema]) -> Result<String, Error> {
+    let mut writer = Writer {
+        lines: Vec::from([
+            String::from("/* Generated by binrw */"),
+            String::from("#include <assert.h>"),
+            String::from("#include <stdint.h>"),
+        ]),
+        sizes: BTreeMap::new(),
+    };
+    for schema in schemas {
+        writer.lines.push(String::new());
+        writer.schema(schema)?;
+    }
+    writer.lines.push(String::new());
+    Ok(writer.lines.join("\n"))
+}
+
+/// An error which occurs when a type cannot be represented in C.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Error {
+    /// The name of the type.
+    pub ty: &'static str,
+    /// The name of the field, if the error is specific to one field.
+    pub field: Option<&'static str>,
+    /// The reason the type cannot be represented.
+    pub reason: Reason,
+}
+
+/// The reason a type cannot be represented in C.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Reason {
+    /// The field is only present under a condition.
+    Condition,
+    /// The number of items is not an integer literal.
+    Count,
+    /// The padding or alignment is not an integer literal.
+    Padding,
+    /// The byte order is determined at runtime.
+    Endian,
+    /// The type is an enum with fields, or with a magic number on the enum
+    /// itself.
+    Enum,
+    /// The type is converted by a `map` function.
+    Mapped,
+    /// The type is not a primitive, array, or previously exported type.
+    UnknownType(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}", self.ty)?;
+        if let Some(field) = self.field {
+            write!(f, "::{field}")?;
+        }
+        write!(f, "` cannot be represented in C: ")?;
+        match self.reason {
+            Reason::Condition => write!(f, "it has a condition"),
+            Reason::Count => write!(f, "its count is not an integer literal"),
+            Reason::Padding => write!(f, "its padding is not an integer literal"),
+            Reason::Endian => write!(f, "its byte order is determined at runtime"),
+            Reason::Enum => write!(f, "enums with fields or magic are not supported"),
+            Reason::Mapped => write!(f, "it is converted by a `map` function"),
+            Reason::UnknownType(ty) => write!(f, "unknown type `{ty}`"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+struct Writer {
+    lines: Vec<String>,
+    sizes: BTreeMap<&'static str, (String, usize)>,
+}
+
+impl Writer {
+    fn schema(&mut self, schema: &Schema) -> Result<(), Error> {
+        let error = |field, reason| Error {
+            ty: schema.name,
+            field,
+            reason,
+        };
+
+        match schema.layout {
+            Layout::Struct(fields) => {
+                let mut st = StructWriter {
+                    lines: Vec::new(),
+                    size: 0,
+                    pads: 0,
+                };
+                if let Some(magic) = schema.magic {
+                    st.magic(magic, "magic", schema.endian);
+                }
+                for field in fields {
+                    st.field(self, field, schema.endian)
+                        .map_err(|reason| error(Some(field.name), reason))?;
+                }
+                self.struct_def(schema.name, &st.lines, st.size);
+                self.sizes
+                    .insert(schema.name, (String::from(schema.name), st.size));
+            }
+            Layout::Enum(variants) => {
+                if schema.magic.is_some() || variants.iter().any(|v| !v.fields.is_empty()) {
+                    return Err(error(None, Reason::Enum));
+                }
+                self.unit_enum(schema, variants)
+                    .ok_or_else(|| error(None, Reason::Enum))?;
+            }
+            Layout::Mapped => {
+                let repr = schema.repr.ok_or_else(|| error(None, Reason::Mapped))?;
+                let (ty, size) = self
+                    .scalar(repr)
+                    .ok_or_else(|| error(None, Reason::UnknownType(repr)))?;
+                self.lines.push(format!("typedef {} {};", ty, schema.name));
+                self.sizes
+                    .insert(schema.name, (String::from(schema.name), size));
+            }
+        }
+        Ok(())
+    }
+
+    fn struct_def(&mut self, name: &str, lines: &[String], size: usize) {
+        self.lines.push(String::from("#pragma pack(push, 1)"));
+        self.lines.push(format!("typedef struct {name} {{"));
+        self.lines
+            .extend(lines.iter().map(|line| format!("    {line}")));
+        self.lines.push(format!("}} {name};"));
+        self.lines.push(String::from("#pragma pack(pop)"));
+        self.lines.push(format!(
+            "static_assert(sizeof({name}) == {size}, \"{name} must be {size} bytes\");"
+        ));
+    }
+
+    /// Writes an enum whose variants have no fields as a typedef of its
+    /// underlying type, with constants for its values if they are known.
+    fn unit_enum(&mut self, schema: &Schema, variants: &[Variant]) -> Option<()> {
+        let ty = match (schema.repr, variants.first().and_then(|v| v.magic)) {
+            (Some(repr), _) => repr,
+            (None, Some(magic)) => int_literal(magic)?.1,
+            (None, None) => return None,
+        };
+        let (c_ty, size) = self.scalar(ty)?;
+        self.lines
+            .push(format!("typedef {} {};", c_ty, schema.name));
+
+        let values = variants
+            .iter()
+            .map(|variant| {
+                let (value, magic_ty) = int_literal(variant.magic?)?;
+                (magic_ty == ty)
+                    .then(|| format!("    {}_{} = {},", schema.name, variant.name, value))
+            })
+            .collect::<Option<Vec<_>>>();
+        if let Some(values) = values {
+            self.lines.push(String::from("enum {"));
+            self.lines.extend(values);
+            self.lines.push(String::from("};"));
+        } else {
+            let names = variants.iter().map(|v| v.name).collect::<Vec<_>>();
+            self.lines
+                .push(format!("/* {}: {} */", schema.name, names.join(", ")));
+        }
+        self.sizes
+            .insert(schema.name, (String::from(schema.name), size));
+        Some(())
+    }
+
+    /// Returns the C type and size of a primitive or previously exported type.
+    fn scalar(&self, ty: &str) -> Option<(String, usize)> {
+        let name = ty.rsplit("::").next().unwrap_or(ty);
+        match primitive(name) {
+            Some((c_ty, size)) => Some((String::from(c_ty), size)),
+            None => self.sizes.get(name).cloned(),
+        }
+    }
+}
+
+struct StructWriter {
+    lines: Vec<String>,
+    size: usize,
+    pads: usize,
+}
+
+impl StructWriter {
+    fn field(&mut self, writer: &Writer, field: &Field, endian: Endianness) -> Result<(), Reason> {
+        if field.condition.is_some() {
+            return Err(Reason::Condition);
+        }
+        let endian = match field.endian {
+            Endianness::Inherited => endian,
+            Endianness::Fixed(_) | Endianness::Runtime(..) => field.endian,
+        };
+        if matches!(endian, Endianness::Runtime(..)) {
+            return Err(Reason::Endian);
+        }
+
+        if let Some(pad) = field.pad_before {
+            self.pad(literal(pad).ok_or(Reason::Padding)?);
+        }
+        if let Some(align) = field.align_before {
+            self.align(literal(align).ok_or(Reason::Padding)?);
+        }
+        if let Some(magic) = field.magic {
+            self.magic(magic, &format!("{}_magic", field.name), endian);
+        }
+
+        let start = self.size;
+        let (ty, dims, size, is_primitive) = field_type(writer, field)?;
+        let count = dims.iter().product::<usize>();
+        let dims = dims
+            .iter()
+            .map(|dim| format!("[{dim}]"))
+            .collect::<Vec<_>>()
+            .concat();
+        let endian = endian_name(endian, size).filter(|_| is_primitive);
+        self.lines.push(match endian {
+            Some(endian) => format!("{} {}{}; /* {} */", ty, field.name, dims, endian),
+            None => format!("{} {}{};", ty, field.name, dims),
+        });
+        self.size += count * size;
+
+        if let Some(size) = field.pad_size_to {
+            let size = literal(size).ok_or(Reason::Padding)?;
+            self.pad((start + size).saturating_sub(self.size));
+        }
+        if let Some(align) = field.align_after {
+            self.align(literal(align).ok_or(Reason::Padding)?);
+        }
+        if let Some(pad) = field.pad_after {
+            self.pad(literal(pad).ok_or(Reason::Padding)?);
+        }
+        Ok(())
+    }
+
+    fn magic(&mut self, magic: &str, name: &str, endian: Endianness) {
+        // Only the first alternative is written
+        let magic = magic.split(" | ").next().unwrap_or(magic);
+        if let Some(bytes) = magic.strip_prefix("b\"") {
+            let len = byte_str_len(bytes.trim_end_matches('"'));
+            self.lines
+                .push(format!("uint8_t {name}[{len}]; /* {magic} */"));
+            self.size += len;
+        } else if let Some((c_ty, size, value)) = int_literal(magic)
+            .and_then(|(value, ty)| primitive(ty).map(|(c_ty, size)| (c_ty, size, value)))
+        {
+            self.lines.push(match endian_name(endian, size) {
+                Some(endian) => format!("{c_ty} {name}; /* {value}, {endian} */"),
+                None => format!("{c_ty} {name}; /* {value} */"),
+            });
+            self.size += size;
+        }
+    }
+
+    fn pad(&mut self, len: usize) {
+        if len != 0 {
+            self.lines
+                .push(format!("uint8_t _pad{}[{}];", self.pads, len));
+            self.pads += 1;
+            self.size += len;
+        }
+    }
+
+    fn align(&mut self, align: usize) {
+        if align != 0 {
+            self.pad((align - self.size % align) % align);
+        }
+    }
+}
+
+/// Returns the C element type, array dimensions, and element size of a field,
+/// and whether the element type is a primitive.
+fn field_type(writer: &Writer, field: &Field) -> Result<(String, Vec<usize>, usize, bool), Reason> {
+    let mut dims = Vec::new();
+    let mut ty = field.ty;
+    if let Some(inner) = generic_arg(ty, "Vec") {
+        dims.push(literal(field.count.ok_or(Reason::Count)?).ok_or(Reason::Count)?);
+        ty = inner;
+    } else if let Some(inner) = generic_arg(ty, "Option") {
+        ty = inner;
+    }
+    while let Some(inner) = ty.strip_prefix('[').and_then(|ty| ty.strip_suffix(']')) {
+        let (inner, len) = inner
+            .rsplit_once("; ")
+            .ok_or(Reason::UnknownType(field.ty))?;
+        dims.push(literal(len).ok_or(Reason::Count)?);
+        ty = inner;
+    }
+    let (c_ty, size) = writer.scalar(ty).ok_or(Reason::UnknownType(ty))?;
+    Ok((c_ty, dims, size, primitive(ty).is_some()))
+}
+
+/// Returns the C type and size of a primitive type.
+fn primitive(ty: &str) -> Option<(&'static str, usize)> {
+    Some(match ty {
+        "u8" => ("uint8_t", 1),
+        "i8" => ("int8_t", 1),
+        "u16" => ("uint16_t", 2),
+        "i16" => ("int16_t", 2),
+        "u32" => ("uint32_t", 4),
+        "i32" => ("int32_t", 4),
+        "u64" => ("uint64_t", 8),
+        "i64" => ("int64_t", 8),
+        "f32" => ("float", 4),
+        "f64" => ("double", 8),
+        _ => return None,
+    })
+}
+
+/// Returns the byte order of a value for a comment, if it has one.
+fn endian_name(endian: Endianness, size: usize) -> Option<&'static str> {
+    match endian {
+        Endianness::Fixed(Endian::Big) if size > 1 => Some("big-endian"),
+        Endianness::Fixed(Endian::Little) if size > 1 => Some("little-endian"),
+        _ => None,
+    }
+}
+
+fn generic_arg<'a>(ty: &'a str, name: &str) -> Option<&'a str> {
+    ty.strip_prefix(name)?.strip_prefix('<')?.strip_suffix('>')
+}
+
+/// Parses an integer literal with an optional type suffix.
+fn literal(text: &str) -> Option<usize> {
+    let (value, _) = int_literal(text)?;
+    match value.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// Splits an integer literal into its value and its type suffix, which is
+/// `i32` if there is none.
+fn int_literal(text: &str) -> Option<(String, &'static str)> {
+    if let Some(byte) = text.strip_prefix("b'") {
+        return Some((format!("'{byte}"), "u8"));
+    }
+    if !text.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    // Hex digits never include `u` or `i`, so a suffix is unambiguous
+    let ty = [
+        "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize",
+    ]
+    .into_iter()
+    .find(|ty| text.ends_with(ty));
+    let value = ty.map_or(text, |ty| text.trim_end_matches(ty));
+    Some((value.replace('_', ""), ty.unwrap_or("i32")))
+}
+
+/// Returns the number of bytes in the contents of a byte string literal.
+fn byte_str_len(value: &str) -> usize {
+    let mut chars = value.chars();
+    let mut len = 0;
+    while let Some(c) = chars.next() {
+        // `\xNN` has two more characters than other escapes
+        if c == '\\' && chars.next() == Some('x') {
+            chars.nth(1);
+        }
+        len += 1;
+    }
+    len
+}
@@ -0,0 +1,121 @@
+//! Runtime descriptions of the binary layout of types.
+//!
+//! Derived types with a [`schema` directive](crate::docs::attribute#schema)
+//! implement [`ReadSchema`] and/or [`WriteSchema`], which describe the fields,
+//! types, byte order, magic numbers, counts, and conditions of the type as it
+//! is read or written. This can be used to generate documentation, drive
+//! generic tooling such as hex viewers, or compare a format definition against
+//! a specification. The [`template`] and [`c`] modules use it to export
+//! templates for hex editors and C headers.
+//!
+//! Expressions passed to directives cannot be evaluated without an instance of
+//! the type, so they are stored as source code.
+
+use crate::Endian;
+
+pub mod c;
+pub mod template;
+
+/// Types with a runtime description of their read layout.
+///
+/// This trait is automatically defined on derived types with a
+/// [schema directive](crate::docs::attribute#schema).
+pub trait ReadSchema {
+    /// The layout of the type when it is read.
+    const SCHEMA: Schema;
+}
+
+/// Types with a runtime description of their write layout.
+///
+/// This trait is automatically defined on derived types with a
+/// [schema directive](crate::docs::attribute#schema).
+pub trait WriteSchema {
+    /// The layout of the type when it is written.
+    const SCHEMA: Schema;
+}
+
+/// The layout of a struct or enum.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Schema {
+    /// The name of the type.
+    pub name: &'static str,
+    /// The byte order of the type.
+    pub endian: Endianness,
+    /// The magic number of the type, as source code.
+    pub magic: Option<&'static str>,
+    /// The type given by a `repr` directive, as source code.
+    pub repr: Option<&'static str>,
+    /// The contents of the type.
+    pub layout: Layout,
+}
+
+/// The contents of a type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Layout {
+    /// A struct with the given fields, in order.
+    Struct(&'static [Field]),
+    /// An enum with the given variants, in the order they are tried.
+    Enum(&'static [Variant]),
+    /// A type which is converted from or to another type by a `map`,
+    /// `try_map`, or `repr` directive instead of being read field-by-field.
+    Mapped,
+}
+
+/// A variant of an enum.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Variant {
+    /// The name of the variant.
+    pub name: &'static str,
+    /// The byte order of the variant.
+    pub endian: Endianness,
+    /// The magic number of the variant, as source code.
+    pub magic: Option<&'static str>,
+    /// The fields of the variant, in order. Unit variants have no fields.
+    pub fields: &'static [Field],
+}
+
+/// A field of a struct or enum variant.
+///
+/// Only fields which are actually read or written are included, so fields
+/// which are computed or ignored in one direction do not appear in the
+/// schema for that direction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Field {
+    /// The name of the field. Tuple fields are named `self_0`, `self_1`, etc.
+    pub name: &'static str,
+    /// The type of the field, as source code.
+    pub ty: &'static str,
+    /// The byte order of the field.
+    pub endian: Endianness,
+    /// The magic number which precedes the field, as source code.
+    pub magic: Option<&'static str>,
+    /// The number of items in the field, as source code.
+    pub count: Option<&'static str>,
+    /// The condition under which the field is present, as source code.
+    pub condition: Option<&'static str>,
+    /// The padding before the field, as source code.
+    pub pad_before: Option<&'static str>,
+    /// The padding after the field, as source code.
+    pub pad_after: Option<&'static str>,
+    /// The alignment before the field, as source code.
+    pub align_before: Option<&'static str>,
+    /// The alignment after the field, as source code.
+    pub align_after: Option<&'static str>,
+    /// The minimum size of the field including padding, as source code.
+    pub pad_size_to: Option<&'static str>,
+    /// Whether the field is a temporary variable instead of a member of the
+    /// type.
+    pub temp: bool,
+}
+
+/// The byte order of a type or field.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Endianness {
+    /// The byte order is inherited from the parent type or the caller.
+    Inherited,
+    /// The byte order is fixed.
+    Fixed(Endian),
+    /// The byte order is the given one if the expression, as source code, is
+    /// true, and the opposite one otherwise.
+    Runtime(Endian, &'static str),
+}
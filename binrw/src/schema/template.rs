@@ -0,0 +1,507 @@
+//! Export of schemas as templates for hex editors.
+//!
+//! [`imhex`] generates an [ImHex](https://imhex.werwolv.net/) pattern and
+//! [`binary_template`] generates a [010 Editor](https://www.sweetscape.com/010editor/)
+//! binary template (`.bt`) from the [read schemas](super::ReadSchema) of
+//! derived types, so that format definitions written with binrw can be used
+//! to inspect files without Rust.
+//!
+//! Templates are generated on a best-effort basis:
+//!
+//! * Counts and conditions are copied as source code, so they only work if the
+//!   expression is also valid in the template language (e.g. `len` or
+//!   `len > 1`, but not `len as usize`).
+//! * Types are referenced by name. Every type used by a field, other than
+//!   primitives, arrays, `Vec`, `Option`, and `NullString`, must also be passed
+//!   in, before the types which use it.
+//! * Enum variants are selected by their magic numbers. A variant without a
+//!   magic number is used as a fallback if it is the first such variant, and
+//!   skipped otherwise.
+//! * Types with a `map` or `try_map` directive are not described.
+//!
+//! # Example
+//!
+//! ```
+//! # use binrw::{prelude::*, schema::{template, ReadSchema}};
+//! #[derive(BinRead)]
+//! #[br(big, magic = b"CHNK", schema)]
+//! struct Chunk {
+//!     len: u32,
+//!     #[br(count = len)]
+//!     data: Vec<u8>,
+//! }
+//!
+//! let pattern = template::imhex(&[Chunk::SCHEMA]);
+//! assert!(pattern.contains("be u32 len;"));
+//! assert!(pattern.contains("u8 data[len];"));
+//! ```
+
+use super::{Endianness, Field, Layout, Schema, Variant};
+use crate::Endian;
+use alloc::{format, string::String, vec::Vec};
+
+/// Generates an [ImHex](https://imhex.werwolv.net/) pattern from the given schemas.
+///
+/// Schemas must be given in dependency order. See the
+/// [module documentation](self) for limitations.
+#[must_use]
+pub fn imhex(schemas: &[Schema]) -> String {
+    generate(Format::ImHex, schemas)
+}
+
+/// Generates a 010 Editor binary template from the given schemas.
+///
+/// Schemas must be given in dependency order. See the
+/// [module documentation](self) for limitations.
+#[must_use]
+pub fn binary_template(schemas: &[Schema]) -> String {
+    generate(Format::BinaryTemplate, schemas)
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Format {
+    ImHex,
+    BinaryTemplate,
+}
+
+fn generate(format: Format, schemas: &[Schema]) -> String {
+    let mut writer = Writer {
+        format,
+        out: String::new(),
+        indent: 0,
+    };
+    writer.line("// Generated by binrw");
+    if format == Format::ImHex {
+        writer.line("import std.mem;");
+        writer.line("import std.string;");
+    }
+    for schema in schemas {
+        writer.line("");
+        writer.schema(schema);
+    }
+    writer.out
+}
+
+struct Writer {
+    format: Format,
+    out: String,
+    indent: usize,
+}
+
+impl Writer {
+    fn line(&mut self, text: &str) {
+        if !text.is_empty() {
+            for _ in 0..self.indent {
+                self.out.push_str("    ");
+            }
+        }
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    fn open(&mut self, text: &str) {
+        self.line(text);
+        self.indent += 1;
+    }
+
+    fn close(&mut self, text: &str) {
+        self.indent -= 1;
+        self.line(text);
+    }
+
+    fn schema(&mut self, schema: &Schema) {
+        match schema.layout {
+            Layout::Struct(fields) => {
+                self.open_struct(schema.name);
+                if let Some(magic) = schema.magic {
+                    self.magic(magic, "magic", schema.endian);
+                }
+                for field in fields {
+                    self.field(field, schema.endian);
+                }
+                self.close_struct(schema.name);
+            }
+            Layout::Enum(variants) => {
+                if !self.unit_enum(schema, variants) {
+                    self.open_struct(schema.name);
+                    if let Some(magic) = schema.magic {
+                        self.magic(magic, "magic", schema.endian);
+                    }
+                    self.variants(variants, schema.endian);
+                    self.close_struct(schema.name);
+                }
+            }
+            Layout::Mapped => match schema.repr {
+                Some(repr) => {
+                    let ty = self.ty(repr);
+                    self.line(&match self.format {
+                        Format::ImHex => format!("using {} = {};", schema.name, ty),
+                        Format::BinaryTemplate => format!("typedef {} {};", ty, schema.name),
+                    });
+                }
+                None => self.line(&format!(
+                    "// `{}` is converted by a `map` function and cannot be described",
+                    schema.name
+                )),
+            },
+        }
+    }
+
+    fn open_struct(&mut self, name: &str) {
+        self.open(&match self.format {
+            Format::ImHex => format!("struct {name} {{"),
+            Format::BinaryTemplate => String::from("typedef struct {"),
+        });
+    }
+
+    fn close_struct(&mut self, name: &str) {
+        self.close(&match self.format {
+            Format::ImHex => String::from("};"),
+            Format::BinaryTemplate => format!("}} {name};"),
+        });
+    }
+
+    /// Writes an enum whose variants have no fields as a native enum, if the
+    /// type of its values is known.
+    fn unit_enum(&mut self, schema: &Schema, variants: &[Variant]) -> bool {
+        if variants.iter().any(|variant| !variant.fields.is_empty()) {
+            return false;
+        }
+
+        let values = variants
+            .iter()
+            .map(|variant| variant.magic.map(MagicLit::parse))
+            .collect::<Vec<_>>();
+        let repr = match schema.repr {
+            Some(repr) if values.iter().all(Option::is_none) => repr,
+            None => match values.first() {
+                Some(Some(MagicLit::Int(_, ty)))
+                    if values.iter().all(
+                        |value| matches!(value, Some(MagicLit::Int(_, other)) if other == ty),
+                    ) =>
+                {
+                    ty
+                }
+                _ => return false,
+            },
+            Some(_) => return false,
+        };
+
+        let ty = self.ty(repr);
+        self.open(&match self.format {
+            Format::ImHex => format!("enum {} : {} {{", schema.name, ty),
+            Format::BinaryTemplate => format!("typedef enum <{ty}> {{"),
+        });
+        for (variant, value) in variants.iter().zip(values) {
+            self.line(&match value {
+                Some(MagicLit::Int(value, _)) => format!("{} = {},", variant.name, value),
+                _ => format!("{},", variant.name),
+            });
+        }
+        self.close(&match self.format {
+            Format::ImHex => String::from("};"),
+            Format::BinaryTemplate => format!("}} {};", schema.name),
+        });
+        true
+    }
+
+    fn variants(&mut self, variants: &[Variant], endian: Endianness) {
+        let mut has_fallback = false;
+        let mut first = true;
+        for variant in variants {
+            let endian = inherit(variant.endian, endian);
+            let test = variant
+                .magic
+                .map(|magic| self.peek_magic(MagicLit::parse(magic), endian));
+            let open = match (test, first) {
+                _ if has_fallback => None,
+                (Some(Some(test)), true) => Some(format!("if ({test}) {{")),
+                (Some(Some(test)), false) => Some(format!("}} else if ({test}) {{")),
+                (None, true) => Some(String::from("{")),
+                (None, false) => Some(String::from("} else {")),
+                (Some(None), _) => None,
+            };
+
+            let Some(open) = open else {
+                self.line(&format!("// `{}` cannot be selected", variant.name));
+                continue;
+            };
+            if first {
+                self.open(&open);
+            } else {
+                self.close_open(&open);
+            }
+            first = false;
+            has_fallback = variant.magic.is_none();
+
+            self.line(&format!("// {}", variant.name));
+            if let Some(magic) = variant.magic {
+                self.magic(magic, "magic", endian);
+            }
+            for field in variant.fields {
+                self.field(field, endian);
+            }
+        }
+        if !first {
+            self.close("}");
+        }
+    }
+
+    fn close_open(&mut self, text: &str) {
+        self.indent -= 1;
+        self.open(text);
+    }
+
+    /// Returns an expression which compares the data at the current position
+    /// with a magic number, without consuming it.
+    fn peek_magic(&self, magic: MagicLit<'_>, endian: Endianness) -> Option<String> {
+        Some(match (self.format, magic) {
+            (Format::ImHex, MagicLit::Bytes(value, len)) => {
+                format!("std::mem::read_string($, {len}) == {value}")
+            }
+            (Format::BinaryTemplate, MagicLit::Bytes(value, len)) => {
+                format!("ReadString(FTell(), {len}) == {value}")
+            }
+            (Format::ImHex, MagicLit::Int(value, ty)) => {
+                let (signed, size) = int_info(ty)?;
+                let read = if signed {
+                    "read_signed"
+                } else {
+                    "read_unsigned"
+                };
+                match endian {
+                    Endianness::Fixed(Endian::Big) => {
+                        format!("std::mem::{read}($, {size}, std::mem::Endian::Big) == {value}")
+                    }
+                    Endianness::Fixed(Endian::Little) => {
+                        format!("std::mem::{read}($, {size}, std::mem::Endian::Little) == {value}")
+                    }
+                    Endianness::Inherited | Endianness::Runtime(..) => {
+                        format!("std::mem::{read}($, {size}) == {value}")
+                    }
+                }
+            }
+            (Format::BinaryTemplate, MagicLit::Int(value, ty)) => {
+                let read = match ty {
+                    "u8" => "ReadUByte",
+                    "u16" => "ReadUShort",
+                    "u32" => "ReadUInt",
+                    "u64" => "ReadUInt64",
+                    "i8" => "ReadByte",
+                    "i16" => "ReadShort",
+                    "i32" => "ReadInt",
+                    "i64" => "ReadInt64",
+                    _ => return None,
+                };
+                format!("{read}(FTell()) == {value}")
+            }
+        })
+    }
+
+    fn magic(&mut self, magic: &str, name: &str, endian: Endianness) {
+        match MagicLit::parse(magic) {
+            MagicLit::Bytes(value, len) => {
+                self.line(&format!("char {name}[{len}]; // {value}"));
+            }
+            MagicLit::Int(value, ty) => {
+                let ty = self.ty(ty);
+                self.declare(endian, &format!("{ty} {name}; // {value}"));
+            }
+        }
+    }
+
+    fn field(&mut self, field: &Field, endian: Endianness) {
+        let endian = inherit(field.endian, endian);
+        if let Some(condition) = field.condition {
+            self.open(&format!("if ({condition}) {{"));
+        }
+        if let Some(magic) = field.magic {
+            self.magic(magic, &format!("{}_magic", field.name), endian);
+        }
+
+        let (ty, len) = self.field_ty(field);
+        match len {
+            Length::One => self.declare(endian, &format!("{} {};", ty, field.name)),
+            Length::Count(count) => {
+                self.declare(endian, &format!("{} {}[{}];", ty, field.name, count));
+            }
+            Length::Eof => match self.format {
+                Format::ImHex => self.declare(
+                    endian,
+                    &format!("{} {}[while(!std::mem::eof())];", ty, field.name),
+                ),
+                Format::BinaryTemplate => {
+                    self.open("while (!FEof()) {");
+                    self.declare(endian, &format!("{} {};", ty, field.name));
+                    self.close("}");
+                }
+            },
+        }
+
+        if field.condition.is_some() {
+            self.close("}");
+        }
+    }
+
+    /// Writes a declaration with the given byte order.
+    fn declare(&mut self, endian: Endianness, decl: &str) {
+        match (self.format, endian) {
+            (_, Endianness::Inherited) => self.line(decl),
+            (Format::ImHex, Endianness::Fixed(endian)) => {
+                self.line(&format!("{} {}", imhex_endian(endian), decl));
+            }
+            (Format::BinaryTemplate, Endianness::Fixed(endian)) => {
+                self.line(bt_endian(endian));
+                self.line(decl);
+            }
+            (_, Endianness::Runtime(endian, condition)) => {
+                self.open(&format!("if ({condition}) {{"));
+                self.declare(Endianness::Fixed(endian), decl);
+                self.close_open("} else {");
+                self.declare(Endianness::Fixed(flipped(endian)), decl);
+                self.close("}");
+            }
+        }
+    }
+
+    fn field_ty<'a>(&self, field: &'a Field) -> (String, Length<'a>) {
+        let ty = field.ty;
+        if let Some(inner) = ty.strip_prefix('[').and_then(|ty| ty.strip_suffix(']')) {
+            if let Some((inner, len)) = inner.rsplit_once("; ") {
+                return (self.ty(inner), Length::Count(len));
+            }
+        }
+        if let Some(inner) = generic_arg(ty, "Vec") {
+            return (
+                self.ty(inner),
+                field.count.map_or(Length::Eof, Length::Count),
+            );
+        }
+        if let Some(inner) = generic_arg(ty, "Option") {
+            return (self.ty(inner), Length::One);
+        }
+        (self.ty(ty), Length::One)
+    }
+
+    /// Converts a Rust type to the equivalent type in the template language.
+    fn ty(&self, ty: &str) -> String {
+        let name = ty.split('<').next().unwrap_or(ty);
+        let name = name.rsplit("::").next().unwrap_or(name);
+        let (imhex, bt) = match name {
+            "u8" => ("u8", "uchar"),
+            "u16" => ("u16", "ushort"),
+            "u32" => ("u32", "uint"),
+            "u64" => ("u64", "uint64"),
+            "i8" => ("s8", "char"),
+            "i16" => ("s16", "short"),
+            "i32" => ("s32", "int"),
+            "i64" => ("s64", "int64"),
+            "f32" => ("float", "float"),
+            "f64" => ("double", "double"),
+            "bool" => ("bool", "uchar"),
+            "NullString" => ("std::string::NullString", "string"),
+            _ => (name, name),
+        };
+        String::from(match self.format {
+            Format::ImHex => imhex,
+            Format::BinaryTemplate => bt,
+        })
+    }
+}
+
+enum Length<'a> {
+    One,
+    Count(&'a str),
+    Eof,
+}
+
+/// A magic number, parsed from its source code.
+enum MagicLit<'a> {
+    /// A byte string literal without the `b` prefix, and its length.
+    Bytes(&'a str, usize),
+    /// An integer literal without its type suffix, and the type.
+    Int(String, &'a str),
+}
+
+impl<'a> MagicLit<'a> {
+    fn parse(magic: &'a str) -> Self {
+        // Only the first alternative is used
+        let magic = magic.split(" | ").next().unwrap_or(magic);
+        if let Some(value) = magic.strip_prefix('b') {
+            if value.starts_with('"') {
+                return Self::Bytes(value, byte_str_len(value));
+            }
+            return Self::Int(String::from(value), "u8");
+        }
+
+        for ty in [
+            "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "f32", "f64",
+        ] {
+            if let Some(value) = magic.strip_suffix(ty) {
+                return Self::Int(value.replace('_', ""), ty);
+            }
+        }
+        Self::Int(magic.replace('_', ""), "i32")
+    }
+}
+
+/// Returns the number of bytes in a quoted byte string literal.
+fn byte_str_len(value: &str) -> usize {
+    let mut chars = value.trim_matches('"').chars();
+    let mut len = 0;
+    while let Some(c) = chars.next() {
+        // `\xNN` has two more characters than other escapes
+        if c == '\\' && chars.next() == Some('x') {
+            chars.nth(1);
+        }
+        len += 1;
+    }
+    len
+}
+
+/// Returns the signedness and size in bytes of an integer type.
+fn int_info(ty: &str) -> Option<(bool, usize)> {
+    Some(match ty {
+        "u8" => (false, 1),
+        "u16" => (false, 2),
+        "u32" => (false, 4),
+        "u64" => (false, 8),
+        "i8" => (true, 1),
+        "i16" => (true, 2),
+        "i32" => (true, 4),
+        "i64" => (true, 8),
+        _ => return None,
+    })
+}
+
+fn generic_arg<'a>(ty: &'a str, name: &str) -> Option<&'a str> {
+    ty.strip_prefix(name)?.strip_prefix('<')?.strip_suffix('>')
+}
+
+fn inherit(endian: Endianness, parent: Endianness) -> Endianness {
+    match endian {
+        Endianness::Inherited => parent,
+        Endianness::Fixed(_) | Endianness::Runtime(..) => endian,
+    }
+}
+
+fn flipped(endian: Endian) -> Endian {
+    match endian {
+        Endian::Big => Endian::Little,
+        Endian::Little => Endian::Big,
+    }
+}
+
+fn imhex_endian(endian: Endian) -> &'static str {
+    match endian {
+        Endian::Big => "be",
+        Endian::Little => "le",
+    }
+}
+
+fn bt_endian(endian: Endian) -> &'static str {
+    match endian {
+        Endian::Big => "BigEndian();",
+        Endian::Little => "LittleEndian();",
+    }
+}
@@ -0,0 +1,178 @@
+//! Type definitions for reading sequences of messages from stateful protocols.
+//!
+//! Many protocols decode each message using state which was negotiated by
+//! earlier messages, like a protocol version or whether compression is
+//! enabled. A [`Session`] holds this state as a typed context which is passed
+//! to every message it reads as an [argument](crate::docs::attribute#arguments).
+//! After a message is read, it can update the context for the messages which
+//! follow it by implementing [`UpdateContext`].
+//!
+//! # Examples
+//!
+//! ```
+//! use binrw::{binread, io::Cursor, session::{Session, UpdateContext}, Endian};
+//!
+//! #[derive(Default)]
+//! struct State {
+//!     version: u8,
+//! }
+//!
+//! #[binread]
+//! #[br(import(state: &'a State))]
+//! enum Message {
+//!     #[br(magic = 0u8)]
+//!     Hello { version: u8 },
+//!     #[br(magic = 1u8)]
+//!     Data {
+//!         // Version 2 added a sequence number
+//!         #[br(if(state.version >= 2))]
+//!         seq: Option<u8>,
+//!         value: u8,
+//!     },
+//! }
+//!
+//! impl UpdateContext<State> for Message {
+//!     fn update_context(&self, state: &mut State) {
+//!         if let Message::Hello { version } = self {
+//!             state.version = *version;
+//!         }
+//!     }
+//! }
+//!
+//! let data = b"\x01\x05\x00\x02\x01\x07\x06";
+//! let mut session = Session::new(Cursor::new(data), Endian::Little, State::default());
+//! let messages = session.messages::<Message>().collect::<Result<Vec<_>, _>>().unwrap();
+//!
+//! assert!(matches!(messages[0], Message::Data { seq: None, value: 5 }));
+//! assert!(matches!(messages[2], Message::Data { seq: Some(7), value: 6 }));
+//! assert_eq!(session.context().version, 2);
+//! ```
+
+use crate::{
+    io::{Read, Seek},
+    BinRead, BinResult, Endian,
+};
+
+/// A message which updates the context of a [`Session`] after it is read.
+///
+/// The default implementation leaves the context unchanged, so messages which
+/// do not affect the state of the protocol can use an empty `impl` block.
+pub trait UpdateContext<C> {
+    /// Updates the context which is passed to the messages after this one.
+    fn update_context(&self, context: &mut C) {
+        let _ = context;
+    }
+}
+
+/// A reader which passes an evolving context to each message it reads.
+///
+/// Messages are read with [`BinRead::read_options`] and receive a reference to
+/// the context as their only argument, so a message type should use
+/// `#[br(import(context: &'a C))]`. After each message is read successfully,
+/// [`UpdateContext::update_context`] is called so that the message can change
+/// the context for the messages which follow it.
+#[derive(Debug)]
+pub struct Session<R, C> {
+    reader: R,
+    endian: Endian,
+    context: C,
+}
+
+impl<R: Read + Seek, C> Session<R, C> {
+    /// Creates a new session which reads messages from `reader` with the
+    /// given byte order, starting with the given context.
+    pub fn new(reader: R, endian: Endian, context: C) -> Self {
+        Self {
+            reader,
+            endian,
+            context,
+        }
+    }
+
+    /// Reads the next message and updates the context with it.
+    ///
+    /// If reading fails, the context is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    pub fn read<T>(&mut self) -> BinResult<T>
+    where
+        T: for<'a> BinRead<Args<'a> = (&'a C,)> + UpdateContext<C>,
+    {
+        let message = T::read_options(&mut self.reader, self.endian, (&self.context,))?;
+        message.update_context(&mut self.context);
+        Ok(message)
+    }
+
+    /// Returns an iterator which reads messages until the end of the stream.
+    ///
+    /// The iterator stops after the first error, so a message which is cut
+    /// off by the end of the stream is returned as an error.
+    pub fn messages<T>(&mut self) -> Messages<'_, R, C, T>
+    where
+        T: for<'a> BinRead<Args<'a> = (&'a C,)> + UpdateContext<C>,
+    {
+        Messages {
+            session: self,
+            done: false,
+            _message: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns a reference to the current context.
+    pub fn context(&self) -> &C {
+        &self.context
+    }
+
+    /// Returns a mutable reference to the current context.
+    pub fn context_mut(&mut self) -> &mut C {
+        &mut self.context
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn reader_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Consumes this session, returning the underlying reader and the final
+    /// context.
+    pub fn into_inner(self) -> (R, C) {
+        (self.reader, self.context)
+    }
+}
+
+/// An iterator over the messages of a [`Session`].
+///
+/// This is created by [`Session::messages`].
+#[derive(Debug)]
+pub struct Messages<'s, R, C, T> {
+    session: &'s mut Session<R, C>,
+    done: bool,
+    _message: core::marker::PhantomData<fn() -> T>,
+}
+
+impl<R, C, T> Iterator for Messages<'_, R, C, T>
+where
+    R: Read + Seek,
+    T: for<'a> BinRead<Args<'a> = (&'a C,)> + UpdateContext<C>,
+{
+    type Item = BinResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = match crate::__private::is_eof(&mut self.session.reader) {
+            Ok(true) => {
+                self.done = true;
+                return None;
+            }
+            Ok(false) => self.session.read(),
+            Err(err) => Err(err),
+        };
+        self.done = result.is_err();
+        Some(result)
+    }
+}
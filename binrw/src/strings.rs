@@ -2,11 +2,14 @@
 
 use crate::{
     alloc::string::{FromUtf16Error, FromUtf8Error},
-    io::{Read, Seek, Write},
-    BinRead, BinResult, BinWrite, Endian,
+    io::{Read, Seek, SeekFrom, Write},
+    BinRead, BinResult, BinWrite, Endian, Error,
 };
 use alloc::{string::String, vec, vec::Vec};
-use core::fmt::{self, Write as _};
+use core::{
+    fmt::{self, Write as _},
+    marker::PhantomData,
+};
 
 /// A null-terminated 8-bit string.
 ///
@@ -67,6 +70,11 @@ impl BinWrite for NullString {
 
         Ok(())
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.len().saturating_add(1);
+        (len, Some(len))
+    }
 }
 
 impl From<&str> for NullString {
@@ -189,6 +197,11 @@ impl BinWrite for NullWideString {
 
         Ok(())
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.len().saturating_add(1).saturating_mul(2);
+        (len, Some(len))
+    }
 }
 
 impl From<NullWideString> for Vec<u16> {
@@ -245,6 +258,271 @@ impl fmt::Debug for NullWideString {
     }
 }
 
+/// A length-prefixed 16-bit string.
+///
+/// The string is preceded by an integer of type `L` containing the number of
+/// 16-bit code units in the string. There is no null terminator.
+///
+/// ```
+/// use binrw::{BinReaderExt, BinWriterExt, PrefixedWideString, io::Cursor};
+///
+/// let mut data = Cursor::new(b"\x05\0h\0e\0l\0l\0o\0");
+/// let s = data.read_le::<PrefixedWideString<u16>>().unwrap();
+/// assert_eq!(s.to_string(), "hello");
+///
+/// let mut out = Cursor::new(Vec::new());
+/// out.write_le(&s).unwrap();
+/// assert_eq!(out.into_inner(), data.into_inner());
+/// ```
+#[derive(Clone, Eq, PartialEq, Default)]
+pub struct PrefixedWideString<L>(
+    /// The raw wide byte string.
+    pub Vec<u16>,
+    PhantomData<L>,
+);
+
+impl<L> BinRead for PrefixedWideString<L>
+where
+    L: for<'a> BinRead<Args<'a> = ()> + TryInto<usize>,
+{
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = reader.stream_position()?;
+        let len = L::read_options(reader, endian, ())?
+            .try_into()
+            .map_err(|_| Error::AssertFail {
+                pos,
+                message: "string length does not fit in `usize`".into(),
+            })?;
+
+        let mut values = Vec::new();
+        for _ in 0..len {
+            values.push(<u16>::read_options(reader, endian, ())?);
+        }
+        Ok(Self::from(values))
+    }
+}
+
+impl<L> BinWrite for PrefixedWideString<L>
+where
+    L: for<'a> BinWrite<Args<'a> = ()> + TryFrom<usize>,
+{
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let len = L::try_from(self.0.len()).map_err(|_| Error::AssertFail {
+            pos: writer.stream_position().unwrap_or_default(),
+            message: alloc::format!(
+                "string length {} does not fit in `{}`",
+                self.0.len(),
+                core::any::type_name::<L>()
+            )
+            .into(),
+        })?;
+        len.write_options(writer, endian, args)?;
+        self.0.write_options(writer, endian, args)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let data = self.0.len().saturating_mul(2);
+        L::try_from(self.0.len()).map_or((0, None), |len| {
+            let (lower, upper) = len.size_hint();
+            (
+                lower.saturating_add(data),
+                upper.and_then(|upper| upper.checked_add(data)),
+            )
+        })
+    }
+}
+
+impl<L> From<Vec<u16>> for PrefixedWideString<L> {
+    fn from(s: Vec<u16>) -> Self {
+        Self(s, PhantomData)
+    }
+}
+
+impl<L> From<PrefixedWideString<L>> for Vec<u16> {
+    fn from(s: PrefixedWideString<L>) -> Self {
+        s.0
+    }
+}
+
+impl<L> From<&str> for PrefixedWideString<L> {
+    fn from(s: &str) -> Self {
+        Self::from(s.encode_utf16().collect::<Vec<_>>())
+    }
+}
+
+impl<L> From<String> for PrefixedWideString<L> {
+    fn from(s: String) -> Self {
+        Self::from(s.as_str())
+    }
+}
+
+impl<L> TryFrom<PrefixedWideString<L>> for String {
+    type Error = FromUtf16Error;
+
+    fn try_from(value: PrefixedWideString<L>) -> Result<Self, Self::Error> {
+        String::from_utf16(&value.0)
+    }
+}
+
+impl<L> core::ops::Deref for PrefixedWideString<L> {
+    type Target = Vec<u16>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<L> core::ops::DerefMut for PrefixedWideString<L> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<L> fmt::Display for PrefixedWideString<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        display_utf16(&self.0, f, core::iter::once)
+    }
+}
+
+impl<L> fmt::Debug for PrefixedWideString<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PrefixedWideString(\"")?;
+        display_utf16(&self.0, f, char::escape_debug)?;
+        write!(f, "\")")
+    }
+}
+
+/// A value preceded by an optional UTF-16 byte order mark.
+///
+/// When reading, if the data starts with a byte order mark (U+FEFF), the
+/// inner value is read using the byte order of the mark. Otherwise, nothing
+/// is consumed and the inner value is read using the current byte order.
+///
+/// When writing, the byte order mark is written only if [`bom`](Self::bom)
+/// is set, and the inner value is written using that byte order.
+///
+/// ```
+/// use binrw::{BinReaderExt, BinWriterExt, Endian, NullWideString, WithBom, io::Cursor};
+///
+/// let mut data = Cursor::new(b"\xfe\xff\0h\0i\0\0");
+/// // The byte order mark overrides the byte order used to read the string
+/// let s = data.read_le::<WithBom<NullWideString>>().unwrap();
+/// assert_eq!(s.bom, Some(Endian::Big));
+/// assert_eq!(s.to_string(), "hi");
+///
+/// let mut out = Cursor::new(Vec::new());
+/// out.write_le(&s).unwrap();
+/// assert_eq!(out.into_inner(), data.into_inner());
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WithBom<T> {
+    /// The byte order given by the byte order mark, or `None` if there was no
+    /// byte order mark.
+    pub bom: Option<Endian>,
+
+    /// The inner value.
+    pub value: T,
+}
+
+impl<T> WithBom<T> {
+    /// Consumes this wrapper, returning the inner value.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: BinRead> BinRead for WithBom<T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let mut mark = [0; 2];
+        reader.read_exact(&mut mark)?;
+        let bom = match mark {
+            [0xfe, 0xff] => Some(Endian::Big),
+            [0xff, 0xfe] => Some(Endian::Little),
+            _ => {
+                reader.seek(SeekFrom::Current(-2))?;
+                None
+            }
+        };
+
+        Ok(Self {
+            bom,
+            value: T::read_options(reader, bom.unwrap_or(endian), args)?,
+        })
+    }
+}
+
+impl<T: BinWrite> BinWrite for WithBom<T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        if let Some(bom) = self.bom {
+            0xfeff_u16.write_options(writer, bom, ())?;
+        }
+        self.value
+            .write_options(writer, self.bom.unwrap_or(endian), args)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let bom = if self.bom.is_some() { 2 } else { 0 };
+        let (lower, upper) = self.value.size_hint();
+        (
+            lower.saturating_add(bom),
+            upper.and_then(|upper| upper.checked_add(bom)),
+        )
+    }
+}
+
+impl<T> From<T> for WithBom<T> {
+    fn from(value: T) -> Self {
+        Self { bom: None, value }
+    }
+}
+
+impl<T> core::ops::Deref for WithBom<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> core::ops::DerefMut for WithBom<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for WithBom<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
 fn display_utf16<Transformer: Fn(char) -> O, O: Iterator<Item = char>>(
     input: &[u16],
     f: &mut fmt::Formatter<'_>,
@@ -0,0 +1,586 @@
+//! Type definitions for timestamps in common binary formats.
+//!
+//! Each type stores the raw encoded value, so reading and then writing a
+//! timestamp always produces the original bytes. The values can be converted
+//! to and from the number of seconds or nanoseconds since the UNIX epoch and,
+//! with the `std` feature, to and from `SystemTime`.
+//!
+//! With the `chrono` feature, the values can also be converted to and from
+//! [`chrono::DateTime`](https://docs.rs/chrono/latest/chrono/struct.DateTime.html)
+//! and, with the `time` feature, to and from
+//! [`time::OffsetDateTime`](https://docs.rs/time/latest/time/struct.OffsetDateTime.html).
+//!
+//! # Examples
+//!
+//! ```
+//! use binrw::{io::Cursor, timestamp::{DosDateTime, FileTime, UnixTimestamp}, BinRead};
+//!
+//! #[derive(BinRead)]
+//! struct Times {
+//!     created: UnixTimestamp<u32>,
+//!     modified: FileTime,
+//!     archived: DosDateTime,
+//! }
+//!
+//! let data = b"\x80\x9e\x8e\x5e\x00\xc0\x6c\xc9\x23\x0e\xd6\x01\x00\x00\x61\x50";
+//! let times = Times::read_le(&mut Cursor::new(data)).unwrap();
+//! assert_eq!(times.created.seconds(), 1_586_404_992);
+//! assert_eq!(times.modified.unix_nanos(), 1_586_404_992_000_000_000);
+//! assert_eq!(
+//!     (times.archived.year(), times.archived.month(), times.archived.day()),
+//!     (2020, 3, 1)
+//! );
+//! ```
+
+use crate::{
+    io::{Read, Seek, Write},
+    BinRead, BinResult, BinWrite, Endian,
+};
+#[cfg(feature = "std")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(any(feature = "chrono", feature = "std", feature = "time"))]
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// A number of seconds since the UNIX epoch, 1970-01-01 00:00:00 UTC.
+///
+/// The inner type is the integer type used to store the value, which is
+/// usually [`u32`], [`i32`], or [`i64`].
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{io::Cursor, timestamp::UnixTimestamp, BinReaderExt};
+///
+/// let time: UnixTimestamp<i64> = Cursor::new(b"\0\0\0\0\x5e\x8e\x9e\x80").read_be().unwrap();
+/// assert_eq!(time.seconds(), 1_586_404_992);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct UnixTimestamp<T>(
+    /// The number of seconds since the UNIX epoch.
+    pub T,
+);
+
+impl<T: Copy + Into<i128>> UnixTimestamp<T> {
+    /// Returns the number of seconds since the UNIX epoch.
+    #[must_use]
+    pub fn seconds(&self) -> i128 {
+        self.0.into()
+    }
+
+    /// Converts this timestamp to a [`SystemTime`].
+    ///
+    /// Returns `None` if the timestamp cannot be represented by the platform.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        system_time_from_unix_nanos(self.seconds().checked_mul(NANOS_PER_SECOND)?)
+    }
+
+    /// Converts this timestamp to a [`chrono::DateTime`].
+    ///
+    /// Returns `None` if the timestamp is out of range of `chrono`.
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn to_chrono(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono_from_unix_nanos(self.seconds().checked_mul(NANOS_PER_SECOND)?)
+    }
+
+    /// Converts this timestamp to a [`time::OffsetDateTime`] in UTC.
+    ///
+    /// Returns `None` if the timestamp is out of range of `time`.
+    #[cfg(feature = "time")]
+    #[must_use]
+    pub fn to_offset_date_time(&self) -> Option<time::OffsetDateTime> {
+        time::OffsetDateTime::from_unix_timestamp_nanos(
+            self.seconds().checked_mul(NANOS_PER_SECOND)?,
+        )
+        .ok()
+    }
+}
+
+impl<T: TryFrom<i128>> UnixTimestamp<T> {
+    /// Creates a timestamp from a number of seconds since the UNIX epoch.
+    ///
+    /// Returns `None` if the value is out of range of the inner type.
+    #[must_use]
+    pub fn from_seconds(seconds: i128) -> Option<Self> {
+        T::try_from(seconds).ok().map(Self)
+    }
+
+    /// Creates a timestamp from a [`SystemTime`], rounding down to the
+    /// nearest second.
+    ///
+    /// Returns `None` if the time is out of range of the inner type.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn from_system_time(time: SystemTime) -> Option<Self> {
+        Self::from_seconds(unix_nanos_from_system_time(time)?.div_euclid(NANOS_PER_SECOND))
+    }
+
+    /// Creates a timestamp from a [`chrono::DateTime`], rounding down to the
+    /// nearest second.
+    ///
+    /// Returns `None` if the time is out of range of the inner type.
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn from_chrono<Tz: chrono::TimeZone>(time: &chrono::DateTime<Tz>) -> Option<Self> {
+        Self::from_seconds(unix_nanos_from_chrono(time).div_euclid(NANOS_PER_SECOND))
+    }
+
+    /// Creates a timestamp from a [`time::OffsetDateTime`], rounding down to
+    /// the nearest second.
+    ///
+    /// Returns `None` if the time is out of range of the inner type.
+    #[cfg(feature = "time")]
+    #[must_use]
+    pub fn from_offset_date_time(time: time::OffsetDateTime) -> Option<Self> {
+        Self::from_seconds(time.unix_timestamp_nanos().div_euclid(NANOS_PER_SECOND))
+    }
+}
+
+impl<T> BinRead for UnixTimestamp<T>
+where
+    T: for<'a> BinRead<Args<'a> = ()>,
+{
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        T::read_options(reader, endian, ()).map(Self)
+    }
+}
+
+impl<T> BinWrite for UnixTimestamp<T>
+where
+    T: for<'a> BinWrite<Args<'a> = ()>,
+{
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.0.write_options(writer, endian, ())
+    }
+}
+
+/// A Windows `FILETIME`, which is a 64-bit number of 100-nanosecond
+/// intervals since 1601-01-01 00:00:00 UTC.
+///
+/// This is also the timestamp format used by NTFS and the extra fields of ZIP
+/// archives created on Windows.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct FileTime(
+    /// The number of 100-nanosecond intervals since 1601-01-01.
+    pub u64,
+);
+
+impl FileTime {
+    /// The number of 100-nanosecond intervals between 1601-01-01 and the UNIX
+    /// epoch.
+    const UNIX_EPOCH: i128 = 116_444_736_000_000_000;
+
+    /// Returns the number of nanoseconds since the UNIX epoch.
+    #[must_use]
+    pub fn unix_nanos(&self) -> i128 {
+        (i128::from(self.0) - Self::UNIX_EPOCH) * 100
+    }
+
+    /// Creates a timestamp from a number of nanoseconds since the UNIX epoch,
+    /// rounding down to the nearest 100 nanoseconds.
+    ///
+    /// Returns `None` if the value is before 1601 or too large to represent.
+    #[must_use]
+    pub fn from_unix_nanos(nanos: i128) -> Option<Self> {
+        u64::try_from(nanos.div_euclid(100) + Self::UNIX_EPOCH)
+            .ok()
+            .map(Self)
+    }
+
+    /// Converts this timestamp to a [`SystemTime`].
+    ///
+    /// Returns `None` if the timestamp cannot be represented by the platform.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        system_time_from_unix_nanos(self.unix_nanos())
+    }
+
+    /// Creates a timestamp from a [`SystemTime`], rounding down to the
+    /// nearest 100 nanoseconds.
+    ///
+    /// Returns `None` if the time is before 1601 or too large to represent.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn from_system_time(time: SystemTime) -> Option<Self> {
+        Self::from_unix_nanos(unix_nanos_from_system_time(time)?)
+    }
+
+    /// Converts this timestamp to a [`chrono::DateTime`].
+    ///
+    /// Returns `None` if the timestamp is out of range of `chrono`.
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn to_chrono(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono_from_unix_nanos(self.unix_nanos())
+    }
+
+    /// Creates a timestamp from a [`chrono::DateTime`], rounding down to the
+    /// nearest 100 nanoseconds.
+    ///
+    /// Returns `None` if the time is before 1601.
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn from_chrono<Tz: chrono::TimeZone>(time: &chrono::DateTime<Tz>) -> Option<Self> {
+        Self::from_unix_nanos(unix_nanos_from_chrono(time))
+    }
+
+    /// Converts this timestamp to a [`time::OffsetDateTime`] in UTC.
+    ///
+    /// Returns `None` if the timestamp is out of range of `time`.
+    #[cfg(feature = "time")]
+    #[must_use]
+    pub fn to_offset_date_time(&self) -> Option<time::OffsetDateTime> {
+        time::OffsetDateTime::from_unix_timestamp_nanos(self.unix_nanos()).ok()
+    }
+
+    /// Creates a timestamp from a [`time::OffsetDateTime`], rounding down to
+    /// the nearest 100 nanoseconds.
+    ///
+    /// Returns `None` if the time is before 1601.
+    #[cfg(feature = "time")]
+    #[must_use]
+    pub fn from_offset_date_time(time: time::OffsetDateTime) -> Option<Self> {
+        Self::from_unix_nanos(time.unix_timestamp_nanos())
+    }
+}
+
+impl BinRead for FileTime {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        u64::read_options(reader, endian, ()).map(Self)
+    }
+}
+
+impl BinWrite for FileTime {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.0.write_options(writer, endian, ())
+    }
+}
+
+/// An MS-DOS date and time, as used by FAT file systems and ZIP archives.
+///
+/// The value is stored as a 16-bit time followed by a 16-bit date. It has no
+/// time zone, a resolution of two seconds, and a range of 1980 to 2107.
+/// Conversions to and from the UNIX epoch treat the value as UTC.
+///
+/// The raw fields are not validated when reading, so the accessors may
+/// return out of range values for corrupt data. [`DosDateTime::unix_seconds`]
+/// returns `None` for these values.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{io::Cursor, timestamp::DosDateTime, BinReaderExt};
+///
+/// let time: DosDateTime = Cursor::new(b"\x7b\x6d\x6c\x50").read_le().unwrap();
+/// assert_eq!(time, DosDateTime::new(2020, 3, 12, 13, 43, 54).unwrap());
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DosDateTime {
+    /// The encoded time.
+    pub time: u16,
+    /// The encoded date.
+    pub date: u16,
+}
+
+impl DosDateTime {
+    /// Creates a date and time from its components.
+    ///
+    /// Odd seconds are rounded down. Returns `None` if any component is out
+    /// of range.
+    #[must_use]
+    pub fn new(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> Option<Self> {
+        if !(1980..=2107).contains(&year)
+            || !(1..=12).contains(&month)
+            || day < 1
+            || day > days_in_month(year.into(), month)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return None;
+        }
+
+        Some(Self {
+            time: u16::from(hour) << 11 | u16::from(minute) << 5 | u16::from(second / 2),
+            date: (year - 1980) << 9 | u16::from(month) << 5 | u16::from(day),
+        })
+    }
+
+    /// Returns the year.
+    #[must_use]
+    pub fn year(&self) -> u16 {
+        (self.date >> 9) + 1980
+    }
+
+    /// Returns the month, starting from 1.
+    #[must_use]
+    pub fn month(&self) -> u8 {
+        ((self.date >> 5) & 0xf) as u8
+    }
+
+    /// Returns the day of the month, starting from 1.
+    #[must_use]
+    pub fn day(&self) -> u8 {
+        (self.date & 0x1f) as u8
+    }
+
+    /// Returns the hour.
+    #[must_use]
+    pub fn hour(&self) -> u8 {
+        (self.time >> 11) as u8
+    }
+
+    /// Returns the minute.
+    #[must_use]
+    pub fn minute(&self) -> u8 {
+        ((self.time >> 5) & 0x3f) as u8
+    }
+
+    /// Returns the second, which is always even.
+    #[must_use]
+    pub fn second(&self) -> u8 {
+        (self.time & 0x1f) as u8 * 2
+    }
+
+    /// Returns the number of seconds since the UNIX epoch.
+    ///
+    /// Returns `None` if the encoded date or time is invalid.
+    #[must_use]
+    pub fn unix_seconds(&self) -> Option<i64> {
+        let valid = Self::new(
+            self.year(),
+            self.month(),
+            self.day(),
+            self.hour(),
+            self.minute(),
+            self.second(),
+        );
+        (valid == Some(*self)).then(|| {
+            days_from_civil(self.year().into(), self.month(), self.day()) * 86_400
+                + i64::from(self.hour()) * 3_600
+                + i64::from(self.minute()) * 60
+                + i64::from(self.second())
+        })
+    }
+
+    /// Creates a date and time from a number of seconds since the UNIX epoch,
+    /// rounding down to the nearest two seconds.
+    ///
+    /// Returns `None` if the time is outside of the range 1980 to 2107.
+    #[must_use]
+    pub fn from_unix_seconds(seconds: i64) -> Option<Self> {
+        let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+        let time = seconds.rem_euclid(86_400);
+        // Lint: `time` is less than 86,400, so every component fits in `u8`
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Self::new(
+            u16::try_from(year).ok()?,
+            month,
+            day,
+            (time / 3_600) as u8,
+            (time / 60 % 60) as u8,
+            (time % 60) as u8,
+        )
+    }
+
+    /// Converts this date and time to a [`SystemTime`].
+    ///
+    /// Returns `None` if the encoded date or time is invalid.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        system_time_from_unix_nanos(i128::from(self.unix_seconds()?) * NANOS_PER_SECOND)
+    }
+
+    /// Creates a date and time from a [`SystemTime`], rounding down to the
+    /// nearest two seconds.
+    ///
+    /// Returns `None` if the time is outside of the range 1980 to 2107.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn from_system_time(time: SystemTime) -> Option<Self> {
+        let seconds = unix_nanos_from_system_time(time)?.div_euclid(NANOS_PER_SECOND);
+        Self::from_unix_seconds(i64::try_from(seconds).ok()?)
+    }
+
+    /// Converts this date and time to a [`chrono::DateTime`].
+    ///
+    /// Returns `None` if the encoded date or time is invalid.
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn to_chrono(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::from_timestamp(self.unix_seconds()?, 0)
+    }
+
+    /// Creates a date and time from a [`chrono::DateTime`], rounding down to
+    /// the nearest two seconds.
+    ///
+    /// Returns `None` if the time is outside of the range 1980 to 2107.
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn from_chrono<Tz: chrono::TimeZone>(time: &chrono::DateTime<Tz>) -> Option<Self> {
+        Self::from_unix_seconds(time.timestamp())
+    }
+
+    /// Converts this date and time to a [`time::OffsetDateTime`] in UTC.
+    ///
+    /// Returns `None` if the encoded date or time is invalid.
+    #[cfg(feature = "time")]
+    #[must_use]
+    pub fn to_offset_date_time(&self) -> Option<time::OffsetDateTime> {
+        time::OffsetDateTime::from_unix_timestamp(self.unix_seconds()?).ok()
+    }
+
+    /// Creates a date and time from a [`time::OffsetDateTime`], rounding down
+    /// to the nearest two seconds.
+    ///
+    /// Returns `None` if the time is outside of the range 1980 to 2107.
+    #[cfg(feature = "time")]
+    #[must_use]
+    pub fn from_offset_date_time(time: time::OffsetDateTime) -> Option<Self> {
+        Self::from_unix_seconds(time.unix_timestamp())
+    }
+}
+
+impl BinRead for DosDateTime {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let time = u16::read_options(reader, endian, ())?;
+        let date = u16::read_options(reader, endian, ())?;
+        Ok(Self { time, date })
+    }
+}
+
+impl BinWrite for DosDateTime {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.time.write_options(writer, endian, ())?;
+        self.date.write_options(writer, endian, ())
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days since the UNIX epoch for a date in the
+/// proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let (month, day) = (i64::from(month), i64::from(day));
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the year, month, and day in the proleptic Gregorian calendar for a
+/// number of days since the UNIX epoch.
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    // Lint: The day and month are always in range for `u8`
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (month, day) = (
+        ((month_index + 2) % 12 + 1) as u8,
+        (day_of_year - (153 * month_index + 2) / 5 + 1) as u8,
+    );
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(feature = "std")]
+fn system_time_from_unix_nanos(nanos: i128) -> Option<SystemTime> {
+    let duration = |nanos: i128| {
+        let nanos = nanos.unsigned_abs();
+        let seconds = u64::try_from(nanos / 1_000_000_000).ok()?;
+        // Lint: The remainder is less than one billion
+        #[allow(clippy::cast_possible_truncation)]
+        let subsec = (nanos % 1_000_000_000) as u32;
+        Some(Duration::new(seconds, subsec))
+    };
+
+    if nanos >= 0 {
+        UNIX_EPOCH.checked_add(duration(nanos)?)
+    } else {
+        UNIX_EPOCH.checked_sub(duration(nanos)?)
+    }
+}
+
+#[cfg(feature = "std")]
+fn unix_nanos_from_system_time(time: SystemTime) -> Option<i128> {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => i128::try_from(duration.as_nanos()).ok(),
+        Err(error) => i128::try_from(error.duration().as_nanos()).ok().map(|n| -n),
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn chrono_from_unix_nanos(nanos: i128) -> Option<chrono::DateTime<chrono::Utc>> {
+    let seconds = i64::try_from(nanos.div_euclid(NANOS_PER_SECOND)).ok()?;
+    // Lint: The remainder is less than one billion
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let subsec = nanos.rem_euclid(NANOS_PER_SECOND) as u32;
+    chrono::DateTime::from_timestamp(seconds, subsec)
+}
+
+#[cfg(feature = "chrono")]
+fn unix_nanos_from_chrono<Tz: chrono::TimeZone>(time: &chrono::DateTime<Tz>) -> i128 {
+    i128::from(time.timestamp()) * NANOS_PER_SECOND + i128::from(time.timestamp_subsec_nanos())
+}
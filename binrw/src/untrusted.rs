@@ -0,0 +1,131 @@
+//! Limits for reading [untrusted input](crate::docs::attribute#untrusted-input).
+//!
+//! Types which use the `untrusted` directive apply these limits while they are
+//! being read. The limits are intentionally generous for well-formed data and
+//! exist only to turn hostile data into errors instead of huge allocations,
+//! stack overflows, or panics.
+
+/// The default maximum number of items which may be read by a single `count`
+/// directive.
+///
+/// Counts are also checked against the number of bytes remaining in the
+/// stream, so this limit only matters for very large streams. The limit can
+/// be changed for the current thread with `set_max_count` when the `std`
+/// feature is enabled.
+pub const MAX_COUNT: usize = 1 << 24;
+
+/// The maximum number of bytes which are allocated up front when reading a
+/// collection.
+///
+/// This applies to every collection read by binrw, not only to untrusted
+/// types. Larger collections are still read, but memory for them is
+/// allocated as items are actually read instead of all at once, so a bogus
+/// count fails when the stream runs out of data instead of exhausting memory.
+pub const MAX_PREALLOCATION: usize = 1 << 16;
+
+/// Returns the maximum number of items which may be read by a single `count`
+/// directive in an untrusted type on this thread.
+#[must_use]
+pub fn max_count() -> usize {
+    #[cfg(feature = "std")]
+    {
+        MAX_COUNT_LIMIT.with(core::cell::Cell::get)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        MAX_COUNT
+    }
+}
+
+/// Sets the maximum number of items which may be read by a single `count`
+/// directive in an untrusted type on this thread.
+///
+/// The limit only applies to the current thread, so parsers running on other
+/// threads are not affected.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, io::Cursor};
+/// #[derive(BinRead)]
+/// #[br(untrusted)]
+/// struct Test {
+///     len: u8,
+///     #[br(count = len)]
+///     data: Vec<u8>,
+/// }
+///
+/// binrw::untrusted::set_max_count(2);
+/// assert!(Test::read_le(&mut Cursor::new(b"\x03\x01\x02\x03")).is_err());
+/// binrw::untrusted::set_max_count(binrw::untrusted::MAX_COUNT);
+/// assert!(Test::read_le(&mut Cursor::new(b"\x03\x01\x02\x03")).is_ok());
+/// ```
+#[cfg(feature = "std")]
+pub fn set_max_count(limit: usize) {
+    MAX_COUNT_LIMIT.with(|max_count| max_count.set(limit));
+}
+
+/// Returns the number of items of type `T` to allocate up front when reading
+/// a collection of `count` items.
+pub(crate) fn preallocation<T>(count: usize) -> usize {
+    count.min(MAX_PREALLOCATION / core::mem::size_of::<T>().max(1))
+}
+
+/// The default maximum number of nested untrusted types which may be read at
+/// once.
+///
+/// This prevents stack overflows when reading recursive types. Only types
+/// which use the `untrusted` directive count towards the limit. Nesting is
+/// tracked per thread, so this limit is only enforced when the `std` feature
+/// is enabled. The limit can be changed with `set_max_depth`.
+pub const MAX_DEPTH: usize = 128;
+
+/// Returns the number of untrusted types currently being read on this thread.
+#[cfg(feature = "std")]
+#[must_use]
+pub fn depth() -> usize {
+    DEPTH.with(core::cell::Cell::get)
+}
+
+/// Returns the maximum number of nested untrusted types which may be read at
+/// once on this thread.
+#[cfg(feature = "std")]
+#[must_use]
+pub fn max_depth() -> usize {
+    MAX_DEPTH_LIMIT.with(core::cell::Cell::get)
+}
+
+/// Sets the maximum number of nested untrusted types which may be read at
+/// once on this thread.
+///
+/// The limit only applies to the current thread, since the stack size of each
+/// thread can be different.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, io::Cursor};
+/// #[derive(BinRead)]
+/// #[br(untrusted)]
+/// enum Node {
+///     #[br(magic = 0u8)]
+///     Leaf,
+///     #[br(magic = 1u8)]
+///     Branch(Box<Node>),
+/// }
+///
+/// binrw::untrusted::set_max_depth(2);
+/// assert!(Node::read_le(&mut Cursor::new(b"\x01\0")).is_ok());
+/// assert!(Node::read_le(&mut Cursor::new(b"\x01\x01\0")).is_err());
+/// ```
+#[cfg(feature = "std")]
+pub fn set_max_depth(limit: usize) {
+    MAX_DEPTH_LIMIT.with(|max_depth| max_depth.set(limit));
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    pub(crate) static DEPTH: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+    static MAX_COUNT_LIMIT: core::cell::Cell<usize> = const { core::cell::Cell::new(MAX_COUNT) };
+    static MAX_DEPTH_LIMIT: core::cell::Cell<usize> = const { core::cell::Cell::new(MAX_DEPTH) };
+}
@@ -0,0 +1,173 @@
+//! Type definitions for UUIDs and GUIDs.
+//!
+//! A UUID is a 128-bit identifier which is usually displayed as 32
+//! hexadecimal digits in five groups, like
+//! `c12a7328-f81f-11d2-ba4b-00a0c93ec93b`. There are two common binary
+//! layouts:
+//!
+//! * The RFC 4122 layout stores all 16 bytes in the order they are displayed.
+//!   [`Uuid`] is read and written using this layout.
+//! * The Microsoft GUID layout stores the first three groups as integers in
+//!   the byte order of the stream, followed by the last eight bytes in the
+//!   order they are displayed. This layout is used by GPT, PE/COFF, COM, and
+//!   many other formats which originated on Windows. Use [`Guid`] for this
+//!   layout.
+//!
+//! This module is only available with the `uuid` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use binrw::{io::Cursor, uuid::Guid, BinRead};
+//! use uuid::Uuid;
+//!
+//! #[derive(BinRead)]
+//! #[br(little)]
+//! struct PartitionEntry {
+//!     type_guid: Guid,
+//!     unique_guid: Guid,
+//! }
+//!
+//! let data = b"\x28\x73\x2a\xc1\x1f\xf8\xd2\x11\xba\x4b\x00\xa0\xc9\x3e\xc9\x3b\
+//!              \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01";
+//! let entry = PartitionEntry::read(&mut Cursor::new(data)).unwrap();
+//! assert_eq!(entry.type_guid.to_string(), "c12a7328-f81f-11d2-ba4b-00a0c93ec93b");
+//! assert_eq!(*entry.unique_guid, Uuid::from_u128(1));
+//! ```
+
+use crate::{
+    io::{Read, Seek, Write},
+    BinRead, BinResult, BinWrite, Endian,
+};
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+pub use uuid::Uuid;
+
+/// The bytes are stored in the order they are displayed, regardless of the
+/// byte order of the stream.
+impl BinRead for Uuid {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        <[u8; 16]>::read_options(reader, endian, ()).map(Self::from_bytes)
+    }
+}
+
+/// The bytes are stored in the order they are displayed, regardless of the
+/// byte order of the stream.
+impl BinWrite for Uuid {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.as_bytes().write_options(writer, endian, ())
+    }
+}
+
+/// A UUID stored in the Microsoft GUID layout.
+///
+/// The first three fields are stored as integers using the byte order of the
+/// stream, and the last eight bytes are stored in the order they are
+/// displayed. GUIDs in Windows formats are little-endian. When the stream is
+/// big-endian, this layout is the same as the RFC 4122 layout used by
+/// [`Uuid`].
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{io::Cursor, uuid::Guid, BinReaderExt, BinWrite};
+///
+/// let data = b"\x28\x73\x2a\xc1\x1f\xf8\xd2\x11\xba\x4b\x00\xa0\xc9\x3e\xc9\x3b";
+/// let guid: Guid = Cursor::new(data).read_le().unwrap();
+/// assert_eq!(guid.to_string(), "c12a7328-f81f-11d2-ba4b-00a0c93ec93b");
+///
+/// let mut out = Cursor::new(Vec::new());
+/// guid.write_le(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), data);
+/// ```
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Guid(
+    /// The UUID.
+    pub Uuid,
+);
+
+impl BinRead for Guid {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let d1 = u32::read_options(reader, endian, ())?;
+        let d2 = u16::read_options(reader, endian, ())?;
+        let d3 = u16::read_options(reader, endian, ())?;
+        let d4 = <[u8; 8]>::read_options(reader, endian, ())?;
+        Ok(Self(Uuid::from_fields(d1, d2, d3, &d4)))
+    }
+}
+
+impl BinWrite for Guid {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        let (d1, d2, d3, d4) = self.0.as_fields();
+        d1.write_options(writer, endian, ())?;
+        d2.write_options(writer, endian, ())?;
+        d3.write_options(writer, endian, ())?;
+        d4.write_options(writer, endian, ())
+    }
+}
+
+impl fmt::Display for Guid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Debug for Guid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Guid({})", self.0)
+    }
+}
+
+impl Deref for Guid {
+    type Target = Uuid;
+
+    fn deref(&self) -> &Uuid {
+        &self.0
+    }
+}
+
+impl DerefMut for Guid {
+    fn deref_mut(&mut self) -> &mut Uuid {
+        &mut self.0
+    }
+}
+
+impl From<Uuid> for Guid {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+impl From<Guid> for Uuid {
+    fn from(guid: Guid) -> Self {
+        guid.0
+    }
+}
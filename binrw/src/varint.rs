@@ -0,0 +1,387 @@
+//! Type definitions and helpers for variable-length integers.
+//!
+//! Variable-length integers store seven bits of the value in each byte and
+//! use the high bit of the byte to mark whether more bytes follow. Smaller
+//! values therefore use fewer bytes.
+
+use crate::{
+    io::{Read, Seek, Write},
+    BinRead, BinResult, BinWrite, Endian, Error,
+};
+use core::ops::{Deref, DerefMut};
+
+/// A [LEB128](https://en.wikipedia.org/wiki/LEB128) variable-length integer.
+///
+/// The least significant group of seven bits is stored first. Signed types
+/// use the signed LEB128 encoding. This is the encoding used by Protocol
+/// Buffers (for unsigned values), DWARF, and WebAssembly.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{io::Cursor, BinRead, BinWrite, Leb128};
+///
+/// #[derive(BinRead, BinWrite)]
+/// struct Record {
+///     id: Leb128<u32>,
+///     delta: Leb128<i32>,
+/// }
+///
+/// let data = b"\xe5\x8e\x26\x7f";
+/// let record = Record::read_le(&mut Cursor::new(data)).unwrap();
+/// assert_eq!(*record.id, 624_485);
+/// assert_eq!(*record.delta, -1);
+///
+/// let mut out = Cursor::new(Vec::new());
+/// record.write_le(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), data);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Leb128<T>(
+    /// The integer value.
+    pub T,
+);
+
+/// A big-endian variable-length quantity.
+///
+/// The most significant group of seven bits is stored first. This is the
+/// encoding used by MIDI files.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{io::Cursor, BinReaderExt, Vlq};
+///
+/// let value: Vlq<u32> = Cursor::new(b"\x81\x80\x00").read_le().unwrap();
+/// assert_eq!(*value, 0x4000);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Vlq<T>(
+    /// The integer value.
+    pub T,
+);
+
+/// The maximum number of bytes in an encoded 128-bit integer.
+const MAX_LEN: usize = 19;
+
+fn overflow(pos: u64, ty: &str) -> Error {
+    Error::AssertFail {
+        pos,
+        message: alloc::format!("variable-length integer overflows {ty}").into(),
+    }
+}
+
+fn read_byte<R: Read>(reader: &mut R) -> BinResult<u8> {
+    let mut byte = [0];
+    reader.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+/// Reads LEB128 data, returning the raw bits, the number of bits read, and
+/// the last byte.
+///
+/// If the final group only partly fits in 128 bits, the bits that do not fit
+/// must be zero, or, for signed values, copies of the sign bit.
+fn read_leb128_bits<R: Read + Seek>(
+    reader: &mut R,
+    ty: &str,
+    signed: bool,
+) -> BinResult<(u128, u32, u8)> {
+    let pos = reader.stream_position()?;
+    let mut value = 0_u128;
+    let mut shift = 0;
+    loop {
+        let byte = read_byte(reader)?;
+        let bits = u128::from(byte & 0x7f);
+        if shift >= u128::BITS {
+            return Err(overflow(pos, ty));
+        }
+        let kept = u128::BITS - shift;
+        if kept < 7 {
+            let sign = signed && (bits >> (kept - 1)) & 1 != 0;
+            let extension = if sign { 0x7f >> kept } else { 0 };
+            if bits >> kept != extension {
+                return Err(overflow(pos, ty));
+            }
+        }
+        value |= bits << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            return Ok((value, shift, byte));
+        }
+    }
+}
+
+fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> BinResult<()> {
+    writer.write_all(bytes)?;
+    Ok(())
+}
+
+macro_rules! impl_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl BinRead for Leb128<$ty> {
+                type Args<'a> = ();
+
+                fn read_options<R: Read + Seek>(
+                    reader: &mut R,
+                    _: Endian,
+                    (): Self::Args<'_>,
+                ) -> BinResult<Self> {
+                    let pos = reader.stream_position()?;
+                    let (value, _, _) = read_leb128_bits(reader, stringify!($ty), false)?;
+                    <$ty>::try_from(value)
+                        .map(Self)
+                        .map_err(|_| overflow(pos, stringify!($ty)))
+                }
+            }
+
+            impl BinWrite for Leb128<$ty> {
+                type Args<'a> = ();
+
+                fn write_options<W: Write + Seek>(
+                    &self,
+                    writer: &mut W,
+                    _: Endian,
+                    (): Self::Args<'_>,
+                ) -> BinResult<()> {
+                    let mut value = u128::from(self.0);
+                    let mut bytes = [0; MAX_LEN];
+                    let mut len = 0;
+                    loop {
+                        // Lint: Masked to seven bits
+                        #[allow(clippy::cast_possible_truncation)]
+                        let byte = (value & 0x7f) as u8;
+                        value >>= 7;
+                        bytes[len] = byte | if value == 0 { 0 } else { 0x80 };
+                        len += 1;
+                        if value == 0 {
+                            return write_bytes(writer, &bytes[..len]);
+                        }
+                    }
+                }
+            }
+
+            impl BinRead for Vlq<$ty> {
+                type Args<'a> = ();
+
+                fn read_options<R: Read + Seek>(
+                    reader: &mut R,
+                    _: Endian,
+                    (): Self::Args<'_>,
+                ) -> BinResult<Self> {
+                    let pos = reader.stream_position()?;
+                    let mut value = 0_u128;
+                    loop {
+                        let byte = read_byte(reader)?;
+                        if value >> (u128::BITS - 7) != 0 {
+                            return Err(overflow(pos, stringify!($ty)));
+                        }
+                        value = (value << 7) | u128::from(byte & 0x7f);
+                        if byte & 0x80 == 0 {
+                            break;
+                        }
+                    }
+                    <$ty>::try_from(value)
+                        .map(Self)
+                        .map_err(|_| overflow(pos, stringify!($ty)))
+                }
+            }
+
+            impl BinWrite for Vlq<$ty> {
+                type Args<'a> = ();
+
+                fn write_options<W: Write + Seek>(
+                    &self,
+                    writer: &mut W,
+                    _: Endian,
+                    (): Self::Args<'_>,
+                ) -> BinResult<()> {
+                    let mut value = u128::from(self.0);
+                    let mut bytes = [0; MAX_LEN];
+                    let mut start = MAX_LEN;
+                    loop {
+                        start -= 1;
+                        // Lint: Masked to seven bits
+                        #[allow(clippy::cast_possible_truncation)]
+                        let byte = (value & 0x7f) as u8;
+                        value >>= 7;
+                        bytes[start] = byte | if start == MAX_LEN - 1 { 0 } else { 0x80 };
+                        if value == 0 {
+                            return write_bytes(writer, &bytes[start..]);
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128);
+
+macro_rules! impl_signed {
+    ($($ty:ty),*) => {
+        $(
+            impl BinRead for Leb128<$ty> {
+                type Args<'a> = ();
+
+                fn read_options<R: Read + Seek>(
+                    reader: &mut R,
+                    _: Endian,
+                    (): Self::Args<'_>,
+                ) -> BinResult<Self> {
+                    let pos = reader.stream_position()?;
+                    let (mut value, shift, last) = read_leb128_bits(reader, stringify!($ty), true)?;
+                    if shift < u128::BITS && last & 0x40 != 0 {
+                        value |= u128::MAX << shift;
+                    }
+                    // Lint: Reinterpreting the sign-extended bits is intended
+                    #[allow(clippy::cast_possible_wrap)]
+                    let value = value as i128;
+                    <$ty>::try_from(value)
+                        .map(Self)
+                        .map_err(|_| overflow(pos, stringify!($ty)))
+                }
+            }
+
+            impl BinWrite for Leb128<$ty> {
+                type Args<'a> = ();
+
+                fn write_options<W: Write + Seek>(
+                    &self,
+                    writer: &mut W,
+                    _: Endian,
+                    (): Self::Args<'_>,
+                ) -> BinResult<()> {
+                    let mut value = i128::from(self.0);
+                    let mut bytes = [0; MAX_LEN];
+                    let mut len = 0;
+                    loop {
+                        // Lint: Masked to seven bits
+                        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                        let byte = (value & 0x7f) as u8;
+                        value >>= 7;
+                        let done = (value == 0 && byte & 0x40 == 0)
+                            || (value == -1 && byte & 0x40 != 0);
+                        bytes[len] = byte | if done { 0 } else { 0x80 };
+                        len += 1;
+                        if done {
+                            return write_bytes(writer, &bytes[..len]);
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_signed!(i8, i16, i32, i64, i128);
+
+impl<T> Deref for Leb128<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Leb128<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Leb128<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Deref for Vlq<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Vlq<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Vlq<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+/// Reads a [LEB128](Leb128) integer into a plain integer field.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{io::Cursor, BinRead, varint};
+/// #[derive(BinRead)]
+/// struct Record {
+///     #[br(parse_with = varint::read_leb128)]
+///     len: u64,
+/// }
+///
+/// # assert_eq!(Record::read_le(&mut Cursor::new(b"\xac\x02")).unwrap().len, 300);
+/// ```
+///
+/// # Errors
+///
+/// If reading fails, or if the value does not fit in `T`, an [`Error`]
+/// variant will be returned.
+#[crate::parser(reader, endian)]
+pub fn read_leb128<T>() -> BinResult<T>
+where
+    Leb128<T>: for<'a> BinRead<Args<'a> = ()>,
+{
+    Leb128::<T>::read_options(reader, endian, ()).map(|value| value.0)
+}
+
+/// Writes a plain integer field as a [LEB128](Leb128) integer.
+///
+/// # Errors
+///
+/// If writing fails, an [`Error`] variant will be returned.
+#[crate::writer(writer, endian)]
+pub fn write_leb128<T>(value: &T) -> BinResult<()>
+where
+    T: Copy,
+    Leb128<T>: for<'a> BinWrite<Args<'a> = ()>,
+{
+    Leb128(*value).write_options(writer, endian, ())
+}
+
+/// Reads a [VLQ](Vlq) integer into a plain integer field.
+///
+/// # Errors
+///
+/// If reading fails, or if the value does not fit in `T`, an [`Error`]
+/// variant will be returned.
+#[crate::parser(reader, endian)]
+pub fn read_vlq<T>() -> BinResult<T>
+where
+    Vlq<T>: for<'a> BinRead<Args<'a> = ()>,
+{
+    Vlq::<T>::read_options(reader, endian, ()).map(|value| value.0)
+}
+
+/// Writes a plain integer field as a [VLQ](Vlq) integer.
+///
+/// # Errors
+///
+/// If writing fails, an [`Error`] variant will be returned.
+#[crate::writer(writer, endian)]
+pub fn write_vlq<T>(value: &T) -> BinResult<()>
+where
+    T: Copy,
+    Vlq<T>: for<'a> BinWrite<Args<'a> = ()>,
+{
+    Vlq(*value).write_options(writer, endian, ())
+}
@@ -0,0 +1,51 @@
+use binrw::{
+    io::{Cursor, Seek, SeekFrom},
+    AppendWriter, BinWrite, Endian,
+};
+
+#[test]
+fn append_options() {
+    let mut writer = Cursor::new(b"\x01\x02\x03".to_vec());
+    writer.seek(SeekFrom::Start(1)).unwrap();
+    assert_eq!(
+        0x0405_u16
+            .append_options(&mut writer, Endian::Big, ())
+            .unwrap(),
+        3..5
+    );
+    assert_eq!(writer.position(), 5);
+    assert_eq!(writer.into_inner(), b"\x01\x02\x03\x04\x05");
+}
+
+#[test]
+fn append_writer() {
+    let mut writer = AppendWriter::new(Cursor::new(Vec::new()));
+    assert!(writer.extents().is_empty());
+    assert_eq!(writer.append(&1_u8, Endian::Little).unwrap(), 0..1);
+
+    // Moving the writer does not change where the next object goes
+    writer.get_mut().seek(SeekFrom::Start(0)).unwrap();
+    assert_eq!(writer.append(&2_u16, Endian::Big).unwrap(), 1..3);
+    assert_eq!(
+        writer
+            .append_args(&vec![3_u8, 4], Endian::Little, ())
+            .unwrap(),
+        3..5
+    );
+    assert_eq!(writer.extents(), [0..1, 1..3, 3..5]);
+
+    writer.clear();
+    assert!(writer.extents().is_empty());
+    assert_eq!(writer.into_inner().into_inner(), b"\x01\0\x02\x03\x04");
+}
+
+#[test]
+fn append_writer_error() {
+    // A fixed-size buffer is always full, so nothing can be appended
+    let mut buf = [0_u8; 2];
+    let mut writer = AppendWriter::new(Cursor::new(&mut buf[..]));
+    writer
+        .append(&1_u8, Endian::Little)
+        .expect_err("wrote past the end of the buffer");
+    assert!(writer.extents().is_empty());
+}
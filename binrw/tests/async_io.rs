@@ -0,0 +1,235 @@
+#![cfg(feature = "tokio")]
+
+use binrw::{binrw, AsyncBinRead, AsyncBinWrite, BinRead, FilePtr8, NullString};
+use std::io::Cursor;
+
+fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+#[binrw]
+#[brw(big, magic = b"REC")]
+#[derive(Debug, PartialEq)]
+struct Record {
+    #[bw(try_calc = u16::try_from(data.len()))]
+    len: u16,
+    #[br(count = len)]
+    data: Vec<u8>,
+}
+
+#[test]
+fn read_sequential() {
+    block_on(async {
+        let mut data = Vec::new();
+        for _ in 0..2 {
+            data.extend_from_slice(b"REC\0\x50");
+            data.extend((0..0x50).map(|i| i as u8));
+        }
+        let mut stream = Cursor::new(data);
+
+        for _ in 0..2 {
+            let record = Record::read_async(&mut stream).await.unwrap();
+            assert_eq!(record.data, (0..0x50).collect::<Vec<u8>>());
+        }
+        assert_eq!(stream.position(), 2 * (5 + 0x50));
+        assert!(Record::read_async(&mut stream).await.unwrap_err().is_eof());
+    });
+}
+
+#[test]
+fn read_with_seeks() {
+    #[derive(BinRead, Debug)]
+    struct Test {
+        ptr: FilePtr8<NullString>,
+        value: u8,
+    }
+
+    block_on(async {
+        let mut stream = Cursor::new(b"\x02\x2aabc\0");
+        let test = Test::read_le_async(&mut stream).await.unwrap();
+        assert_eq!(test.ptr.to_string(), "abc");
+        assert_eq!(test.value, 0x2a);
+        assert_eq!(stream.position(), 2);
+    });
+}
+
+#[test]
+fn read_error() {
+    block_on(async {
+        let mut stream = Cursor::new(b"BAD\0\0");
+        let error = Record::read_async(&mut stream).await.unwrap_err();
+        assert!(matches!(error, binrw::Error::BadMagic { pos: 0, .. }));
+    });
+}
+
+#[test]
+fn write() {
+    block_on(async {
+        let mut stream = Cursor::new(b"\xff".to_vec());
+        stream.set_position(1);
+        Record { data: vec![1, 2] }
+            .write_async(&mut stream)
+            .await
+            .unwrap();
+        assert_eq!(stream.position(), 8);
+        assert_eq!(stream.into_inner(), b"\xffREC\0\x02\x01\x02");
+    });
+}
+
+#[test]
+fn read_until_eof() {
+    #[derive(BinRead, Debug)]
+    struct Test {
+        value: u8,
+        #[br(parse_with = binrw::helpers::until_eof)]
+        data: Vec<u8>,
+    }
+
+    block_on(async {
+        let mut stream = Cursor::new((0..200).map(|i| i as u8).collect::<Vec<_>>());
+        let test = Test::read_le_async(&mut stream).await.unwrap();
+        assert_eq!(test.value, 0);
+        assert_eq!(test.data, (1..200).map(|i| i as u8).collect::<Vec<_>>());
+        assert_eq!(stream.position(), 200);
+    });
+}
+
+#[test]
+fn read_untrusted_count() {
+    #[derive(BinRead, Debug)]
+    #[br(untrusted)]
+    #[allow(dead_code)]
+    struct Test {
+        len: u8,
+        #[br(count = len)]
+        data: Vec<u8>,
+    }
+
+    block_on(async {
+        let mut data = vec![100];
+        data.extend(0..100);
+        let test = Test::read_le_async(&mut Cursor::new(data)).await.unwrap();
+        assert_eq!(test.data, (0..100).collect::<Vec<u8>>());
+    });
+}
+
+#[test]
+fn read_parses_once() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    #[binrw::parser(reader, endian)]
+    fn counted() -> binrw::BinResult<u16> {
+        CALLS.fetch_add(1, Ordering::Relaxed);
+        u16::read_options(reader, endian, ())
+    }
+
+    #[derive(BinRead, Debug)]
+    struct Test {
+        #[br(parse_with = counted)]
+        value: u16,
+    }
+
+    block_on(async {
+        let test = Test::read_le_async(&mut Cursor::new(b"4\x12")).await.unwrap();
+        assert_eq!(test.value, 0x1234);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    });
+}
+
+#[test]
+fn read_incremental() {
+    block_on(async {
+        let mut data = b"REC\0P".to_vec();
+        data.extend(0..0x50);
+        data.extend_from_slice(b"REC\0\0");
+        let mut stream = Cursor::new(data);
+        let record = Record::read_options_incremental_async(&mut stream, binrw::Endian::Big, ())
+            .await
+            .unwrap();
+        assert_eq!(record.data, (0..0x50).collect::<Vec<u8>>());
+        assert_eq!(stream.position(), 5 + 0x50);
+    });
+}
+
+#[binrw::binread]
+#[br(async, big, magic = b"HDR", import(version: u8))]
+#[br(assert(entries.len() == usize::from(count)))]
+#[derive(Debug, PartialEq)]
+struct Header {
+    #[br(temp)]
+    count: u8,
+    #[br(little)]
+    flags: u16,
+    #[br(if(version >= 2))]
+    extra: Option<u8>,
+    #[br(count = count, args { inner: (version,) })]
+    entries: Vec<Entry>,
+    #[br(calc = flags & 1 != 0)]
+    compressed: bool,
+    #[br(magic = 0xffu8, assert(trailer == 0x2a, "bad trailer {}", trailer))]
+    trailer: u8,
+}
+
+#[derive(BinRead, Debug, PartialEq)]
+#[br(import(version: u8))]
+struct Entry {
+    id: u8,
+    #[br(if(version >= 2))]
+    size: Option<u16>,
+}
+
+#[test]
+fn derive_async() {
+    block_on(async {
+        let mut stream = Cursor::new(b"\xffHDR\x02\x01\0\x07\x01\0\x05\x02\0\x06\xff\x2a");
+        stream.set_position(1);
+        let header = Header::read_args_async(&mut stream, (2,)).await.unwrap();
+        assert_eq!(
+            header,
+            Header {
+                flags: 1,
+                extra: Some(7),
+                entries: vec![
+                    Entry {
+                        id: 1,
+                        size: Some(5)
+                    },
+                    Entry {
+                        id: 2,
+                        size: Some(6)
+                    },
+                ],
+                compressed: true,
+                trailer: 0x2a,
+            }
+        );
+        assert_eq!(stream.position(), 16);
+    });
+}
+
+#[test]
+fn derive_async_error() {
+    block_on(async {
+        let mut stream = Cursor::new(b"HDR\x01\x01\0\x01\xff\x2b");
+        let error = Header::read_args_async(&mut stream, (1,))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error.root_cause(), binrw::Error::AssertFail { pos: 0, message } if message == "bad trailer 43"),
+            "{error:?}"
+        );
+        assert_eq!(stream.position(), 0);
+
+        let mut stream = Cursor::new(b"HDR\x01\x01\0\x01");
+        let error = Header::read_args_async(&mut stream, (1,))
+            .await
+            .unwrap_err();
+        assert!(error.root_cause().is_eof(), "{error:?}");
+        assert_eq!(stream.position(), 0);
+    });
+}
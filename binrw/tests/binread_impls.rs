@@ -1,4 +1,4 @@
-use binrw::{io::Cursor, BinRead};
+use binrw::{args, io::Cursor, BinRead};
 
 #[test]
 fn boxed() {
@@ -11,6 +11,45 @@ fn boxed() {
         .is_eof());
 }
 
+#[test]
+fn boxed_slice() {
+    assert_eq!(
+        Box::<[u16]>::read_be_args(&mut Cursor::new(b"\0\x01\0\x02"), args! { count: 2 }).unwrap(),
+        vec![1_u16, 2].into_boxed_slice()
+    );
+}
+
+#[test]
+fn shared() {
+    use std::{rc::Rc, sync::Arc};
+
+    #[derive(BinRead, Debug, Eq, PartialEq)]
+    #[br(import(mul: u8))]
+    struct Test(#[br(map = |val: u8| mul * val)] u8);
+
+    assert_eq!(
+        Rc::<Test>::read_le_args(&mut Cursor::new(b"\x03"), (2,)).unwrap(),
+        Rc::new(Test(6))
+    );
+    assert_eq!(
+        Arc::<Test>::read_le_args(&mut Cursor::new(b"\x03"), (2,)).unwrap(),
+        Arc::new(Test(6))
+    );
+    assert!(Rc::<u16>::read_le(&mut Cursor::new(b"\x03"))
+        .unwrap_err()
+        .is_eof());
+}
+
+#[test]
+fn cow() {
+    use std::borrow::Cow;
+
+    let value =
+        Cow::<[u8]>::read_le_args(&mut Cursor::new(b"\x01\x02\x03"), args! { count: 2 }).unwrap();
+    assert!(matches!(value, Cow::Owned(_)));
+    assert_eq!(value, &[1_u8, 2][..]);
+}
+
 #[test]
 fn convenience_endian() {
     #[derive(BinRead, Debug, Eq, PartialEq)]
@@ -75,12 +114,92 @@ fn clone_args() {
     TestCloneArray::read_le(&mut Cursor::new(b"")).unwrap();
 }
 
+#[cfg(feature = "half")]
 #[test]
-fn non_zero() {
+fn f16() {
+    use half::f16;
+
+    assert_eq!(
+        f16::read_be(&mut Cursor::new(b"\x3c\0")).unwrap(),
+        f16::from_f32(1.0)
+    );
+    assert_eq!(
+        f16::read_le(&mut Cursor::new(b"\0\xc0")).unwrap(),
+        f16::from_f32(-2.0)
+    );
+    assert!(f16::read_le(&mut Cursor::new(b"\0")).unwrap_err().is_eof());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn file_ptr_parse_all_parallel() {
+    use binrw::{args, Endian, FilePtr16};
+
+    let mut data = Vec::new();
+    let ptrs = (0..1000_u16).map(|i| 2000 + i * 2).collect::<Vec<_>>();
+    data.resize(2000, 0);
+    for i in 0..1000_u16 {
+        data.extend(i.to_be_bytes());
+    }
+
+    let reader = Cursor::new(&data[..]);
+    let values =
+        FilePtr16::<u16>::parse_all_parallel(&reader, Endian::Big, &ptrs, args! {}).unwrap();
+    assert_eq!(values, (0..1000).collect::<Vec<_>>());
+
+    let values =
+        FilePtr16::<u8>::parse_all_parallel(&reader, Endian::Big, &ptrs[..3], args! { offset: 1 })
+            .unwrap();
+    assert_eq!(values, [0, 1, 2]);
+
+    let error = FilePtr16::<u16>::parse_all_parallel(&reader, Endian::Big, &[0, 5000], args! {})
+        .unwrap_err();
     assert!(matches!(
-        core::num::NonZeroU8::read(&mut Cursor::new(b"\0")).expect_err("accepted bad data"),
-        binrw::Error::Io(..)
+        error,
+        binrw::Error::OffsetOutOfBounds { offset: 5000, .. }
     ));
+}
+
+#[test]
+fn lazy_file_ptr() {
+    use binrw::{args, file_ptr::LazyFilePtr, io::Seek, Endian};
+
+    let mut reader = Cursor::new(b"\x01\x03\0\0\x12\x34".to_vec());
+    let ptrs =
+        <[LazyFilePtr<u8, u16>; 2]>::read_options(&mut reader, Endian::Little, args! { offset: 1 })
+            .unwrap();
+
+    assert_eq!(*ptrs[0].get(&mut reader).unwrap(), 0);
+    assert_eq!(reader.stream_position().unwrap(), 2);
+    assert_eq!(*ptrs[1].get(&mut reader).unwrap(), 0x3412);
+
+    // The value is only read once
+    reader.get_mut()[4] = 0;
+    assert_eq!(*ptrs[1].get(&mut reader).unwrap(), 0x3412);
+    assert_eq!(ptrs[1].value(), Some(&0x3412));
+
+    let ptr = LazyFilePtr::<u8, u16>::read_le(&mut Cursor::new(b"\x08")).unwrap();
+    ptr.get(&mut Cursor::new(b"\x08"))
+        .expect_err("accepted bad pointer");
+    assert!(ptr.value().is_none());
+}
+
+#[test]
+fn non_zero() {
+    let mut data = Cursor::new(b"\x01\0\0");
+    data.set_position(1);
+    match core::num::NonZeroU16::read_le(&mut data).expect_err("accepted bad data") {
+        binrw::Error::AssertFail { pos, message } => {
+            assert_eq!(pos, 1);
+            assert_eq!(message, "expected a non-zero u16, found zero");
+        }
+        error => panic!("unexpected error: {error:?}"),
+    }
+    assert_eq!(data.position(), 1, "reader was not rewound");
+    assert_eq!(
+        core::num::NonZeroI32::read_be(&mut Cursor::new(b"\xff\xff\xff\xfe")).unwrap(),
+        core::num::NonZeroI32::new(-2).unwrap()
+    );
     assert_eq!(
         core::num::NonZeroU8::read(&mut Cursor::new(b"\x01")).unwrap(),
         core::num::NonZeroU8::new(1).unwrap()
@@ -111,3 +230,153 @@ fn vec_u8() {
         binrw::Error::Io(..)
     ));
 }
+
+#[test]
+fn vec_primitive() {
+    let args = || binrw::VecArgs::builder().count(2).finalize();
+    assert_eq!(
+        Vec::<u32>::read_be_args(&mut Cursor::new(b"\0\0\0\x01\x01\x02\x03\x04"), args()).unwrap(),
+        [1, 0x0102_0304]
+    );
+    assert_eq!(
+        Vec::<i16>::read_le_args(&mut Cursor::new(b"\xff\xff\x02\x01"), args()).unwrap(),
+        [-1, 0x0102]
+    );
+    assert_eq!(
+        Vec::<f32>::read_be_args(&mut Cursor::new(b"\x3f\x80\0\0\xc0\0\0\0"), args()).unwrap(),
+        [1.0, -2.0]
+    );
+    assert_eq!(
+        Vec::<f64>::read_le_args(
+            &mut Cursor::new(b"\0\0\0\0\0\0\xf0\x3f\0\0\0\0\0\0\0\xc0"),
+            args()
+        )
+        .unwrap(),
+        [1.0, -2.0]
+    );
+
+    // A large count is read in chunks but must still produce every item
+    let data = (0..0x10000_u32)
+        .flat_map(u32::to_be_bytes)
+        .collect::<Vec<_>>();
+    let values = Vec::<u32>::read_be_args(
+        &mut Cursor::new(data),
+        binrw::VecArgs::builder().count(0x10000).finalize(),
+    )
+    .unwrap();
+    assert!(values.iter().copied().eq(0..0x10000));
+}
+
+#[test]
+fn vec_huge_count() {
+    // A bogus count must fail when the data runs out instead of trying to
+    // allocate memory for every item up front
+    for count in [usize::MAX, 0xffff_ffff] {
+        assert!(matches!(
+            Vec::<u8>::read_args(
+                &mut Cursor::new(b"\x01\x02"),
+                binrw::VecArgs::builder().count(count).finalize()
+            )
+            .expect_err("accepted bad data"),
+            binrw::Error::Io(..)
+        ));
+
+        assert!(matches!(
+            binrw::punctuated::Punctuated::<u16, u8>::separated(
+                &mut Cursor::new(b"\x01\x02"),
+                binrw::Endian::Little,
+                binrw::VecArgs::builder().count(count).finalize()
+            )
+            .expect_err("accepted bad data"),
+            binrw::Error::Io(..)
+        ));
+    }
+}
+
+#[test]
+fn maps_and_sets() {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    #[binrw::binread]
+    #[br(big)]
+    struct Test {
+        #[br(temp)]
+        count: u8,
+        #[br(count = count)]
+        map: BTreeMap<u8, u16>,
+        #[br(count = count)]
+        set: BTreeSet<u8>,
+    }
+
+    let data = b"\x02\x02\0\x01\x01\0\x02\x02\x01";
+    let test = Test::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(test.map, BTreeMap::from([(1, 2), (2, 1)]));
+    assert_eq!(test.set, BTreeSet::from([1, 2]));
+
+    // Later duplicate keys replace earlier values
+    let map = BTreeMap::<u8, u8>::read_args(
+        &mut Cursor::new(b"\x01\x01\x01\x02"),
+        binrw::VecArgs::builder().count(2).finalize(),
+    )
+    .unwrap();
+    assert_eq!(map, BTreeMap::from([(1, 2)]));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hash_maps_and_sets() {
+    use std::collections::{HashMap, HashSet};
+
+    #[binrw::binread]
+    #[br(big)]
+    struct Test {
+        #[br(temp)]
+        count: u8,
+        #[br(count = count)]
+        map: HashMap<u8, u16>,
+        #[br(count = count)]
+        set: HashSet<u8>,
+    }
+
+    let data = b"\x02\x02\0\x01\x01\0\x02\x02\x01";
+    let test = Test::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(test.map, HashMap::from([(1, 2), (2, 1)]));
+    assert_eq!(test.set, HashSet::from([1, 2]));
+}
+
+#[test]
+fn sequences() {
+    use std::collections::{BinaryHeap, LinkedList, VecDeque};
+
+    #[derive(BinRead, Debug, Eq, PartialEq, PartialOrd, Ord)]
+    #[br(import(mul: u16))]
+    struct Scaled(#[br(map = |val: u16| mul * val)] u16);
+
+    #[binrw::binread]
+    #[br(big)]
+    struct Test {
+        #[br(temp)]
+        count: u8,
+        #[br(count = count)]
+        deque: VecDeque<u16>,
+        #[br(count = count, args { inner: (2,) })]
+        list: LinkedList<Scaled>,
+        #[br(count = count)]
+        heap: BinaryHeap<u8>,
+    }
+
+    let data = b"\x03\0\x01\0\x02\0\x03\0\x03\0\x01\0\x02\x02\x03\x01";
+    let test = Test::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(test.deque, VecDeque::from([1, 2, 3]));
+    assert_eq!(
+        test.list,
+        LinkedList::from([Scaled(6), Scaled(2), Scaled(4)])
+    );
+    assert_eq!(test.heap.into_sorted_vec(), [1, 2, 3]);
+
+    assert!(
+        VecDeque::<u16>::read_be_args(&mut Cursor::new(b"\0\x01\0"), args! { count: 2 })
+            .unwrap_err()
+            .is_eof()
+    );
+}
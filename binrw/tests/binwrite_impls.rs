@@ -23,6 +23,17 @@ fn boxed() {
     compare!(vec![3_u8; 2].into_boxed_slice(), b"\x03\x03");
 }
 
+#[test]
+fn shared() {
+    use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+    compare!(Rc::new(3_u16), Endian::Big, b"\0\x03");
+    compare!(Arc::new(3_u16), Endian::Little, b"\x03\0");
+    compare!(Rc::<[u8]>::from(&b"ab"[..]), b"ab");
+    compare!(Cow::<[u8]>::Borrowed(b"ab"), b"ab");
+    compare!(Cow::<[u8]>::Owned(b"cd".to_vec()), b"cd");
+}
+
 // This is a compile-time regression test to ensure library types allow
 // cloneable arguments.
 #[test]
@@ -75,6 +86,13 @@ fn non_zero() {
     );
 }
 
+#[cfg(feature = "half")]
+#[test]
+fn f16() {
+    compare!(half::f16::from_f32(1.0), Endian::Big, b"\x3c\0");
+    compare!(half::f16::from_f32(-2.0), Endian::Little, b"\0\xc0");
+}
+
 #[test]
 fn option() {
     compare!(Some(1_i32), Endian::Big, b"\0\0\0\x01");
@@ -99,3 +117,303 @@ fn vec_i8() {
     vec![-1_i8; 4].write(&mut output).unwrap();
     assert_eq!(output.into_inner(), b"\xff\xff\xff\xff");
 }
+
+#[test]
+fn vec_primitive() {
+    compare!(
+        vec![1_u32, 0x0102_0304],
+        Endian::Big,
+        b"\0\0\0\x01\x01\x02\x03\x04"
+    );
+    compare!(vec![-1_i16, 0x0102], Endian::Little, b"\xff\xff\x02\x01");
+    compare!(vec![1.0_f32, -2.0], Endian::Big, b"\x3f\x80\0\0\xc0\0\0\0");
+    compare!(
+        vec![1.0_f64, -2.0],
+        Endian::Little,
+        b"\0\0\0\0\0\0\xf0\x3f\0\0\0\0\0\0\0\xc0"
+    );
+    compare!(Vec::<u64>::new(), Endian::Big, b"");
+
+    // Swapped values are written in chunks, which must not drop any items
+    let values = (0..0x10000_u32).collect::<Vec<_>>();
+    for endian in [Endian::Big, Endian::Little] {
+        let mut output = binrw::io::Cursor::new(vec![]);
+        values.write_options(&mut output, endian, ()).unwrap();
+        let expected = values
+            .iter()
+            .flat_map(|value| match endian {
+                Endian::Big => value.to_be_bytes(),
+                Endian::Little => value.to_le_bytes(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(output.into_inner(), expected);
+    }
+}
+
+#[test]
+fn file_ptr() {
+    use binrw::{args, file_ptr::FilePtrQueue, io::Cursor, BinReaderExt, FilePtr16, FilePtr8};
+
+    #[binrw::binrw]
+    #[brw(big)]
+    #[bw(import(queue: &FilePtrQueue))]
+    #[derive(Debug, PartialEq)]
+    struct Node {
+        value: u8,
+        #[bw(args { queue })]
+        next: FilePtr8<u16>,
+    }
+
+    let queue = FilePtrQueue::new();
+    let mut output = Cursor::new(vec![]);
+    let nodes = [
+        FilePtr16 {
+            ptr: 0,
+            value: Some(Node {
+                value: 1,
+                next: FilePtr8 {
+                    ptr: 0,
+                    value: Some(0x1234),
+                },
+            }),
+        },
+        FilePtr16 {
+            ptr: 0,
+            value: Some(Node {
+                value: 2,
+                next: FilePtr8 {
+                    ptr: 0x42,
+                    value: None,
+                },
+            }),
+        },
+    ];
+    nodes
+        .write_options(
+            &mut output,
+            Endian::Big,
+            args! { queue: &queue, inner: (&queue,) },
+        )
+        .unwrap();
+    assert_eq!(queue.len(), 2);
+    queue.flush(&mut output).unwrap();
+    assert!(queue.is_empty());
+    assert_eq!(output.get_ref(), b"\0\x04\0\x06\x01\x08\x02\x42\x12\x34");
+
+    output.set_position(0);
+    let first = output.read_be::<FilePtr16<Node>>().unwrap().into_inner();
+    assert_eq!(first.value, 1);
+    assert_eq!(*first.next, 0x1234);
+}
+
+#[test]
+fn file_ptr_offset() {
+    use binrw::{args, file_ptr::FilePtrQueue, io::Cursor, FilePtr8};
+
+    let queue = FilePtrQueue::new();
+    let mut output = Cursor::new(vec![0; 4]);
+    output.set_position(4);
+    FilePtr8 {
+        ptr: 0,
+        value: Some(0xff_u8),
+    }
+    .write_options(&mut output, Endian::Big, args! { queue: &queue, offset: 4 })
+    .unwrap();
+    queue.flush(&mut output).unwrap();
+    assert_eq!(output.into_inner(), b"\0\0\0\0\x01\xff");
+}
+
+#[test]
+fn file_ptr_aligned() {
+    use binrw::{args, binwrite, file_ptr::FilePtrQueue, io::Cursor, FilePtr8};
+
+    #[binwrite]
+    #[bw(big)]
+    struct Aligned {
+        a: u8,
+        #[bw(align_before = 4)]
+        b: u8,
+    }
+
+    let queue = FilePtrQueue::new();
+    let mut output = Cursor::new(Vec::new());
+    FilePtr8 {
+        ptr: 0,
+        value: Some(Aligned { a: 1, b: 2 }),
+    }
+    .write_options(&mut output, Endian::Big, args! { queue: &queue, align: 4 })
+    .unwrap();
+    0xff_u8.write_be(&mut output).unwrap();
+    queue.flush(&mut output).unwrap();
+    assert_eq!(output.into_inner(), b"\x04\xff\0\0\x01\0\0\0\x02");
+}
+
+#[test]
+fn file_ptr_deep_chain() {
+    use binrw::{args, file_ptr::FilePtrQueue, io::Cursor, FilePtr8};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+    struct Node(Option<Box<Node>>);
+
+    impl BinWrite for Node {
+        type Args<'a> = (&'a FilePtrQueue,);
+
+        fn write_options<W: binrw::io::Write + binrw::io::Seek>(
+            &self,
+            writer: &mut W,
+            endian: Endian,
+            (queue,): Self::Args<'_>,
+        ) -> binrw::BinResult<()> {
+            WRITES.fetch_add(1, Ordering::Relaxed);
+            match &self.0 {
+                Some(next) => FilePtr8 {
+                    ptr: 0,
+                    value: Some(next),
+                }
+                .write_options(writer, endian, args! { queue, inner: (queue,) }),
+                None => 0_u8.write_options(writer, endian, ()),
+            }
+        }
+    }
+
+    let mut node = Node(None);
+    for _ in 0..20 {
+        node = Node(Some(Box::new(node)));
+    }
+
+    let queue = FilePtrQueue::new();
+    let mut output = Cursor::new(Vec::new());
+    node.write_options(&mut output, Endian::Big, (&queue,))
+        .unwrap();
+    queue.flush(&mut output).unwrap();
+
+    // Each node is only serialized once, no matter how deep it is
+    assert_eq!(WRITES.load(Ordering::Relaxed), 21);
+    let expected = (1..=20).chain([0]).collect::<Vec<u8>>();
+    assert_eq!(output.into_inner(), expected);
+}
+
+#[test]
+fn file_ptr_nullable() {
+    use binrw::{binrw, file_ptr::FilePtrQueue, io::Cursor, BinRead, FilePtr16};
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    #[bw(import(queue: &FilePtrQueue))]
+    struct Test {
+        #[br(parse_with = FilePtr16::with_nullable(0xffff))]
+        #[bw(write_with = FilePtr16::write_nullable(0xffff), args { queue })]
+        a: Option<FilePtr16<u8>>,
+        #[br(parse_with = FilePtr16::with_nullable(0xffff))]
+        #[bw(write_with = FilePtr16::write_nullable(0xffff), args { queue })]
+        b: Option<FilePtr16<u8>>,
+    }
+
+    let data = b"\xff\xff\0\x04\x12";
+    let test = Test::read(&mut Cursor::new(data)).unwrap();
+    assert!(test.a.is_none());
+    assert_eq!(test.b.as_deref(), Some(&0x12));
+
+    let queue = FilePtrQueue::new();
+    let mut output = Cursor::new(Vec::new());
+    test.write_args(&mut output, (&queue,)).unwrap();
+    queue.flush(&mut output).unwrap();
+    assert_eq!(output.into_inner(), data);
+}
+
+#[test]
+fn file_ptr_nullable_recursive() {
+    use binrw::{binrw, file_ptr::FilePtrQueue, io::Cursor, BinRead, FilePtr16};
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    #[bw(import(queue: &FilePtrQueue))]
+    struct Node {
+        value: u8,
+        #[br(parse_with = FilePtr16::with_nullable(0xffff))]
+        #[bw(write_with = FilePtr16::write_nullable(0xffff), args { queue, inner: (queue,) })]
+        next: Option<FilePtr16<Box<Node>>>,
+    }
+
+    let data = b"\x01\0\x03\x02\0\x06\x03\xff\xff";
+    let node = Node::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(node.value, 1);
+    assert_eq!(node.next.as_deref().unwrap().value, 2);
+
+    let queue = FilePtrQueue::new();
+    let mut output = Cursor::new(Vec::new());
+    node.write_args(&mut output, (&queue,)).unwrap();
+    queue.flush(&mut output).unwrap();
+    assert_eq!(output.into_inner(), data);
+}
+
+#[test]
+fn file_ptr_overflow() {
+    use binrw::{args, file_ptr::FilePtrQueue, io::Cursor, FilePtr8};
+
+    let queue = FilePtrQueue::new();
+    let mut output = Cursor::new(vec![0; 0x100]);
+    output.set_position(0x100);
+    FilePtr8 {
+        ptr: 0,
+        value: Some(0xff_u8),
+    }
+    .write_options(&mut output, Endian::Big, args! { queue: &queue })
+    .unwrap();
+    let error = queue.flush(&mut output).unwrap_err();
+    assert!(
+        matches!(error, binrw::Error::AssertFail { pos: 0x100, .. }),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn maps_and_sets() {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    // BTree collections are written in sorted order
+    let mut output = binrw::io::Cursor::new(vec![]);
+    BTreeMap::from([(2u8, 1u16), (1, 2)])
+        .write_be(&mut output)
+        .unwrap();
+    assert_eq!(output.into_inner(), b"\x01\0\x02\x02\0\x01");
+    compare!(BTreeSet::from([2u8, 1, 3]), Endian::Big, b"\x01\x02\x03");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hash_maps_and_sets() {
+    use std::collections::{HashMap, HashSet};
+
+    // Hash collections are written in sorted order
+    let map = HashMap::from([(2u8, 1u16), (1, 2)]);
+    let mut output = binrw::io::Cursor::new(vec![]);
+    map.write_le(&mut output).unwrap();
+    assert_eq!(output.into_inner(), b"\x01\x02\0\x02\x01\0");
+    assert_eq!(map.size_hint(), (6, Some(6)));
+
+    compare!(HashSet::from([2u8, 1, 3]), Endian::Little, b"\x01\x02\x03");
+}
+
+#[test]
+fn sequences() {
+    use std::collections::{BinaryHeap, LinkedList, VecDeque};
+
+    let mut deque = VecDeque::from([2u16, 3]);
+    deque.push_front(1);
+    compare!(deque, Endian::Big, b"\0\x01\0\x02\0\x03");
+    assert_eq!(deque.size_hint(), (6, Some(6)));
+    compare!(LinkedList::from([3u8, 1, 2]), b"\x03\x01\x02");
+
+    // Heaps are written in ascending order
+    compare!(BinaryHeap::from([2u8, 3, 1]), b"\x01\x02\x03");
+    compare!(
+        BinaryHeap::from([1u16, 3, 2]),
+        Endian::Little,
+        b"\x01\0\x02\0\x03\0"
+    );
+}
@@ -0,0 +1,44 @@
+use binrw::{args, io::Cursor, BinRead, BinWrite, BitArray, BitOrder, PackedBits};
+
+#[test]
+fn packed_bits_round_trip() {
+    for (order, data) in [(BitOrder::Msb0, b"\xc1\x80"), (BitOrder::Lsb0, b"\x83\x01")] {
+        let expected = [true, true, false, false, false, false, false, true, true];
+        let bits =
+            PackedBits::read_le_args(&mut Cursor::new(data), args! { count: 9, order }).unwrap();
+        assert_eq!(*bits, expected);
+
+        let mut out = Cursor::new(Vec::new());
+        bits.write_le_args(&mut out, order).unwrap();
+        assert_eq!(out.into_inner(), data);
+    }
+}
+
+#[test]
+fn packed_bits_ignores_unused_bits() {
+    let bits = PackedBits::read_le_args(&mut Cursor::new(b"\xff\xff"), args! { count: 3 }).unwrap();
+    assert_eq!(*bits, [true, true, true]);
+
+    let mut out = Cursor::new(Vec::new());
+    bits.write_le(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\xe0");
+}
+
+#[test]
+fn packed_bits_eof() {
+    PackedBits::read_le_args(&mut Cursor::new(b"\xff"), args! { count: 9 }).unwrap_err();
+    let bits = PackedBits::read_le_args(&mut Cursor::new(b""), args! { count: 0 }).unwrap();
+    assert!(bits.is_empty());
+}
+
+#[test]
+fn bit_array() {
+    let bits = BitArray::<16>::read_be_args(&mut Cursor::new(b"\x01\x80"), BitOrder::Lsb0).unwrap();
+    assert!(bits[0] && bits[15]);
+    assert_eq!(bits.iter().filter(|bit| **bit).count(), 2);
+    assert_eq!(bits.size_hint(), (2, Some(2)));
+
+    let mut out = Cursor::new(Vec::new());
+    BitArray([true, false, true]).write_le(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\xa0");
+}
@@ -0,0 +1,117 @@
+use binrw::{
+    codegen,
+    io::{Cursor, Read, Seek, Write},
+    BinRead, BinResult, BinWrite, Endian,
+};
+
+// What a third-party derive macro might generate for:
+//
+// #[magic = b"HD"]
+// struct Header {
+//     #[is_big = flags & 1 != 0]
+//     flags: u8,
+//     #[assert(value != 0)]
+//     value: u16,
+// }
+#[derive(Debug, PartialEq)]
+struct Header {
+    flags: u8,
+    value: u16,
+}
+
+impl BinRead for Header {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        codegen::rewind_on_error(reader, |reader, pos| {
+            codegen::read_magic(reader, *b"HD", endian)?;
+            let flags = codegen::field_context(reader, "Header", "flags", |reader| {
+                u8::read_options(reader, endian, ())
+            })?;
+            let endian = codegen::conditional_endian(flags & 1 != 0, Endian::Big);
+            let value = codegen::field_context(reader, "Header", "value", |reader| {
+                u16::read_options(reader, endian, ())
+            })?;
+            codegen::assert(value != 0, pos, || "value != 0".into())?;
+            Ok(Self { flags, value })
+        })
+    }
+}
+
+impl BinWrite for Header {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        _: Self::Args<'_>,
+    ) -> BinResult<()> {
+        codegen::write_magic(writer, b"HD", endian)?;
+        self.flags.write_options(writer, endian, ())?;
+        let endian = codegen::conditional_endian(self.flags & 1 != 0, Endian::Big);
+        self.value.write_options(writer, endian, ())
+    }
+}
+
+#[test]
+fn round_trip() {
+    for (data, expected) in [
+        (
+            &b"HD\x01\x01\x02"[..],
+            Header {
+                flags: 1,
+                value: 0x102,
+            },
+        ),
+        (
+            b"HD\x00\x01\x02",
+            Header {
+                flags: 0,
+                value: 0x201,
+            },
+        ),
+    ] {
+        let value = Header::read_le(&mut Cursor::new(data)).unwrap();
+        assert_eq!(value, expected);
+
+        let mut out = Cursor::new(Vec::new());
+        value.write_le(&mut out).unwrap();
+        assert_eq!(out.into_inner(), data);
+    }
+}
+
+#[test]
+fn errors() {
+    let mut reader = Cursor::new(b"\0\0HX\x01\x01\x02");
+    reader.set_position(2);
+    let error = Header::read_le(&mut reader).unwrap_err();
+    assert!(matches!(error, binrw::Error::BadMagic { pos: 2, .. }));
+    assert_eq!(reader.position(), 2);
+
+    let mut reader = Cursor::new(b"\0HD\x01\0\0");
+    reader.set_position(1);
+    let error = Header::read_le(&mut reader).unwrap_err();
+    assert!(matches!(
+        error,
+        binrw::Error::AssertFail { pos: 1, ref message } if message == "value != 0"
+    ));
+    assert_eq!(reader.position(), 1);
+}
+
+#[test]
+#[cfg(not(feature = "compact-errors"))]
+fn field_frame() {
+    let error = Header::read_le(&mut Cursor::new(b"HD\x01\x01")).unwrap_err();
+    assert!(error.root_cause().is_eof());
+    assert!(
+        error
+            .to_string()
+            .contains("While parsing field 'value' in Header"),
+        "{error}"
+    );
+}
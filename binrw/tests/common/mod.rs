@@ -0,0 +1,34 @@
+//! Helpers shared by the integration tests.
+
+// Each test crate only uses some of the helpers
+#![allow(dead_code)]
+
+use binrw::{io::Cursor, BinRead, BinWrite, Endian};
+
+/// Asserts that `data` reads as `expected`, and that writing the value back
+/// produces `data` again.
+pub fn round_trip<T>(data: &[u8], endian: Endian, expected: T)
+where
+    T: for<'a> BinRead<Args<'a> = ()> + for<'a> BinWrite<Args<'a> = ()>,
+    T: PartialEq + core::fmt::Debug,
+{
+    round_trip_args(data, endian, (), (), expected);
+}
+
+/// Like [`round_trip`], for types which take arguments.
+pub fn round_trip_args<'a, T>(
+    data: &[u8],
+    endian: Endian,
+    read_args: <T as BinRead>::Args<'a>,
+    write_args: <T as BinWrite>::Args<'a>,
+    expected: T,
+) where
+    T: BinRead + BinWrite + PartialEq + core::fmt::Debug,
+{
+    let value = T::read_options(&mut Cursor::new(data), endian, read_args).unwrap();
+    assert_eq!(value, expected);
+
+    let mut out = Cursor::new(Vec::new());
+    value.write_options(&mut out, endian, write_args).unwrap();
+    assert_eq!(out.into_inner(), data);
+}
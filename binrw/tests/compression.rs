@@ -0,0 +1,90 @@
+use binrw::{
+    args,
+    io::{self, Cursor},
+    BinRead, BinWrite, Codec, Compressed, CompressionContext,
+};
+
+/// A codec which stores each byte as a pair of itself and its index in the
+/// dictionary, or `0xff` if it is not in the dictionary.
+struct Indexed;
+
+impl Codec for Indexed {
+    fn compress(&self, data: &[u8], dictionary: Option<&[u8]>) -> io::Result<Vec<u8>> {
+        let dictionary = dictionary.unwrap_or_default();
+        Ok(data
+            .iter()
+            .map(|byte| {
+                dictionary
+                    .iter()
+                    .position(|entry| entry == byte)
+                    .map_or(*byte, |index| index as u8 | 0x80)
+            })
+            .collect())
+    }
+
+    fn decompress(&self, data: &[u8], dictionary: Option<&[u8]>) -> io::Result<Vec<u8>> {
+        let dictionary = dictionary.unwrap_or_default();
+        data.iter()
+            .map(|byte| match byte {
+                0x80.. => dictionary
+                    .get(usize::from(byte & 0x7f))
+                    .copied()
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "bad index")),
+                _ => Ok(*byte),
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn compressed_dictionary() {
+    let dictionary = b"\xaa\xbb";
+    let context = CompressionContext::new(&Indexed).with_dictionary(dictionary);
+
+    let data = b"\x81\x01\x80\x02";
+    let value = Compressed::<Vec<u8>>::read_le_args(
+        &mut Cursor::new(data),
+        args! { context, size: 4, inner: args! { count: 4 } },
+    )
+    .unwrap();
+    assert_eq!(*value, [0xbb, 1, 0xaa, 2]);
+
+    let mut out = Cursor::new(Vec::new());
+    value.write_le_args(&mut out, args! { context }).unwrap();
+    assert_eq!(out.into_inner(), data);
+
+    // Without the dictionary, the same value compresses differently
+    let mut out = Cursor::new(Vec::new());
+    Compressed(0xaa01_u16)
+        .write_be_args(
+            &mut out,
+            args! { context: CompressionContext::new(&Indexed) },
+        )
+        .unwrap();
+    assert_eq!(out.into_inner(), b"\xaa\x01");
+}
+
+#[test]
+fn compressed_errors() {
+    let context = CompressionContext::new(&Indexed);
+
+    // Not enough compressed data
+    let error =
+        Compressed::<u8>::read_le_args(&mut Cursor::new(b"\x01"), args! { context, size: 2 })
+            .expect_err("accepted bad data");
+    assert!(matches!(error, binrw::Error::Io(_)));
+
+    // The codec rejects the data
+    let error =
+        Compressed::<u8>::read_le_args(&mut Cursor::new(b"\x80"), args! { context, size: 1 })
+            .expect_err("accepted bad data");
+    assert!(
+        matches!(error, binrw::Error::Io(ref error) if error.kind() == io::ErrorKind::InvalidData)
+    );
+
+    // Not enough decompressed data
+    let error =
+        Compressed::<u16>::read_le_args(&mut Cursor::new(b"\x01\x02"), args! { context, size: 1 })
+            .expect_err("accepted bad data");
+    assert!(matches!(error, binrw::Error::Io(_)));
+}
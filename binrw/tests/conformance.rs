@@ -0,0 +1,52 @@
+use binrw::{binrw, Endian, Error, NullString, VecArgs};
+
+#[binrw]
+#[derive(Debug, PartialEq)]
+#[brw(big, magic = b"PT")]
+struct Point {
+    x: i16,
+    y: i16,
+}
+
+binrw::conformance! {
+    Point, Endian::Big;
+    round_trip origin: b"PT\0\0\0\0" => Point { x: 0, y: 0 },
+    round_trip negative: b"PT\xff\xff\0\x01" => Point { x: -1, y: 1 },
+    read read_point: b"PT\0\x02\0\x03" => Point { x: 2, y: 3 },
+    write write_point: Point { x: 2, y: 3 } => b"PT\0\x02\0\x03",
+    error bad_magic: b"XX\0\0\0\0" => Error::BadMagic { pos: 0, .. },
+    error truncated: b"PT\0" => Error::Io(_)
+}
+
+mod with_args {
+    use super::*;
+
+    binrw::conformance! {
+        Vec<u16>, Endian::Little, read_args = VecArgs { count: 2, inner: () };
+        round_trip pair: b"\x01\0\x02\0" => vec![1, 2],
+        error short: b"\x01\0" => Error::Io(_),
+    }
+}
+
+binrw::conformance! {
+    NullString, Endian::Little;
+    round_trip null_string: b"abc\0" => NullString::from("abc"),
+}
+
+#[test]
+#[should_panic(expected = "only 1 of 2 bytes were read")]
+fn leftover_bytes() {
+    binrw::conformance::assert_read::<u8>(b"\x01\x02", Endian::Little, (), &1);
+}
+
+#[test]
+#[should_panic(expected = "wrong bytes written")]
+fn wrong_bytes() {
+    binrw::conformance::assert_write::<u16>(&1, Endian::Big, (), b"\x01\0");
+}
+
+#[test]
+#[should_panic(expected = "expected an error")]
+fn unexpected_success() {
+    binrw::conformance::read_error::<u8>(b"\x01", Endian::Little, ());
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "std")]
+
+use binrw::{binread, binrw, context, io::Cursor, BinRead, BinWrite};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Version(u8);
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, PartialEq)]
+struct Inner {
+    #[br(if(context::get::<Version>() == Some(Version(2))))]
+    #[bw(if(context::get::<Version>() == Some(Version(2))))]
+    extra: Option<u8>,
+    value: u8,
+}
+
+#[binrw]
+#[brw(little)]
+#[derive(Debug, PartialEq)]
+struct Outer {
+    inner: Inner,
+}
+
+#[test]
+fn context_read_write() {
+    let value = Outer {
+        inner: Inner {
+            extra: None,
+            value: 1,
+        },
+    };
+    assert_eq!(Outer::read(&mut Cursor::new(b"\x01\x02")).unwrap(), value);
+
+    let value = Outer {
+        inner: Inner {
+            extra: Some(1),
+            value: 2,
+        },
+    };
+    let read = context::with(Version(2), || Outer::read(&mut Cursor::new(b"\x01\x02")));
+    assert_eq!(read.unwrap(), value);
+
+    let mut output = Cursor::new(Vec::new());
+    context::with(Version(2), || value.write(&mut output)).unwrap();
+    assert_eq!(output.into_inner(), b"\x01\x02");
+}
+
+#[test]
+fn context_directive() {
+    #[binread]
+    #[br(little)]
+    #[derive(Debug, PartialEq)]
+    struct Entry {
+        #[br(temp, context)]
+        version: Version,
+        #[br(if(version.0 >= 2))]
+        extra: Option<u8>,
+        value: u8,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    struct Wrapper(Entry);
+
+    let read = context::with(Version(2), || {
+        Wrapper::read_le(&mut Cursor::new(b"\x01\x02"))
+    });
+    assert_eq!(
+        read.unwrap(),
+        Wrapper(Entry {
+            extra: Some(1),
+            value: 2,
+        })
+    );
+
+    let read = context::with(Version(1), || {
+        Wrapper::read_le(&mut Cursor::new(b"\x01\x02"))
+    });
+    assert_eq!(
+        read.unwrap(),
+        Wrapper(Entry {
+            extra: None,
+            value: 1,
+        })
+    );
+
+    let mut data = Cursor::new(b"\x01\x02");
+    data.set_position(1);
+    let error = Wrapper::read_le(&mut data).unwrap_err();
+    assert!(
+        matches!(
+            error.root_cause(),
+            binrw::Error::AssertFail { pos: 1, message } if message.contains("Version")
+        ),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn context_nesting() {
+    assert_eq!(context::get::<Version>(), None);
+    context::with(Version(1), || {
+        context::with(7u32, || {
+            assert_eq!(context::get::<Version>(), Some(Version(1)));
+            context::with(Version(2), || {
+                assert_eq!(context::get::<Version>(), Some(Version(2)));
+                assert_eq!(context::get::<u32>(), Some(7));
+            });
+            assert_eq!(context::get::<Version>(), Some(Version(1)));
+        });
+        assert_eq!(context::get::<u32>(), None);
+    });
+    assert_eq!(context::get::<Version>(), None);
+}
+
+#[test]
+fn context_panic() {
+    let result = std::panic::catch_unwind(|| context::with(Version(1), || panic!("oops")));
+    assert!(result.is_err());
+    assert_eq!(context::get::<Version>(), None);
+}
+
+#[test]
+fn context_reentrant_clone() {
+    #[derive(Debug, PartialEq)]
+    struct Table(u8);
+
+    impl Clone for Table {
+        fn clone(&self) -> Self {
+            context::with(Version(self.0), || {
+                Table(context::get::<Version>().unwrap().0)
+            })
+        }
+    }
+
+    context::with(Table(3), || {
+        assert_eq!(context::get::<Table>(), Some(Table(3)));
+    });
+}
@@ -1,7 +1,7 @@
 #[cfg(feature = "std")]
 #[test]
 fn dbg() {
-    use binrw::{io::Cursor, BinRead};
+    use binrw::{binwrite, io::Cursor, BinRead, BinWrite};
 
     #[derive(BinRead, Debug)]
     struct Inner(u32);
@@ -13,19 +13,33 @@ fn dbg() {
         before: u16,
         #[br(dbg, pad_before = 2)]
         value: u32,
-        #[br(dbg)]
+        #[br(dbg(range))]
         inner: Inner,
     }
 
+    #[binwrite]
+    #[bw(big)]
+    struct Output {
+        #[bw(dbg, calc = data.len() as u16)]
+        len: u16,
+        #[bw(dbg(range), pad_before = 1)]
+        data: Vec<u8>,
+    }
+
     // 🥴
-    if let Some("1") = option_env!("BINRW_IN_CHILD_PROC") {
+    if std::env::var_os("BINRW_IN_CHILD_PROC").is_some() {
         Test::read(&mut Cursor::new(b"\0\0\xff\xff\0\0\0\x04\0\x0e\xff\xed")).unwrap();
+        Output { data: vec![1, 2] }
+            .write(&mut Cursor::new(Vec::new()))
+            .unwrap();
     } else {
         use std::process::{Command, Stdio};
 
-        let result = Command::new(env!("CARGO"))
+        // Running the test binary directly keeps compiler output from cargo
+        // out of the captured stderr
+        let result = Command::new(std::env::current_exe().unwrap())
             .env("BINRW_IN_CHILD_PROC", "1")
-            .args(["test", "-q", "--test", "dbg", "--", "--nocapture"])
+            .args(["--exact", "dbg", "--nocapture"])
             .stdin(Stdio::null())
             .stdout(Stdio::null())
             .output()
@@ -37,13 +51,20 @@ fn dbg() {
             format!(
                 concat!(
                     "[{file}:{offset_0} | offset 0x4] value = 0x4\n",
-                    "[{file}:{offset_1} | offset 0x8] inner = Inner(\n",
+                    "[{file}:{offset_1} | offset 0x8..0xc] inner = Inner(\n",
                     "    0xeffed,\n",
-                    ")\n"
+                    ")\n",
+                    "[{file}:{offset_2} | offset 0x0] len = 0x2\n",
+                    "[{file}:{offset_3} | offset 0x3..0x5] data = [\n",
+                    "    0x1,\n",
+                    "    0x2,\n",
+                    "]\n",
                 ),
                 file = core::file!(),
                 offset_0 = if cfg!(nightly) { 15 } else { 10 },
                 offset_1 = if cfg!(nightly) { 17 } else { 10 },
+                offset_2 = if cfg!(nightly) { 24 } else { 20 },
+                offset_3 = if cfg!(nightly) { 26 } else { 20 },
             )
         );
     }
@@ -0,0 +1,132 @@
+use binrw::{args, binread, io::Cursor, BinRead, BinWrite};
+
+struct StringTable(Vec<&'static str>);
+
+#[derive(Clone, Copy)]
+struct Context<'a> {
+    strings: &'a StringTable,
+}
+
+#[derive(BinRead, BinWrite, Debug, PartialEq)]
+#[br(import(strings: &'a StringTable))]
+#[bw(import(strings: &'a StringTable))]
+struct Name {
+    #[br(map = |index: u8| strings.0[usize::from(index)])]
+    #[bw(map = |name: &&str| strings.0.iter().position(|s| s == name).unwrap() as u8)]
+    name: &'static str,
+}
+
+#[test]
+fn borrowed_tuple_args() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(import(context: Context<'a>))]
+    struct Test {
+        #[brw(args(context.strings))]
+        first: Name,
+        #[br(count = 2, args { inner: (context.strings,) })]
+        #[bw(args(context.strings))]
+        rest: Vec<Name>,
+    }
+
+    let strings = StringTable(vec!["zero", "one", "two"]);
+    let context = Context { strings: &strings };
+    let test = Test::read_le_args(&mut Cursor::new(b"\x01\x02\x00"), (context,)).unwrap();
+    assert_eq!(test.first.name, "one");
+    assert_eq!(test.rest[0].name, "two");
+    assert_eq!(test.rest[1].name, "zero");
+
+    let mut data = Cursor::new(Vec::new());
+    test.write_le_args(&mut data, (context,)).unwrap();
+    assert_eq!(data.into_inner(), b"\x01\x02\x00");
+}
+
+#[test]
+fn borrowed_named_args() {
+    #[derive(BinRead, Debug)]
+    #[br(import { strings: &'a StringTable, fallback: Option<&str> })]
+    struct Test {
+        #[br(args(strings))]
+        name: Name,
+        #[br(calc = fallback.unwrap_or(name.name).len())]
+        len: usize,
+    }
+
+    let strings = StringTable(vec!["zero", "one"]);
+    let fallback = String::from("three");
+    let test = Test::read_le_args(
+        &mut Cursor::new(b"\x01"),
+        args! { strings: &strings, fallback: Some(&fallback) },
+    )
+    .unwrap();
+    assert_eq!(test.name.name, "one");
+    assert_eq!(test.len, 5);
+}
+
+#[test]
+fn borrowed_raw_args() {
+    #[derive(BinRead, Debug)]
+    #[br(import_raw(context: Context<'_>))]
+    struct Test {
+        #[br(args(context.strings))]
+        name: Name,
+    }
+
+    let strings = StringTable(vec!["zero", "one"]);
+    let test =
+        Test::read_le_args(&mut Cursor::new(b"\x00"), Context { strings: &strings }).unwrap();
+    assert_eq!(test.name.name, "zero");
+}
+
+#[test]
+fn zero_copy_borrow() {
+    use binrw::helpers::{args_iter, borrow_bytes, borrow_str};
+    use core::iter;
+
+    #[binread]
+    #[derive(Debug)]
+    #[br(import(source: &'a [u8]))]
+    struct Entry<'a> {
+        #[br(temp)]
+        name_len: u8,
+        #[br(parse_with = borrow_str(source, name_len.into()))]
+        name: &'a str,
+        #[br(temp)]
+        data_len: u8,
+        #[br(parse_with = borrow_bytes(source, data_len.into()))]
+        data: &'a [u8],
+    }
+
+    #[binread]
+    #[derive(Debug)]
+    #[br(big, import(source: &'a [u8]))]
+    struct Archive<'a> {
+        #[br(temp)]
+        count: u8,
+        #[br(parse_with = args_iter(iter::repeat_n((source,), count.into())))]
+        entries: Vec<Entry<'a>>,
+        #[br(args(source))]
+        last: Entry<'a>,
+    }
+
+    let source = b"\x02\x01a\x02\x01\x02\x02bc\x00\x01z\x01\xff".as_slice();
+    let archive = Archive::read_args(&mut Cursor::new(source), (source,)).unwrap();
+    assert_eq!(archive.entries.len(), 2);
+    assert_eq!(archive.entries[0].name, "a");
+    assert_eq!(archive.entries[0].data, [1, 2]);
+    assert_eq!(archive.entries[1].name, "bc");
+    assert_eq!(archive.entries[1].data, []);
+    assert_eq!(archive.last.name, "z");
+    assert_eq!(archive.last.data, [0xff]);
+    assert!(core::ptr::eq(
+        archive.last.data.as_ptr(),
+        &source[source.len() - 1]
+    ));
+
+    Archive::read_args(&mut Cursor::new(&source[..5]), (&source[..5],))
+        .expect_err("borrowed past the end of the source");
+    Archive::read_args(
+        &mut Cursor::new(b"\x00\x01\xff\x00"),
+        (b"\x00\x01\xff\x00".as_slice(),),
+    )
+    .expect_err("borrowed invalid UTF-8");
+}
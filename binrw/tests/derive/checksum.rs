@@ -0,0 +1,202 @@
+use binrw::{binread, binrw, io::Cursor, BinRead, BinWrite};
+
+fn sum(data: &[u8]) -> u8 {
+    data.iter().fold(0, |acc, b| acc.wrapping_add(*b))
+}
+
+fn xor(data: &[u8]) -> u8 {
+    data.iter().fold(0, |acc, b| acc ^ b)
+}
+
+#[test]
+fn verify_default_range() {
+    #[derive(BinRead, Debug)]
+    #[br(big, magic = b"CK")]
+    struct Test {
+        a: u16,
+        b: u8,
+        #[br(verify(sum))]
+        checksum: u8,
+    }
+
+    let test = Test::read(&mut Cursor::new(b"CK\x01\x02\x03\x94")).unwrap();
+    assert_eq!(test.a, 0x102);
+    assert_eq!(test.b, 3);
+    assert_eq!(test.checksum, 0x94);
+
+    let error =
+        Test::read(&mut Cursor::new(b"CK\x01\x02\x03\x95")).expect_err("accepted bad checksum");
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::AssertFail { pos: 5, .. }
+    ));
+    assert!(format!("{}", error.root_cause()).contains("checksum mismatch over bytes 0x0..0x5"));
+}
+
+#[test]
+fn verify_explicit_range() {
+    #[binread]
+    #[derive(Debug)]
+    #[br(big)]
+    struct Test {
+        #[br(temp)]
+        len: u8,
+        #[br(count = len)]
+        data: Vec<u8>,
+        #[br(verify(xor, 1..1 + u64::from(len)))]
+        checksum: u8,
+        trailer: u8,
+    }
+
+    let test = Test::read(&mut Cursor::new(b"\x03\x01\x02\x04\x07\xff")).unwrap();
+    assert_eq!(test.data, [1, 2, 4]);
+    assert_eq!(test.checksum, 7);
+    assert_eq!(test.trailer, 0xff);
+
+    Test::read(&mut Cursor::new(b"\x03\x01\x02\x04\x06\xff")).expect_err("accepted bad checksum");
+}
+
+#[test]
+fn verify_range_past_end() {
+    #[binread]
+    #[derive(Debug)]
+    struct Test {
+        #[br(temp, verify(sum, 0..8))]
+        checksum: u8,
+    }
+
+    let error = Test::read_le(&mut Cursor::new(b"\x00")).expect_err("accepted short stream");
+    assert!(matches!(error, binrw::Error::Io(..)));
+}
+
+#[test]
+fn checksum_write() {
+    #[derive(BinWrite)]
+    #[bw(big, magic = b"CK")]
+    struct Test {
+        a: u16,
+        #[bw(map = |b: &u8| b.wrapping_add(1))]
+        b: u8,
+        #[bw(checksum(sum))]
+        checksum: u8,
+        #[bw(checksum(xor, 2..4))]
+        parity: u8,
+    }
+
+    let mut data = Cursor::new(Vec::new());
+    Test {
+        a: 0x102,
+        b: 2,
+        checksum: 0,
+        parity: 0,
+    }
+    .write(&mut data)
+    .unwrap();
+    assert_eq!(data.into_inner(), b"CK\x01\x02\x03\x94\x03");
+}
+
+#[test]
+fn checksum_range_not_written() {
+    #[derive(BinWrite)]
+    struct Test {
+        #[bw(checksum(sum, 0..4))]
+        checksum: u8,
+    }
+
+    Test { checksum: 0 }
+        .write_le(&mut Cursor::new(Vec::new()))
+        .expect_err("checksummed unwritten data");
+}
+
+#[test]
+fn checksum_round_trip() {
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(little)]
+    enum Test {
+        #[brw(magic = 1u8)]
+        Message {
+            #[bw(calc = text.len() as u8)]
+            len: u8,
+            #[br(count = len)]
+            text: Vec<u8>,
+            #[br(verify(sum))]
+            #[bw(checksum(sum))]
+            checksum: u8,
+        },
+    }
+
+    let value = Test::Message {
+        text: b"hi".to_vec(),
+        checksum: 0,
+    };
+    let mut data = Cursor::new(Vec::new());
+    value.write(&mut data).unwrap();
+    let data = data.into_inner();
+    assert_eq!(data, b"\x01\x02hi\xd4");
+
+    let read = Test::read(&mut Cursor::new(&data)).unwrap();
+    assert_eq!(
+        read,
+        Test::Message {
+            text: b"hi".to_vec(),
+            checksum: 0xd4,
+        }
+    );
+}
+
+#[test]
+fn crc_round_trip() {
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big, magic = b"PNG")]
+    struct Chunk {
+        #[bw(calc = data.len() as u32)]
+        len: u32,
+        #[br(count = len)]
+        data: Vec<u8>,
+        #[brw(crc(algorithm = "crc32", over = 7..7 + u64::from(len)))]
+        crc: u32,
+    }
+
+    let chunk = Chunk {
+        data: b"123456789".to_vec(),
+        crc: 0,
+    };
+    let mut data = Cursor::new(Vec::new());
+    chunk.write(&mut data).unwrap();
+    let data = data.into_inner();
+    assert_eq!(data, b"PNG\0\0\0\x09123456789\xcb\xf4\x39\x26".as_slice());
+
+    let chunk = Chunk::read(&mut Cursor::new(&data)).unwrap();
+    assert_eq!(chunk.crc, 0xcbf4_3926);
+
+    let mut corrupt = data.clone();
+    corrupt[7] ^= 1;
+    let error = Chunk::read(&mut Cursor::new(&corrupt)).expect_err("accepted bad crc");
+    assert!(format!("{}", error.root_cause()).contains("checksum mismatch over bytes 0x7..0x10"));
+}
+
+#[test]
+fn crc_default_range() {
+    #[derive(BinRead, BinWrite, Debug)]
+    #[brw(little)]
+    struct Test {
+        data: [u8; 9],
+        #[brw(crc(algorithm = "crc16"))]
+        crc: u16,
+    }
+
+    let test = Test::read(&mut Cursor::new(b"123456789\x3d\xbb")).unwrap();
+    assert_eq!(test.data, *b"123456789");
+    assert_eq!(test.crc, 0xbb3d);
+
+    let mut data = Cursor::new(Vec::new());
+    Test {
+        data: *b"123456789",
+        crc: 0,
+    }
+    .write(&mut data)
+    .unwrap();
+    assert_eq!(data.into_inner(), b"123456789\x3d\xbb");
+}
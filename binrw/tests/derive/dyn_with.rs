@@ -0,0 +1,72 @@
+use binrw::{binrw, io::Cursor, BinRead, BinResult, BinWrite, DynBinWrite};
+
+trait Shape: DynBinWrite {
+    fn area(&self) -> u32;
+}
+
+#[binrw]
+struct Square(u8);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        u32::from(self.0) * u32::from(self.0)
+    }
+}
+
+#[binrw]
+struct Rect(u8, u8);
+
+impl Shape for Rect {
+    fn area(&self) -> u32 {
+        u32::from(self.0) * u32::from(self.1)
+    }
+}
+
+#[binrw::parser(reader, endian)]
+fn read_shape(kind: u8) -> BinResult<Box<dyn Shape>> {
+    Ok(match kind {
+        0 => Box::new(Square::read_options(reader, endian, ())?),
+        _ => Box::new(Rect::read_options(reader, endian, ())?),
+    })
+}
+
+#[binrw]
+#[brw(little)]
+struct Shapes {
+    kind: u8,
+    #[br(dyn_with = read_shape, args(kind))]
+    #[bw(dyn_with)]
+    shape: Box<dyn Shape>,
+    #[br(dyn_with = read_shape, args(1))]
+    #[bw(dyn_with)]
+    rect: Box<dyn Shape>,
+}
+
+#[test]
+fn dyn_with_round_trip() {
+    let mut data = Cursor::new(b"\0\x03\x02\x05");
+    let result = Shapes::read(&mut data).unwrap();
+    assert_eq!(result.kind, 0);
+    assert_eq!(result.shape.area(), 9);
+    assert_eq!(result.rect.area(), 10);
+
+    let mut written = Cursor::new(Vec::new());
+    result.write(&mut written).unwrap();
+    assert_eq!(written.into_inner(), data.into_inner());
+}
+
+#[test]
+fn dyn_with_map() {
+    #[binrw]
+    #[brw(big)]
+    struct Test {
+        #[br(dyn_with = |_, _, _: ()| Ok(Box::new(0x1234_u16) as Box<dyn DynBinWrite>))]
+        #[bw(dyn_with, map = |_| Box::new(0x5678_u16) as Box<dyn DynBinWrite>)]
+        value: Box<dyn DynBinWrite>,
+    }
+
+    let result = Test::read(&mut Cursor::new(b"")).unwrap();
+    let mut written = Cursor::new(Vec::new());
+    result.write(&mut written).unwrap();
+    assert_eq!(written.into_inner(), b"\x56\x78");
+}
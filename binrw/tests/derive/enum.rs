@@ -47,6 +47,43 @@ fn enum_non_copy_args() {
     struct NonCopyArg(u8);
 }
 
+#[test]
+fn enum_catch_all() {
+    use binrw::BinWrite;
+
+    #[binrw::binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big, repr = u16)]
+    enum Message {
+        #[brw(magic = 1)]
+        Ping { seq: u16 },
+        #[br(catch_all)]
+        Unknown { tag: u16, body: Vec<u8> },
+    }
+
+    let result = Message::read(&mut Cursor::new(b"\0\x01\0\x02")).unwrap();
+    assert_eq!(result, Message::Ping { seq: 2 });
+
+    let data = b"\0\x09abc";
+    let result = Message::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(
+        result,
+        Message::Unknown {
+            tag: 9,
+            body: b"abc".to_vec()
+        }
+    );
+
+    // A known tag with bad data is an error, not an unknown message
+    let mut bad_data = Cursor::new(b"\0\x01\0");
+    Message::read(&mut bad_data).expect_err("accepted bad data");
+    assert_eq!(bad_data.position(), 0);
+
+    let mut out = Cursor::new(Vec::new());
+    result.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+}
+
 #[test]
 fn enum_calc_temp_field() {
     #[binread]
@@ -107,6 +144,177 @@ fn enum_endianness() {
     );
 }
 
+#[test]
+fn enum_tag() {
+    use binrw::BinWrite;
+
+    #[binrw::binrw]
+    #[derive(Debug, PartialEq)]
+    #[br(import(kind: u8), tag = kind)]
+    #[bw(little)]
+    enum Chunk {
+        #[br(tag_value = 1)]
+        Header {
+            version: u16,
+        },
+        #[br(tag_value = 2)]
+        Data(u8, u8),
+        Other(u16),
+    }
+
+    let data = b"\x03\0";
+    assert_eq!(
+        Chunk::read_le_args(&mut Cursor::new(data), (1,)).unwrap(),
+        Chunk::Header { version: 3 }
+    );
+    assert_eq!(
+        Chunk::read_le_args(&mut Cursor::new(data), (2,)).unwrap(),
+        Chunk::Data(3, 0)
+    );
+    assert_eq!(
+        Chunk::read_le_args(&mut Cursor::new(data), (9,)).unwrap(),
+        Chunk::Other(3)
+    );
+
+    // A variant with a different tag is never tried
+    let error =
+        Chunk::read_le_args(&mut Cursor::new(b"\x03"), (1,)).expect_err("accepted bad data");
+    match error {
+        binrw::Error::EnumErrors { variant_errors, .. } => {
+            let names = variant_errors.iter().map(|(name, _)| *name);
+            assert_eq!(names.collect::<Vec<_>>(), ["Header", "Other"]);
+        }
+        _ => panic!("wrong error type"),
+    }
+
+    let mut out = Cursor::new(Vec::new());
+    Chunk::Header { version: 3 }.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+}
+
+#[test]
+fn enum_tag_into() {
+    use binrw::BinWrite;
+
+    #[binrw::binrw]
+    #[derive(Debug, PartialEq)]
+    #[br(import(kind: u8), tag = kind)]
+    #[bw(little)]
+    enum Chunk {
+        #[br(tag_value = 1)]
+        Header { version: u16 },
+        #[br(tag_into = kind)]
+        Other {
+            #[bw(ignore)]
+            kind: u32,
+            value: u8,
+        },
+    }
+
+    assert_eq!(
+        Chunk::read_le_args(&mut Cursor::new(b"\x03"), (7,)).unwrap(),
+        Chunk::Other { kind: 7, value: 3 }
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    Chunk::Other { kind: 7, value: 3 }.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\x03");
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    enum Command {
+        #[br(magic = 0x10u8, tag_into = self_0)]
+        Short(u8, u8),
+        #[br(magic = b"LG", tag_into = self_0)]
+        Long([u8; 2], u16),
+    }
+
+    assert_eq!(
+        Command::read(&mut Cursor::new(b"\x10\x05")).unwrap(),
+        Command::Short(0x10, 5)
+    );
+    assert_eq!(
+        Command::read(&mut Cursor::new(b"LG\x01\x02")).unwrap(),
+        Command::Long(*b"LG", 0x102)
+    );
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little, repr = u16)]
+    enum Op {
+        #[br(magic = 1 | 3, tag_into = self_0)]
+        Odd(u16, u8),
+        #[br(magic = 2)]
+        Even(u8),
+    }
+
+    assert_eq!(
+        Op::read(&mut Cursor::new(b"\x03\0\x05")).unwrap(),
+        Op::Odd(3, 5)
+    );
+    assert_eq!(
+        Op::read(&mut Cursor::new(b"\x01\0\x05")).unwrap(),
+        Op::Odd(1, 5)
+    );
+    assert_eq!(
+        Op::read(&mut Cursor::new(b"\x02\0\x05")).unwrap(),
+        Op::Even(5)
+    );
+
+    #[derive(BinRead, Debug, PartialEq)]
+    enum Masked {
+        #[br(magic(0x10u8, mask = 0xf0), tag_into = version)]
+        V1 { version: u8, value: u8 },
+        #[br(magic(0x20u8, mask = 0xf0))]
+        V2,
+    }
+
+    assert_eq!(
+        Masked::read_le(&mut Cursor::new(b"\x13\x05")).unwrap(),
+        Masked::V1 {
+            version: 0x13,
+            value: 5
+        }
+    );
+    assert_eq!(
+        Masked::read_le(&mut Cursor::new(b"\x2f")).unwrap(),
+        Masked::V2
+    );
+}
+
+#[test]
+fn enum_tag_endian() {
+    use binrw::BinWrite;
+
+    #[binrw::binrw]
+    #[derive(Debug, Eq, PartialEq)]
+    #[brw(little, tag_endian = big)]
+    enum Test {
+        #[brw(magic(1u16))]
+        One { a: u16 },
+        #[brw(magic(2u16))]
+        Two,
+        #[brw(tag_endian = little, magic(3u16))]
+        Three { b: u8 },
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\0\x01\x02\0")).unwrap(),
+        Test::One { a: 2 }
+    );
+    assert_eq!(Test::read(&mut Cursor::new(b"\0\x02")).unwrap(), Test::Two);
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x03\0\x04")).unwrap(),
+        Test::Three { b: 4 }
+    );
+    Test::read(&mut Cursor::new(b"\x01\0\x02\0")).expect_err("accepted bad data");
+
+    let mut out = Cursor::new(Vec::new());
+    Test::One { a: 2 }.write(&mut out).unwrap();
+    Test::Two.write(&mut out).unwrap();
+    Test::Three { b: 4 }.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\0\x01\x02\0\0\x02\x03\0\x04");
+}
+
 #[test]
 fn enum_magic() {
     #[derive(BinRead, Debug, PartialEq)]
@@ -124,6 +332,36 @@ fn enum_magic() {
     assert_eq!(result, Test::One { a: 515 });
 }
 
+#[test]
+fn enum_magic_repr() {
+    use binrw::BinWrite;
+
+    #[binrw::binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(little, repr = u32)]
+    enum Test {
+        #[brw(magic = 1)]
+        One {
+            a: u8,
+        },
+        #[brw(magic = 2u32)]
+        Two(u16),
+        Other(u8),
+    }
+
+    let result = Test::read(&mut Cursor::new(b"\x01\0\0\0\x05")).unwrap();
+    assert_eq!(result, Test::One { a: 5 });
+    let result = Test::read(&mut Cursor::new(b"\x02\0\0\0\x06\0")).unwrap();
+    assert_eq!(result, Test::Two(6));
+    // Only the first byte matches the tag of `One`
+    let result = Test::read(&mut Cursor::new(b"\x01\x01\0\0\x05")).unwrap();
+    assert_eq!(result, Test::Other(1));
+
+    let mut out = Cursor::new(Vec::new());
+    Test::Two(6).write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\x02\0\0\0\x06\0");
+}
+
 #[test]
 fn enum_magic_holey() {
     #[derive(BinRead, Debug, PartialEq)]
@@ -140,6 +378,58 @@ fn enum_magic_holey() {
     assert_eq!(result, Test::Right { a: 0x102 });
 }
 
+#[test]
+fn enum_magic_dispatch() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big, return_all_errors)]
+    enum Test {
+        #[br(magic(b"AB"))]
+        Ab(u8),
+        #[br(magic(b"CD" | b"cd"))]
+        Cd(u16),
+        #[br(magic(b"EF"))]
+        Ef {
+            #[br(assert(a != 0))]
+            a: u8,
+        },
+        #[br(catch_all)]
+        Unknown(Vec<u8>),
+    }
+
+    let result = Test::read(&mut Cursor::new(b"AB\x01")).unwrap();
+    assert_eq!(result, Test::Ab(1));
+    let result = Test::read(&mut Cursor::new(b"cd\x01\x02")).unwrap();
+    assert_eq!(result, Test::Cd(0x102));
+    let result = Test::read(&mut Cursor::new(b"EF\x03")).unwrap();
+    assert_eq!(result, Test::Ef { a: 3 });
+    let result = Test::read(&mut Cursor::new(b"GH")).unwrap();
+    assert_eq!(result, Test::Unknown(b"GH".to_vec()));
+
+    // A matched variant which fails to parse does not fall back to the
+    // catch-all, and every variant is still reported
+    let mut data = Cursor::new(b"EF\0");
+    let error = Test::read(&mut data).expect_err("accepted bad data");
+    assert_eq!(data.position(), 0);
+    match error {
+        binrw::Error::EnumErrors { variant_errors, .. } => {
+            let names = variant_errors
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>();
+            assert_eq!(names, ["Ab", "Cd", "Ef"]);
+            assert!(matches!(
+                variant_errors[1].1,
+                binrw::Error::BadMagic { pos: 0, .. }
+            ));
+            assert!(matches!(
+                variant_errors[2].1.root_cause(),
+                binrw::Error::AssertFail { .. }
+            ));
+        }
+        _ => panic!("wrong error type"),
+    }
+}
+
 #[test]
 fn enum_pre_assert() {
     #[derive(BinRead, Debug, PartialEq)]
@@ -281,3 +571,28 @@ fn mixed_enum() {
     let result = Test::read(&mut Cursor::new(b"\x02\0\x03\0\x04")).unwrap();
     assert_eq!(result, Test::Two { a: 3, b: 4 });
 }
+
+#[test]
+fn enum_map_stream() {
+    use binrw::io::TakeSeekExt;
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(map_stream = |reader| reader.take_seek(3))]
+    enum Test {
+        #[br(magic = 0u8)]
+        Zero(#[br(parse_with = binrw::helpers::until_eof)] Vec<u8>),
+        #[br(magic = 1u8)]
+        One(u8),
+    }
+
+    let mut data = Cursor::new(b"\0abc");
+    assert_eq!(
+        Test::read_le(&mut data).unwrap(),
+        Test::Zero(b"ab".to_vec())
+    );
+    assert_eq!(data.stream_position().unwrap(), 3);
+    assert_eq!(
+        Test::read_le(&mut Cursor::new(b"\x01abc")).unwrap(),
+        Test::One(b'a')
+    );
+}
@@ -25,3 +25,107 @@ fn single_arg() {
     written.write_le(&result).unwrap();
     assert_eq!(written.into_inner(), b"\x42\x00\x00\x00");
 }
+
+#[binrw::parser(reader, args_raw)]
+fn raw_arg_parser(len: usize) -> BinResult<Vec<u8>> {
+    let mut out = vec![0; len];
+    reader.read_exact(&mut out)?;
+    Ok(out)
+}
+
+#[binrw::writer(writer, args_raw)]
+#[allow(clippy::ptr_arg)]
+fn raw_arg_writer(object: &Vec<u8>, len: usize) -> BinResult<()> {
+    writer.write_all(&object[..len])?;
+    Ok(())
+}
+
+#[binrw]
+struct RawArg {
+    len: u8,
+    #[br(parse_with = raw_arg_parser, args_raw = usize::from(len))]
+    #[bw(write_with = raw_arg_writer, args_raw = usize::from(*len))]
+    data: Vec<u8>,
+}
+
+#[test]
+fn raw_arg() {
+    let result: RawArg = Cursor::new(b"\x02\x01\x02\x03").read_le().unwrap();
+    assert_eq!(result.data, [1, 2]);
+    let mut written = Cursor::new(Vec::new());
+    written.write_le(&result).unwrap();
+    assert_eq!(written.into_inner(), b"\x02\x01\x02");
+}
+
+#[test]
+fn parse_with_closure() {
+    use binrw::BinRead;
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        base: u8,
+        #[br(parse_with = |reader, endian, ()| {
+            let value = u8::read_options(reader, endian, ())?;
+            Ok(u16::from(base) + u16::from(value))
+        })]
+        sum: u16,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x01\x02")).unwrap(),
+        Test { base: 1, sum: 3 }
+    );
+}
+
+#[test]
+fn write_with_closure() {
+    use binrw::{BinRead, BinWrite};
+
+    #[binrw]
+    #[brw(big)]
+    struct Test {
+        base: u8,
+        #[br(parse_with = |reader, endian, (scale,): (u8,)| {
+            let value = u8::read_options(reader, endian, ())?;
+            Ok(u16::from(base) + u16::from(value * scale))
+        }, args(2))]
+        #[bw(write_with = |sum: &u16, writer, endian, ()| {
+            let value = u8::try_from((*sum - u16::from(*base)) / 2).unwrap();
+            value.write_options(writer, endian, ())
+        })]
+        sum: u16,
+    }
+
+    let result = Test::read(&mut Cursor::new(b"\x01\x02")).unwrap();
+    assert_eq!(result.sum, 5);
+    let mut written = Cursor::new(Vec::new());
+    result.write(&mut written).unwrap();
+    assert_eq!(written.into_inner(), b"\x01\x02");
+}
+
+#[test]
+fn closure_without_args_param() {
+    use binrw::{BinRead, BinWrite};
+
+    #[binrw]
+    #[brw(big, import(scale: u8))]
+    struct Test {
+        base: u8,
+        #[br(parse_with = |reader, endian| {
+            let value = u8::read_options(reader, endian, ())?;
+            Ok(u16::from(base) + u16::from(value) * u16::from(scale))
+        })]
+        #[bw(write_with = |sum, writer, endian| {
+            let value = u8::try_from((*sum - u16::from(*base)) / u16::from(scale)).unwrap();
+            value.write_options(writer, endian, ())
+        })]
+        sum: u16,
+    }
+
+    let result = Test::read_args(&mut Cursor::new(b"\x01\x02"), (3,)).unwrap();
+    assert_eq!(result.sum, 7);
+    let mut written = Cursor::new(Vec::new());
+    result.write_args(&mut written, (3,)).unwrap();
+    assert_eq!(written.into_inner(), b"\x01\x02");
+}
@@ -0,0 +1,147 @@
+use binrw::{binrw, io::Cursor, BinRead, BinWrite, Error};
+
+macro_rules! record {
+    (
+        $(#[$attr:meta])*
+        struct $name:ident {
+            $($(#[$field_attr:meta])* $field:ident: $ty:ty),* $(,)?
+        }
+    ) => {
+        #[binrw]
+        #[derive(Debug, PartialEq)]
+        $(#[$attr])*
+        struct $name {
+            $($(#[$field_attr])* $field: $ty),*
+        }
+    };
+}
+
+#[test]
+fn macro_rules_pass_through_attrs() {
+    record! {
+        #[brw(little, magic = b"RC", import(has_extra: bool))]
+        #[br(assert(!data.is_empty()))]
+        struct Record {
+            #[br(temp, assert(len < 10, "bad length {}", len))]
+            #[bw(calc = data.len() as u8)]
+            len: u8,
+            #[br(count = len, map = |v: Vec<u8>| v.into_iter().map(u16::from).collect())]
+            #[bw(map = |v: &Vec<u16>| v.iter().map(|x| *x as u8).collect::<Vec<_>>())]
+            data: Vec<u16>,
+            #[brw(if(has_extra), pad_size_to = 2)]
+            extra: Option<u8>,
+            #[brw(restore_position, pad_before = 1)]
+            peek: u8,
+            last: u8,
+        }
+    }
+
+    let data = b"RC\x02\x01\x02\x05\0\0\x06";
+    let record = Record::read_args(&mut Cursor::new(data), (true,)).unwrap();
+    assert_eq!(
+        record,
+        Record {
+            data: vec![1, 2],
+            extra: Some(5),
+            peek: 6,
+            last: 0,
+        }
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    record.write_args(&mut out, (true,)).unwrap();
+    assert_eq!(out.into_inner(), data);
+
+    let error = Record::read_args(&mut Cursor::new(b"RC\x0a"), (false,)).unwrap_err();
+    match error.root_cause() {
+        Error::AssertFail { message, .. } => {
+            assert_eq!(message, "bad length 10");
+        }
+        _ => panic!("bad error type"),
+    }
+}
+
+#[test]
+fn macro_rules_expr_fragments() {
+    macro_rules! sized {
+        (
+            $name:ident,
+            $endian:ident,
+            $magic:expr,
+            $count:expr,
+            $value:ident,
+            $cond:expr,
+            $max:expr,
+            $message:literal
+        ) => {
+            #[binrw]
+            #[derive(Debug, PartialEq)]
+            #[brw($endian, magic = $magic)]
+            struct $name {
+                #[br(count = $count)]
+                data: Vec<u16>,
+                #[br(assert($cond), assert($value < $max, $message))]
+                $value: u8,
+            }
+        };
+    }
+
+    sized!(Sized, big, 0xffu8, 1 + 1, value, value != 0, 10, "too big");
+
+    let data = b"\xff\0\x01\0\x02\x03";
+    let sized = Sized::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(
+        sized,
+        Sized {
+            data: vec![1, 2],
+            value: 3,
+        }
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    sized.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+
+    let error = Sized::read(&mut Cursor::new(b"\xff\0\x01\0\x02\0")).unwrap_err();
+    match error.root_cause() {
+        Error::AssertFail { message, .. } => {
+            #[cfg(not(feature = "compact-errors"))]
+            assert_eq!(
+                message,
+                "assertion failed: `value != 0` (left: `0`, right: `0`)"
+            );
+            #[cfg(feature = "compact-errors")]
+            assert_eq!(message, "assertion failed: `value != 0`");
+        }
+        _ => panic!("bad error type"),
+    }
+
+    let error = Sized::read(&mut Cursor::new(b"\xff\0\x01\0\x02\x0a")).unwrap_err();
+    match error.root_cause() {
+        Error::AssertFail { message, .. } => assert_eq!(message, "too big"),
+        _ => panic!("bad error type"),
+    }
+}
+
+#[test]
+fn macro_rules_enum_variants() {
+    macro_rules! tagged {
+        ($name:ident { $($variant:ident = $magic:expr),* $(,)? }) => {
+            #[binrw]
+            #[derive(Debug, PartialEq)]
+            #[brw(little)]
+            enum $name {
+                $(#[brw(magic = $magic)] $variant(u8)),*
+            }
+        };
+    }
+
+    tagged!(Tagged { A = 1u8, B = 2u8 });
+
+    let tagged = Tagged::read(&mut Cursor::new(b"\x02\x05")).unwrap();
+    assert_eq!(tagged, Tagged::B(5));
+
+    let mut out = Cursor::new(Vec::new());
+    tagged.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\x02\x05");
+}
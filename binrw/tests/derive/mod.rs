@@ -1,10 +1,15 @@
 mod binwrite_temp;
+mod borrowed_args;
+mod checksum;
+mod dyn_with;
 mod r#enum;
 mod fn_helper;
+mod macro_rules;
 mod map_args;
 mod r#struct;
 mod struct_generic;
 mod struct_map;
 mod unit_enum;
 mod unit_struct;
+mod untrusted;
 mod write;
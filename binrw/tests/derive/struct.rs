@@ -1,7 +1,7 @@
 use binrw::{
     args, binread,
     io::{Cursor, Seek, SeekFrom},
-    BinRead, BinResult, FilePtr, NullString,
+    BinRead, BinResult, FilePtr, FilePtr8, NullString,
 };
 
 #[test]
@@ -72,12 +72,49 @@ fn assert() {
     match error {
         binrw::Error::AssertFail { pos, message } => {
             assert_eq!(pos, 0);
+            #[cfg(not(feature = "compact-errors"))]
+            assert_eq!(
+                message,
+                "assertion failed: `a == 1` (left: `0`, right: `1`)"
+            );
+            #[cfg(feature = "compact-errors")]
             assert_eq!(message, "assertion failed: `a == 1`");
         }
         _ => panic!("bad error type"),
     }
 }
 
+#[test]
+fn assert_values_without_debug() {
+    #[derive(BinRead, PartialEq)]
+    struct Id(u8);
+
+    #[allow(dead_code)]
+    #[derive(BinRead)]
+    struct Test {
+        #[br(assert(id != Id(0)))]
+        id: Id,
+        #[br(assert(a > 1 || a == 0))]
+        a: u8,
+    }
+
+    let error = Test::read_le(&mut Cursor::new("\0\0")).err().unwrap();
+    match error {
+        binrw::Error::AssertFail { message, .. } => {
+            assert_eq!(message, "assertion failed: `id != Id(0)`");
+        }
+        _ => panic!("bad error type"),
+    }
+
+    let error = Test::read_le(&mut Cursor::new("\x01\x01")).err().unwrap();
+    match error {
+        binrw::Error::AssertFail { message, .. } => {
+            assert_eq!(message, "assertion failed: `a > 1 || a == 0`");
+        }
+        _ => panic!("bad error type"),
+    }
+}
+
 #[test]
 fn assert_custom_err() {
     #[derive(Debug)]
@@ -185,6 +222,152 @@ fn count_no_useless_conversion_lint() {
     );
 }
 
+#[test]
+#[cfg(not(feature = "compact-errors"))]
+fn count_bytes_of() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        size: u8,
+        header_size: u8,
+        #[br(count_bytes_of = size - header_size)]
+        data: Vec<u16>,
+        trailer: u8,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x06\x02\0\x01\0\x02\xff")).unwrap(),
+        Test {
+            size: 6,
+            header_size: 2,
+            data: vec![1, 2],
+            trailer: 0xff,
+        }
+    );
+
+    let error = Test::read(&mut Cursor::new(b"\x05\x02\0\x01\0\x02\xff"))
+        .expect_err("accepted item overrunning the byte count");
+    match error {
+        binrw::Error::Backtrace(bt) => match *bt.error {
+            binrw::Error::AssertFail { pos, message } => {
+                assert_eq!(pos, 4);
+                assert_eq!(
+                    message,
+                    "item ended 1 byte(s) past the end of a 3-byte region"
+                );
+            }
+            _ => panic!("bad error type"),
+        },
+        _ => panic!("bad error type"),
+    }
+}
+
+#[test]
+fn count_from_external_lengths() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big, import(record_count: u8, lengths: &[i32]))]
+    struct Test {
+        #[br(count = record_count)]
+        ids: Vec<u8>,
+        #[br(parse_with = binrw::helpers::count_from(lengths))]
+        records: Vec<Vec<u16>>,
+    }
+
+    assert_eq!(
+        Test::read_args(
+            &mut Cursor::new(b"\x0a\x0b\0\x01\0\x02\0\x03"),
+            (2, &[1, 2])
+        )
+        .unwrap(),
+        Test {
+            ids: vec![10, 11],
+            records: vec![vec![1], vec![2, 3]],
+        }
+    );
+
+    let error = Test::read_args(&mut Cursor::new(b"\x0a\x0b\0\x01"), (2, &[1, -1]))
+        .expect_err("accepted negative length");
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::AssertFail { pos: 4, message } if message == "external length 1 is out of range"
+    ));
+}
+
+#[test]
+fn follow_links() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Node {
+        next: u16,
+        value: u8,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        #[br(parse_with = binrw::helpers::follow_links(3, |node: &Node| {
+            (node.next != 0xffff).then(|| node.next.into())
+        }))]
+        nodes: Vec<Node>,
+        after: u8,
+    }
+
+    let test = Test::read(&mut Cursor::new(b"\0\x07\x01\x02\xff\xff\x03\0\x04\x02")).unwrap();
+    assert_eq!(
+        test.nodes.iter().map(|node| node.value).collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+    assert_eq!(test.after, 2);
+
+    let error = Test::read(&mut Cursor::new(b"\0\x03\x01\0\x06\x02\0\0\x03")).unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::AssertFail { pos: 6, message } if message == "linked list contains a cycle back to 0x0"
+    ));
+
+    let error = Test::read(&mut Cursor::new(
+        b"\0\x03\x01\0\x06\x02\0\x09\x03\xff\xff\x04",
+    ))
+    .unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::AssertFail { pos: 9, message } if message == "linked list is longer than 3 nodes"
+    ));
+}
+
+#[test]
+fn count_bytes_of_with_args_and_parser() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import(add: u8))]
+    struct Item(#[br(map = |x: u8| x + add)] u8);
+
+    #[binrw::parser(reader, endian)]
+    fn double(add: u8) -> BinResult<(Item, Item)> {
+        Ok((
+            Item::read_options(reader, endian, (add,))?,
+            Item::read_options(reader, endian, (add,))?,
+        ))
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    struct Test {
+        size: u8,
+        #[br(count_bytes_of = size, args(1))]
+        items: Vec<Item>,
+        #[br(count_bytes_of = 2, parse_with = double, args(2))]
+        pairs: Vec<(Item, Item)>,
+    }
+
+    assert_eq!(
+        Test::read_le(&mut Cursor::new(b"\x02\x01\x02\x03\x04")).unwrap(),
+        Test {
+            size: 2,
+            items: vec![Item(2), Item(3)],
+            pairs: vec![(Item(5), Item(6))],
+        }
+    );
+}
+
 #[test]
 fn deref_now() {
     #[derive(BinRead, Debug, PartialEq)]
@@ -210,6 +393,83 @@ fn deref_now() {
     );
 }
 
+#[test]
+fn offset_base() {
+    #[derive(BinRead, Debug)]
+    #[br(big)]
+    struct Inner {
+        #[br(offset_base = absolute)]
+        a: FilePtr8<u8>,
+        #[br(offset_base = parent)]
+        b: FilePtr8<u8>,
+        #[br(pad_before = 1, offset_base = current)]
+        c: FilePtr8<u8>,
+        #[br(offset_base = parent, parse_with = FilePtr8::parse, deref_now)]
+        d: u8,
+    }
+
+    #[derive(BinRead, Debug)]
+    #[br(big)]
+    enum Outer {
+        #[br(magic = 0xffu8)]
+        Inner(Inner),
+        #[br(magic = 0xfeu8)]
+        Direct(#[br(offset_base = parent)] FilePtr8<u8>),
+    }
+
+    let result = Outer::read(&mut Cursor::new(
+        b"\xff\x07\x07\0\x05\x09\0\x0a\x0b\x0c\x0d",
+    ))
+    .unwrap();
+    let Outer::Inner(inner) = result else {
+        panic!("unexpected variant {result:?}");
+    };
+    assert_eq!(*inner.a, 0x0a);
+    assert_eq!(*inner.b, 0x0b);
+    assert_eq!(*inner.c, 0x0c);
+    assert_eq!(inner.d, 0x0d);
+
+    let result = Outer::read(&mut Cursor::new(b"\xfe\x02\x0a")).unwrap();
+    let Outer::Direct(ptr) = result else {
+        panic!("unexpected variant {result:?}");
+    };
+    assert_eq!(*ptr, 0x0a);
+}
+
+#[test]
+fn file_ptr_translated() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        base: u8,
+        #[br(parse_with = FilePtr8::parse_translated(|address| address.checked_sub(base.into())), offset = 2)]
+        a: u8,
+        #[br(parse_with = FilePtr8::with_translation(|address| address.checked_sub(base.into())))]
+        b: FilePtr<u8, u8>,
+        c: u8,
+    }
+
+    let result = Test::read(&mut Cursor::new(b"\x10\x13\x14\xff\xaa\xbb")).unwrap();
+    assert_eq!(
+        result,
+        Test {
+            base: 0x10,
+            a: 0xbb,
+            b: FilePtr {
+                ptr: 0x14,
+                value: Some(0xaa)
+            },
+            c: 0xff,
+        }
+    );
+
+    let error = Test::read(&mut Cursor::new(b"\x10\x02\x14\xff")).unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::AssertFail { .. }
+    ));
+}
+
 // See https://github.com/jam1garner/binrw/issues/118
 #[test]
 fn move_temp_field() {
@@ -332,6 +592,58 @@ fn if_alternate() {
     assert_eq!(result.a, 10);
 }
 
+#[test]
+fn if_alternate_round_trip() {
+    const LEGACY_FLAGS: u16 = 0x8000;
+
+    #[binrw::binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    struct Test {
+        version: u8,
+        #[br(if(version > 2, LEGACY_FLAGS))]
+        #[bw(if(*version > 2))]
+        flags: u16,
+        #[br(if(version > 1, u32::from(version) * 10))]
+        #[bw(if(*version > 1))]
+        size: u32,
+    }
+
+    for (data, expected) in [
+        (
+            &b"\x01"[..],
+            Test {
+                version: 1,
+                flags: LEGACY_FLAGS,
+                size: 10,
+            },
+        ),
+        (
+            &b"\x02\0\0\0\x05"[..],
+            Test {
+                version: 2,
+                flags: LEGACY_FLAGS,
+                size: 5,
+            },
+        ),
+        (
+            &b"\x03\0\x01\0\0\0\x05"[..],
+            Test {
+                version: 3,
+                flags: 1,
+                size: 5,
+            },
+        ),
+    ] {
+        let result = Test::read(&mut Cursor::new(data)).unwrap();
+        assert_eq!(result, expected);
+
+        let mut out = Cursor::new(Vec::new());
+        binrw::BinWrite::write(&result, &mut out).unwrap();
+        assert_eq!(out.into_inner(), data);
+    }
+}
+
 #[test]
 fn ignore_and_default() {
     #[derive(Debug, Eq, PartialEq)]
@@ -389,6 +701,61 @@ fn magic_field() {
     assert_eq!(result, Test { b: b'B', d: b'D' });
 }
 
+#[test]
+fn magic_alternatives() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(magic = b"RIFF" | b"RIFX", tag_into = signature)]
+    struct Test {
+        signature: [u8; 4],
+        #[br(is_big = signature == *b"RIFX")]
+        size: u32,
+        #[br(magic = 1u8 | 2u8)]
+        kind: u8,
+    }
+
+    assert_eq!(
+        Test::read_le(&mut Cursor::new(b"RIFF\x04\0\0\0\x01\x05")).unwrap(),
+        Test {
+            signature: *b"RIFF",
+            size: 4,
+            kind: 5
+        }
+    );
+    assert_eq!(
+        Test::read_le(&mut Cursor::new(b"RIFX\0\0\0\x04\x02\x05")).unwrap(),
+        Test {
+            signature: *b"RIFX",
+            size: 4,
+            kind: 5
+        }
+    );
+    let error =
+        Test::read_le(&mut Cursor::new(b"RIFZ\0\0\0\x04\x01\x05")).expect_err("accepted bad data");
+    assert!(matches!(error, binrw::Error::BadMagic { pos: 0, .. }));
+    Test::read_le(&mut Cursor::new(b"RIFF\0\0\0\x04\x03\x05")).expect_err("accepted bad data");
+}
+
+#[test]
+fn magic_mask() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(magic(b"HDR\x10", mask = b"\xff\xff\xff\xf0"), tag_into = signature)]
+    struct Test {
+        signature: [u8; 4],
+        #[br(magic(0x8000u16, mask = 0xc000))]
+        flags: u8,
+    }
+
+    assert_eq!(
+        Test::read_be(&mut Cursor::new(b"HDR\x13\xbf\xff\x01")).unwrap(),
+        Test {
+            signature: *b"HDR\x13",
+            flags: 1
+        }
+    );
+    Test::read_be(&mut Cursor::new(b"HDR\x23\x80\0\x01")).expect_err("accepted bad data");
+    Test::read_be(&mut Cursor::new(b"HDR\x10\xc0\0\x01")).expect_err("accepted bad data");
+}
+
 #[test]
 fn magic_const() {
     use binrw::meta::ReadMagic;
@@ -441,6 +808,29 @@ fn map_stream_field() {
     );
 }
 
+#[test]
+fn field_stream() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import(data: &mut Cursor<Vec<u8>>))]
+    struct Test {
+        len: u8,
+        #[br(stream = data, count = len)]
+        a: Vec<u8>,
+        b: u8,
+        #[br(stream = data, count = b)]
+        c: Vec<u8>,
+        #[br(stream = data)]
+        d: binrw::FilePtr8<u8>,
+    }
+
+    let mut data = Cursor::new(b"helloworld\x04".to_vec());
+    let test = Test::read_le_args(&mut Cursor::new(b"\x05\x05"), (&mut data,)).unwrap();
+    assert_eq!(test.a, b"hello");
+    assert_eq!(test.c, b"world");
+    assert_eq!(*test.d, b'o');
+    assert_eq!(data.position(), 11);
+}
+
 #[test]
 fn named_args_trailing_commas() {
     #[rustfmt::skip]
@@ -494,6 +884,183 @@ fn pad_size_to() {
     assert_eq!(result, Test { a: 1, b: 2 });
 }
 
+#[test]
+fn size_region() {
+    #[binread]
+    #[derive(Debug, PartialEq)]
+    #[br(import(len: u8), size = len)]
+    struct Chunk {
+        kind: u8,
+        #[br(parse_with = binrw::helpers::until_eof)]
+        data: Vec<u8>,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import(len: u8), size = len)]
+    struct Header {
+        kind: u8,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    struct Test {
+        len: u8,
+        #[br(args(len))]
+        header: Header,
+        #[br(args(len))]
+        chunk: Chunk,
+        last: u8,
+    }
+
+    let result = Test::read_le(&mut Cursor::new(b"\x03\x01\xff\xff\x02\x03\x04\x05")).unwrap();
+    assert_eq!(
+        result,
+        Test {
+            len: 3,
+            header: Header { kind: 1 },
+            chunk: Chunk {
+                kind: 2,
+                data: vec![3, 4],
+            },
+            last: 5,
+        }
+    );
+
+    // Fields cannot read past the end of the region
+    let error = Test::read_le(&mut Cursor::new(b"\0\x01\x02")).unwrap_err();
+    assert!(
+        matches!(error.root_cause(), binrw::Error::Io(..)),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn size_region_trailing_error() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import(len: u32), size = len, trailing = error)]
+    struct Test {
+        a: u8,
+    }
+
+    let result = Test::read_le_args(&mut Cursor::new(b"\x01"), (1,)).unwrap();
+    assert_eq!(result, Test { a: 1 });
+
+    let mut data = Cursor::new(b"\x01\x02");
+    let error = Test::read_le_args(&mut data, (2,)).unwrap_err();
+    assert!(
+        matches!(error.root_cause(), binrw::Error::AssertFail { pos: 1, .. }),
+        "{error:?}"
+    );
+    assert_eq!(data.position(), 0);
+}
+
+#[test]
+fn size_region_unit_struct() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(magic = b'U', size = 2)]
+    struct Unit;
+
+    #[derive(BinRead, Debug, PartialEq)]
+    struct Test(Unit, u8);
+
+    let result = Test::read_le(&mut Cursor::new(b"U\0\0\x01")).unwrap();
+    assert_eq!(result, Test(Unit, 1));
+}
+
+#[test]
+fn size_region_trailing_capture() {
+    #[binrw::binrw]
+    #[derive(Debug, PartialEq)]
+    #[br(import(len: u8), size = len, trailing = capture(extra))]
+    struct Chunk {
+        kind: u8,
+        extra: Vec<u8>,
+    }
+
+    #[binrw::binrw]
+    #[derive(Debug, PartialEq)]
+    struct Test {
+        #[bw(calc = 1 + chunk.extra.len() as u8)]
+        len: u8,
+        #[br(args(len))]
+        chunk: Chunk,
+        last: u8,
+    }
+
+    let data = b"\x03\x01\x02\x03\x04";
+    let result = Test::read_le(&mut Cursor::new(data)).unwrap();
+    assert_eq!(
+        result,
+        Test {
+            chunk: Chunk {
+                kind: 1,
+                extra: vec![2, 3],
+            },
+            last: 4,
+        }
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    binrw::BinWrite::write_le(&result, &mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+
+    // Nothing is left over
+    let result = Test::read_le(&mut Cursor::new(b"\x01\x01\x02")).unwrap();
+    assert_eq!(result.chunk.extra, []);
+    assert_eq!(result.last, 2);
+}
+
+#[test]
+fn save_position() {
+    #[derive(BinRead, Debug, PartialEq)]
+    struct Test {
+        #[br(save_position = start)]
+        kind: u8,
+        #[br(if(kind == 1))]
+        len: Option<u8>,
+        #[br(seek_to_saved = start, pad_before = 3)]
+        value: u8,
+        #[br(save_position = end, seek_to_saved = start)]
+        again: u8,
+        #[br(seek_to_saved = end)]
+        last: u8,
+    }
+
+    let result = Test::read_le(&mut Cursor::new(b"\x01\x02\0\x03\x04")).unwrap();
+    assert_eq!(
+        result,
+        Test {
+            kind: 1,
+            len: Some(2),
+            value: 3,
+            again: 1,
+            last: 4,
+        }
+    );
+}
+
+#[test]
+fn save_position_string_label() {
+    #[derive(BinRead, Debug, PartialEq)]
+    struct Test {
+        #[br(save_position = "toc")]
+        offset: u8,
+        #[br(seek_to_saved("toc"))]
+        again: u8,
+        #[br(seek_to_saved = toc)]
+        last: u8,
+    }
+
+    let result = Test::read_le(&mut Cursor::new(b"\x01")).unwrap();
+    assert_eq!(
+        result,
+        Test {
+            offset: 1,
+            again: 1,
+            last: 1,
+        }
+    );
+}
+
 #[test]
 fn parse_with_default_args() {
     #[derive(Clone)]
@@ -752,6 +1319,90 @@ fn try_directive() {
     assert_eq!(result.a, Some([-1, 0]));
 }
 
+#[test]
+fn if_eof_none() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        a: u8,
+        #[br(if_eof_none)]
+        b: Option<u32>,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x01")).unwrap(),
+        Test { a: 1, b: None }
+    );
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x01\0\0\0\x02")).unwrap(),
+        Test { a: 1, b: Some(2) }
+    );
+
+    // Truncated data is an error, unlike with `try`
+    let mut data = Cursor::new(b"\x01\0\0");
+    let error = Test::read(&mut data).expect_err("accepted truncated data");
+    assert!(error.is_eof());
+    assert_eq!(data.position(), 0);
+}
+
+#[test]
+fn rest() {
+    #[binrw::binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    struct Test {
+        a: u8,
+        #[br(rest)]
+        payload: Vec<u8>,
+    }
+
+    let data = b"\x01\x02\x03\x04";
+    let result = Test::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(
+        result,
+        Test {
+            a: 1,
+            payload: vec![2, 3, 4],
+        }
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    binrw::BinWrite::write(&result, &mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x01")).unwrap().payload,
+        Vec::<u8>::new()
+    );
+}
+
+#[test]
+fn rest_size_region() {
+    #[derive(BinRead, Debug)]
+    #[br(import(len: u32), size = len)]
+    struct Record {
+        kind: u8,
+        #[br(rest)]
+        extra: Box<[u8]>,
+    }
+
+    #[binread]
+    #[derive(Debug)]
+    #[br(little)]
+    struct Chunk {
+        #[br(temp)]
+        len: u32,
+        #[br(args(len))]
+        record: Record,
+        next: u8,
+    }
+
+    let chunk = Chunk::read(&mut Cursor::new(b"\x03\0\0\0\x01\x02\x03\x04")).unwrap();
+    assert_eq!(chunk.record.kind, 1);
+    assert_eq!(&*chunk.record.extra, [2, 3]);
+    assert_eq!(chunk.next, 4);
+}
+
 #[test]
 fn try_calc() {
     #[derive(BinRead, Debug, PartialEq)]
@@ -768,6 +1419,137 @@ fn try_calc() {
     Test::read_args(&mut Cursor::new(b""), (0x1_0000,)).unwrap_err();
 }
 
+#[test]
+#[cfg(not(feature = "compact-errors"))]
+fn try_calc_err_context() {
+    #[derive(BinRead, Debug)]
+    #[allow(dead_code)]
+    #[br(big, import(v: u32))]
+    struct Test {
+        #[br(try_calc = <_>::try_from(v))]
+        a: u16,
+        #[br(try_calc = <_>::try_from(v), err_context("v = {}", v))]
+        b: u8,
+    }
+
+    let error = Test::read_args(&mut Cursor::new(b""), (0x1_0000,)).unwrap_err();
+    match error {
+        binrw::Error::Backtrace(bt) => {
+            assert!(matches!(*bt.error, binrw::Error::Custom { pos: 0, .. }));
+            assert!(bt.to_string().contains("field 'a' in Test"), "{bt}");
+        }
+        _ => panic!("expected backtrace, got {error:?}"),
+    }
+
+    let error = Test::read_args(&mut Cursor::new(b""), (0x100,)).unwrap_err();
+    match error {
+        binrw::Error::Backtrace(bt) => {
+            assert!(matches!(*bt.error, binrw::Error::Custom { .. }));
+            assert!(bt.to_string().contains("v = 256"), "{bt}");
+        }
+        _ => panic!("expected backtrace, got {error:?}"),
+    }
+}
+
+#[test]
+fn finally() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big, import(base: u8), finally = |this: &mut Self, end| {
+        this.end = end;
+        this.names = this.names.iter().map(|name| name - base).collect();
+        if this.names.iter().any(|&name| usize::from(name) >= this.names.len()) {
+            return Err(binrw::Error::AssertFail {
+                pos: end,
+                message: "name out of range".into(),
+            });
+        }
+        Ok(())
+    })]
+    struct Test {
+        count: u8,
+        #[br(count = count)]
+        names: Vec<u8>,
+        #[br(ignore)]
+        end: u64,
+    }
+
+    let result = Test::read_args(&mut Cursor::new(b"\x02\x11\x10"), (0x10,)).unwrap();
+    assert_eq!(
+        result,
+        Test {
+            count: 2,
+            names: vec![1, 0],
+            end: 3
+        }
+    );
+
+    let mut data = Cursor::new(b"\x02\x11\x12");
+    let error = Test::read_args(&mut data, (0x10,)).expect_err("accepted bad data");
+    assert!(matches!(error, binrw::Error::AssertFail { pos: 3, .. }));
+    assert_eq!(data.position(), 0);
+}
+
+#[test]
+fn float_checks() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little)]
+    struct Test {
+        #[br(finite)]
+        a: f32,
+        #[br(no_subnormal)]
+        b: f32,
+        #[br(finite, no_subnormal, count = 2)]
+        c: Vec<f64>,
+    }
+
+    let mut data = Vec::new();
+    data.extend(1.0f32.to_le_bytes());
+    data.extend(f32::INFINITY.to_le_bytes());
+    data.extend(2.0f64.to_le_bytes());
+    data.extend(0.0f64.to_le_bytes());
+    assert_eq!(
+        Test::read(&mut Cursor::new(&data)).unwrap(),
+        Test {
+            a: 1.0,
+            b: f32::INFINITY,
+            c: vec![2.0, 0.0],
+        }
+    );
+
+    let check = |data: &[u8], expected_pos: u64, expected: &str| {
+        let error = Test::read(&mut Cursor::new(data)).unwrap_err();
+        #[cfg(not(feature = "compact-errors"))]
+        assert!(error.to_string().contains("While parsing field"), "{error}");
+        match error.root_cause() {
+            binrw::Error::AssertFail { pos, message } => {
+                assert_eq!(*pos, expected_pos);
+                assert_eq!(message, expected);
+            }
+            _ => panic!("bad error type"),
+        }
+    };
+
+    let mut nan = data.clone();
+    nan[..4].copy_from_slice(&f32::NAN.to_le_bytes());
+    check(&nan, 0, "expected a finite float, found NaN");
+
+    let mut subnormal = data.clone();
+    subnormal[4..8].copy_from_slice(&f32::from_bits(1).to_le_bytes());
+    check(
+        &subnormal,
+        4,
+        "expected a normal float, found a subnormal value",
+    );
+
+    let mut infinite = data;
+    infinite[16..].copy_from_slice(&f64::NEG_INFINITY.to_le_bytes());
+    check(
+        &infinite,
+        8,
+        "expected a finite, normal float, found an infinite value",
+    );
+}
+
 #[test]
 fn tuple() {
     #[derive(BinRead, Debug, Eq, PartialEq)]
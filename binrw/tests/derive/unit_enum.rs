@@ -20,6 +20,56 @@ fn unit_enum_magic() {
     assert_eq!(Test::read(&mut Cursor::new(b"\0\x02")).unwrap(), Test::Two);
 }
 
+#[test]
+fn unit_enum_tag_endian() {
+    use binrw::BinWrite;
+
+    #[binrw::binrw]
+    #[derive(Debug, Eq, PartialEq)]
+    #[brw(little, tag_endian = big, repr = u16)]
+    enum Test {
+        One = 1,
+        Two = 2,
+    }
+
+    assert_eq!(Test::read(&mut Cursor::new(b"\0\x02")).unwrap(), Test::Two);
+    let mut out = Cursor::new(Vec::new());
+    Test::One.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\0\x01");
+}
+
+#[test]
+fn unit_enum_magic_repr() {
+    use binrw::BinWrite;
+
+    #[binrw::binrw]
+    #[derive(Debug, Eq, PartialEq)]
+    #[brw(big, repr = u16)]
+    enum Test {
+        #[brw(magic = 1)]
+        One,
+        #[brw(magic = 0x200u16)]
+        Two,
+        #[brw(magic = 3)]
+        Three,
+    }
+
+    assert_eq!(Test::read(&mut Cursor::new(b"\0\x01")).unwrap(), Test::One);
+    assert_eq!(Test::read(&mut Cursor::new(b"\x02\0")).unwrap(), Test::Two);
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\0\x03")).unwrap(),
+        Test::Three
+    );
+    let mut data = Cursor::new(b"\x01\0");
+    let error = Test::read(&mut data).expect_err("accepted bad data");
+    assert!(matches!(error, binrw::Error::NoVariantMatch { .. }));
+    assert_eq!(data.stream_position().unwrap(), 0);
+
+    let mut out = Cursor::new(Vec::new());
+    [Test::One, Test::Two, Test::Three].write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\0\x01\x02\0\0\x03");
+}
+
 #[test]
 fn unit_enum_magic_different_types() {
     #[derive(BinRead, Debug, Eq, PartialEq)]
@@ -82,6 +132,30 @@ fn unit_enum_magic_bytes() {
     assert_eq!(Test::read(&mut Cursor::new(b"two0")).unwrap(), Test::Two);
 }
 
+#[test]
+fn unit_enum_magic_alternatives() {
+    use binrw::BinWrite;
+
+    #[derive(BinRead, BinWrite, Debug, Eq, PartialEq)]
+    #[brw(big)]
+    enum Test {
+        #[brw(magic(b"one0" | b"ONE0"))]
+        One,
+
+        #[brw(magic(b"two0"))]
+        Two,
+    }
+
+    assert_eq!(Test::read(&mut Cursor::new(b"one0")).unwrap(), Test::One);
+    assert_eq!(Test::read(&mut Cursor::new(b"ONE0")).unwrap(), Test::One);
+    assert_eq!(Test::read(&mut Cursor::new(b"two0")).unwrap(), Test::Two);
+    Test::read(&mut Cursor::new(b"One0")).expect_err("accepted bad data");
+
+    let mut out = Cursor::new(Vec::new());
+    Test::One.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"one0");
+}
+
 #[test]
 fn unit_enum_magic_pre_assert() {
     #[derive(BinRead, Debug, Eq, PartialEq)]
@@ -215,3 +289,37 @@ fn unit_enum_rewind_on_no_variant() {
     Test::read(&mut data).expect_err("accepted bad data");
     assert_eq!(expected, data.stream_position().unwrap());
 }
+
+#[test]
+fn unit_enum_tag() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import(kind: u32), tag = kind)]
+    enum Test {
+        #[br(tag_value = 1)]
+        A,
+        #[br(tag_value = 2)]
+        B,
+        #[br(tag_value = 4, pre_assert(false))]
+        C,
+    }
+
+    let mut data = Cursor::new(b"");
+    assert_eq!(Test::read_le_args(&mut data, (1,)).unwrap(), Test::A);
+    assert_eq!(Test::read_le_args(&mut data, (2,)).unwrap(), Test::B);
+    Test::read_le_args(&mut data, (4,)).expect_err("accepted bad tag");
+    assert_eq!(data.stream_position().unwrap(), 0);
+}
+
+#[test]
+fn unit_enum_map_stream() {
+    use binrw::io::TakeSeekExt;
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(map_stream = |reader| reader.take_seek(1))]
+    enum Test {
+        #[br(magic = b"AB")]
+        Ab,
+    }
+
+    Test::read_le(&mut Cursor::new(b"AB")).expect_err("magic should be outside the stream");
+}
@@ -0,0 +1,162 @@
+use binrw::{binread, io::Cursor, BinRead, BinReaderExt, Error, FilePtr64};
+
+fn assert_fail_message(error: Error) -> String {
+    match error {
+        Error::AssertFail { message, .. } => message.to_string(),
+        Error::Backtrace(bt) => assert_fail_message(*bt.error),
+        error => panic!("unexpected error: {error:?}"),
+    }
+}
+
+#[test]
+fn untrusted_count_remaining() {
+    #[derive(BinRead, Debug)]
+    #[br(big, untrusted)]
+    #[allow(dead_code)]
+    struct Test {
+        len: u32,
+        #[br(count = len)]
+        data: Vec<u8>,
+    }
+
+    let error = Test::read(&mut Cursor::new(b"\0\0\x10\0\x01\x02")).unwrap_err();
+    let message = assert_fail_message(error);
+    assert!(message.contains("2 byte(s) remaining"), "{message}");
+
+    let result = Test::read(&mut Cursor::new(b"\0\0\0\x02\x01\x02")).unwrap();
+    assert_eq!(result.data, [1, 2]);
+}
+
+#[test]
+fn untrusted_count_zero_size() {
+    #[derive(BinRead, Debug)]
+    #[br(big, untrusted)]
+    #[allow(dead_code)]
+    struct Test {
+        len: u32,
+        #[br(count = len)]
+        data: Vec<()>,
+    }
+
+    let result = Test::read(&mut Cursor::new(b"\0\0\x10\0")).unwrap();
+    assert_eq!(result.data.len(), 0x1000);
+}
+
+#[test]
+fn untrusted_count_limit() {
+    #[derive(BinRead, Debug)]
+    #[br(untrusted)]
+    #[allow(dead_code)]
+    struct Test {
+        #[br(count = binrw::untrusted::MAX_COUNT + 1)]
+        data: Vec<u8>,
+    }
+
+    let error = Test::read_le(&mut Cursor::new(b"")).unwrap_err();
+    assert!(assert_fail_message(error).contains("untrusted input limit"));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn untrusted_max_count() {
+    #[derive(BinRead, Debug)]
+    #[br(big, untrusted)]
+    #[allow(dead_code)]
+    struct Test {
+        len: u8,
+        #[br(count = len)]
+        data: Vec<u8>,
+    }
+
+    let data = b"\x03\x01\x02\x03";
+    binrw::untrusted::set_max_count(2);
+    let error = Test::read(&mut Cursor::new(data)).unwrap_err();
+    assert!(assert_fail_message(error).contains("untrusted input limit of 2"));
+
+    // The limit only applies to the thread which set it
+    std::thread::spawn(move || {
+        assert_eq!(binrw::untrusted::max_count(), binrw::untrusted::MAX_COUNT);
+        Test::read(&mut Cursor::new(data)).unwrap();
+    })
+    .join()
+    .unwrap();
+
+    binrw::untrusted::set_max_count(binrw::untrusted::MAX_COUNT);
+    Test::read(&mut Cursor::new(data)).unwrap();
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn untrusted_depth() {
+    #[derive(BinRead, Debug)]
+    #[br(untrusted)]
+    #[allow(dead_code)]
+    enum Node {
+        #[br(magic = 0u8)]
+        Leaf,
+        #[br(magic = 1u8)]
+        Branch(Box<Node>),
+    }
+
+    let mut data = vec![1; binrw::untrusted::MAX_DEPTH - 1];
+    data.push(0);
+    Node::read_le(&mut Cursor::new(&data)).unwrap();
+    assert_eq!(binrw::untrusted::depth(), 0);
+
+    data.insert(0, 1);
+    Node::read_le(&mut Cursor::new(&data)).unwrap_err();
+    assert_eq!(binrw::untrusted::depth(), 0);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn untrusted_max_depth() {
+    #[derive(BinRead, Debug)]
+    #[br(untrusted, big)]
+    #[allow(dead_code)]
+    struct Node {
+        value: u8,
+        #[br(if(value != 0))]
+        next: Option<binrw::FilePtr8<Box<Node>>>,
+    }
+
+    // Each node points back to the first one
+    let data = b"\x01\x00";
+    binrw::untrusted::set_max_depth(4);
+    let error = Node::read(&mut Cursor::new(data)).unwrap_err();
+    assert!(assert_fail_message(error).contains("untrusted input limit of 4"));
+    assert_eq!(binrw::untrusted::depth(), 0);
+
+    binrw::untrusted::set_max_depth(binrw::untrusted::MAX_DEPTH);
+    assert_eq!(binrw::untrusted::max_depth(), binrw::untrusted::MAX_DEPTH);
+    Node::read(&mut Cursor::new(b"\x01\x02\0")).unwrap();
+}
+
+#[test]
+fn untrusted_align() {
+    #[binread]
+    #[derive(Debug)]
+    #[br(untrusted)]
+    struct Test {
+        #[br(temp)]
+        align: u8,
+        #[br(align_before = align)]
+        value: u8,
+    }
+
+    let error = Test::read_le(&mut Cursor::new(b"\0\x01")).unwrap_err();
+    assert!(assert_fail_message(error).contains("invalid alignment 0"));
+    assert_eq!(
+        Test::read_le(&mut Cursor::new(b"\x02\0\x01"))
+            .unwrap()
+            .value,
+        1
+    );
+}
+
+#[test]
+fn untrusted_file_ptr() {
+    Cursor::new(b"\xff\xff\xff\xff\xff\xff\xff\xff")
+        .read_be::<FilePtr64<u8>>()
+        .unwrap_err();
+}
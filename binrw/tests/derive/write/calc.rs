@@ -62,3 +62,116 @@ fn try_calc() {
         .write_args(&mut Cursor::new(Vec::new()), (0x1_0000,))
         .unwrap_err();
 }
+
+#[test]
+#[cfg(not(feature = "compact-errors"))]
+fn try_calc_err_context() {
+    #[binwrite]
+    #[bw(big, import(v: u32))]
+    struct Test {
+        #[bw(try_calc = <_>::try_from(v), err_context("v = {}", v))]
+        a: u16,
+    }
+
+    let error = Test {}
+        .write_args(&mut Cursor::new(Vec::new()), (0x1_0000,))
+        .unwrap_err();
+    match error {
+        binrw::Error::Backtrace(bt) => {
+            assert!(matches!(*bt.error, binrw::Error::Custom { .. }));
+            assert!(bt.to_string().contains("v = 65536"), "{bt}");
+        }
+        _ => panic!("expected backtrace, got {error:?}"),
+    }
+}
+
+#[test]
+fn calc_count_of() {
+    #[binrw::binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    struct Test {
+        #[bw(calc_count_of = items)]
+        len: u16,
+        #[br(count = len)]
+        items: Vec<u8>,
+        #[bw(calc_count_of = name)]
+        name_len: u8,
+        #[br(count = name_len, try_map = String::from_utf8)]
+        #[bw(map = String::as_bytes)]
+        name: String,
+    }
+
+    let value = Test {
+        items: vec![1, 2, 3],
+        name: "ab".into(),
+    };
+    let mut x = Cursor::new(Vec::new());
+    value.write(&mut x).unwrap();
+    assert_eq!(x.get_ref(), b"\0\x03\x01\x02\x03\x02ab");
+
+    x.set_position(0);
+    assert_eq!(<Test as binrw::BinRead>::read(&mut x).unwrap(), value);
+
+    let error = Test {
+        items: vec![],
+        name: "a".repeat(256),
+    }
+    .write(&mut Cursor::new(Vec::new()))
+    .unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::Custom { pos: 0, .. }
+    ));
+    assert!(
+        error.to_string().contains("count 256 out of range of u8"),
+        "{error}"
+    );
+}
+
+#[test]
+fn prepare() {
+    #[binwrite]
+    #[bw(big, magic = b'P', prepare(total = items.iter().map(|&item| u16::from(item)).sum::<u16>()))]
+    struct Test {
+        #[bw(calc = u8::try_from(items.len()).unwrap())]
+        len: u8,
+        #[bw(calc = total)]
+        total: u16,
+        items: Vec<u8>,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test {
+        items: vec![0x80, 0x81],
+    }
+    .write(&mut x)
+    .unwrap();
+    assert_eq!(x.into_inner(), b"P\x02\x01\x01\x80\x81");
+
+    #[binwrite]
+    #[bw(big, import(limit: usize))]
+    enum Message {
+        #[bw(magic = 1u8, prepare = {
+            if data.len() > limit {
+                return Err(binrw::Error::AssertFail {
+                    pos: 0,
+                    message: "message too long".into(),
+                });
+            }
+        })]
+        Data { data: Vec<u8> },
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Message::Data { data: vec![1, 2] }
+        .write_args(&mut x, (2,))
+        .unwrap();
+    assert_eq!(x.into_inner(), b"\x01\x01\x02");
+
+    let mut x = Cursor::new(Vec::new());
+    Message::Data { data: vec![1, 2] }
+        .write_args(&mut x, (1,))
+        .unwrap_err();
+    assert!(x.into_inner().is_empty());
+}
@@ -24,3 +24,35 @@ fn custom_writer() {
 
     assert_eq!(x.into_inner(), b"\x01abcd");
 }
+
+#[test]
+#[cfg(not(feature = "compact-errors"))]
+fn custom_writer_err_context() {
+    #[derive(BinWrite)]
+    struct Test {
+        x: u8,
+
+        #[bw(write_with = failing_writer, err_context("x = {}", x))]
+        y: u16,
+    }
+
+    #[binrw::writer]
+    fn failing_writer(_this: &u16) -> binrw::BinResult<()> {
+        Err(binrw::Error::AssertFail {
+            pos: 0,
+            message: "oops".into(),
+        })
+    }
+
+    let err = Test { x: 1, y: 2 }
+        .write_options(&mut Cursor::new(Vec::new()), Endian::Big, ())
+        .unwrap_err();
+
+    match err {
+        binrw::Error::Backtrace(bt) => {
+            assert!(matches!(*bt.error, binrw::Error::AssertFail { .. }));
+            assert!(bt.to_string().contains("x = 1"));
+        }
+        _ => panic!("expected backtrace, got {err:?}"),
+    }
+}
@@ -0,0 +1,34 @@
+use binrw::{binwrite, io::Cursor, BinWrite};
+
+#[test]
+fn canonical_nan() {
+    #[binwrite]
+    #[bw(big)]
+    struct Test {
+        #[bw(canonical_nan)]
+        a: f32,
+        #[bw(canonical_nan)]
+        b: [f64; 2],
+        #[bw(canonical_nan, calc = f32::from_bits(0xffff_ffff))]
+        c: f32,
+        #[bw(canonical_nan, map = |v: &f32| Some(*v))]
+        d: f32,
+    }
+
+    let mut out = Cursor::new(Vec::new());
+    Test {
+        a: f32::from_bits(0x7fc0_0001),
+        b: [1.5, -f64::NAN],
+        d: f32::from_bits(0x7f80_0001),
+    }
+    .write(&mut out)
+    .unwrap();
+
+    let mut expected = Vec::new();
+    expected.extend(f32::NAN.to_be_bytes());
+    expected.extend(1.5f64.to_be_bytes());
+    expected.extend(f64::NAN.to_be_bytes());
+    expected.extend(f32::NAN.to_be_bytes());
+    expected.extend(f32::NAN.to_be_bytes());
+    assert_eq!(out.into_inner(), expected);
+}
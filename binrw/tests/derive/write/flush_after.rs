@@ -0,0 +1,76 @@
+use binrw::{
+    io::{Cursor, Seek, SeekFrom, Write},
+    BinWrite,
+};
+
+/// A writer which records the stream position of every flush.
+#[derive(Default)]
+struct FlushLog {
+    inner: Cursor<Vec<u8>>,
+    flushes: Vec<u64>,
+}
+
+impl Write for FlushLog {
+    fn write(&mut self, buf: &[u8]) -> binrw::io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> binrw::io::Result<()> {
+        self.flushes.push(self.inner.position());
+        Ok(())
+    }
+}
+
+impl Seek for FlushLog {
+    fn seek(&mut self, pos: SeekFrom) -> binrw::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn flush_after_field() {
+    #[derive(BinWrite)]
+    #[bw(little)]
+    struct Test {
+        #[bw(flush_after)]
+        a: u16,
+        b: u8,
+        #[bw(flush_after, if(*b != 0))]
+        c: u32,
+    }
+
+    let mut writer = FlushLog::default();
+    Test { a: 1, b: 0, c: 2 }.write(&mut writer).unwrap();
+    assert_eq!(writer.inner.into_inner(), b"\x01\0\0");
+    assert_eq!(writer.flushes, [2]);
+
+    let mut writer = FlushLog::default();
+    Test { a: 1, b: 1, c: 2 }.write(&mut writer).unwrap();
+    assert_eq!(writer.flushes, [2, 7]);
+}
+
+#[test]
+fn flush_after_type() {
+    #[derive(BinWrite)]
+    #[bw(little, flush_after)]
+    struct Record(u16, u16);
+
+    #[derive(BinWrite)]
+    #[bw(big, flush_after)]
+    enum Packet {
+        #[bw(magic = 1u8)]
+        Short(u8),
+        #[bw(magic = 2u8)]
+        Long(u32),
+    }
+
+    let mut writer = FlushLog::default();
+    vec![Record(1, 2), Record(3, 4)].write(&mut writer).unwrap();
+    assert_eq!(writer.flushes, [4, 8]);
+
+    let mut writer = FlushLog::default();
+    Packet::Long(1).write(&mut writer).unwrap();
+    Packet::Short(2).write(&mut writer).unwrap();
+    assert_eq!(writer.inner.into_inner(), b"\x02\0\0\0\x01\x01\x02");
+    assert_eq!(writer.flushes, [5, 7]);
+}
@@ -1,4 +1,4 @@
-use binrw::{binwrite, io::Cursor, BinWrite, Endian};
+use binrw::{binrw, binwrite, io::Cursor, BinRead, BinWrite, Endian};
 
 #[test]
 fn ignore_is_not_written() {
@@ -17,3 +17,33 @@ fn ignore_is_not_written() {
     // Since it's bw(ignore), nothing is written here.
     assert_eq!(x.into_inner(), b"");
 }
+
+#[test]
+fn read_only_is_read_but_not_written() {
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    struct Test {
+        len: u16,
+        #[br(calc = len * 2, read_only)]
+        doubled: u16,
+        #[brw(read_only)]
+        checked: u8,
+        tail: u8,
+    }
+
+    let value = Test::read(&mut Cursor::new(b"\0\x02\x03\x04")).unwrap();
+    assert_eq!(
+        value,
+        Test {
+            len: 2,
+            doubled: 4,
+            checked: 3,
+            tail: 4
+        }
+    );
+
+    let mut x = Cursor::new(Vec::new());
+    value.write(&mut x).unwrap();
+    assert_eq!(x.into_inner(), b"\0\x02\x04");
+}
@@ -21,6 +21,55 @@ fn magic_round_trip() {
     assert_eq!(out.into_inner(), data);
 }
 
+#[test]
+fn magic_alternatives() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(little, magic = b"RIFX" | b"RIFF")]
+    struct Test {
+        size: u32,
+    }
+
+    let mut out = Cursor::new(Vec::new());
+    Test::read(&mut Cursor::new(b"RIFF\x04\0\0\0"))
+        .unwrap()
+        .write(&mut out)
+        .unwrap();
+    assert_eq!(out.into_inner(), b"RIFX\x04\0\0\0");
+
+    // Writing the magic number which was read by storing it in a field
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[br(little, magic = b"RIFF" | b"RIFX", tag_into = signature)]
+    #[bw(little)]
+    struct Stored {
+        signature: [u8; 4],
+        size: u32,
+    }
+
+    let data = b"RIFX\x04\0\0\0";
+    let mut out = Cursor::new(Vec::new());
+    Stored::read(&mut Cursor::new(data))
+        .unwrap()
+        .write(&mut out)
+        .unwrap();
+    assert_eq!(out.into_inner(), data);
+}
+
+#[test]
+fn magic_mask() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(little, magic(0x0102_u16, mask = 0xff0f))]
+    struct Test {
+        x: u8,
+    }
+
+    let mut out = Cursor::new(Vec::new());
+    Test::read(&mut Cursor::new(b"\x42\x01\x05"))
+        .unwrap()
+        .write(&mut out)
+        .unwrap();
+    assert_eq!(out.into_inner(), b"\x02\x01\x05");
+}
+
 #[test]
 fn magic_one_way() {
     #[derive(BinRead, BinWrite)]
@@ -68,3 +68,21 @@ fn map_stream_field() {
 
     assert_eq!(out.into_inner(), &[0, 1, 3, 0, 132, 129, 135, 128],);
 }
+
+#[test]
+fn map_stream_enum() {
+    #[derive(BinWrite, Debug, PartialEq)]
+    #[bw(big, map_stream = BadCrypt::new)]
+    enum Test {
+        #[bw(magic = 1u8)]
+        One(Vec<u8>),
+        #[bw(magic = 2u8)]
+        Two,
+    }
+
+    let mut out = Cursor::new(vec![]);
+    Test::One(vec![1, 2]).write(&mut out).unwrap();
+    Test::Two.write(&mut out).unwrap();
+
+    assert_eq!(out.into_inner(), &[1, 0, 2, 2]);
+}
@@ -6,6 +6,8 @@ mod calc;
 mod custom_writer;
 mod endian;
 mod r#enum;
+mod float;
+mod flush_after;
 mod if_cond;
 mod ignore;
 mod import;
@@ -15,5 +17,7 @@ mod map_stream;
 mod padding;
 mod restore_position;
 mod simple;
+mod size;
+mod size_of;
 mod stream;
 mod top_level_map;
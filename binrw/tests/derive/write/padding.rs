@@ -73,3 +73,102 @@ fn padding_one_way() {
 
     assert_eq!(x.into_inner(), data);
 }
+
+#[test]
+fn padding_fill() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(pad_with = 0xff)]
+    struct Test {
+        #[brw(pad_before = 0x2_u32, align_after = 0x4)]
+        x: u8,
+
+        #[brw(pad_with = 0xcc, pad_after = 0x1_u32)]
+        y: u8,
+
+        #[brw(pad_size_to = 0x4_u32)]
+        z: u16,
+    }
+
+    let data = [
+        /* pad_before: */ 0xff, 0xff, /* x */ 1, /* align: */ 0xff, /* y */ 2,
+        /* pad_after: */ 0xcc, /* z */ 0xab, 0xcd, /* pad_size_to */ 0xff, 0xff,
+    ];
+
+    let mut x = Cursor::new(Vec::new());
+    let test = Test {
+        x: 1,
+        y: 2,
+        z: 0xabcd,
+    };
+    test.write_be(&mut x).unwrap();
+    assert_eq!(x.into_inner(), data);
+
+    assert_eq!(Test::read_be(&mut Cursor::new(data)).unwrap(), test);
+
+    let mut bad_data = data;
+    bad_data[9] = 0;
+    let error = Test::read_be(&mut Cursor::new(bad_data)).unwrap_err();
+    assert!(
+        matches!(error.root_cause(), binrw::Error::AssertFail { pos: 9, .. }),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn padding_fill_write_only() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    struct Test {
+        #[brw(pad_before = 0x2_u32)]
+        #[bw(pad_with = 0xff)]
+        x: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test { x: 1 }.write_le(&mut x).unwrap();
+    assert_eq!(x.into_inner(), b"\xff\xff\x01");
+
+    assert_eq!(
+        Test::read_le(&mut Cursor::new(b"\0\x7f\x01")).unwrap(),
+        Test { x: 1 }
+    );
+}
+
+#[test]
+fn padding_fill_enum() {
+    #[derive(BinWrite)]
+    #[bw(pad_with = 0xee)]
+    enum Test {
+        #[bw(magic = 0u8)]
+        A(#[bw(pad_before = 1)] u8),
+        #[bw(magic = 1u8, pad_with = 0xdd)]
+        B(#[bw(pad_after = 1)] u8),
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test::A(2).write_le(&mut x).unwrap();
+    Test::B(3).write_le(&mut x).unwrap();
+    assert_eq!(x.into_inner(), b"\0\xee\x02\x01\x03\xdd");
+}
+
+#[test]
+fn seek_after_round_trip() {
+    use binrw::io::SeekFrom;
+
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(big)]
+    struct Test {
+        #[brw(pad_after = 1, seek_after = SeekFrom::Current(2))]
+        x: u8,
+        #[brw(seek_after = SeekFrom::Start(1))]
+        y: u8,
+        z: u8,
+    }
+
+    let data = b"\x01\x03\0\0\x02";
+    let test = Test::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(test, Test { x: 1, y: 2, z: 3 });
+
+    let mut out = Cursor::new(Vec::new());
+    test.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+}
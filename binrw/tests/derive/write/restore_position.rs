@@ -1,4 +1,7 @@
-use binrw::{io::Cursor, BinWrite};
+use binrw::{
+    io::{Cursor, SeekFrom},
+    BinWrite,
+};
 
 #[test]
 fn restore_position_writing() {
@@ -21,3 +24,29 @@ fn restore_position_writing() {
     }
     assert_eq!(x, b"\0\xff\xff\xff");
 }
+
+#[test]
+fn save_position_writing() {
+    #[derive(BinWrite)]
+    struct Test {
+        #[bw(save_position = header)]
+        magic: [u8; 2],
+        #[bw(pad_before = 2)]
+        body: u16,
+        #[bw(save_position = end, seek_to_saved = header, seek_before = SeekFrom::Current(2))]
+        body_len: u16,
+        #[bw(seek_to_saved = end)]
+        trailer: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test {
+        magic: *b"AB",
+        body: 0x1234,
+        body_len: 2,
+        trailer: 0xff,
+    }
+    .write_le(&mut x)
+    .unwrap();
+    assert_eq!(x.into_inner(), b"AB\x02\0\x34\x12\xff");
+}
@@ -0,0 +1,211 @@
+use binrw::{
+    binwrite,
+    io::{Cursor, SeekFrom},
+    BinSize, BinWrite, Endian,
+};
+
+#[test]
+fn size_matches_written_length() {
+    #[binwrite]
+    #[bw(little, magic = b"HDR")]
+    struct Test {
+        #[bw(calc = items.len() as u16)]
+        count: u16,
+        #[bw(align_after = 8)]
+        items: Vec<u32>,
+        #[bw(pad_size_to = 5)]
+        name: Vec<u8>,
+    }
+
+    let test = Test {
+        items: vec![1, 2, 3],
+        name: b"abc".to_vec(),
+    };
+
+    let mut data = Cursor::new(Vec::new());
+    test.write(&mut data).unwrap();
+    assert_eq!(
+        test.binary_size(()).unwrap(),
+        data.into_inner().len() as u64
+    );
+    assert_eq!(test.binary_size(()).unwrap(), 29);
+}
+
+#[test]
+fn size_with_args() {
+    #[binwrite]
+    #[bw(import(extra: u8))]
+    struct Test {
+        #[bw(pad_after = extra)]
+        a: u8,
+        #[bw(if(extra > 1))]
+        b: Option<u32>,
+    }
+
+    let test = Test { a: 1, b: Some(2) };
+    assert_eq!(test.binary_size_options(Endian::Big, (0,)).unwrap(), 1);
+    assert_eq!(test.binary_size_options(Endian::Big, (2,)).unwrap(), 7);
+}
+
+#[test]
+fn size_with_restore_position() {
+    #[binwrite]
+    #[bw(big)]
+    struct Test {
+        #[bw(restore_position)]
+        a: u32,
+        b: u16,
+    }
+
+    assert_eq!(Test { a: 1, b: 2 }.binary_size(()).unwrap(), 4);
+}
+
+#[test]
+fn size_hint_exact() {
+    #[binwrite]
+    #[bw(magic = b"HDR")]
+    struct Test {
+        a: u16,
+        #[bw(magic = 1u8)]
+        b: (u8, [u32; 2]),
+        #[bw(ignore)]
+        c: u64,
+    }
+
+    let test = Test {
+        a: 1,
+        b: (2, [3, 4]),
+        c: 5,
+    };
+    assert_eq!(test.size_hint(), (15, Some(15)));
+    assert_eq!(test.binary_size_options(Endian::Little, ()).unwrap(), 15);
+}
+
+#[test]
+fn size_hint_bounded() {
+    #[binwrite]
+    struct Test {
+        a: u8,
+        #[bw(if(*a > 1))]
+        b: u32,
+        #[bw(calc = 1u16)]
+        c: u16,
+    }
+
+    assert_eq!(Test { a: 2, b: 3 }.size_hint(), (1, None));
+
+    #[binwrite]
+    #[bw(magic = 0u8)]
+    enum Enum {
+        #[bw(magic = 1u8)]
+        A(u32),
+        B {
+            a: u8,
+            b: Option<u16>,
+        },
+        #[bw(magic = 2u16)]
+        C,
+    }
+
+    assert_eq!(Enum::A(1).size_hint(), (6, Some(6)));
+    assert_eq!(Enum::B { a: 1, b: None }.size_hint(), (2, Some(2)));
+    assert_eq!(Enum::C.size_hint(), (3, Some(3)));
+}
+
+/// Checks the size hint of a value and that the number of bytes written by
+/// the value is within it.
+fn check_hint<T>(value: &T, expected: (usize, Option<usize>))
+where
+    T: BinWrite,
+    for<'a> T::Args<'a>: Default,
+{
+    let mut data = Cursor::new(Vec::new());
+    value
+        .write_options(&mut data, Endian::Little, <_>::default())
+        .unwrap();
+    let len = data.into_inner().len();
+    let hint = value.size_hint();
+    assert_eq!(hint, expected);
+    assert!(hint.0 <= len && hint.1.is_none_or(|upper| len <= upper));
+}
+
+#[test]
+fn size_hint_with_seeks() {
+    #[binwrite]
+    struct SeekBefore {
+        #[bw(seek_before = SeekFrom::Current(4))]
+        a: u8,
+    }
+
+    check_hint(&SeekBefore { a: 1 }, (0, None));
+
+    #[binwrite]
+    #[bw(magic = b"HDR")]
+    struct RestorePosition {
+        #[bw(restore_position)]
+        a: u32,
+        b: u8,
+    }
+
+    check_hint(&RestorePosition { a: 1, b: 2 }, (0, None));
+
+    #[binwrite]
+    enum Enum {
+        A(u8),
+        B(#[bw(seek_before = SeekFrom::Current(2))] u8),
+    }
+
+    check_hint(&Enum::A(1), (1, Some(1)));
+    check_hint(&Enum::B(1), (0, None));
+
+    #[binwrite]
+    struct SeekAfter {
+        #[bw(seek_after = SeekFrom::Current(4))]
+        a: u8,
+        b: u8,
+    }
+
+    check_hint(&SeekAfter { a: 1, b: 2 }, (0, None));
+
+    #[binwrite]
+    struct SavePosition {
+        #[bw(save_position = start)]
+        a: u8,
+        b: u8,
+    }
+
+    check_hint(&SavePosition { a: 1, b: 2 }, (2, Some(2)));
+
+    #[binwrite]
+    struct SeekToSaved {
+        #[bw(save_position = start)]
+        a: u8,
+        b: u8,
+        #[bw(seek_to_saved = start)]
+        c: u8,
+    }
+
+    check_hint(&SeekToSaved { a: 1, b: 2, c: 3 }, (0, None));
+}
+
+#[test]
+fn size_hint_unit_enum() {
+    #[binwrite]
+    enum Test {
+        #[bw(magic = b"A")]
+        A,
+        #[bw(magic = b"BB")]
+        B,
+    }
+
+    assert_eq!(Test::A.size_hint(), (1, Some(1)));
+    assert_eq!(Test::B.size_hint(), (2, Some(2)));
+
+    #[binwrite]
+    #[bw(repr = u8)]
+    enum Repr {
+        A,
+    }
+
+    assert_eq!(Repr::A.size_hint(), (0, None));
+}
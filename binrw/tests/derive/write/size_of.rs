@@ -0,0 +1,87 @@
+use binrw::{binrw, io::Cursor, BinRead, BinSize, BinWrite};
+
+#[test]
+fn size_of_patches_earlier_field() {
+    #[binrw]
+    #[brw(big)]
+    struct Test {
+        #[bw(size_of = data)]
+        len: u16,
+        flags: u8,
+        #[br(count = len)]
+        #[bw(pad_size_to = 4)]
+        data: Vec<u8>,
+        trailer: u8,
+    }
+
+    let test = Test {
+        len: 0,
+        flags: 0xaa,
+        data: vec![1, 2, 3, 4, 5],
+        trailer: 0xff,
+    };
+
+    let mut out = Cursor::new(Vec::new());
+    test.write(&mut out).unwrap();
+    let data = out.into_inner();
+    assert_eq!(data, b"\0\x05\xaa\x01\x02\x03\x04\x05\xff");
+    assert_eq!(test.binary_size(()).unwrap(), data.len() as u64);
+
+    let test = Test::read(&mut Cursor::new(&data)).unwrap();
+    assert_eq!(test.len, 5);
+    assert_eq!(test.data, [1, 2, 3, 4, 5]);
+
+    let test = Test {
+        len: 0,
+        flags: 0,
+        data: vec![1],
+        trailer: 0,
+    };
+    let mut out = Cursor::new(Vec::new());
+    test.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\0\x04\0\x01\0\0\0\0");
+}
+
+#[test]
+fn size_of_with_map_and_multiple_fields() {
+    #[derive(BinWrite)]
+    #[bw(little)]
+    struct Test {
+        #[bw(size_of = body, map = |len: &u64| *len as u8)]
+        short_len: u64,
+        #[bw(size_of = body, big)]
+        long_len: u32,
+        body: Vec<u16>,
+    }
+
+    let mut out = Cursor::new(Vec::new());
+    Test {
+        short_len: 0,
+        long_len: 0,
+        body: vec![1, 2, 3],
+    }
+    .write(&mut out)
+    .unwrap();
+    assert_eq!(out.into_inner(), b"\x06\0\0\0\x06\x01\0\x02\0\x03\0");
+}
+
+#[test]
+fn size_of_overflow() {
+    #[derive(BinWrite)]
+    struct Test {
+        #[bw(size_of = data)]
+        len: u8,
+        data: Vec<u8>,
+    }
+
+    let error = Test {
+        len: 0,
+        data: vec![0; 0x100],
+    }
+    .write_le(&mut Cursor::new(Vec::new()))
+    .expect_err("accepted size which does not fit");
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::AssertFail { pos: 0, .. }
+    ));
+}
@@ -57,3 +57,30 @@ fn writer_var() {
 
     assert_eq!(out.into_inner(), b"\x01\x02\x03\x04\x0a");
 }
+
+#[test]
+fn field_stream() {
+    #[binwrite]
+    #[bw(little, import(data: &mut Cursor<Vec<u8>>))]
+    struct Test {
+        len: u8,
+        #[bw(stream = data)]
+        a: Vec<u8>,
+        #[bw(stream = data, map = |b: &u16| b + 1)]
+        b: u16,
+    }
+
+    let mut data = Cursor::new(vec![]);
+    let test = Test {
+        len: 5,
+        a: b"hello".to_vec(),
+        b: 0x101,
+    };
+    assert_eq!(test.size_hint(), (1, Some(1)));
+
+    let mut out = Cursor::new(vec![]);
+    test.write_args(&mut out, (&mut data,)).unwrap();
+
+    assert_eq!(out.into_inner(), b"\x05");
+    assert_eq!(data.into_inner(), b"hello\x02\x01");
+}
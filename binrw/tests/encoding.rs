@@ -0,0 +1,85 @@
+#![cfg(feature = "encoding_rs")]
+
+use binrw::{
+    args,
+    encoding::{
+        encoding_rs::{EUC_JP, SHIFT_JIS, UTF_16LE, WINDOWS_1252},
+        EncodedString, EncodingError,
+    },
+    io::Cursor,
+    BinRead, BinWrite,
+};
+
+#[test]
+fn null_terminated() {
+    let mut data = Cursor::new(b"caf\xe9\0rest");
+    let s = EncodedString::read_le_args(&mut data, args! { encoding: WINDOWS_1252 }).unwrap();
+    assert_eq!(*s, "café");
+    assert_eq!(data.position(), 5);
+
+    let mut out = Cursor::new(Vec::new());
+    s.write_le_args(&mut out, args! { encoding: WINDOWS_1252 })
+        .unwrap();
+    assert_eq!(out.into_inner(), b"caf\xe9\0");
+}
+
+#[test]
+fn fixed_size() {
+    let mut data = Cursor::new(b"\xa4\xb3\xa4\xf3\0\0\0\0rest");
+    let s =
+        EncodedString::read_le_args(&mut data, args! { encoding: EUC_JP, count: Some(8) }).unwrap();
+    assert_eq!(*s, "こん");
+    assert_eq!(data.position(), 8);
+
+    let mut out = Cursor::new(Vec::new());
+    s.write_le_args(&mut out, args! { encoding: EUC_JP, count: Some(8) })
+        .unwrap();
+    assert_eq!(out.into_inner(), b"\xa4\xb3\xa4\xf3\0\0\0\0");
+
+    let error = s
+        .write_le_args(
+            &mut Cursor::new(Vec::new()),
+            args! { encoding: EUC_JP, count: Some(3) },
+        )
+        .unwrap_err();
+    assert_eq!(
+        error.custom_err::<EncodingError>(),
+        Some(&EncodingError::TooLong(3))
+    );
+}
+
+#[test]
+fn invalid_data() {
+    let error = EncodedString::read_le_args(
+        &mut Cursor::new(b"\x01\x82\0"),
+        args! { encoding: SHIFT_JIS },
+    )
+    .unwrap_err();
+    assert!(matches!(error, binrw::Error::Custom { pos: 0, .. }));
+    assert_eq!(
+        error.custom_err::<EncodingError>(),
+        Some(&EncodingError::Decode(SHIFT_JIS))
+    );
+    assert_eq!(error.to_string(), "invalid Shift_JIS data at 0x0");
+}
+
+#[test]
+fn unencodable() {
+    let s = EncodedString::from("🦀");
+    let error = s
+        .write_le_args(&mut Cursor::new(Vec::new()), args! { encoding: SHIFT_JIS })
+        .unwrap_err();
+    assert_eq!(
+        error.custom_err::<EncodingError>(),
+        Some(&EncodingError::Encode(SHIFT_JIS))
+    );
+
+    // encoding_rs cannot encode UTF-16
+    let error = s
+        .write_le_args(&mut Cursor::new(Vec::new()), args! { encoding: UTF_16LE })
+        .unwrap_err();
+    assert_eq!(
+        error.custom_err::<EncodingError>(),
+        Some(&EncodingError::Encode(UTF_16LE))
+    );
+}
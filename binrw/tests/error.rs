@@ -1,7 +1,9 @@
 extern crate alloc;
 
+#[cfg(not(feature = "compact-errors"))]
 #[path = "error/backtrace.rs"]
 mod backtrace;
+#[cfg(not(feature = "compact-errors"))]
 #[path = "error/backtrace_2.rs"]
 mod backtrace_2;
 
@@ -40,6 +42,7 @@ fn custom_error_trait() {
 }
 
 #[test]
+#[cfg(not(feature = "compact-errors"))]
 fn display() {
     let err = format!(
         "{}",
@@ -129,6 +132,46 @@ fn enum_is_eol() {
         .is_eof());
 }
 
+#[test]
+fn enum_assertion_summary() {
+    use binrw::{io::Cursor, BinRead};
+
+    #[derive(BinRead, Debug)]
+    #[allow(dead_code)]
+    struct Inner {
+        #[br(assert(v == 2, "v must be 2"))]
+        v: u8,
+    }
+
+    #[derive(BinRead, Debug)]
+    #[allow(dead_code)]
+    #[br(import(kind: u8))]
+    enum Test {
+        #[br(pre_assert(kind == 1, "kind must be 1"))]
+        A(u8),
+        B(Inner),
+        #[br(assert(c == 3, "c was {}", c))]
+        C {
+            c: u8,
+        },
+    }
+
+    let err = Test::read_le_args(&mut Cursor::new(b"\x01"), (7,))
+        .expect_err("accepted bad data")
+        .to_string();
+    assert!(err.contains("failed an assertion"), "{err}");
+    assert!(err.contains("A: kind must be 1 at 0x0"), "{err}");
+    // Only the failed assertion is shown for errors from nested fields
+    assert!(err.contains("B: v must be 2 at 0x0\n"), "{err}");
+    assert!(err.contains("C: c was 1 at 0x0"), "{err}");
+
+    // Variants which fail for other reasons are still shown in full
+    let err = Test::read_le_args(&mut Cursor::new(b""), (7,))
+        .expect_err("accepted bad data")
+        .to_string();
+    assert!(!err.contains("failed an assertion"), "{err}");
+}
+
 #[test]
 fn is_eof() {
     use binrw::{io::Cursor, BinRead};
@@ -159,6 +202,7 @@ fn not_custom_error() {
 }
 
 #[test]
+#[cfg(not(feature = "compact-errors"))]
 fn show_backtrace() {
     use alloc::borrow::Cow;
     use binrw::{io::Cursor, BinReaderExt};
@@ -191,6 +235,7 @@ fn show_backtrace() {
 }
 
 #[test]
+#[cfg(not(feature = "compact-errors"))]
 fn show_backtrace_2() {
     use alloc::borrow::Cow;
     use binrw::{io::Cursor, BinReaderExt};
@@ -221,3 +266,232 @@ fn show_backtrace_2() {
         }
     );
 }
+
+#[test]
+#[cfg(not(feature = "compact-errors"))]
+fn backtrace_field_path() {
+    use binrw::{error::BacktraceFrame, io::Cursor, BinRead};
+
+    #[derive(BinRead, Debug)]
+    #[br(little)]
+    struct Inner {
+        _a: u16,
+        _b: u32,
+    }
+
+    #[derive(BinRead, Debug)]
+    #[br(little)]
+    struct Outer {
+        _header: u8,
+        _inner: Inner,
+    }
+
+    let err = Outer::read(&mut Cursor::new(b"\x01\x02\x03\x04")).unwrap_err();
+    let Error::Backtrace(backtrace) = err else {
+        panic!("not a backtrace: {err:?}");
+    };
+
+    assert!(backtrace.error.is_eof());
+    assert_eq!(backtrace.field_path(), "Outer._inner._b");
+    assert!(matches!(
+        backtrace.frames[..],
+        [
+            BacktraceFrame::Field {
+                type_name: "Inner",
+                field: "_b",
+                pos: Some(3),
+                ..
+            },
+            BacktraceFrame::Field {
+                type_name: "Outer",
+                field: "_inner",
+                pos: Some(1),
+                ..
+            },
+        ]
+    ));
+}
+
+#[test]
+fn custom_error_type() {
+    use binrw::{error::ReadError, io::Cursor, BinRead};
+
+    #[derive(Debug, PartialEq)]
+    enum FormatError {
+        BadVersion(u8),
+        Other(String),
+    }
+
+    impl core::fmt::Display for FormatError {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "{self:?}")
+        }
+    }
+
+    impl From<Error> for FormatError {
+        fn from(err: Error) -> Self {
+            Self::Other(err.root_cause().to_string())
+        }
+    }
+
+    #[derive(BinRead, Debug)]
+    #[br(big, error = FormatError)]
+    struct Header {
+        #[br(assert(version == 1, FormatError::BadVersion(version)))]
+        version: u8,
+        _len: u16,
+    }
+
+    assert_eq!(
+        Header::try_read(&mut Cursor::new(b"\x01\0\x02"))
+            .unwrap()
+            .version,
+        1
+    );
+    assert_eq!(
+        Header::try_read(&mut Cursor::new(b"\x02\0\x02")).unwrap_err(),
+        FormatError::BadVersion(2)
+    );
+    assert!(matches!(
+        Header::try_read(&mut Cursor::new(b"\x01\0")).unwrap_err(),
+        FormatError::Other(_)
+    ));
+}
+
+#[test]
+#[cfg(not(feature = "compact-errors"))]
+fn offset_out_of_bounds() {
+    use binrw::{io::Cursor, BinRead, FilePtr, FilePtr8};
+
+    #[derive(BinRead, Debug)]
+    struct Test {
+        _len: u8,
+        #[br(offset = 2)]
+        _ptr: FilePtr8<u8>,
+    }
+
+    #[derive(BinRead, Debug)]
+    struct Signed {
+        _ptr: FilePtr<i8, u8>,
+    }
+
+    assert!(Test::read_be(&mut Cursor::new(b"\x01\x00\x05")).is_ok());
+
+    let err = Test::read_be(&mut Cursor::new(b"\x01\x02\x05")).unwrap_err();
+    let Error::Backtrace(backtrace) = err else {
+        panic!("not a backtrace: {err:?}");
+    };
+    assert_eq!(backtrace.field_path(), "Test._ptr");
+    assert!(matches!(
+        *backtrace.error,
+        Error::OffsetOutOfBounds {
+            pos: 2,
+            offset: 4,
+            len: 3
+        }
+    ));
+
+    let err = Signed::read_be(&mut Cursor::new(b"\xfe")).unwrap_err();
+    assert!(matches!(
+        err.root_cause(),
+        Error::OffsetOutOfBounds {
+            pos: 1,
+            offset: -2,
+            len: 1
+        }
+    ));
+    assert!(err
+        .root_cause()
+        .to_string()
+        .starts_with("offset -0x2 is out of bounds for stream of length 0x1 at 0x1"));
+}
+
+#[test]
+#[cfg(not(feature = "compact-errors"))]
+fn offset_out_of_bounds_pos() {
+    use binrw::{
+        io::{Cursor, Read, Seek, SeekFrom},
+        BinRead, FilePtr16,
+    };
+
+    #[derive(BinRead, Debug)]
+    #[br(big)]
+    struct Test {
+        _header: u16,
+        _ptr: FilePtr16<u8>,
+    }
+
+    // The position is the one immediately after the pointer
+    let err = Test::read(&mut Cursor::new(b"\0\0\0\x09\xff")).unwrap_err();
+    assert!(matches!(
+        err.root_cause(),
+        Error::OffsetOutOfBounds {
+            pos: 4,
+            offset: 9,
+            len: 5
+        }
+    ));
+
+    // Valid pointers do not need the length of the stream
+    struct NoEnd(Cursor<&'static [u8]>);
+
+    impl Read for NoEnd {
+        fn read(&mut self, buf: &mut [u8]) -> binrw::io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+
+    impl Seek for NoEnd {
+        fn seek(&mut self, pos: SeekFrom) -> binrw::io::Result<u64> {
+            match pos {
+                SeekFrom::End(_) => Err(binrw::io::Error::new(
+                    binrw::io::ErrorKind::Unsupported,
+                    "unknown length",
+                )),
+                pos => self.0.seek(pos),
+            }
+        }
+    }
+
+    Test::read(&mut NoEnd(Cursor::new(b"\0\0\0\x04\xff"))).unwrap();
+}
+
+#[test]
+#[cfg(feature = "compact-errors")]
+fn compact_errors() {
+    use binrw::{
+        error::{ContextExt, FoundMagic},
+        io::Cursor,
+        BinRead,
+    };
+
+    #[allow(dead_code)]
+    #[derive(BinRead, Debug)]
+    #[br(big, magic = b"AB")]
+    struct Test {
+        #[br(assert(a != 0, "a must not be zero"))]
+        a: u8,
+    }
+
+    match Test::read(&mut Cursor::new(b"CD\x01")).unwrap_err() {
+        Error::BadMagic { pos: 0, found } => assert_eq!(format!("{found:?}"), "[67, 68]"),
+        err => panic!("unexpected error: {err:?}"),
+    }
+
+    match Test::read(&mut Cursor::new(b"AB\0")).unwrap_err() {
+        Error::AssertFail { message, .. } => {
+            assert!(matches!(
+                message,
+                alloc::borrow::Cow::Borrowed("a must not be zero")
+            ));
+        }
+        err => panic!("unexpected error: {err:?}"),
+    }
+
+    let found = format!("{:?}", FoundMagic::new(&"é".repeat(FoundMagic::CAPACITY)));
+    assert!(found.strip_suffix('…').unwrap().len() <= FoundMagic::CAPACITY);
+
+    // Context which is added explicitly is kept
+    let err = Error::NoVariantMatch { pos: 0 }.with_context("context");
+    assert!(matches!(err, Error::Backtrace(_)));
+}
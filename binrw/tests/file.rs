@@ -0,0 +1,66 @@
+#![cfg(feature = "std")]
+
+use binrw::{binrw, BinRead, BinWrite};
+
+#[binrw]
+#[derive(Debug, PartialEq)]
+#[brw(big, magic = b"FILE")]
+struct Test {
+    #[bw(calc_count_of = items)]
+    len: u16,
+    #[br(count = len)]
+    items: Vec<u32>,
+}
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("binrw-{}-{name}", std::process::id()))
+}
+
+#[test]
+fn round_trip() {
+    let path = temp_path("round_trip");
+    let value = Test {
+        items: vec![1, 2, 3],
+    };
+    value.write_to_path(&path).unwrap();
+    assert_eq!(
+        std::fs::read(&path).unwrap(),
+        b"FILE\0\x03\0\0\0\x01\0\0\0\x02\0\0\0\x03"
+    );
+    assert_eq!(Test::read_from_path(&path).unwrap(), value);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[cfg(not(feature = "compact-errors"))]
+fn path_in_errors() {
+    let path = temp_path("missing");
+    let error = Test::read_from_path(&path).unwrap_err();
+    assert!(matches!(error.root_cause(), binrw::Error::Io(_)));
+    assert!(
+        error
+            .to_string()
+            .contains(&format!("While reading file '{}'", path.display())),
+        "{error}"
+    );
+
+    let path = temp_path("truncated");
+    std::fs::write(&path, b"FILE\0\x02\0\0\0\x01").unwrap();
+    let error = Test::read_from_path(&path).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert!(
+        error
+            .to_string()
+            .contains(&format!("While reading file '{}'", path.display())),
+        "{error}"
+    );
+
+    let path = temp_path("missing-dir").join("out");
+    let error = Test { items: vec![] }.write_to_path(&path).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains(&format!("While writing file '{}'", path.display())),
+        "{error}"
+    );
+}
@@ -0,0 +1,72 @@
+use binrw::{fixed_point::FixedPoint, io::Cursor, BinReaderExt, BinWrite};
+
+#[test]
+fn read_write() {
+    let value: FixedPoint<i32, 16> = Cursor::new(b"\xff\xfe\x80\x00").read_be().unwrap();
+    assert_eq!(value, FixedPoint(-0x18000));
+    assert_eq!(value.to_f64(), -1.5);
+
+    let mut out = Cursor::new(Vec::new());
+    value.write_be(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\xff\xfe\x80\x00");
+
+    let value: FixedPoint<u16, 8> = Cursor::new(b"\x80\x01").read_le().unwrap();
+    assert_eq!(value.to_f32(), 1.5);
+    assert_eq!(value.to_string(), "1.5");
+    assert_eq!(f64::from(value), 1.5);
+}
+
+#[test]
+fn from_float() {
+    assert_eq!(
+        FixedPoint::<i32, 16>::from_f64(-1.5),
+        Some(FixedPoint(-0x18000))
+    );
+    assert_eq!(FixedPoint::<i16, 8>::from_f32(0.25), Some(FixedPoint(0x40)));
+
+    // Rounds to the nearest value, with ties away from zero
+    assert_eq!(
+        FixedPoint::<i16, 8>::from_f64(1.0 / 512.0),
+        Some(FixedPoint(1))
+    );
+    assert_eq!(
+        FixedPoint::<i16, 8>::from_f64(-1.0 / 512.0),
+        Some(FixedPoint(-1))
+    );
+    assert_eq!(
+        FixedPoint::<i16, 8>::from_f64(1.0 / 1024.0),
+        Some(FixedPoint(0))
+    );
+    // Adding 0.5 to the largest value below 0.5 rounds up to 1.0
+    assert_eq!(
+        FixedPoint::<i16, 0>::from_f64(0.499_999_999_999_999_94),
+        Some(FixedPoint(0))
+    );
+    // Adding 0.5 to odd values above 2^52 rounds up to the next even value
+    assert_eq!(
+        FixedPoint::<i64, 0>::from_f64(4_503_599_627_370_497.0),
+        Some(FixedPoint(4_503_599_627_370_497))
+    );
+    assert_eq!(
+        FixedPoint::<i64, 0>::from_f64(-4_503_599_627_370_497.0),
+        Some(FixedPoint(-4_503_599_627_370_497))
+    );
+
+    assert_eq!(
+        FixedPoint::<i16, 8>::from_f64(127.99),
+        Some(FixedPoint(0x7ffd))
+    );
+    assert_eq!(FixedPoint::<i16, 8>::from_f64(128.0), None);
+    assert_eq!(
+        FixedPoint::<i16, 8>::from_f64(-128.0),
+        Some(FixedPoint(i16::MIN))
+    );
+    assert_eq!(FixedPoint::<u8, 4>::from_f64(-0.5), None);
+    assert_eq!(FixedPoint::<i64, 32>::from_f64(f64::NAN), None);
+    assert_eq!(FixedPoint::<i64, 32>::from_f64(f64::INFINITY), None);
+    assert_eq!(
+        FixedPoint::<u32, 32>::from_f64(0.5),
+        Some(FixedPoint(0x8000_0000))
+    );
+    assert_eq!(FixedPoint::<u8, 0>::from_f64(255.0), Some(FixedPoint(255)));
+}
@@ -0,0 +1,133 @@
+use binrw::{binrw, fuzz, Endian, Error};
+
+#[binrw]
+#[derive(Clone, Debug, PartialEq)]
+struct Stable {
+    #[bw(calc = data.len() as u8)]
+    len: u8,
+    #[br(count = len)]
+    data: Vec<u16>,
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Stable {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let len = u.int_in_range(0..=u8::MAX)?;
+        let data = (0..len)
+            .map(|_| u.arbitrary())
+            .collect::<arbitrary::Result<_>>()?;
+        Ok(Self { data })
+    }
+}
+
+#[test]
+fn roundtrip() {
+    assert_eq!(
+        fuzz::roundtrip::<Stable>(b"\x02\x01\0\x02\0").unwrap(),
+        Some(Stable { data: vec![1, 2] })
+    );
+    // Trailing bytes are ignored
+    assert_eq!(
+        fuzz::roundtrip::<Stable>(b"\0\xff").unwrap(),
+        Some(Stable { data: vec![] })
+    );
+    assert_eq!(fuzz::roundtrip::<Stable>(b"\x02\x01").unwrap(), None);
+}
+
+#[test]
+fn value_divergence() {
+    #[binrw]
+    #[derive(Clone, Debug, PartialEq)]
+    struct Lossy {
+        #[br(map = |x: u8| x.saturating_add(1))]
+        value: u8,
+    }
+
+    let err = fuzz::roundtrip::<Lossy>(b"\x05").unwrap_err();
+    assert!(matches!(
+        err,
+        fuzz::Divergence::Value { first: Lossy { value: 6 }, second: Lossy { value: 7 }, ref written } if written == b"\x06"
+    ));
+    assert_eq!(
+        err.to_string(),
+        "Lossy { value: 6 } was written as [06], which reads as Lossy { value: 7 }"
+    );
+
+    assert!(fuzz::roundtrip_value(&Lossy { value: 255 }, Endian::Little, (), ()).is_ok());
+    assert!(matches!(
+        fuzz::roundtrip_value(&Lossy { value: 1 }, Endian::Big, (), ()),
+        Err(fuzz::Divergence::Value { .. })
+    ));
+    assert!(fuzz::roundtrip_value(&Stable { data: vec![3] }, Endian::Big, (), ()).is_ok());
+}
+
+#[test]
+fn roundtrip_write_and_reread_errors() {
+    #[binrw]
+    #[derive(Clone, Debug, PartialEq)]
+    struct Unwritable {
+        #[bw(assert(*value < 10))]
+        value: u8,
+    }
+
+    assert!(matches!(
+        fuzz::roundtrip::<Unwritable>(b"\x0a"),
+        Err(fuzz::Divergence::Write {
+            value: Unwritable { value: 10 },
+            error: Error::AssertFail { .. }
+        })
+    ));
+    // Unwritable values are not a divergence when starting from a value
+    assert!(fuzz::roundtrip_value(&Unwritable { value: 10 }, Endian::Little, (), ()).is_ok());
+
+    #[binrw]
+    #[derive(Clone, Debug, PartialEq)]
+    struct Unreadable {
+        #[br(assert(value != 0))]
+        #[bw(map = |_| 0u8)]
+        value: u8,
+    }
+
+    assert!(matches!(
+        fuzz::roundtrip::<Unreadable>(b"\x01"),
+        Err(fuzz::Divergence::Reread { value: Unreadable { value: 1 }, ref written, .. }) if written == b"\0"
+    ));
+}
+
+#[test]
+#[cfg(feature = "arbitrary")]
+fn roundtrip_arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    #[binrw]
+    #[derive(Clone, Debug, PartialEq)]
+    struct Lossy {
+        #[br(map = |x: u8| x.saturating_add(1))]
+        value: u8,
+    }
+
+    impl<'a> Arbitrary<'a> for Lossy {
+        fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+            Ok(Self {
+                value: u.arbitrary()?,
+            })
+        }
+    }
+
+    for data in [&b""[..], b"\x02\x01\0\x02\0", &[0xff; 64]] {
+        assert!(fuzz::roundtrip_arbitrary::<Stable>(data).unwrap().is_some());
+    }
+
+    assert_eq!(
+        fuzz::roundtrip_arbitrary::<Lossy>(b"\xff").unwrap(),
+        Some(Lossy { value: 255 })
+    );
+    assert!(matches!(
+        fuzz::roundtrip_arbitrary::<Lossy>(b"\x05"),
+        Err(fuzz::Divergence::Value {
+            first: Lossy { value: 5 },
+            second: Lossy { value: 6 },
+            ..
+        })
+    ));
+}
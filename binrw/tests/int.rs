@@ -0,0 +1,68 @@
+mod common;
+
+use binrw::{BinWrite, Endian, I24, I48, U24, U40, U48, U56};
+use common::round_trip;
+
+#[test]
+fn unsigned() {
+    round_trip(b"\x01\x02\x03", Endian::Big, U24::new(0x01_0203).unwrap());
+    round_trip(
+        b"\x03\x02\x01",
+        Endian::Little,
+        U24::new(0x01_0203).unwrap(),
+    );
+    round_trip(b"\xff\xff\xff", Endian::Big, U24::MAX);
+    round_trip(b"\xff\xff\xff", Endian::Little, U24::MAX);
+    round_trip(
+        b"\x01\x02\x03\x04\x05",
+        Endian::Big,
+        U40::new(0x01_0203_0405).unwrap(),
+    );
+    round_trip(
+        b"\x05\x04\x03\x02\x01",
+        Endian::Little,
+        U40::new(0x01_0203_0405).unwrap(),
+    );
+    round_trip(b"\0\0\0\0\0\x01", Endian::Big, U48::from(1_u8));
+    round_trip(b"\x01\0\0\0\0\0", Endian::Little, U48::from(1_u8));
+    round_trip(b"\xff\xff\xff\xff\xff\xff\xff", Endian::Big, U56::MAX);
+    round_trip(b"\xff\xff\xff\xff\xff\xff\xff", Endian::Little, U56::MAX);
+}
+
+#[test]
+fn signed() {
+    round_trip(b"\xff\xff\xfe", Endian::Big, I24::new(-2).unwrap());
+    round_trip(b"\xfe\xff\xff", Endian::Little, I24::new(-2).unwrap());
+    round_trip(b"\x80\0\0", Endian::Big, I24::MIN);
+    round_trip(b"\0\0\x80", Endian::Little, I24::MIN);
+    round_trip(b"\x7f\xff\xff", Endian::Big, I24::MAX);
+    round_trip(b"\xff\xff\x7f", Endian::Little, I24::MAX);
+    round_trip(b"\xff\xff\xff\xff\xff\xff", Endian::Big, I48::from(-1_i8));
+    round_trip(
+        b"\xff\xff\xff\xff\xff\xff",
+        Endian::Little,
+        I48::from(-1_i8),
+    );
+    round_trip(b"\x80\0\0\0\0\0", Endian::Big, I48::MIN);
+    round_trip(b"\0\0\0\0\0\x80", Endian::Little, I48::MIN);
+}
+
+#[test]
+fn conversions() {
+    assert_eq!(U24::MAX.get(), 0xff_ffff);
+    assert_eq!(I24::MIN.get(), -0x80_0000);
+    assert_eq!(U24::BITS, 24);
+    assert_eq!(U56::BYTES, 7);
+
+    assert!(U24::try_from(0x100_0000_u32).is_err());
+    assert!(I24::try_from(0x80_0000_i32).is_err());
+    assert!(I24::try_from(-0x80_0001_i32).is_err());
+    assert_eq!(U24::new_truncated(0x1ff_ffff), U24::MAX);
+    assert_eq!(I24::new_truncated(0xff_ffff), I24::new(-1).unwrap());
+
+    assert_eq!(u64::from(U24::MAX) + 1, 0x100_0000);
+    assert_eq!(i64::from(U48::MAX), 0xffff_ffff_ffff);
+    assert_eq!(i32::from(I24::from(-5_i16)) * 2, -10);
+    assert_eq!(U40::from(7_u32).to_string(), "7");
+    assert_eq!(U24::default().size_hint(), (3, Some(3)));
+}
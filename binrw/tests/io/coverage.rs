@@ -0,0 +1,54 @@
+#![allow(clippy::single_range_in_vec_init)]
+use binrw::io::{CoverageReader, Cursor, Read, Seek, SeekFrom};
+
+#[test]
+fn coverage_reader() {
+    let mut reader = CoverageReader::new(Cursor::new(b"0123456789abcdef"));
+    let mut buf = [0; 4];
+    assert!(reader.ranges().is_empty());
+    assert_eq!(reader.holes(16), [0..16]);
+
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"0123");
+    reader.seek(SeekFrom::Start(8)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(reader.stream_position().unwrap(), 12);
+    assert_eq!(reader.ranges(), [0..4, 8..12]);
+    assert_eq!(reader.holes(16), [4..8, 12..16]);
+    assert_eq!(reader.holes(10), [4..8]);
+
+    // Overlapping and adjacent reads are merged
+    reader.seek(SeekFrom::Start(2)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    reader.read_exact(&mut buf[..2]).unwrap();
+    assert_eq!(reader.ranges(), [0..12]);
+    assert_eq!(reader.holes(16), [12..16]);
+
+    // Reading at EOF records nothing
+    reader.seek(SeekFrom::End(0)).unwrap();
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    assert_eq!(reader.ranges(), [0..12]);
+
+    reader.clear();
+    assert!(reader.ranges().is_empty());
+    assert_eq!(reader.into_inner().position(), 16);
+}
+
+#[test]
+fn coverage_reader_derive() {
+    use binrw::{binread, BinRead};
+
+    #[binread]
+    #[br(big)]
+    struct Test {
+        #[br(temp)]
+        len: u8,
+        #[br(count = len, pad_after = 1)]
+        _data: Vec<u8>,
+        _tail: u16,
+    }
+
+    let mut reader = CoverageReader::new(Cursor::new(b"\x02ab\xff\0\x01\xee"));
+    Test::read(&mut reader).unwrap();
+    assert_eq!(reader.holes(7), [3..4, 6..7]);
+}
@@ -1,5 +1,6 @@
 #[cfg(feature = "std")]
 mod bufreader;
+mod coverage;
 #[cfg(not(feature = "std"))]
 mod no_std;
 mod seek;
@@ -9,8 +9,6 @@ fn read() {
     assert_eq!(stream.stream_position().unwrap(), 0);
     assert_eq!(stream.seek(SeekFrom::Start(0)).unwrap(), 0);
     assert_eq!(stream.stream_position().unwrap(), 0);
-    stream.seek(SeekFrom::Start(1)).unwrap_err();
-    stream.seek(SeekFrom::Current(1)).unwrap_err();
     stream.seek(SeekFrom::Current(-1)).unwrap_err();
     stream.seek(SeekFrom::End(0)).unwrap_err();
 
@@ -83,7 +81,6 @@ fn write() {
     assert_eq!(stream.seek(SeekFrom::Start(10)).unwrap(), 10);
     assert_eq!(stream.get_ref(), b"helloworld");
     stream.seek(SeekFrom::Start(1)).unwrap_err();
-    stream.seek(SeekFrom::Current(1)).unwrap_err();
     stream.seek(SeekFrom::Current(-1)).unwrap_err();
     stream.seek(SeekFrom::End(0)).unwrap_err();
 
@@ -99,6 +96,67 @@ fn write() {
     assert!(stream.get_ref().flushed);
 }
 
+#[test]
+fn read_skip() {
+    let mut stream = NoSeek::new(b"helloworld".as_slice());
+    let mut buf = [0; 2];
+
+    assert_eq!(stream.seek(SeekFrom::Start(2)).unwrap(), 2);
+    assert_eq!(stream.seek(SeekFrom::Current(1)).unwrap(), 3);
+    assert_eq!(stream.stream_position().unwrap(), 3);
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"lo");
+    assert_eq!(stream.stream_position().unwrap(), 5);
+    stream.seek(SeekFrom::Start(4)).unwrap_err();
+
+    assert_eq!(stream.seek(SeekFrom::Current(3)).unwrap(), 8);
+    let mut rest = Vec::new();
+    stream.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"ld");
+
+    let mut stream = NoSeek::new(b"abc".as_slice());
+    stream.seek(SeekFrom::Start(4)).unwrap();
+    assert_eq!(
+        stream.read(&mut buf).unwrap_err().kind(),
+        binrw::io::ErrorKind::UnexpectedEof
+    );
+}
+
+#[test]
+fn write_skip() {
+    let mut stream = NoSeek::new(Vec::new());
+    stream.write_all(b"ab").unwrap();
+    assert_eq!(stream.seek(SeekFrom::Current(3)).unwrap(), 5);
+    assert_eq!(stream.get_ref(), b"ab");
+    stream.write_all(b"cd").unwrap();
+    assert_eq!(stream.get_ref(), b"ab\0\0\0cd");
+
+    stream.seek(SeekFrom::Start(300)).unwrap();
+    stream.flush().unwrap();
+    assert_eq!(stream.get_ref().len(), 300);
+    assert_eq!(stream.stream_position().unwrap(), 300);
+}
+
+#[test]
+fn derive_skip() {
+    use binrw::{BinRead, BinWrite};
+
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(little)]
+    struct Test {
+        a: u8,
+        #[brw(pad_before = 2, seek_before = SeekFrom::Current(1))]
+        b: u16,
+    }
+
+    let value = Test::read(&mut NoSeek::new(b"\x01\xff\xff\xff\x02\x03".as_slice())).unwrap();
+    assert_eq!(value, Test { a: 1, b: 0x302 });
+
+    let mut stream = NoSeek::new(Vec::new());
+    value.write(&mut stream).unwrap();
+    assert_eq!(stream.into_inner(), b"\x01\0\0\0\x02\x03");
+}
+
 #[test]
 #[cfg(feature = "std")]
 fn write_vectored() {
@@ -52,3 +52,24 @@ fn take_seek_ref() {
     assert_eq!(data.take_seek(5).read(&mut buf).unwrap(), 1);
     assert_eq!(&buf, b"dworl");
 }
+
+#[test]
+fn take_window() {
+    let data = &mut Cursor::new(b"hello world");
+    data.seek(SeekFrom::Start(6)).unwrap();
+    let mut buf = [0; 5];
+    let mut window = data.take_window(3);
+    assert_eq!(window.seek(SeekFrom::Start(0)).unwrap(), 6);
+    assert_eq!(window.seek(SeekFrom::End(0)).unwrap(), 9);
+    assert_eq!(window.seek(SeekFrom::End(5)).unwrap(), 9);
+    assert_eq!(window.read(&mut buf).unwrap(), 0);
+    assert_eq!(window.seek(SeekFrom::Current(-2)).unwrap(), 7);
+    assert_eq!(window.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"or");
+    window.seek(SeekFrom::Current(-10)).unwrap_err();
+    window.set_limit(1);
+    assert_eq!(window.seek(SeekFrom::Start(0)).unwrap(), 9);
+    assert_eq!(window.read(&mut buf).unwrap(), 1);
+    assert_eq!(&buf[..1], b"l");
+    assert_eq!(window.into_inner().position(), 10);
+}
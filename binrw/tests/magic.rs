@@ -0,0 +1,70 @@
+use binrw::{
+    io::Cursor,
+    meta::{ReadMagic, WriteMagic},
+    BinRead, BinReaderExt, BinWrite, BinWriterExt, Magic,
+};
+
+struct Riff;
+
+impl ReadMagic for Riff {
+    type MagicType = u32;
+    const MAGIC: Self::MagicType = 0x5249_4646;
+}
+
+impl WriteMagic for Riff {
+    type MagicType = u32;
+    const MAGIC: Self::MagicType = 0x5249_4646;
+}
+
+#[test]
+fn magic_marker() {
+    let magic: Magic<Riff> = Cursor::new(b"RIFF").read_be().unwrap();
+    assert_eq!(magic, Magic::new());
+    assert_eq!(magic.size_hint(), (4, Some(4)));
+
+    let mut out = Cursor::new(Vec::new());
+    out.write_le(&magic).unwrap();
+    assert_eq!(out.into_inner(), b"FFIR");
+}
+
+#[test]
+fn magic_marker_mismatch() {
+    let mut data = Cursor::new(b"\0\0RIFF");
+    let error = data.read_be::<Magic<Riff>>().unwrap_err();
+    assert!(matches!(error, binrw::Error::BadMagic { pos: 0, .. }));
+    assert_eq!(data.position(), 0);
+}
+
+#[test]
+fn magic_marker_derived() {
+    #[derive(BinRead, BinWrite)]
+    #[brw(magic = b"TEST")]
+    struct Test {
+        #[allow(dead_code)]
+        a: u8,
+    }
+
+    let mut data = Cursor::new(b"TESTRIFF");
+    data.read_le::<Magic<Test>>().unwrap();
+    assert_eq!(data.position(), 4);
+}
+
+#[test]
+fn magic_marker_generic() {
+    #[derive(BinRead, BinWrite)]
+    #[brw(little)]
+    struct Chunk<M>
+    where
+        M: ReadMagic<MagicType = u32> + WriteMagic<MagicType = u32> + 'static,
+    {
+        magic: Magic<M>,
+        len: u32,
+    }
+
+    let chunk = Chunk::<Riff>::read(&mut Cursor::new(b"FFIR\x04\0\0\0")).unwrap();
+    assert_eq!(chunk.len, 4);
+
+    let mut out = Cursor::new(Vec::new());
+    chunk.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"FFIR\x04\0\0\0");
+}
@@ -55,3 +55,158 @@ fn write_endian() {
     assert_eq!(<(u8, Big)>::ENDIAN, EndianKind::Mixed);
     assert_eq!(<(u8, Big)>::ENDIAN.endian(), None);
 }
+
+#[test]
+fn audit() {
+    use binrw::{
+        meta::{ReadAudit, WriteAudit},
+        BinRead, BinWrite,
+    };
+
+    #[derive(BinRead, BinWrite)]
+    #[brw(audit)]
+    struct Small(u8);
+
+    #[derive(BinRead, BinWrite)]
+    #[brw(audit, big, magic = b"BIG")]
+    struct Large {
+        len: u32,
+        #[br(count = len)]
+        data: Vec<u16>,
+        #[br(if(len > 1))]
+        tail: Option<u64>,
+    }
+
+    #[derive(BinRead, BinWrite)]
+    #[brw(audit, repr = u8)]
+    enum Unit {
+        A,
+        B,
+    }
+
+    const { assert!(<Small as ReadAudit>::CODE_SIZE > 0) };
+    const { assert!(<Small as WriteAudit>::CODE_SIZE > 0) };
+    const { assert!(<Small as ReadAudit>::CODE_SIZE < <Large as ReadAudit>::CODE_SIZE) };
+    const { assert!(<Small as WriteAudit>::CODE_SIZE < <Large as WriteAudit>::CODE_SIZE) };
+    const { assert!(<Unit as ReadAudit>::CODE_SIZE > 0) };
+}
+
+#[test]
+fn schema() {
+    use binrw::{
+        binrw,
+        schema::{Endianness, Field, Layout, ReadSchema, Variant, WriteSchema},
+        BinRead, BinWrite, Endian,
+    };
+
+    #[binrw]
+    #[brw(schema, big, magic = b"HDR")]
+    struct Header {
+        #[br(temp)]
+        #[bw(calc = data.len() as u32)]
+        len: u32,
+        #[br(count = len)]
+        data: Vec<u16>,
+        #[brw(little, if(len > 1))]
+        tail: Option<u64>,
+        #[brw(ignore)]
+        cache: u8,
+    }
+
+    #[allow(dead_code)]
+    #[derive(BinRead)]
+    #[br(schema, import(version: u8))]
+    enum Command {
+        #[br(magic = 0u8)]
+        Nop,
+        #[br(magic = 1u8, is_big = version > 1)]
+        Jump { loc: [u8; 4] },
+    }
+
+    #[derive(BinRead, BinWrite)]
+    #[brw(schema, repr = u8)]
+    enum Unit {
+        A,
+        B,
+    }
+
+    #[derive(BinRead)]
+    #[br(schema, map = |x: u8| Self(x.into()))]
+    struct Mapped(u16);
+
+    let field = |name, ty| Field {
+        name,
+        ty,
+        endian: Endianness::Inherited,
+        magic: None,
+        count: None,
+        condition: None,
+        pad_before: None,
+        pad_after: None,
+        align_before: None,
+        align_after: None,
+        pad_size_to: None,
+        temp: false,
+    };
+
+    let schema = <Header as ReadSchema>::SCHEMA;
+    assert_eq!(schema.name, "Header");
+    assert_eq!(schema.endian, Endianness::Fixed(Endian::Big));
+    assert_eq!(schema.magic, Some("b\"HDR\""));
+    assert_eq!(schema.repr, None);
+    let Layout::Struct(fields) = schema.layout else {
+        panic!("expected a struct layout");
+    };
+    assert_eq!(
+        fields,
+        [
+            Field {
+                temp: true,
+                ..field("len", "u32")
+            },
+            Field {
+                count: Some("len"),
+                ..field("data", "Vec<u16>")
+            },
+            Field {
+                endian: Endianness::Fixed(Endian::Little),
+                condition: Some("len > 1"),
+                ..field("tail", "Option<u64>")
+            },
+        ]
+    );
+
+    let Layout::Struct(fields) = <Header as WriteSchema>::SCHEMA.layout else {
+        panic!("expected a struct layout");
+    };
+    assert_eq!(
+        fields.iter().map(|field| field.name).collect::<Vec<_>>(),
+        ["len", "data", "tail"]
+    );
+    assert_eq!(fields[1].count, None);
+
+    let Layout::Enum(variants) = Command::SCHEMA.layout else {
+        panic!("expected an enum layout");
+    };
+    assert_eq!(
+        variants[0],
+        Variant {
+            name: "Nop",
+            endian: Endianness::Inherited,
+            magic: Some("0u8"),
+            fields: &[],
+        }
+    );
+    assert_eq!(variants[1].name, "Jump");
+    assert_eq!(
+        variants[1].endian,
+        Endianness::Runtime(Endian::Big, "version > 1")
+    );
+    assert_eq!(variants[1].fields, [field("loc", "[u8; 4]")]);
+
+    let schema = <Unit as ReadSchema>::SCHEMA;
+    assert_eq!(schema.repr, Some("u8"));
+    assert!(matches!(schema.layout, Layout::Enum(variants) if variants.len() == 2));
+
+    assert_eq!(Mapped::SCHEMA.layout, Layout::Mapped);
+}
@@ -56,3 +56,85 @@ fn test() {
     assert_eq!(x.borrow, &s);
     assert_eq!(x.array, [42; 2]);
 }
+
+#[test]
+fn custom_parser_args() {
+    use binrw::{binread, io::Cursor, BinRead, BinResult};
+
+    #[derive(Clone, NamedArgs)]
+    struct ScaleArgs {
+        #[named_args(default = 1)]
+        scale: u32,
+        #[named_args(default = 0)]
+        bias: u32,
+    }
+
+    #[binrw::parser(reader, endian, args_raw)]
+    fn scaled(args: ScaleArgs) -> BinResult<u32> {
+        Ok(u32::from(u8::read_options(reader, endian, ())?) * args.scale + args.bias)
+    }
+
+    #[binread]
+    struct Test {
+        #[br(parse_with = scaled, args {})]
+        a: u32,
+        #[br(parse_with = scaled, args { scale: 10 })]
+        b: u32,
+        #[br(parse_with = scaled, args { bias: 5, scale: 2 })]
+        c: u32,
+    }
+
+    let test = Test::read_le(&mut Cursor::new(b"\x01\x02\x03")).unwrap();
+    assert_eq!((test.a, test.b, test.c), (1, 20, 11));
+}
+
+#[test]
+fn args_of() {
+    use binrw::{
+        helpers::{args_of, args_type_name, write_args_of, ArgsOf, WriteArgsOf},
+        io::{Cursor, Read, Seek},
+        BinRead, BinResult, BinWrite, Endian,
+    };
+
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(import { add: u8 = 1 })]
+    struct Test {
+        #[br(map = |x: u8| x + add)]
+        #[bw(map = |x| x - add)]
+        a: u8,
+    }
+
+    struct Wrapper<T: BinRead>(T);
+
+    impl<T: BinRead> BinRead for Wrapper<T> {
+        type Args<'a> = ArgsOf<'a, T>;
+
+        fn read_options<R: Read + Seek>(
+            reader: &mut R,
+            endian: Endian,
+            args: Self::Args<'_>,
+        ) -> BinResult<Self> {
+            T::read_options(reader, endian, args).map(Self)
+        }
+    }
+
+    // Default arguments use the default values of the imports
+    assert_eq!(
+        Test::read_le(&mut Cursor::new(b"\x01")).unwrap(),
+        Test { a: 2 }
+    );
+
+    let mut data = Cursor::new(b"\x01\x01");
+    let test = Wrapper::<Test>::read_le_args(&mut data, args_of::<Test>()).unwrap();
+    assert_eq!(test.0, Test { a: 2 });
+    let test = Wrapper::<Test>::read_le_args(&mut data, TestBinReadArgs { add: 2 }).unwrap();
+    assert_eq!(test.0, Test { a: 3 });
+
+    let args: WriteArgsOf<'_, Test> = write_args_of::<Test>();
+    let mut out = Cursor::new(Vec::new());
+    test.0.write_le_args(&mut out, args).unwrap();
+    assert_eq!(out.into_inner(), b"\x02");
+
+    assert_eq!(args_type_name::<u8>(), "()");
+    assert!(args_type_name::<Test>().ends_with("TestBinReadArgs"));
+}
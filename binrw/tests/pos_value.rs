@@ -1,7 +1,7 @@
 extern crate alloc;
 
 use alloc::format;
-use binrw::{io::Cursor, BinRead, BinReaderExt, PosValue};
+use binrw::{io::Cursor, BinRead, BinReaderExt, BinWrite, Endian, PosValue};
 
 #[test]
 fn pos_value() {
@@ -24,3 +24,27 @@ fn pos_value() {
     assert_eq!(*clone, *val.b);
     assert_eq!(clone.pos, val.b.pos);
 }
+
+#[test]
+fn pos_value_write() {
+    #[derive(BinRead, BinWrite)]
+    #[brw(big)]
+    struct MyType {
+        a: u16,
+        b: PosValue<u8>,
+    }
+
+    let data = b"\xFF\xFE\xFD";
+    let val = Cursor::new(data).read_be::<MyType>().unwrap();
+    let mut out = Cursor::new(Vec::new());
+    val.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+    assert_eq!(val.b.size_hint(), (1, Some(1)));
+
+    let mut b = val.b;
+    let mut out = Cursor::new(Vec::new());
+    out.set_position(5);
+    b.write_options_updating_pos(&mut out, Endian::Big, ())
+        .unwrap();
+    assert_eq!(b.pos, 5);
+}
@@ -1,14 +1,14 @@
 extern crate alloc;
 
 use alloc::format;
-use binrw::{io::Cursor, punctuated::Punctuated, BinRead, BinReaderExt};
+use binrw::{io::Cursor, punctuated::Punctuated, BinRead, BinReaderExt, BinWrite};
 
-#[derive(BinRead, Clone, Copy, Debug)]
-#[br(magic = 1u8)]
+#[derive(BinRead, BinWrite, Clone, Copy, Debug)]
+#[brw(magic = 1u8)]
 struct One;
 
-#[derive(BinRead, Clone, Copy, Debug)]
-#[br(magic = 2u8)]
+#[derive(BinRead, BinWrite, Clone, Copy, Debug)]
+#[brw(magic = 2u8)]
 struct Two;
 
 #[derive(BinRead)]
@@ -89,3 +89,55 @@ fn punctuated_trailing() {
 //
 //     let _: MissingCountTrailing = x.read_be().unwrap();
 // }
+
+#[test]
+fn punctuated_write() {
+    let mut x = Cursor::new(TEST_DATA);
+    let y: PunctuatedTest = x.read_be().unwrap();
+    let mut out = Cursor::new(Vec::new());
+    y.list.write_be(&mut out).unwrap();
+    assert_eq!(out.into_inner(), &TEST_DATA[1..]);
+
+    let mut x = Cursor::new(TEST_DATA_TRAILING);
+    let mut y: PunctuatedTestTrailing = x.read_be().unwrap();
+    let mut out = Cursor::new(Vec::new());
+    y.list.write_be(&mut out).unwrap();
+    assert_eq!(out.into_inner(), &TEST_DATA_TRAILING[1..]);
+
+    let mut out = Cursor::new(Vec::new());
+    Punctuated::write_separated(&y.list, &mut out, binrw::Endian::Big, ()).unwrap();
+    assert_eq!(out.into_inner(), &TEST_DATA[1..]);
+
+    y.list.separators.truncate(1);
+    let error = y.list.write_be(&mut Cursor::new(Vec::new())).unwrap_err();
+    assert!(
+        matches!(error, binrw::Error::AssertFail { pos: 0, ref message } if message == "expected 2 or 3 separators for 3 values, found 1"),
+        "{error}"
+    );
+    Punctuated::write_separated(
+        &y.list,
+        &mut Cursor::new(Vec::new()),
+        binrw::Endian::Big,
+        (),
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn punctuated_write_with() {
+    #[binrw::binrw]
+    #[brw(big)]
+    struct Test {
+        count: u8,
+
+        #[br(count = count)]
+        #[br(parse_with = Punctuated::<One, Two>::separated_trailing)]
+        #[bw(write_with = Punctuated::write_separated)]
+        list: Punctuated<One, Two>,
+    }
+
+    let value = Test::read(&mut Cursor::new(TEST_DATA_TRAILING)).unwrap();
+    let mut out = Cursor::new(Vec::new());
+    value.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), TEST_DATA);
+}
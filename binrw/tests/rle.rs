@@ -0,0 +1,65 @@
+mod common;
+
+use binrw::{args, io::Cursor, BinRead, BinWrite, Endian, Rle, RleBytes, RleScheme};
+use common::round_trip_args;
+
+#[test]
+fn rle_pairs() {
+    round_trip_args::<Rle<u8>>(
+        b"\x03\x01\x01\x02\x02\x03",
+        Endian::Little,
+        args! { count: 6, scheme: RleScheme::Pairs },
+        RleScheme::Pairs,
+        vec![1, 1, 1, 2, 3, 3].into(),
+    );
+    round_trip_args::<Rle<u16>>(
+        b"\x02\x34\x12\x01\x01\0",
+        Endian::Little,
+        args! { count: 3, scheme: RleScheme::Pairs },
+        RleScheme::Pairs,
+        vec![0x1234, 0x1234, 1].into(),
+    );
+}
+
+#[test]
+fn rle_pairs_long_run() {
+    let rle = RleBytes::from(vec![9; 300]);
+    let mut out = Cursor::new(Vec::new());
+    rle.write_le(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\xff\x09\x2d\x09");
+}
+
+#[test]
+fn rle_packbits() {
+    round_trip_args::<Rle<u8>>(
+        b"\xfe\xaa\x02\x80\0\x2a\xfd\xaa",
+        Endian::Little,
+        args! { count: 10, scheme: RleScheme::PackBits },
+        RleScheme::PackBits,
+        vec![0xaa, 0xaa, 0xaa, 0x80, 0, 0x2a, 0xaa, 0xaa, 0xaa, 0xaa].into(),
+    );
+    round_trip_args::<Rle<u8>>(
+        b"\0\x01",
+        Endian::Little,
+        args! { count: 1, scheme: RleScheme::PackBits },
+        RleScheme::PackBits,
+        vec![1].into(),
+    );
+}
+
+#[test]
+fn rle_packbits_noop() {
+    let rle = RleBytes::read_le_args(
+        &mut Cursor::new(b"\x80\xff\x05"),
+        args! { count: 2, scheme: RleScheme::PackBits },
+    )
+    .unwrap();
+    assert_eq!(rle.into_inner(), [5, 5]);
+}
+
+#[test]
+fn rle_overlong_run() {
+    let error = RleBytes::read_le_args(&mut Cursor::new(b"\x05\x01"), args! { count: 4 })
+        .expect_err("accepted overlong run");
+    assert!(matches!(error, binrw::Error::AssertFail { pos: 0, .. }));
+}
@@ -0,0 +1,236 @@
+use binrw::{
+    schema::{c, template, ReadSchema},
+    BinRead, NullString,
+};
+
+#[allow(dead_code)]
+#[derive(BinRead)]
+#[br(big, schema)]
+struct Entry {
+    name: NullString,
+    #[br(little)]
+    offset: u32,
+}
+
+#[allow(dead_code)]
+#[derive(BinRead)]
+#[br(schema, repr = u8)]
+enum Kind {
+    File,
+    Directory,
+}
+
+#[allow(dead_code)]
+#[derive(BinRead)]
+#[br(schema, little)]
+enum Body {
+    #[br(magic = b"ENT")]
+    Entries {
+        count: u16,
+        #[br(count = count)]
+        entries: Vec<Entry>,
+    },
+    #[br(magic = 2u16)]
+    Kind(Kind),
+    Unknown(#[br(parse_with = binrw::helpers::until_eof)] Vec<u8>),
+}
+
+#[allow(dead_code)]
+#[derive(BinRead)]
+#[br(big, magic = b"AR\x01", schema)]
+struct Archive {
+    flags: u8,
+    #[br(if(flags > 0), is_little = flags == 2)]
+    extra: Option<i64>,
+    body: Body,
+}
+
+const SCHEMAS: [binrw::schema::Schema; 4] =
+    [Entry::SCHEMA, Kind::SCHEMA, Body::SCHEMA, Archive::SCHEMA];
+
+#[test]
+fn imhex() {
+    assert_eq!(
+        template::imhex(&SCHEMAS),
+        r#"// Generated by binrw
+import std.mem;
+import std.string;
+
+struct Entry {
+    be std::string::NullString name;
+    le u32 offset;
+};
+
+enum Kind : u8 {
+    File,
+    Directory,
+};
+
+struct Body {
+    if (std::mem::read_string($, 3) == "ENT") {
+        // Entries
+        char magic[3]; // "ENT"
+        le u16 count;
+        le Entry entries[count];
+    } else if (std::mem::read_unsigned($, 2, std::mem::Endian::Little) == 2) {
+        // Kind
+        le u16 magic; // 2
+        le Kind self_0;
+    } else {
+        // Unknown
+        le u8 self_0[while(!std::mem::eof())];
+    }
+};
+
+struct Archive {
+    char magic[3]; // "AR\x01"
+    be u8 flags;
+    if (flags > 0) {
+        if (flags == 2) {
+            le s64 extra;
+        } else {
+            be s64 extra;
+        }
+    }
+    be Body body;
+};
+"#
+    );
+}
+
+#[test]
+fn binary_template() {
+    assert_eq!(
+        template::binary_template(&SCHEMAS[1..]),
+        r#"// Generated by binrw
+
+typedef enum <uchar> {
+    File,
+    Directory,
+} Kind;
+
+typedef struct {
+    if (ReadString(FTell(), 3) == "ENT") {
+        // Entries
+        char magic[3]; // "ENT"
+        LittleEndian();
+        ushort count;
+        LittleEndian();
+        Entry entries[count];
+    } else if (ReadUShort(FTell()) == 2) {
+        // Kind
+        LittleEndian();
+        ushort magic; // 2
+        LittleEndian();
+        Kind self_0;
+    } else {
+        // Unknown
+        while (!FEof()) {
+            LittleEndian();
+            uchar self_0;
+        }
+    }
+} Body;
+
+typedef struct {
+    char magic[3]; // "AR\x01"
+    BigEndian();
+    uchar flags;
+    if (flags > 0) {
+        if (flags == 2) {
+            LittleEndian();
+            int64 extra;
+        } else {
+            BigEndian();
+            int64 extra;
+        }
+    }
+    BigEndian();
+    Body body;
+} Archive;
+"#
+    );
+}
+
+#[allow(dead_code)]
+#[derive(BinRead)]
+#[br(little, schema)]
+struct Point {
+    x: i16,
+    #[br(pad_after = 2)]
+    y: i16,
+}
+
+#[allow(dead_code)]
+#[derive(BinRead)]
+#[br(big, magic = 0x55AAu16, schema)]
+struct Packet {
+    kind: Kind,
+    #[br(align_before = 4, count = 2)]
+    points: Vec<Point>,
+    #[br(pad_size_to = 8)]
+    name: [u8; 5],
+    flag: u8,
+}
+
+#[test]
+fn c_header() {
+    assert_eq!(
+        c::header(&[Kind::SCHEMA, Point::SCHEMA, Packet::SCHEMA]).unwrap(),
+        r#"/* Generated by binrw */
+#include <assert.h>
+#include <stdint.h>
+
+typedef uint8_t Kind;
+/* Kind: File, Directory */
+
+#pragma pack(push, 1)
+typedef struct Point {
+    int16_t x; /* little-endian */
+    int16_t y; /* little-endian */
+    uint8_t _pad0[2];
+} Point;
+#pragma pack(pop)
+static_assert(sizeof(Point) == 6, "Point must be 6 bytes");
+
+#pragma pack(push, 1)
+typedef struct Packet {
+    uint16_t magic; /* 0x55AA, big-endian */
+    Kind kind;
+    uint8_t _pad0[1];
+    Point points[2];
+    uint8_t name[5];
+    uint8_t _pad1[3];
+    uint8_t flag;
+} Packet;
+#pragma pack(pop)
+static_assert(sizeof(Packet) == 25, "Packet must be 25 bytes");
+"#
+    );
+}
+
+#[test]
+fn c_header_unrepresentable() {
+    let err = c::header(&[Archive::SCHEMA]).unwrap_err();
+    assert_eq!(
+        err,
+        c::Error {
+            ty: "Archive",
+            field: Some("extra"),
+            reason: c::Reason::Condition,
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "`Archive::extra` cannot be represented in C: it has a condition"
+    );
+
+    assert_eq!(
+        c::header(&[Entry::SCHEMA]).unwrap_err().reason,
+        c::Reason::UnknownType("NullString")
+    );
+    assert_eq!(
+        c::header(&[Body::SCHEMA]).unwrap_err().reason,
+        c::Reason::Enum
+    );
+}
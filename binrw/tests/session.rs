@@ -0,0 +1,116 @@
+use binrw::{
+    binread,
+    io::Cursor,
+    session::{Session, UpdateContext},
+    Endian,
+};
+
+#[derive(Debug, Default, PartialEq)]
+struct State {
+    compressed: bool,
+    count: u32,
+}
+
+#[binread]
+#[derive(Debug, PartialEq)]
+#[br(import(state: &'a State))]
+enum Message {
+    #[br(magic = b"C")]
+    SetCompression(#[br(map = |flag: u8| flag != 0)] bool),
+    #[br(magic = b"D")]
+    Data {
+        #[br(temp)]
+        len: u8,
+        #[br(count = len, map = |data: Vec<u8>| if state.compressed {
+            data.into_iter().flat_map(|byte| [byte, byte]).collect()
+        } else {
+            data
+        })]
+        data: Vec<u8>,
+    },
+}
+
+impl UpdateContext<State> for Message {
+    fn update_context(&self, state: &mut State) {
+        state.count += 1;
+        if let Message::SetCompression(compressed) = self {
+            state.compressed = *compressed;
+        }
+    }
+}
+
+#[derive(binrw::BinRead, Debug, PartialEq)]
+#[br(import(_state: &'a State))]
+struct Ping(u8);
+
+impl UpdateContext<State> for Ping {}
+
+#[test]
+fn session_updates_context() {
+    let data = b"D\x01\x02C\x01D\x02\x03\x04";
+    let mut session = Session::new(Cursor::new(data), Endian::Big, State::default());
+
+    assert_eq!(
+        session.read::<Message>().unwrap(),
+        Message::Data { data: vec![2] }
+    );
+    assert_eq!(
+        session.read::<Message>().unwrap(),
+        Message::SetCompression(true)
+    );
+    assert!(session.context().compressed);
+    assert_eq!(
+        session.read::<Message>().unwrap(),
+        Message::Data {
+            data: vec![3, 3, 4, 4]
+        }
+    );
+
+    let (reader, state) = session.into_inner();
+    assert_eq!(reader.position(), 9);
+    assert_eq!(
+        state,
+        State {
+            compressed: true,
+            count: 3
+        }
+    );
+}
+
+#[test]
+fn session_messages() {
+    let data = b"C\x01D\x01\x02D\x05";
+    let mut session = Session::new(Cursor::new(data), Endian::Big, State::default());
+    let mut messages = session.messages::<Message>();
+
+    assert_eq!(
+        messages.next().unwrap().unwrap(),
+        Message::SetCompression(true)
+    );
+    assert_eq!(
+        messages.next().unwrap().unwrap(),
+        Message::Data { data: vec![2, 2] }
+    );
+    // A message which is cut off is an error, and ends the iterator
+    assert!(messages.next().unwrap().is_err());
+    assert!(messages.next().is_none());
+
+    // A failed read does not update the context
+    assert_eq!(session.context().count, 2);
+
+    session.context_mut().count = 0;
+    session.reader_mut().set_position(0);
+    assert_eq!(session.messages::<Message>().count(), 3);
+    assert_eq!(session.context().count, 2);
+}
+
+#[test]
+fn session_unchanged_context() {
+    let mut session = Session::new(Cursor::new(b"\x01\x02"), Endian::Little, State::default());
+    let pings = session
+        .messages::<Ping>()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(pings, [Ping(1), Ping(2)]);
+    assert_eq!(session.context(), &State::default());
+}
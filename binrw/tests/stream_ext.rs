@@ -36,6 +36,37 @@ fn BinReaderExt() {
     );
 }
 
+#[test]
+fn read_at() {
+    use binrw::{
+        io::{Seek, SeekFrom},
+        Endian,
+    };
+
+    let mut data = binrw::io::Cursor::new(b"\x01\x02\x03\x04");
+    data.seek(SeekFrom::Start(1)).unwrap();
+    assert_eq!(data.read_be_at::<u16>(2).unwrap(), 0x304);
+    assert_eq!(data.read_le_at::<u16>(0).unwrap(), 0x201);
+    assert_eq!(
+        data.read_type_at::<u8>(SeekFrom::End(-1), Endian::Big)
+            .unwrap(),
+        4
+    );
+    assert_eq!(
+        data.read_type_args_at::<Vec<u8>>(
+            SeekFrom::Current(1),
+            Endian::Big,
+            VecArgs::builder().count(2).finalize()
+        )
+        .unwrap(),
+        vec![3, 4]
+    );
+    assert_eq!(data.stream_position().unwrap(), 1);
+
+    data.read_be_at::<u32>(2).unwrap_err();
+    assert_eq!(data.stream_position().unwrap(), 1);
+}
+
 #[test]
 #[allow(non_snake_case)]
 fn BinWriterExt() {
@@ -61,3 +92,56 @@ fn BinWriterExt() {
         b"\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\x11\x12"
     );
 }
+
+#[test]
+fn write_at() {
+    use binrw::{
+        io::{Seek, SeekFrom},
+        Endian,
+    };
+
+    let mut data = binrw::io::Cursor::new(vec![0; 4]);
+    data.seek(SeekFrom::Start(1)).unwrap();
+    data.write_be_at(&0x304_u16, 2).unwrap();
+    data.write_le_at(&0x201_u16, 0).unwrap();
+    data.write_type_at(&5_u8, SeekFrom::End(-1), Endian::Big)
+        .unwrap();
+    data.write_type_args_at(&[6_u16], SeekFrom::Current(0), Endian::Little, ())
+        .unwrap();
+    assert_eq!(data.stream_position().unwrap(), 1);
+    assert_eq!(data.get_ref(), b"\x01\x06\x00\x05");
+
+    // Writing past the end of a fixed-size buffer fails
+    let mut buf = [0; 2];
+    let mut data = binrw::io::Cursor::new(&mut buf[..]);
+    data.write_be_at(&0x0102_0304_u32, 0).unwrap_err();
+    assert_eq!(data.stream_position().unwrap(), 0);
+}
+
+#[test]
+fn read_iter() {
+    use binrw::{io::Cursor, Endian};
+
+    let mut data = Cursor::new(b"\x01\x02\x03\x04\x05");
+    let values = data.read_type_iter::<u16>(Endian::Big).collect::<Vec<_>>();
+    assert_eq!(values.len(), 3);
+    assert_eq!(*values[0].as_ref().unwrap(), 0x102);
+    assert_eq!(*values[1].as_ref().unwrap(), 0x304);
+    assert!(values[2].as_ref().unwrap_err().is_eof());
+
+    let mut data = Cursor::new(b"\x01\x02\x03\x04");
+    let values = data
+        .read_type_iter_args::<Vec<u8>>(
+            Endian::Little,
+            VecArgs {
+                count: 2,
+                inner: (),
+            },
+        )
+        .collect::<binrw::BinResult<Vec<_>>>()
+        .unwrap();
+    assert_eq!(values, [[1, 2], [3, 4]]);
+
+    let mut data = Cursor::new(b"");
+    assert!(data.read_type_iter::<u8>(Endian::Big).next().is_none());
+}
@@ -152,3 +152,65 @@ fn null_wide_string_round_trip() {
 
     assert_eq!(&s2.to_string(), data);
 }
+
+#[test]
+fn prefixed_wide_string() {
+    use binrw::{io::Cursor, BinReaderExt, BinWrite, BinWriterExt, PrefixedWideString};
+
+    let s = Cursor::new(b"\0\0\0\x02\0h\0i")
+        .read_be::<PrefixedWideString<u32>>()
+        .unwrap();
+    assert_eq!(s.to_string(), "hi");
+    assert_eq!(format!("{s:?}"), "PrefixedWideString(\"hi\")");
+    assert_eq!(s.size_hint(), (8, Some(8)));
+
+    let mut x = Cursor::new(Vec::new());
+    x.write_be(&s).unwrap();
+    assert_eq!(x.into_inner(), b"\0\0\0\x02\0h\0i");
+
+    // Surrogate pairs are kept as-is
+    let s = PrefixedWideString::<u8>::from("🦀");
+    let mut x = Cursor::new(Vec::new());
+    x.write_le(&s).unwrap();
+    assert_eq!(x.into_inner(), b"\x02\x3e\xd8\x80\xdd");
+    assert_eq!(String::try_from(s).unwrap(), "🦀");
+
+    let s = PrefixedWideString::<u8>::from("x".repeat(256));
+    Cursor::new(Vec::new())
+        .write_le(&s)
+        .expect_err("accepted overlong string");
+}
+
+#[test]
+fn wide_string_bom() {
+    use binrw::{
+        io::Cursor, BinReaderExt, BinWriterExt, Endian, NullWideString, PrefixedWideString, WithBom,
+    };
+
+    let s: WithBom<NullWideString> = Cursor::new(b"\xff\xfeh\0i\0\0\0").read_be().unwrap();
+    assert_eq!(s.bom, Some(Endian::Little));
+    assert_eq!(s.to_string(), "hi");
+
+    let mut x = Cursor::new(Vec::new());
+    x.write_be(&s).unwrap();
+    assert_eq!(x.into_inner(), b"\xff\xfeh\0i\0\0\0");
+
+    // Without a byte order mark, the current byte order is used
+    let mut data = Cursor::new(b"\x02\0h\0i\0");
+    let s: WithBom<PrefixedWideString<u16>> = data.read_le().unwrap();
+    assert_eq!(s.bom, None);
+    assert_eq!(s.to_string(), "hi");
+    assert_eq!(data.position(), 6);
+
+    let mut x = Cursor::new(Vec::new());
+    x.write_le(&s).unwrap();
+    assert_eq!(x.into_inner(), b"\x02\0h\0i\0");
+
+    let s = WithBom {
+        bom: Some(Endian::Big),
+        value: NullWideString::from("hi"),
+    };
+    let mut x = Cursor::new(Vec::new());
+    x.write_le(&s).unwrap();
+    assert_eq!(x.into_inner(), b"\xfe\xff\0h\0i\0\0");
+}
@@ -0,0 +1,199 @@
+mod common;
+
+use binrw::{
+    io::Cursor,
+    timestamp::{DosDateTime, FileTime, UnixTimestamp},
+    BinRead, Endian,
+};
+use common::round_trip;
+
+#[test]
+fn unix_timestamp() {
+    round_trip(
+        b"\x80\x9e\x8e\x5e",
+        Endian::Little,
+        UnixTimestamp(1_586_404_992_u32),
+    );
+    round_trip(b"\xff\xff\xff\xff", Endian::Little, UnixTimestamp(-1_i32));
+
+    let time = UnixTimestamp(-1_i32);
+    assert_eq!(time.seconds(), -1);
+    assert_eq!(UnixTimestamp::<u32>::from_seconds(-1), None);
+    assert_eq!(
+        UnixTimestamp::<u32>::from_seconds(1_586_404_992),
+        Some(UnixTimestamp(1_586_404_992))
+    );
+}
+
+#[test]
+fn file_time() {
+    round_trip(
+        b"\x00\xc0\x6c\xc9\x23\x0e\xd6\x01",
+        Endian::Little,
+        FileTime(132_308_785_920_000_000),
+    );
+
+    assert_eq!(FileTime(116_444_736_000_000_000).unix_nanos(), 0);
+    assert_eq!(FileTime(0).unix_nanos(), -11_644_473_600_000_000_000);
+    assert_eq!(
+        FileTime::from_unix_nanos(199),
+        Some(FileTime(116_444_736_000_000_001))
+    );
+    assert_eq!(FileTime::from_unix_nanos(-11_644_473_600_000_000_001), None);
+}
+
+#[test]
+fn dos_date_time() {
+    let time = DosDateTime::new(2020, 3, 12, 13, 43, 55).unwrap();
+    round_trip(b"\x7b\x6d\x6c\x50", Endian::Little, time);
+    assert_eq!(
+        (
+            time.year(),
+            time.month(),
+            time.day(),
+            time.hour(),
+            time.minute(),
+            time.second()
+        ),
+        (2020, 3, 12, 13, 43, 54)
+    );
+    assert_eq!(time.unix_seconds(), Some(1_584_020_634));
+    assert_eq!(DosDateTime::from_unix_seconds(1_584_020_635), Some(time));
+
+    assert_eq!(
+        DosDateTime::new(1980, 1, 1, 0, 0, 0)
+            .unwrap()
+            .unix_seconds(),
+        Some(315_532_800)
+    );
+    assert_eq!(
+        DosDateTime::new(2000, 2, 29, 0, 0, 0)
+            .unwrap()
+            .unix_seconds(),
+        Some(951_782_400)
+    );
+    assert_eq!(DosDateTime::new(2100, 2, 29, 0, 0, 0), None);
+    assert_eq!(DosDateTime::new(1979, 12, 31, 23, 59, 58), None);
+    assert_eq!(DosDateTime::from_unix_seconds(315_532_799), None);
+
+    let invalid = DosDateTime::read_le(&mut Cursor::new(b"\0\0\0\0")).unwrap();
+    assert_eq!(invalid.month(), 0);
+    assert_eq!(invalid.unix_seconds(), None);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn system_time() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    assert_eq!(
+        UnixTimestamp(-1_i32).to_system_time(),
+        Some(UNIX_EPOCH - Duration::from_secs(1))
+    );
+    assert_eq!(
+        UnixTimestamp::<i32>::from_system_time(UNIX_EPOCH - Duration::from_millis(1)),
+        Some(UnixTimestamp(-1))
+    );
+
+    let time = UNIX_EPOCH + Duration::from_nanos(1_586_404_992_123_456_789);
+    let file_time = FileTime::from_system_time(time).unwrap();
+    assert_eq!(file_time.unix_nanos(), 1_586_404_992_123_456_700);
+    assert_eq!(
+        file_time.to_system_time(),
+        Some(UNIX_EPOCH + Duration::from_nanos(1_586_404_992_123_456_700))
+    );
+
+    let time = DosDateTime::new(2020, 3, 12, 13, 43, 55).unwrap();
+    assert_eq!(
+        time.to_system_time(),
+        Some(UNIX_EPOCH + Duration::from_secs(1_584_020_634))
+    );
+    assert_eq!(
+        DosDateTime::from_system_time(UNIX_EPOCH + Duration::from_secs(1_584_020_634)),
+        Some(time)
+    );
+
+    let invalid = DosDateTime::read_le(&mut Cursor::new(b"\0\0\0\0")).unwrap();
+    assert_eq!(invalid.to_system_time(), None);
+    assert_eq!(UnixTimestamp(i128::MAX).to_system_time(), None);
+}
+
+#[test]
+#[cfg(feature = "chrono")]
+fn chrono() {
+    use chrono::{DateTime, FixedOffset, Utc};
+
+    let time = DateTime::<Utc>::from_timestamp(1_586_404_992, 123_456_789).unwrap();
+    assert_eq!(
+        UnixTimestamp(1_586_404_992_u32).to_chrono(),
+        DateTime::from_timestamp(1_586_404_992, 0)
+    );
+    assert_eq!(
+        UnixTimestamp::<u32>::from_chrono(&time),
+        Some(UnixTimestamp(1_586_404_992))
+    );
+    assert_eq!(
+        UnixTimestamp::<u32>::from_chrono(&DateTime::<Utc>::from_timestamp(-1, 0).unwrap()),
+        None
+    );
+
+    let file_time = FileTime::from_chrono(&time).unwrap();
+    assert_eq!(file_time.unix_nanos(), 1_586_404_992_123_456_700);
+    assert_eq!(
+        file_time.to_chrono(),
+        DateTime::from_timestamp(1_586_404_992, 123_456_700)
+    );
+    assert_eq!(
+        FileTime(0).to_chrono().map(|time| time.timestamp()),
+        Some(-11_644_473_600)
+    );
+
+    // The offset does not change the instant
+    let offset = time.with_timezone(&FixedOffset::east_opt(3_600).unwrap());
+    let dos_time = DosDateTime::from_chrono(&offset).unwrap();
+    assert_eq!(dos_time, DosDateTime::new(2020, 4, 9, 4, 3, 12).unwrap());
+    assert_eq!(
+        dos_time.to_chrono(),
+        DateTime::from_timestamp(1_586_404_992, 0)
+    );
+    assert_eq!(DosDateTime::default().to_chrono(), None);
+    assert_eq!(UnixTimestamp(i128::MIN).to_chrono(), None);
+}
+
+#[test]
+#[cfg(feature = "time")]
+fn time() {
+    use time::OffsetDateTime;
+
+    let time = OffsetDateTime::from_unix_timestamp_nanos(1_586_404_992_123_456_789).unwrap();
+    assert_eq!(
+        UnixTimestamp(1_586_404_992_u32).to_offset_date_time(),
+        OffsetDateTime::from_unix_timestamp(1_586_404_992).ok()
+    );
+    assert_eq!(
+        UnixTimestamp::<u32>::from_offset_date_time(time),
+        Some(UnixTimestamp(1_586_404_992))
+    );
+    assert_eq!(
+        UnixTimestamp::<u32>::from_offset_date_time(
+            OffsetDateTime::UNIX_EPOCH - time::Duration::SECOND
+        ),
+        None
+    );
+
+    let file_time = FileTime::from_offset_date_time(time).unwrap();
+    assert_eq!(file_time.unix_nanos(), 1_586_404_992_123_456_700);
+    assert_eq!(
+        file_time.to_offset_date_time(),
+        OffsetDateTime::from_unix_timestamp_nanos(1_586_404_992_123_456_700).ok()
+    );
+
+    let dos_time = DosDateTime::from_offset_date_time(time).unwrap();
+    assert_eq!(dos_time, DosDateTime::new(2020, 4, 9, 4, 3, 12).unwrap());
+    assert_eq!(
+        dos_time.to_offset_date_time(),
+        OffsetDateTime::from_unix_timestamp(1_586_404_992).ok()
+    );
+    assert_eq!(DosDateTime::default().to_offset_date_time(), None);
+    assert_eq!(UnixTimestamp(i128::MAX).to_offset_date_time(), None);
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "tracing")]
+
+use binrw::{binrw, io::Cursor, BinRead, BinWrite};
+use core::fmt::{Debug, Write as _};
+use std::sync::{Arc, Mutex};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Metadata, Subscriber,
+};
+
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+impl Recorder {
+    fn take(&self) -> Vec<String> {
+        core::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+        write!(self.0, "{}={:?}", field.name(), value).unwrap();
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        let mut fields = Fields(format!("span {}:", span.metadata().name()));
+        span.record(&mut fields);
+        let mut lines = self.0.lock().unwrap();
+        lines.push(fields.0);
+        span::Id::from_u64(lines.len() as u64)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(String::new());
+        event.record(&mut fields);
+        self.0.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+#[test]
+fn trace_fields() {
+    struct NoDebug;
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    struct Inner {
+        a: u16,
+    }
+
+    #[binrw]
+    #[brw(big, magic = b'T')]
+    struct Test {
+        #[bw(calc = u8::try_from(items.len()).unwrap())]
+        len: u8,
+        #[br(count = len)]
+        items: Vec<Inner>,
+        #[br(calc = NoDebug)]
+        #[bw(ignore)]
+        _extra: NoDebug,
+    }
+
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let value = Test::read(&mut Cursor::new(b"T\x01\0\x02")).unwrap();
+        assert_eq!(
+            recorder.take(),
+            [
+                r#"span read: ty="Test""#,
+                r#"field="len" offset=1 size=1 value=1"#,
+                r#"span read: ty="Inner""#,
+                r#"field="a" offset=2 size=2 value=2"#,
+                r#"field="items" offset=2 size=2 value=[Inner { a: 2 }]"#,
+                r#"field="_extra" offset=4 size=0"#,
+            ]
+        );
+
+        value.write(&mut Cursor::new(Vec::new())).unwrap();
+        assert_eq!(
+            recorder.take(),
+            [
+                r#"span write: ty="Test""#,
+                r#"field="len" offset=1 size=1 value=1"#,
+                r#"span write: ty="Inner""#,
+                r#"field="a" offset=2 size=2 value=2"#,
+                r#"field="items" offset=2 size=2 value=[Inner { a: 2 }]"#,
+            ]
+        );
+    });
+}
@@ -3,7 +3,7 @@ use binrw::BinRead;
 #[derive(BinRead)]
 #[br(repr = u8)]
 enum Foo {
-    #[br(magic = 0u8)] A,
+    #[br(magic = 0u16)] A,
 }
 
 fn main() {}
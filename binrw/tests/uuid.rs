@@ -0,0 +1,42 @@
+#![cfg(feature = "uuid")]
+
+use binrw::{io::Cursor, uuid::Guid, BinRead, BinReaderExt, BinWrite};
+use uuid::Uuid;
+
+const BYTES: &[u8; 16] = b"\x12\x3e\x45\x67\xe8\x9b\x12\xd3\xa4\x56\x42\x66\x14\x17\x40\x00";
+const VALUE: u128 = 0x123e_4567_e89b_12d3_a456_4266_1417_4000;
+
+#[test]
+fn uuid() {
+    for endian in [binrw::Endian::Little, binrw::Endian::Big] {
+        let uuid: Uuid = Cursor::new(BYTES).read_type(endian).unwrap();
+        assert_eq!(uuid, Uuid::from_u128(VALUE));
+
+        let mut out = Cursor::new(Vec::new());
+        uuid.write_options(&mut out, endian, ()).unwrap();
+        assert_eq!(out.into_inner(), BYTES);
+    }
+
+    let uuid = Uuid::read(&mut Cursor::new(BYTES)).unwrap();
+    assert_eq!(uuid.as_u128(), VALUE);
+    assert_eq!(uuid.to_string(), "123e4567-e89b-12d3-a456-426614174000");
+}
+
+#[test]
+fn guid() {
+    let data = b"\x67\x45\x3e\x12\x9b\xe8\xd3\x12\xa4\x56\x42\x66\x14\x17\x40\x00";
+    let guid: Guid = Cursor::new(data).read_le().unwrap();
+    assert_eq!(*guid, Uuid::from_u128(VALUE));
+    assert_eq!(guid.to_string(), "123e4567-e89b-12d3-a456-426614174000");
+    assert_eq!(
+        format!("{guid:?}"),
+        "Guid(123e4567-e89b-12d3-a456-426614174000)"
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    guid.write_le(&mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+
+    let guid: Guid = Cursor::new(BYTES).read_be().unwrap();
+    assert_eq!(Uuid::from(guid), Uuid::from_u128(VALUE));
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use binrw::{binrw, io::Cursor, varint, BinRead, BinReaderExt, BinWrite, Endian, Leb128, Vlq};
+use common::round_trip;
+
+#[test]
+fn leb128_unsigned() {
+    round_trip(b"\0", Endian::Little, Leb128(0_u8));
+    round_trip(b"\x7f", Endian::Little, Leb128(127_u8));
+    round_trip(b"\x80\x01", Endian::Little, Leb128(128_u16));
+    round_trip(b"\xe5\x8e\x26", Endian::Little, Leb128(624_485_u32));
+    round_trip(
+        b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01",
+        Endian::Little,
+        Leb128(u64::MAX),
+    );
+    round_trip(
+        b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x03",
+        Endian::Little,
+        Leb128(u128::MAX),
+    );
+}
+
+#[test]
+fn leb128_signed() {
+    round_trip(b"\0", Endian::Little, Leb128(0_i8));
+    round_trip(b"\x7f", Endian::Little, Leb128(-1_i8));
+    round_trip(b"\x3f", Endian::Little, Leb128(63_i16));
+    round_trip(b"\xc0\0", Endian::Little, Leb128(64_i16));
+    round_trip(b"\xc0\xbb\x78", Endian::Little, Leb128(-123_456_i32));
+    round_trip(b"\x80\x7f", Endian::Little, Leb128(-128_i64));
+    round_trip(
+        b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01",
+        Endian::Little,
+        Leb128(i128::MAX),
+    );
+    round_trip(
+        b"\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x7e",
+        Endian::Little,
+        Leb128(i128::MIN),
+    );
+    round_trip(
+        b"\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x7f",
+        Endian::Little,
+        Leb128(i128::MIN / 2),
+    );
+    round_trip(
+        b"\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x7f",
+        Endian::Little,
+        Leb128(-(1_i128 << 126)),
+    );
+}
+
+#[test]
+fn leb128_padding() {
+    let value: Leb128<u8> = Cursor::new(b"\x81\x80\0").read_le().unwrap();
+    assert_eq!(*value, 1);
+}
+
+#[test]
+fn leb128_overflow() {
+    let error = Cursor::new(b"\x80\x02")
+        .read_le::<Leb128<u8>>()
+        .expect_err("accepted overflowing value");
+    assert!(matches!(error, binrw::Error::AssertFail { pos: 0, .. }));
+    assert!(Cursor::new(b"\xc0\x01").read_le::<Leb128<i8>>().is_err());
+    assert!(Cursor::new([0x80; 20]).read_le::<Leb128<u128>>().is_err());
+
+    let mut data = [0x80; 19];
+    data[18] = 0x04;
+    assert!(Cursor::new(data).read_le::<Leb128<u128>>().is_err());
+    data[18] = 0x7d;
+    assert!(Cursor::new(data).read_le::<Leb128<i128>>().is_err());
+}
+
+#[test]
+fn vlq() {
+    round_trip(b"\0", Endian::Little, Vlq(0_u8));
+    round_trip(b"\x7f", Endian::Little, Vlq(0x7f_u16));
+    round_trip(b"\x81\0", Endian::Little, Vlq(0x80_u16));
+    round_trip(b"\xc0\0", Endian::Little, Vlq(0x2000_u32));
+    round_trip(b"\xff\xff\xff\x7f", Endian::Little, Vlq(0x0fff_ffff_u32));
+    assert!(Cursor::new(b"\x82\0").read_le::<Vlq<u8>>().is_err());
+}
+
+#[test]
+fn varint_helpers() {
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    struct Test {
+        #[br(parse_with = varint::read_leb128)]
+        #[bw(write_with = varint::write_leb128)]
+        a: i32,
+        #[br(parse_with = varint::read_vlq)]
+        #[bw(write_with = varint::write_vlq)]
+        b: u64,
+    }
+
+    let data = b"\x80\x7f\x81\0";
+    let test = Test::read_le(&mut Cursor::new(data)).unwrap();
+    assert_eq!(test, Test { a: -128, b: 0x80 });
+
+    let mut out = Cursor::new(Vec::new());
+    test.write_le(&mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+}
@@ -1,6 +1,6 @@
 use super::{end, start};
 use crate::binrw::parser::{
-    AssertionError, CondEndian, Condition, ErrContext, FieldMode, Map, PassedArgs, StructField,
+    AssertionError, CondEndian, ErrContext, FieldMode, Map, PassedArgs, StructField,
 };
 use core::{
     fmt::{Display, Formatter},
@@ -192,27 +192,25 @@ fn visit_expr_attributes(field: &StructField, visitor: &mut Visitor) {
 
     spans_from_exprs!(
         count,
+        count_bytes_of,
         offset,
         pad_before,
         pad_after,
         align_before,
         align_after,
         seek_before,
-        pad_size_to
+        seek_after,
+        pad_size_to,
+        pad_with
     );
 
     if let Some(tokens) = field.offset_after.clone() {
         visit!((*tokens).clone());
     }
 
-    if let Some(condition) = field.if_cond.clone() {
-        let Condition {
-            condition,
-            alternate,
-        } = condition;
-
-        visit!(condition);
-        if let Some(alternate) = alternate {
+    if let Some(cond) = &field.if_cond {
+        visit!(cond.condition.clone());
+        if let Some(alternate) = cond.alternate.clone() {
             visit!(alternate);
         }
     }
@@ -252,12 +250,18 @@ fn visit_expr_attributes(field: &StructField, visitor: &mut Visitor) {
         PassedArgs::None => (),
     }
 
-    if let FieldMode::Calc(expr) | FieldMode::TryCalc(expr) | FieldMode::Function(expr) =
+    if let FieldMode::Calc(expr) | FieldMode::TryCalc(expr) | FieldMode::Function(expr, _) =
         &field.field_mode
     {
         visit!(expr.clone());
     }
 
+    if let Some(checksum) = &field.checksum {
+        for expr in core::iter::once(&checksum.function).chain(&checksum.range) {
+            visit!(expr.clone());
+        }
+    }
+
     for assert in &field.assertions {
         visit!(assert.condition.clone());
 
@@ -407,11 +411,11 @@ fn is_keyword_ident(ident: &syn::Ident) -> bool {
         Option,
 
         // binrw 'keywords'
-        align_after, align_before, args, args_raw, assert, big, binread, br, brw, binwrite,
-        bw, calc, count, default, deref_now, ignore, import, import_raw, is_big, is_little,
-        little, magic, map, offset, offset_after, pad_after, pad_before, pad_size_to, parse_with,
-        postprocess_now, pre_assert, repr, restore_position, return_all_errors,
-        return_unexpected_error, seek_before, temp, try_map, write_with
+        algorithm, align_after, align_before, args, args_raw, assert, audit, big, binread, br, brw, binwrite,
+        bw, calc, calc_count_of, canonical_nan, catch_all, checksum, context, count, count_bytes_of, crc, default, deref_now, dyn_with, error, finally, finite, flush_after, if_eof_none, ignore, import, import_raw, is_big, is_little,
+        little, magic, map, mask, no_subnormal, offset, offset_after, offset_base, over, pad_after, pad_before, pad_size_to, pad_with, parse_with,
+        postprocess_now, pre_assert, prepare, read_only, repr, rest, restore_position, return_all_errors,
+        return_unexpected_error, save_position, schema, seek_after, seek_before, seek_to_saved, size, size_of, tag, tag_endian, tag_into, tag_value, temp, trailing, try_map, untrusted, verify, write_with
     );
 
     is_keyword
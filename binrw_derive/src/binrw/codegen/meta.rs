@@ -1,4 +1,6 @@
-use super::sanitization::{META_ENDIAN_KIND, READ_ENDIAN, READ_MAGIC, WRITE_ENDIAN, WRITE_MAGIC};
+use super::sanitization::{
+    META_ENDIAN_KIND, READ_ENDIAN, READ_ERROR, READ_MAGIC, WRITE_ENDIAN, WRITE_MAGIC,
+};
 use crate::binrw::parser::{CondEndian, Input, Map};
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -10,17 +12,22 @@ pub(crate) fn generate<const WRITE: bool>(
     let name = &derive_input.ident;
     let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
 
-    let magic = input.magic().as_ref().map(|magic| {
-        let magic_meta = if WRITE { WRITE_MAGIC } else { READ_MAGIC };
-        let ty = TokenStream::from(magic.kind());
-        let val = magic.deref_value();
-        quote! {
-            impl #impl_generics #magic_meta for #name #ty_generics #where_clause {
-                type MagicType = #ty;
-                const MAGIC: Self::MagicType = #val;
+    // A type which accepts more than one magic number has no single `ReadMagic`
+    let magic = input
+        .magic()
+        .as_ref()
+        .filter(|magic| WRITE || magic.is_exact())
+        .map(|magic| {
+            let magic_meta = if WRITE { WRITE_MAGIC } else { READ_MAGIC };
+            let ty = TokenStream::from(magic.kind());
+            let val = magic.deref_value();
+            quote! {
+                impl #impl_generics #magic_meta for #name #ty_generics #where_clause {
+                    type MagicType = #ty;
+                    const MAGIC: Self::MagicType = #val;
+                }
             }
-        }
-    });
+        });
 
     let endian_meta = if WRITE { WRITE_ENDIAN } else { READ_ENDIAN };
 
@@ -53,8 +60,17 @@ pub(crate) fn generate<const WRITE: bool>(
         }
     });
 
+    let error = input.error_type().filter(|_| !WRITE).map(|ty| {
+        quote! {
+            impl #impl_generics #READ_ERROR for #name #ty_generics #where_clause {
+                type Error = #ty;
+            }
+        }
+    });
+
     quote! {
         #magic
         #endian
+        #error
     }
 }
@@ -1,11 +1,16 @@
 mod meta;
 mod read_options;
 pub(crate) mod sanitization;
+mod schema;
+mod versions;
 mod write_options;
 
+#[cfg(feature = "verbose-backtrace")]
+use crate::binrw::backtrace::BacktraceFrame;
 use crate::{
     binrw::parser::{
-        Assert, AssertionError, CondEndian, Imports, Input, ParseResult, PassedArgs, StructField,
+        Assert, AssertionError, CondEndian, Endian, ErrContext, Imports, Input, OffsetBase,
+        ParseResult, PassedArgs, StructField,
     },
     named_args::{arg_type_name, derive_from_imports},
     util::{quote_spanned_any, IdentStr},
@@ -13,10 +18,13 @@ use crate::{
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned, ToTokens};
 use sanitization::{
-    ARGS, ARGS_LIFETIME, ARGS_MACRO, ASSERT, ASSERT_ERROR_FN, BINREAD_TRAIT, BINWRITE_TRAIT,
-    BIN_ERROR, BIN_RESULT, ENDIAN_ENUM, OPT, POS, READER, READ_TRAIT, SEEK_TRAIT, TEMP, WRITER,
-    WRITE_TRAIT,
+    make_ident, ARGS, ARGS_LIFETIME, ARGS_MACRO, ASSERT, ASSERT_ERROR_FN, ASSERT_MESSAGE,
+    ASSERT_VALUE, ASSERT_VALUE_DEBUG, ASSERT_VALUE_NO_DEBUG, BACKTRACE_FRAME, BINREAD_TRAIT,
+    BINWRITE_TRAIT, BIN_ERROR, BIN_RESULT, ENDIAN_ENUM, OPT, PARENT_POS, POS, READER, READ_AUDIT,
+    READ_TRAIT, SEEK_FROM, SEEK_TRAIT, TEMP, UNTRUSTED_COUNT, WRITER, WRITE_AUDIT, WRITE_TRAIT,
 };
+#[cfg(feature = "tracing")]
+use sanitization::{TRACE_SPAN, TRACE_START, TRACING};
 use syn::{spanned::Spanned, DeriveInput, Ident, Type};
 
 pub(crate) fn generate_impl<const WRITE: bool>(
@@ -28,6 +36,7 @@ pub(crate) fn generate_impl<const WRITE: bool>(
             binrw_input.imports(),
             &derive_input.ident,
             &derive_input.vis,
+            &derive_input.generics,
             WRITE,
         ),
         ParseResult::Err(_) => (quote! { () }, None),
@@ -42,10 +51,26 @@ pub(crate) fn generate_impl<const WRITE: bool>(
         ParseResult::Err(_) => None,
     };
 
+    let schema_impl = match binrw_input {
+        ParseResult::Ok(binrw_input) if binrw_input.schema() => {
+            Some(schema::generate::<WRITE>(binrw_input, derive_input))
+        }
+        _ => None,
+    };
+
+    let version_warnings = match binrw_input {
+        ParseResult::Ok(binrw_input) if !WRITE => {
+            Some(versions::generate(binrw_input, derive_input))
+        }
+        _ => None,
+    };
+
     quote! {
         #trait_impl
         #meta_impls
+        #schema_impl
         #arg_type_declaration
+        #version_warnings
     }
 }
 
@@ -53,31 +78,36 @@ fn generate_imports(
     imports: &Imports,
     type_name: &Ident,
     ty_vis: &syn::Visibility,
+    generics: &syn::Generics,
     is_write: bool,
 ) -> (TokenStream, Option<TokenStream>) {
     use syn::fold::Fold;
 
-    fn has_elided_lifetime(ty: &syn::Type) -> bool {
+    fn has_args_lifetime(
+        ty: &syn::Type,
+        is_args_lifetime: &dyn Fn(&syn::Lifetime) -> bool,
+    ) -> bool {
         use syn::visit::Visit;
-        struct Finder(bool);
-        impl Visit<'_> for Finder {
+        struct Finder<'a>(bool, &'a dyn Fn(&syn::Lifetime) -> bool);
+        impl Visit<'_> for Finder<'_> {
             fn visit_lifetime(&mut self, i: &syn::Lifetime) {
-                self.0 |= i.ident == "_";
+                self.0 |= (self.1)(i);
             }
 
             fn visit_type_reference(&mut self, i: &syn::TypeReference) {
                 self.0 |= i.lifetime.is_none();
+                syn::visit::visit_type_reference(self, i);
             }
         }
-        let mut finder = Finder(false);
+        let mut finder = Finder(false, is_args_lifetime);
         finder.visit_type(ty);
         finder.0
     }
 
-    struct ExpandLifetimes;
-    impl Fold for ExpandLifetimes {
+    struct ExpandLifetimes<'a>(&'a dyn Fn(&syn::Lifetime) -> bool);
+    impl Fold for ExpandLifetimes<'_> {
         fn fold_lifetime(&mut self, mut i: syn::Lifetime) -> syn::Lifetime {
-            if i.ident == "_" {
+            if (self.0)(&i) {
                 i.ident = syn::Ident::new(ARGS_LIFETIME, i.ident.span());
             }
             i
@@ -87,18 +117,29 @@ fn generate_imports(
             if i.lifetime.is_none() {
                 i.lifetime = Some(get_args_lifetime(i.and_token.span()));
             }
-            i
+            syn::fold::fold_type_reference(self, i)
         }
     }
 
+    // Any lifetime which is not `'static` or declared by the type itself
+    // refers to the lifetime of the arguments
+    let is_args_lifetime = |lifetime: &syn::Lifetime| {
+        lifetime.ident != "static"
+            && generics
+                .lifetimes()
+                .all(|def| def.lifetime.ident != lifetime.ident)
+    };
+
     match imports {
         Imports::None => (quote! { () }, None),
         Imports::List(_, types) => {
-            let types = types.iter().map(|ty| ExpandLifetimes.fold_type(ty.clone()));
+            let types = types
+                .iter()
+                .map(|ty| ExpandLifetimes(&is_args_lifetime).fold_type(ty.clone()));
             (quote! { (#(#types,)*) }, None)
         }
         Imports::Raw(_, ty) => (
-            ExpandLifetimes
+            ExpandLifetimes(&is_args_lifetime)
                 .fold_type(ty.as_ref().clone())
                 .into_token_stream(),
             None,
@@ -107,7 +148,7 @@ fn generate_imports(
             let name = arg_type_name(type_name, is_write);
             let lifetime = args
                 .iter()
-                .any(|arg| has_elided_lifetime(&arg.ty))
+                .any(|arg| has_args_lifetime(&arg.ty, &is_args_lifetime))
                 .then(|| get_args_lifetime(type_name.span()));
             let defs = derive_from_imports(
                 type_name,
@@ -117,7 +158,7 @@ fn generate_imports(
                 lifetime.clone(),
                 args.iter().map(|arg| {
                     let mut arg = arg.clone();
-                    arg.ty = ExpandLifetimes.fold_type(arg.ty);
+                    arg.ty = ExpandLifetimes(&is_args_lifetime).fold_type(arg.ty);
                     arg
                 }),
             );
@@ -161,6 +202,18 @@ fn generate_trait_impl<const WRITE: bool>(
         )
     };
 
+    let size_hint_impl = match binrw_input {
+        ParseResult::Ok(binrw_input) if WRITE => write_options::size_hint::generate(binrw_input),
+        _ => None,
+    };
+
+    let async_impl = match binrw_input {
+        ParseResult::Ok(binrw_input) if !WRITE && binrw_input.is_async() => {
+            Some(read_options::generate_async(binrw_input, derive_input))
+        }
+        _ => None,
+    };
+
     let fn_impl = match binrw_input {
         ParseResult::Ok(binrw_input) => {
             if WRITE {
@@ -179,21 +232,64 @@ fn generate_trait_impl<const WRITE: bool>(
     let name = &derive_input.ident;
     let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
 
+    let audit = match binrw_input {
+        ParseResult::Ok(binrw_input) if binrw_input.audit() => {
+            let audit_trait = if WRITE { WRITE_AUDIT } else { READ_AUDIT };
+            let code_size = count_tokens(&fn_impl);
+            Some((
+                quote! { #[deny(unsafe_code)] },
+                quote! {
+                    #[automatically_derived]
+                    impl #impl_generics #audit_trait for #name #ty_generics #where_clause {
+                        const CODE_SIZE: usize = #code_size;
+                    }
+                },
+            ))
+        }
+        _ => None,
+    };
+    let (deny_unsafe, audit_impl) = audit.unzip();
+
+    let trace_span = match binrw_input {
+        ParseResult::Ok(_) => get_trace_span(WRITE, name),
+        _ => None,
+    };
+
     let args_lifetime = get_args_lifetime(Span::call_site());
     quote! {
         #[automatically_derived]
         #[allow(non_snake_case)]
         #[allow(clippy::redundant_closure_call)]
+        #deny_unsafe
         impl #impl_generics #trait_name for #name #ty_generics #where_clause {
             type Args<#args_lifetime> = #arg_type;
 
             #fn_sig {
+                #trace_span
                 #fn_impl
             }
+
+            #size_hint_impl
+            #async_impl
         }
+
+        #audit_impl
     }
 }
 
+/// Counts the tokens in a token stream, including the delimiters of every
+/// group.
+fn count_tokens(tokens: &TokenStream) -> usize {
+    tokens
+        .clone()
+        .into_iter()
+        .map(|token| match token {
+            proc_macro2::TokenTree::Group(group) => 2 + count_tokens(&group.stream()),
+            _ => 1,
+        })
+        .sum()
+}
+
 fn get_args_lifetime(span: proc_macro2::Span) -> syn::Lifetime {
     syn::Lifetime::new(&format!("'{ARGS_LIFETIME}"), span)
 }
@@ -213,11 +309,12 @@ fn get_assertions(assertions: &[Assert]) -> impl Iterator<Item = TokenStream> +
                     quote! { #ASSERT_ERROR_FN::Error::<fn() -> &'static str, _>(|| { #error }) }
                 }
                 None => {
-                    let condition = condition.to_string();
-                    quote! { #ASSERT_ERROR_FN::Message::<_, fn() -> !>(|| {
-                        extern crate alloc;
-                        alloc::format!("assertion failed: `{}`", #condition)
-                    }) }
+                    let message = format!("assertion failed: `{condition}`");
+                    if let Some(assertion) = get_comparison_assertion(*kw_span, condition, &message)
+                    {
+                        return assertion;
+                    }
+                    quote! { #ASSERT_ERROR_FN::Message::<_, fn() -> !>(|| #message) }
                 }
             };
 
@@ -228,6 +325,57 @@ fn get_assertions(assertions: &[Assert]) -> impl Iterator<Item = TokenStream> +
     )
 }
 
+/// Generates an assertion for a comparison which includes the values of both
+/// operands in the error message, like [`assert_eq!`].
+fn get_comparison_assertion(
+    kw_span: Span,
+    condition: &TokenStream,
+    message: &str,
+) -> Option<TokenStream> {
+    let mut expr = syn::parse2::<syn::Expr>(condition.clone()).ok()?;
+    // Conditions passed through a `macro_rules!` macro are wrapped in
+    // invisible groups
+    while let syn::Expr::Group(group) = expr {
+        expr = *group.expr;
+    }
+    let syn::Expr::Binary(expr) = expr else {
+        return None;
+    };
+
+    if !matches!(
+        expr.op,
+        syn::BinOp::Eq(_)
+            | syn::BinOp::Ne(_)
+            | syn::BinOp::Lt(_)
+            | syn::BinOp::Le(_)
+            | syn::BinOp::Gt(_)
+            | syn::BinOp::Ge(_)
+    ) {
+        return None;
+    }
+
+    let syn::ExprBinary {
+        left, op, right, ..
+    } = expr;
+    let left_var = Ident::new("__binrw_generated_assert_left", kw_span);
+    let right_var = Ident::new("__binrw_generated_assert_right", kw_span);
+    Some(quote_spanned_any! {kw_span=>
+        match (&(#left), &(#right)) {
+            (#left_var, #right_var) => {
+                #[allow(unused_imports)]
+                use {#ASSERT_VALUE_DEBUG as _, #ASSERT_VALUE_NO_DEBUG as _};
+                #ASSERT(*#left_var #op *#right_var, #POS, #ASSERT_ERROR_FN::Message::<_, fn() -> !>(|| {
+                    #ASSERT_MESSAGE(
+                        #message,
+                        (&#ASSERT_VALUE(#left_var)).debug_value(),
+                        (&#ASSERT_VALUE(#right_var)).debug_value(),
+                    )
+                }))?;
+            }
+        }
+    })
+}
+
 fn get_destructured_imports(
     imports: &Imports,
     type_name: Option<&Ident>,
@@ -277,6 +425,146 @@ fn get_endian(endian: &CondEndian) -> TokenStream {
     }
 }
 
+fn get_tag_endian(tag_endian: Option<Endian>) -> TokenStream {
+    tag_endian.map_or_else(|| OPT.to_token_stream(), |endian| endian.to_token_stream())
+}
+
+/// Returns the source code of a field for a verbose backtrace frame.
+fn get_backtrace_code(field: &StructField) -> TokenStream {
+    #[cfg(feature = "verbose-backtrace")]
+    {
+        let code = BacktraceFrame::from_field(field).to_string();
+        if code.is_empty() {
+            quote! { None }
+        } else {
+            quote! { Some(#code) }
+        }
+    }
+    #[cfg(not(feature = "verbose-backtrace"))]
+    {
+        let _ = field;
+        quote!(None)
+    }
+}
+
+/// Returns code which opens a `tracing` span covering the read or write of a
+/// whole type, if the `tracing` feature is enabled.
+#[cfg_attr(feature = "tracing", allow(clippy::unnecessary_wraps))]
+fn get_trace_span(write: bool, name: &Ident) -> Option<TokenStream> {
+    #[cfg(feature = "tracing")]
+    {
+        let op = if write { "write" } else { "read" };
+        let ty = name.to_string();
+        Some(quote! {
+            let #TRACE_SPAN = #TRACING::trace_span!(#op, ty = #ty).entered();
+        })
+    }
+    #[cfg(not(feature = "tracing"))]
+    {
+        let _ = (write, name);
+        None
+    }
+}
+
+/// Returns code to run before and after a field is read or written which emits
+/// a `tracing` event with its name, offset, size, and value, if the `tracing`
+/// feature is enabled. The value is only recorded if its type implements
+/// `Debug`.
+#[cfg_attr(feature = "tracing", allow(clippy::unnecessary_wraps))]
+fn get_trace_field(
+    stream_var: &TokenStream,
+    ident: &Ident,
+    value: &TokenStream,
+) -> Option<(TokenStream, TokenStream)> {
+    #[cfg(feature = "tracing")]
+    {
+        Some((
+            quote! {
+                let #TRACE_START = if #TRACING::level_enabled!(#TRACING::Level::TRACE) {
+                    Some(#SEEK_TRAIT::stream_position(#stream_var)?)
+                } else {
+                    None
+                };
+            },
+            quote! {
+                if let Some(#TRACE_START) = #TRACE_START {
+                    #[allow(unused_imports)]
+                    use {#ASSERT_VALUE_DEBUG as _, #ASSERT_VALUE_NO_DEBUG as _};
+                    let #POS = #SEEK_TRAIT::stream_position(#stream_var)?;
+                    #TRACING::trace!(
+                        field = ::core::stringify!(#ident),
+                        offset = #TRACE_START,
+                        size = #POS.saturating_sub(#TRACE_START),
+                        value = (&#ASSERT_VALUE(#value))
+                            .debug_value()
+                            .map(#TRACING::field::debug),
+                    );
+                }
+            },
+        ))
+    }
+    #[cfg(not(feature = "tracing"))]
+    {
+        let _ = (stream_var, ident, value);
+        None
+    }
+}
+
+/// Returns the backtrace frame for a field with an `err_context` directive.
+/// Returns the line number of the given span for `dbg` output, falling back to
+/// the line of the macro invocation when it is unavailable.
+fn get_debug_line(span: Span) -> TokenStream {
+    // Unwrapping the proc-macro2 Span is undesirable but necessary until its API
+    // is updated to allow retrieving line/column again. Using a separate function
+    // to unwrap just to make it clearer what needs to be undone later.
+    // <https://github.com/dtolnay/proc-macro2/pull/383>
+    #[cfg(all(feature = "verbose-backtrace", nightly, proc_macro))]
+    fn start_line(span: proc_macro2::Span) -> usize {
+        span.unwrap().start().line()
+    }
+    #[cfg(not(all(feature = "verbose-backtrace", nightly, proc_macro)))]
+    fn start_line(_: proc_macro2::Span) -> usize {
+        0
+    }
+
+    let start_line = start_line(span);
+    if start_line == 0 {
+        quote!(::core::line!())
+    } else {
+        start_line.to_token_stream()
+    }
+}
+
+fn get_err_context_frame(field: &StructField) -> Option<TokenStream> {
+    match field.err_context.as_ref()? {
+        ErrContext::Context(expr) => Some(quote_spanned! {field.ident.span()=>
+            #BACKTRACE_FRAME::Custom(Box::new(#expr) as _)
+        }),
+        ErrContext::Format(fmt, exprs) => {
+            let code = get_backtrace_code(field);
+            let message = if exprs.is_empty() {
+                quote! { (#fmt) }
+            } else {
+                quote! {
+                    {
+                        extern crate alloc;
+                        alloc::format!(#fmt, #(#exprs),*)
+                    }
+                }
+            };
+
+            Some(quote_spanned! {field.ident.span()=>
+                #BACKTRACE_FRAME::Full {
+                    message: #message.into(),
+                    line: ::core::line!(),
+                    file: ::core::file!(),
+                    code: #code,
+                }
+            })
+        }
+    }
+}
+
 fn get_map_err(pos: IdentStr, span: Span) -> TokenStream {
     quote_spanned_any! { span=>
         .map_err(|e| {
@@ -289,11 +577,22 @@ fn get_map_err(pos: IdentStr, span: Span) -> TokenStream {
 }
 
 fn get_passed_args(field: &StructField, stream: IdentStr) -> Option<TokenStream> {
+    let pos = quote! { #SEEK_TRAIT::stream_position(#stream).unwrap_or_default() };
+    get_passed_args_at(field, stream, &pos)
+}
+
+/// Like [`get_passed_args`], but with the position reported by errors given
+/// as an expression instead of being read from `stream`.
+fn get_passed_args_at(
+    field: &StructField,
+    stream: IdentStr,
+    pos: &TokenStream,
+) -> Option<TokenStream> {
     let args = &field.args;
     let span = args.span().unwrap_or_else(|| field.ty.span());
     match args {
         PassedArgs::Named(fields) => Some({
-            let extra_args = directives_to_args(field, stream);
+            let extra_args = directives_to_args(field, stream, pos);
             quote_spanned_any! { span=>
                 #ARGS_MACRO! { #extra_args #(#fields, )* }
             }
@@ -301,7 +600,7 @@ fn get_passed_args(field: &StructField, stream: IdentStr) -> Option<TokenStream>
         PassedArgs::List(list) => Some(quote_spanned! {span=> (#(#list,)*) }),
         PassedArgs::Tuple(tuple) => Some(tuple.as_ref().clone()),
         PassedArgs::None => {
-            let extra_args = directives_to_args(field, stream);
+            let extra_args = directives_to_args(field, stream, pos);
             (!extra_args.is_empty()).then(|| {
                 quote_spanned_any! { span=> #ARGS_MACRO! { #extra_args } }
             })
@@ -309,36 +608,89 @@ fn get_passed_args(field: &StructField, stream: IdentStr) -> Option<TokenStream>
     }
 }
 
+fn get_save_position(stream: &TokenStream, field: &StructField) -> Option<TokenStream> {
+    field.save_position.as_ref().map(|slot| {
+        let slot = make_ident(slot, "saved_position");
+        quote! {
+            let #slot = #SEEK_TRAIT::stream_position(#stream)?;
+        }
+    })
+}
+
+fn get_seek_to_saved(stream: &TokenStream, field: &StructField) -> Option<TokenStream> {
+    field.seek_to_saved.as_ref().map(|slot| {
+        let slot = make_ident(slot, "saved_position");
+        quote! {
+            #SEEK_TRAIT::seek(#stream, #SEEK_FROM::Start(#slot))?;
+        }
+    })
+}
+
+/// Generates a `BinResult` from a `try_calc` expression. The caller is
+/// responsible for adding the context of the field and propagating the error.
 fn get_try_calc(pos: IdentStr, ty: &Type, calc: &TokenStream) -> TokenStream {
     let map_err = get_map_err(pos, calc.span());
     quote_spanned! {ty.span()=> {
         let #TEMP: ::core::result::Result<#ty, _> = #calc;
-        #TEMP #map_err ?
+        #TEMP #map_err
     }}
 }
 
-fn directives_to_args(field: &StructField, stream: IdentStr) -> TokenStream {
+/// Returns the item type of a collection field read with `count`, if it can be
+/// determined from the field type.
+fn count_item_type(field: &StructField) -> Option<&Type> {
+    if !field.can_call_after_parse() {
+        return None;
+    }
+
+    match &field.ty {
+        Type::Array(array) => Some(&*array.elem),
+        Type::Path(path) => match &path.path.segments.last()?.arguments {
+            syn::PathArguments::AngleBracketed(args) => {
+                args.args.iter().find_map(|arg| match arg {
+                    syn::GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn directives_to_args(field: &StructField, stream: IdentStr, pos: &TokenStream) -> TokenStream {
     let args = field
         .count
         .as_ref()
         .map(|count| {
+            let check_count = field.untrusted.then(|| {
+                // Zero-sized items may legitimately have a count larger than
+                // the rest of the stream, so they skip the remaining bytes
+                // check
+                let min_size = count_item_type(field).map_or_else(
+                    || quote! { 1 },
+                    |ty| quote! { if ::core::mem::size_of::<#ty>() == 0 { 0 } else { 1 } },
+                );
+                quote! { let #TEMP = #UNTRUSTED_COUNT(#stream, #TEMP, #min_size)?; }
+            });
             quote_spanned_any! {count.span()=>
                 count: {
                     let #TEMP = #count;
-                    #[allow(clippy::useless_conversion)]
-                    usize::try_from(#TEMP).map_err(|_| {
+                    #[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+                    let #TEMP = usize::try_from(#TEMP).map_err(|_| {
                         extern crate alloc;
                         #BIN_ERROR::AssertFail {
-                            pos: #SEEK_TRAIT::stream_position(#stream)
-                                .unwrap_or_default(),
+                            pos: #pos,
                             // This is using debug formatting instead of display
                             // formatting to reduce the chance of some
                             // additional confusing error complaining about
                             // Display not being implemented if someone tries
                             // using a bogus type with `count`
-                            message: alloc::format!("count {:?} out of range of usize", #TEMP)
+                            message: alloc::format!("count {:?} out of range of usize", #TEMP).into()
                         }
-                    })?
+                    })?;
+                    #check_count
+                    #TEMP
                 }
             }
         })
@@ -349,6 +701,15 @@ fn directives_to_args(field: &StructField, stream: IdentStr) -> TokenStream {
                 .as_ref()
                 .map(|offset| quote_spanned! { offset.span()=> offset: #offset })
                 .into_iter(),
-        );
+        )
+        .chain(field.offset_base.as_ref().map(|base| {
+            let offset = match **base {
+                // The position of the field is only known after any padding
+                // and seeking, so it is set immediately before reading
+                OffsetBase::Absolute | OffsetBase::Current => quote! { 0 },
+                OffsetBase::Parent => PARENT_POS.to_token_stream(),
+            };
+            quote_spanned! { base.span()=> offset: #offset }
+        }));
     quote! { #(#args,)* }
 }
@@ -1,3 +1,4 @@
+mod r#async;
 mod r#enum;
 mod map;
 mod r#struct;
@@ -6,12 +7,14 @@ use super::{get_assertions, get_destructured_imports};
 use crate::{
     binrw::{
         codegen::{
-            get_endian,
+            get_endian, get_tag_endian,
             sanitization::{
-                ARGS, ASSERT_MAGIC, MAP_READER_TYPE_HINT, OPT, POS, READER, SEEK_FROM, SEEK_TRAIT,
+                ARGS, ASSERT_MAGIC, ASSERT_MAGIC_ANY, ASSERT_MAGIC_MASKED, MAGIC_VALUE,
+                MAP_READER_TYPE_HINT, OPT, PARENT_POS, POS, READER, SEEK_FROM, SEEK_TRAIT,
+                UNTRUSTED_ENTER, UNTRUSTED_GUARD,
             },
         },
-        parser::{Input, Magic, Map},
+        parser::{Input, Magic, Map, OffsetBase},
     },
     util::quote_spanned_any,
 };
@@ -21,6 +24,8 @@ use r#enum::{generate_data_enum, generate_unit_enum};
 use r#struct::{generate_struct, generate_unit_struct};
 use syn::{spanned::Spanned, Ident};
 
+pub(crate) use r#async::generate as generate_async;
+
 pub(crate) fn generate(input: &Input, derive_input: &syn::DeriveInput) -> TokenStream {
     let name = Some(&derive_input.ident);
     let inner = match input.map() {
@@ -43,10 +48,25 @@ pub(crate) fn generate(input: &Input, derive_input: &syn::DeriveInput) -> TokenS
     };
 
     let reader_var = input.stream_ident_or(READER);
+    let untrusted_guard = input.untrusted().then(|| {
+        quote! {
+            let #UNTRUSTED_GUARD = #UNTRUSTED_ENTER(#POS)?;
+        }
+    });
+
+    let parent_pos = input
+        .any_field(|field| field.offset_base.as_deref() == Some(&OffsetBase::Parent))
+        .then(|| {
+            quote! {
+                let #PARENT_POS = #POS;
+            }
+        });
 
     quote! {
         let #reader_var = #READER;
         let #POS = #SEEK_TRAIT::stream_position(#reader_var)?;
+        #parent_pos
+        #untrusted_guard
         (|| {
             #inner
         })().or_else(|error| {
@@ -100,7 +120,12 @@ impl<'input> PreludeGenerator<'input> {
 
     fn add_magic_pre_assertion(mut self) -> Self {
         let head = self.out;
-        let magic = get_magic(self.input.magic(), &self.reader_var, OPT);
+        let magic = get_magic(
+            self.input.magic(),
+            &self.reader_var,
+            get_tag_endian(self.input.tag_endian()),
+            true,
+        );
         let pre_assertions = get_assertions(self.input.pre_assertions());
         self.out = quote! {
             #head
@@ -139,15 +164,32 @@ impl<'input> PreludeGenerator<'input> {
     }
 }
 
+/// Generates code to read and check a magic number. If `bind` is set and more
+/// than one magic number is accepted, the one which was read is stored in
+/// `MAGIC_VALUE` for `tag_into`.
 fn get_magic(
     magic: &Magic,
     reader_var: impl ToTokens,
     endian_var: impl ToTokens,
+    bind: bool,
 ) -> Option<TokenStream> {
     magic.as_ref().map(|magic| {
-        let magic = magic.deref_value();
-        quote! {
-            #ASSERT_MAGIC(#reader_var, #magic, #endian_var)?;
+        let binding = bind.then(|| quote! { let #MAGIC_VALUE = });
+        if let Some(mask) = magic.deref_mask() {
+            let values = magic.deref_values();
+            quote! {
+                #binding #ASSERT_MAGIC_MASKED(#reader_var, [#(#values),*], #mask, #endian_var)?;
+            }
+        } else if !magic.is_exact() {
+            let values = magic.deref_values();
+            quote! {
+                #binding #ASSERT_MAGIC_ANY(#reader_var, [#(#values),*], #endian_var)?;
+            }
+        } else {
+            let magic = magic.deref_value();
+            quote! {
+                #ASSERT_MAGIC(#reader_var, #magic, #endian_var)?;
+            }
         }
     })
 }
@@ -0,0 +1,192 @@
+use super::r#struct::get_err_context_at;
+use crate::binrw::{
+    codegen::{
+        get_args_lifetime, get_assertions, get_destructured_imports, get_endian,
+        get_passed_args_at, get_tag_endian, get_try_calc,
+        sanitization::{
+            AFTER_PARSE_ASYNC, ARGS, ASSERT_MAGIC_ASYNC, ASYNC_BINREAD_TRAIT, ASYNC_READ_TRAIT,
+            ASYNC_SEEK_EXT, ASYNC_SEEK_TRAIT, BINREAD_TRAIT, BIN_ERROR, BIN_RESULT, COUNT_POS,
+            ENDIAN_ENUM, OPT, POS, READER, REQUIRED_ARG_TRAIT, SEEK_FROM, TEMP, WITH_CONTEXT,
+        },
+    },
+    parser::{FieldMode, Input, Struct, StructField},
+};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Ident};
+
+/// Generates an override of `BinRead::__read_options_async` which reads each
+/// field of the struct from the asynchronous reader in turn.
+pub(crate) fn generate(input: &Input, derive_input: &syn::DeriveInput) -> TokenStream {
+    let st = match input {
+        Input::Struct(st) | Input::UnitStruct(st) => st,
+        Input::Enum(_) | Input::UnitOnlyEnum(_) => {
+            unreachable!("`async` is only parsed on structs")
+        }
+    };
+
+    if !derive_input.generics.params.is_empty() {
+        return syn::Error::new(
+            derive_input.generics.span(),
+            "`async` does not support generic types",
+        )
+        .to_compile_error();
+    }
+
+    let name = &derive_input.ident;
+    let imports = get_destructured_imports(input.imports(), Some(name), false).map(|imports| {
+        quote! {
+            let #imports = ::core::clone::Clone::clone(&#ARGS);
+        }
+    });
+    let endian = get_endian(input.endian());
+    let magic = get_magic(input, &get_tag_endian(input.tag_endian()));
+    let fields = st.fields.iter().map(|field| generate_field(field, name));
+    let assertions = get_assertions(&st.assertions);
+    let return_value = get_return_value(input, st);
+    let lifetime = get_args_lifetime(Span::call_site());
+
+    quote! {
+        fn __read_options_async<#lifetime, R>(
+            #READER: &#lifetime mut R,
+            #OPT: #ENDIAN_ENUM,
+            #ARGS: Self::Args<#lifetime>,
+        ) -> impl ::core::future::Future<Output = #BIN_RESULT<Self>> + ::core::marker::Send + #lifetime
+        where
+            R: #ASYNC_READ_TRAIT
+                + #ASYNC_SEEK_TRAIT
+                + ::core::marker::Unpin
+                + ::core::marker::Send
+                + ?::core::marker::Sized,
+            Self: ::core::marker::Send + #lifetime,
+            Self::Args<#lifetime>: ::core::clone::Clone + ::core::marker::Send,
+        {
+            async move {
+                let #POS = #ASYNC_SEEK_EXT::stream_position(#READER).await?;
+                let #TEMP = async {
+                    #imports
+                    let #OPT = #endian;
+                    #magic
+                    #(#fields)*
+                    #(#assertions)*
+                    let mut #TEMP = #return_value;
+                    #AFTER_PARSE_ASYNC(&mut #TEMP, #READER, #OPT, #ARGS).await?;
+                    ::core::result::Result::<Self, #BIN_ERROR>::Ok(#TEMP)
+                }
+                .await;
+                if #TEMP.is_err() {
+                    #ASYNC_SEEK_EXT::seek(#READER, #SEEK_FROM::Start(#POS)).await?;
+                }
+                #TEMP
+            }
+        }
+    }
+}
+
+fn generate_field(field: &StructField, name: &Ident) -> TokenStream {
+    // temp + ignore == just don't bother
+    if field.is_temp(false) && matches!(field.field_mode, FieldMode::Default) {
+        return TokenStream::new();
+    }
+
+    let ident = &field.ident;
+    let ty = &field.ty;
+    let endian = get_endian(&field.endian);
+
+    let magic = field.magic.as_ref().map(|magic| {
+        let magic = magic.deref_value();
+        quote! {
+            #ASSERT_MAGIC_ASYNC(#READER, #magic, #endian).await?;
+        }
+    });
+
+    let count_pos = field.count.is_some().then(|| {
+        quote! {
+            let #COUNT_POS = #ASYNC_SEEK_EXT::stream_position(#READER).await?;
+        }
+    });
+
+    let value = match &field.field_mode {
+        FieldMode::Default => quote! { <_>::default() },
+        FieldMode::Calc(calc) => quote! { #calc },
+        FieldMode::TryCalc(calc) => with_err_context(&get_try_calc(POS, ty, calc), field, name),
+        FieldMode::Normal => {
+            let pos = quote! { #COUNT_POS };
+            let args = get_passed_args_at(field, READER, &pos).map_or_else(
+                || quote_spanned! {ty.span()=> <_ as #REQUIRED_ARG_TRAIT>::args() },
+                |args| {
+                    quote_spanned! {ty.span()=> {
+                        let #TEMP: <#ty as #BINREAD_TRAIT>::Args<'_> = #args;
+                        #TEMP
+                    }}
+                },
+            );
+            let value = quote! {
+                <#ty as #ASYNC_BINREAD_TRAIT>::read_options_async(#READER, #endian, #args).await
+            };
+            with_err_context(&value, field, name)
+        }
+        FieldMode::Function(..) => unreachable!("custom parsers are rejected with `async`"),
+    };
+
+    let value = match &field.if_cond {
+        Some(cond) => {
+            let condition = &cond.condition;
+            let alternate = cond
+                .alternate
+                .as_ref()
+                .map_or_else(|| quote! { <_>::default() }, Clone::clone);
+            quote! {
+                if #condition {
+                    #value
+                } else {
+                    #alternate
+                }
+            }
+        }
+        None => value,
+    };
+
+    let assertions = get_assertions(&field.assertions);
+
+    quote! {
+        #magic
+        #count_pos
+        let #ident: #ty = #value;
+        #(#assertions)*
+    }
+}
+
+fn get_magic(input: &Input, endian: &TokenStream) -> Option<TokenStream> {
+    input.magic().as_ref().map(|magic| {
+        let magic = magic.deref_value();
+        quote! {
+            #ASSERT_MAGIC_ASYNC(#READER, #magic, #endian).await?;
+        }
+    })
+}
+
+fn get_return_value(input: &Input, st: &Struct) -> TokenStream {
+    let out_names = st.iter_permanent_idents();
+    if matches!(input, Input::UnitStruct(_)) {
+        quote! { Self }
+    } else if st.is_tuple() {
+        quote! { Self(#(#out_names),*) }
+    } else {
+        quote! { Self { #(#out_names),* } }
+    }
+}
+
+/// Unwraps `result`, adding the field to the backtrace of an error.
+fn with_err_context(result: &TokenStream, field: &StructField, name: &Ident) -> TokenStream {
+    let pos = quote! { #ASYNC_SEEK_EXT::stream_position(#READER).await.ok() };
+    let backtrace = get_err_context_at(&pos, field, Some(name), None);
+    quote! {
+        match #result {
+            ::core::result::Result::Ok(#TEMP) => #TEMP,
+            ::core::result::Result::Err(err) => {
+                return ::core::result::Result::Err(#WITH_CONTEXT(err, #backtrace));
+            }
+        }
+    }
+}
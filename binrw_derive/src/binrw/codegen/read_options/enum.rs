@@ -4,16 +4,20 @@ use super::{
 };
 use crate::binrw::{
     codegen::{
-        get_assertions,
+        get_assertions, get_tag_endian,
         sanitization::{
-            BACKTRACE_FRAME, BIN_ERROR, ERROR_BASKET, OPT, POS, READER, READ_METHOD, SEEK_FROM,
-            SEEK_TRAIT, TEMP, WITH_CONTEXT,
+            BACKTRACE_FRAME, BAD_MAGIC, BIN_ERROR, ERROR_BASKET, MAGIC_VALUE, MATCHED_VARIANT, POS,
+            READER, READ_METHOD, SEEK_FROM, SEEK_TRAIT, TAG, TAG_MATCHED, TEMP, WITH_CONTEXT,
         },
     },
-    parser::{Enum, EnumErrorMode, EnumVariant, Input, UnitEnumField, UnitOnlyEnum},
+    parser::{
+        CondEndian, Endian, Enum, EnumErrorMode, EnumVariant, Input, Struct, UnitEnumField,
+        UnitOnlyEnum,
+    },
 };
 use proc_macro2::TokenStream;
 use quote::quote;
+use std::collections::HashSet;
 use syn::Ident;
 
 pub(super) fn generate_unit_enum(
@@ -25,11 +29,15 @@ pub(super) fn generate_unit_enum(
         .add_imports(name)
         .add_endian()
         .add_magic_pre_assertion()
+        .add_map_stream()
         .finish();
 
-    let read = match en.map.as_repr() {
-        Some(repr) => generate_unit_enum_repr(&input.stream_ident_or(READER), repr, &en.fields),
-        None => generate_unit_enum_magic(&input.stream_ident_or(READER), &en.fields),
+    let reader_var = input.stream_ident_or(READER);
+    let tag_endian = get_tag_endian(en.tag_endian);
+    let read = match (&en.tag, en.map.as_repr()) {
+        (Some(tag), _) => generate_unit_enum_tag(tag, &en.fields),
+        (None, Some(repr)) => generate_unit_enum_repr(&reader_var, &tag_endian, repr, &en.fields),
+        (None, None) => generate_unit_enum_magic(&reader_var, &tag_endian, &en.fields),
     };
 
     quote! {
@@ -40,6 +48,7 @@ pub(super) fn generate_unit_enum(
 
 fn generate_unit_enum_repr(
     reader_var: &TokenStream,
+    endian: &TokenStream,
     repr: &TokenStream,
     variants: &[UnitEnumField],
 ) -> TokenStream {
@@ -58,7 +67,7 @@ fn generate_unit_enum_repr(
     });
 
     quote! {
-        let #TEMP: #repr = #READ_METHOD(#reader_var, #OPT, ())?;
+        let #TEMP: #repr = #READ_METHOD(#reader_var, #endian, ())?;
         #(#clauses else)* {
             Err(#WITH_CONTEXT(
                 #BIN_ERROR::NoVariantMatch {
@@ -73,7 +82,46 @@ fn generate_unit_enum_repr(
     }
 }
 
-fn generate_unit_enum_magic(reader_var: &TokenStream, variants: &[UnitEnumField]) -> TokenStream {
+fn generate_unit_enum_tag(tag: &TokenStream, variants: &[UnitEnumField]) -> TokenStream {
+    let clauses = variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let tag_value = variant
+            .tag_value
+            .iter()
+            .map(|value| quote! { *#TAG == (#value) });
+        let pre_assertions = variant
+            .pre_assertions
+            .iter()
+            .map(|assert| &assert.condition);
+
+        quote! {
+            if true #(&& (#tag_value))* #(&& (#pre_assertions))* {
+                Ok(Self::#ident)
+            }
+        }
+    });
+
+    quote! {
+        let #TAG = &(#tag);
+        #(#clauses else)* {
+            Err(#WITH_CONTEXT(
+                #BIN_ERROR::NoVariantMatch {
+                    pos: #POS,
+                },
+                #BACKTRACE_FRAME::Message({
+                    extern crate alloc;
+                    alloc::format!("Unexpected tag for enum: {:?}", #TAG).into()
+                })
+            ))
+        }
+    }
+}
+
+fn generate_unit_enum_magic(
+    reader_var: &TokenStream,
+    endian: &TokenStream,
+    variants: &[UnitEnumField],
+) -> TokenStream {
     // group fields by the type (Kind) of their magic value, preserve the order
     let group_by_magic_type = variants.iter().fold(
         Vec::new(),
@@ -121,7 +169,7 @@ fn generate_unit_enum_magic(reader_var: &TokenStream, variants: &[UnitEnumField]
         });
 
         let body = quote! {
-            match #amp #READ_METHOD(#reader_var, #OPT, ())? {
+            match #amp #READ_METHOD(#reader_var, #endian, ())? {
                 #(#matches,)*
                 _ => Err(#BIN_ERROR::NoVariantMatch { pos: #POS })
             }
@@ -184,9 +232,42 @@ pub(super) fn generate_data_enum(input: &Input, name: Option<&Ident>, en: &Enum)
         .add_endian()
         .add_magic_pre_assertion()
         .reset_position_after_magic()
+        .add_map_stream()
         .finish();
 
     let reader_var = input.stream_ident_or(READER);
+    let try_variants = generate_magic_dispatch(en, &reader_var, return_all_errors)
+        .unwrap_or_else(|| generate_try_each_variant(en, &reader_var, return_all_errors));
+
+    quote! {
+        #prelude
+        #create_error_basket
+        #try_variants
+        #return_error
+    }
+}
+
+/// Generates code which tries to read each variant of a data enum in turn.
+fn generate_try_each_variant(
+    en: &Enum,
+    reader_var: &TokenStream,
+    return_all_errors: bool,
+) -> TokenStream {
+    // A catch-all variant is only tried if no other variant matched its magic
+    let has_catch_all = en.variants.iter().any(EnumVariant::is_catch_all);
+    let create_tag_matched = has_catch_all.then(|| {
+        quote! {
+            let mut #TAG_MATCHED = false;
+        }
+    });
+
+    // With an external tag, only the variants with a matching `tag_value` are
+    // tried
+    let evaluate_tag = en.tag.as_ref().map(|tag| {
+        quote! {
+            let #TAG = &(#tag);
+        }
+    });
 
     let try_each_variant = en.variants.iter().map(|variant| {
         let body = generate_variant_impl(en, variant);
@@ -200,7 +281,16 @@ pub(super) fn generate_data_enum(input: &Input, name: Option<&Ident>, en: &Enum)
             TokenStream::new()
         };
 
-        quote! {
+        let track_tag_matched = (has_catch_all && !variant.is_catch_all()).then(|| {
+            quote! {
+                #TAG_MATCHED |= !matches!(
+                    #TEMP.as_ref().map_err(#BIN_ERROR::root_cause),
+                    Err(#BIN_ERROR::BadMagic { .. })
+                );
+            }
+        });
+
+        let try_variant = quote! {
             let #TEMP = (|| {
                 #body
             })();
@@ -208,22 +298,206 @@ pub(super) fn generate_data_enum(input: &Input, name: Option<&Ident>, en: &Enum)
             if #TEMP.is_ok() {
                 return #TEMP;
             } else {
+                #track_tag_matched
                 #handle_error
                 #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Start(#POS))?;
             }
+        };
+
+        let try_variant = if variant.is_catch_all() {
+            quote! {
+                if !#TAG_MATCHED {
+                    #try_variant
+                }
+            }
+        } else {
+            try_variant
+        };
+
+        if let Some(tag_value) = variant.tag_value() {
+            quote! {
+                if *#TAG == (#tag_value) {
+                    #try_variant
+                }
+            }
+        } else {
+            try_variant
         }
     });
 
     quote! {
-        #prelude
-        #create_error_basket
+        #evaluate_tag
+        #create_tag_matched
         #(#try_each_variant)*
-        #return_error
     }
 }
 
+/// Generates code which reads the magic number of a data enum once and jumps
+/// to the variant which accepts it, instead of trying each variant in turn.
+///
+/// This is only possible when every variant except a catch-all has a magic
+/// number, all of them are integers or byte strings of the same type which
+/// are read with the same byte order, and no two variants accept the same
+/// magic number. The result, including any errors, is the same as trying
+/// each variant.
+fn generate_magic_dispatch(
+    en: &Enum,
+    reader_var: &TokenStream,
+    return_all_errors: bool,
+) -> Option<TokenStream> {
+    if en.tag.is_some() {
+        return None;
+    }
+
+    let (variants, catch_all) = match en.variants.split_last() {
+        Some((last, rest)) if last.is_catch_all() => (rest, Some(last)),
+        _ => (&en.variants[..], None),
+    };
+
+    let (ty, amp, tag_endian) = get_dispatch_magic(en, variants)?;
+    let endian = get_tag_endian(tag_endian);
+
+    let arms = variants.iter().enumerate().map(|(index, variant)| {
+        let mut variant = variant.clone();
+        let magic = variant.magic_mut().take().unwrap();
+        let pattern = magic.match_value();
+        let bind_magic = (!magic.is_exact()).then(|| {
+            quote! { let #MAGIC_VALUE = #TAG; }
+        });
+        let body = generate_variant_impl(en, &variant);
+        quote! {
+            #pattern => {
+                #bind_magic
+                (#index, (|| {
+                    #body
+                })())
+            }
+        }
+    });
+
+    let handle_error = return_all_errors.then(|| {
+        let names = variants.iter().map(|variant| variant.ident().to_string());
+        quote! {
+            let mut #TEMP = #TEMP.err();
+            for (index, name) in [#(#names),*].into_iter().enumerate() {
+                let error = if index == #MATCHED_VARIANT {
+                    #TEMP.take()
+                } else {
+                    None
+                };
+                #ERROR_BASKET.push((
+                    name,
+                    error.unwrap_or_else(|| #BAD_MAGIC(#POS, #TAG)),
+                ));
+            }
+        }
+    });
+
+    // A catch-all variant is only tried if no other variant matched its magic
+    let create_tag_matched = catch_all.is_some().then(|| {
+        quote! {
+            let #TAG_MATCHED = !matches!(
+                #TEMP.as_ref().map_err(#BIN_ERROR::root_cause),
+                Err(#BIN_ERROR::BadMagic { .. })
+            );
+        }
+    });
+
+    let try_catch_all = catch_all.map(|variant| {
+        let body = generate_variant_impl(en, variant);
+        let handle_error = return_all_errors.then(|| {
+            let name = variant.ident().to_string();
+            quote! {
+                #ERROR_BASKET.push((#name, #TEMP.err().unwrap()));
+            }
+        });
+
+        quote! {
+            if !#TAG_MATCHED {
+                #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Start(#POS))?;
+                let #TEMP = (|| {
+                    #body
+                })();
+
+                if #TEMP.is_ok() {
+                    return #TEMP;
+                }
+
+                #handle_error
+            }
+        }
+    });
+
+    Some(quote! {
+        let #TAG: #ty = #READ_METHOD(#reader_var, #endian, ())?;
+        let (#MATCHED_VARIANT, #TEMP) = match #amp #TAG {
+            #(#arms,)*
+            _ => (usize::MAX, Err(#BAD_MAGIC(#POS, #TAG))),
+        };
+
+        if #TEMP.is_ok() {
+            return #TEMP;
+        }
+
+        #create_tag_matched
+        #handle_error
+        #try_catch_all
+        #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Start(#POS))?;
+    })
+}
+
+/// Returns the type of the magic number which selects a variant of a data
+/// enum, its reference prefix for matching, and its byte order, if the
+/// variants can be selected by reading the magic number once.
+fn get_dispatch_magic(
+    en: &Enum,
+    variants: &[EnumVariant],
+) -> Option<(TokenStream, TokenStream, Option<Endian>)> {
+    let mut first_magic = None;
+    let mut tag_endian = None;
+    let mut keys = HashSet::new();
+    for variant in variants {
+        let st = Struct::from(variant.clone());
+        let magic = st.magic.as_ref()?;
+        if magic.is_float()
+            || magic.has_mask()
+            || st.map_stream.is_some()
+            || st.stream_ident.is_some()
+            || first_magic.get_or_insert_with(|| magic.clone()).kind() != magic.kind()
+        {
+            return None;
+        }
+
+        if !magic.is_endian_independent() {
+            let endian = match (st.tag_endian.or(en.tag_endian), &st.endian) {
+                (Some(endian), _) | (None, &CondEndian::Fixed(endian)) => Some(endian),
+                (None, CondEndian::Inherited) => None,
+                (None, CondEndian::Cond(..)) => return None,
+            };
+            if *tag_endian.get_or_insert(endian) != endian {
+                return None;
+            }
+        }
+
+        for key in magic.value_keys() {
+            if !keys.insert(key) {
+                return None;
+            }
+        }
+    }
+
+    let first_magic = first_magic?;
+    Some((
+        TokenStream::from(first_magic.kind()),
+        first_magic.add_ref(),
+        tag_endian.flatten(),
+    ))
+}
+
 fn generate_variant_impl(en: &Enum, variant: &EnumVariant) -> TokenStream {
-    let input = Input::Struct(variant.clone().into());
+    let mut st = Struct::from(variant.clone());
+    st.tag_endian = st.tag_endian.or(en.tag_endian);
+    let input = Input::Struct(st);
 
     match variant {
         EnumVariant::Variant { ident, options } => StructGenerator::new(&input, options)
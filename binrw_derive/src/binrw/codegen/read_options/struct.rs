@@ -1,18 +1,22 @@
 use super::{get_magic, PreludeGenerator};
-#[cfg(feature = "verbose-backtrace")]
-use crate::binrw::backtrace::BacktraceFrame;
 use crate::{
     binrw::{
         codegen::{
-            get_assertions, get_endian, get_map_err, get_passed_args, get_try_calc,
+            get_assertions, get_backtrace_code, get_debug_line, get_endian, get_err_context_frame,
+            get_map_err, get_passed_args, get_save_position, get_seek_to_saved, get_trace_field,
+            get_try_calc,
             sanitization::{
                 make_ident, AFTER_PARSE, ARGS_MACRO, ARGS_TYPE_HINT, BACKTRACE_FRAME,
-                BINREAD_TRAIT, COERCE_FN, DBG_EPRINTLN, MAP_ARGS_TYPE_HINT, MAP_READER_TYPE_HINT,
-                OPT, PARSE_FN_TYPE_HINT, POS, READER, READ_FUNCTION, READ_METHOD,
-                REQUIRED_ARG_TRAIT, SAVED_POSITION, SEEK_FROM, SEEK_TRAIT, TEMP, WITH_CONTEXT,
+                BINREAD_TRAIT, BIN_ERROR, CHECKSUM_POS, CHECK_FLOAT, COERCE_FN, COUNT_BYTES,
+                COUNT_BYTES_WITH, DBG_EPRINTLN, FINALLY_FN_TYPE_HINT, FLOAT_POS, IS_EOF,
+                MAP_ARGS_TYPE_HINT, MAP_READER_TYPE_HINT, OPT, PARSE_FN_TYPE_HINT, POS, READER,
+                READ_FUNCTION, READ_METHOD, REQUIRED_ARG_TRAIT, SAVED_POSITION, SEEK_FROM,
+                SEEK_TRAIT, SIZE_REGION, SIZE_REGION_CAPTURE, SIZE_REGION_END, SIZE_REGION_VAR,
+                SKIP_FILL, TAKE_SEEK, TEMP, UNTRUSTED_ALIGN, UNTRUSTED_SIZE, VERIFY_CHECKSUM,
+                WITH_CONTEXT,
             },
         },
-        parser::{ErrContext, FieldMode, Input, Map, Struct, StructField},
+        parser::{FieldMode, Input, Map, OffsetBase, Struct, StructField, Trailing},
     },
     util::{quote_spanned_any, IdentStr},
 };
@@ -28,9 +32,18 @@ pub(super) fn generate_unit_struct(
 ) -> TokenStream {
     let prelude = get_prelude(input, name);
     let return_type = get_return_type(variant_ident);
+    let ((region_start, region_end), return_value) = match input {
+        Input::UnitStruct(st) => (
+            get_size_region(input, st),
+            get_return_value(input, st, &return_type),
+        ),
+        _ => (<_>::default(), quote! { Ok(#return_type) }),
+    };
     quote! {
         #prelude
-        Ok(#return_type)
+        #region_start
+        #region_end
+        #return_value
     }
 }
 
@@ -81,21 +94,26 @@ impl<'input> StructGenerator<'input> {
             .st
             .fields
             .iter()
+            .filter(|field| !self.st.is_trailing_capture(field))
             .map(|field| generate_field(self.input, field, name, variant_name));
         let after_parse = {
             let after_parse = self
                 .st
                 .fields
                 .iter()
-                .map(|field| generate_after_parse(self.input, field));
+                .filter(|field| !self.st.is_trailing_capture(field))
+                .map(|field| generate_after_parse(self.input, field, name, variant_name));
             wrap_save_restore(
                 &self.input.stream_ident_or(READER),
                 quote!(#(#after_parse)*),
             )
         };
+        let (region_start, region_end) = get_size_region(self.input, self.st);
         self.out = quote! {
             #prelude
+            #region_start
             #(#read_fields)*
+            #region_end
             #after_parse
         };
 
@@ -111,24 +129,49 @@ impl<'input> StructGenerator<'input> {
             quote! { #return_type { #(#out_names),* } }
         };
 
+        let return_value = get_return_value(self.input, self.st, &return_value);
         let head = self.out;
         self.out = quote! {
             #head
-            Ok(#return_value)
+            #return_value
         };
 
         self
     }
 }
 
-fn generate_after_parse(input: &Input, field: &StructField) -> Option<TokenStream> {
+fn generate_after_parse(
+    input: &Input,
+    field: &StructField,
+    name: Option<&Ident>,
+    variant_name: Option<&str>,
+) -> Option<TokenStream> {
     if field.deref_now.is_none() {
         get_after_parse_handler(field).map(|after_parse_fn| {
-            let (reader_var, endian_var, args_var) = make_field_vars(input, field);
-            AfterParseCallGenerator::new(field)
+            let (mut reader_var, endian_var, args_var) = make_field_vars(input, field);
+            let stream = field.stream.as_ref().filter(|_| field.map_stream.is_none());
+            if let Some(stream) = stream {
+                // Borrowing the stream again instead of reusing the binding
+                // from the field allows other fields to use the same stream
+                reader_var = quote_spanned_any! { stream.span()=> &mut *(#stream) };
+            }
+            let map_err = get_err_context(&reader_var, field, name, variant_name);
+            let call = AfterParseCallGenerator::new(field)
                 .get_value_from_ident()
-                .call_after_parse(after_parse_fn, &reader_var, &endian_var, &args_var)
-                .finish()
+                .call_after_parse(
+                    after_parse_fn,
+                    &reader_var,
+                    &endian_var,
+                    &args_var,
+                    &map_err,
+                )
+                .finish();
+            if stream.is_some() {
+                let call = wrap_save_restore(&reader_var, call);
+                quote! {{ #call }}
+            } else {
+                call
+            }
         })
     } else {
         None
@@ -148,20 +191,26 @@ fn generate_field(
 
     FieldGenerator::new(input, field)
         .read_value()
+        .check_float()
         .try_conversion(name, variant_name)
         .map_value()
-        .deref_now()
+        .wrap_checksum()
+        .deref_now(name, variant_name)
+        .wrap_eof()
         .wrap_debug()
         .wrap_seek()
         .wrap_condition()
         .assign_to_var()
+        .wrap_trace()
         .append_assertions()
         .wrap_restore_position()
         .prefix_magic()
+        .prefix_save_position()
         .prefix_args_and_options()
         .prefix_map_function()
         .prefix_read_function()
         .prefix_map_stream()
+        .prefix_field_stream()
         .finish()
 }
 
@@ -184,6 +233,7 @@ impl<'field> AfterParseCallGenerator<'field> {
         reader_var: &TokenStream,
         endian_var: &TokenStream,
         args_var: &Option<Ident>,
+        map_err: &TokenStream,
     ) -> Self {
         let value = self.out;
         let args_arg = if let Some(offset) = &self.field.offset_after {
@@ -204,7 +254,7 @@ impl<'field> AfterParseCallGenerator<'field> {
         };
 
         self.out = quote! {
-            #after_parse_fn(#value, #reader_var, #endian_var, #args_arg)?;
+            #after_parse_fn(#value, #reader_var, #endian_var, #args_arg) #map_err ?;
         };
 
         self
@@ -233,6 +283,7 @@ struct FieldGenerator<'field> {
     out: TokenStream,
     outer_reader_var: TokenStream,
     reader_var: TokenStream,
+    reader_ty: TokenStream,
     endian_var: TokenStream,
     args_var: Option<Ident>,
 }
@@ -244,47 +295,70 @@ impl<'field> FieldGenerator<'field> {
         Self {
             field,
             out: TokenStream::new(),
-            outer_reader_var: input.stream_ident_or(READER),
+            outer_reader_var: if field.stream.is_some() {
+                make_ident(&field.ident, "stream").into_token_stream()
+            } else {
+                input.stream_ident_or(READER)
+            },
             reader_var,
+            reader_ty: match input {
+                Input::Struct(st) if st.size.is_some() && field.stream.is_none() => {
+                    quote! { #TAKE_SEEK<&mut R> }
+                }
+                _ => quote! { R },
+            },
             endian_var,
             args_var,
         }
     }
 
     fn wrap_debug(mut self) -> Self {
-        // Unwrapping the proc-macro2 Span is undesirable but necessary until its API
-        // is updated to allow retrieving line/column again. Using a separate function
-        // to unwrap just to make it clearer what needs to be undone later.
-        // <https://github.com/dtolnay/proc-macro2/pull/383>
-        #[cfg(all(feature = "verbose-backtrace", nightly, proc_macro))]
-        fn start_line(span: proc_macro2::Span) -> usize {
-            span.unwrap().start().line()
-        }
-        #[cfg(not(all(feature = "verbose-backtrace", nightly, proc_macro)))]
-        fn start_line(_: proc_macro2::Span) -> usize {
-            0
-        }
-
-        if self.field.debug.is_some() {
+        if let Some(debug) = self.field.debug {
             let head = self.out;
             let reader_var = &self.reader_var;
             let ident = &self.field.ident;
-            let start_line = start_line(ident.span());
-            let at = if start_line == 0 {
-                quote!(::core::line!())
+            let at = get_debug_line(ident.span());
+
+            self.out = if debug.range {
+                quote! {{
+                    let #SAVED_POSITION = #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Current(0))?;
+                    let #TEMP = #head;
+                    #DBG_EPRINTLN!(
+                        "[{}:{} | offset {:#x}..{:#x}] {} = {:#x?}",
+                        ::core::file!(),
+                        #at,
+                        #SAVED_POSITION,
+                        #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Current(0))?,
+                        ::core::stringify!(#ident),
+                        &#TEMP
+                    );
+                    #TEMP
+                }}
             } else {
-                start_line.to_token_stream()
+                quote! {{
+                    let #SAVED_POSITION = #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Current(0))?;
+                    let #TEMP = #head;
+                    #DBG_EPRINTLN!(
+                        "[{}:{} | offset {:#x}] {} = {:#x?}",
+                        ::core::file!(), #at, #SAVED_POSITION, ::core::stringify!(#ident), &#TEMP
+                    );
+                    #TEMP
+                }}
             };
+        }
 
-            self.out = quote! {{
-                let #SAVED_POSITION = #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Current(0))?;
-                let #TEMP = #head;
-                #DBG_EPRINTLN!(
-                    "[{}:{} | offset {:#x}] {} = {:#x?}",
-                    ::core::file!(), #at, #SAVED_POSITION, ::core::stringify!(#ident), &#TEMP
-                );
-                #TEMP
-            }};
+        self
+    }
+
+    fn wrap_trace(mut self) -> Self {
+        let ident = &self.field.ident;
+        if let Some((before, after)) = get_trace_field(&self.reader_var, ident, &quote!(&#ident)) {
+            let head = self.out;
+            self.out = quote! {
+                #before
+                #head
+                #after
+            };
         }
 
         self
@@ -310,12 +384,13 @@ impl<'field> FieldGenerator<'field> {
         self
     }
 
-    fn deref_now(mut self) -> Self {
+    fn deref_now(mut self, name: Option<&Ident>, variant_name: Option<&str>) -> Self {
         if self.field.should_use_after_parse() {
             return self;
         }
 
         if let Some(after_parse) = get_after_parse_handler(self.field) {
+            let map_err = get_err_context(&self.reader_var, self.field, name, variant_name);
             let after_parse = AfterParseCallGenerator::new(self.field)
                 .get_value_from_temp()
                 .call_after_parse(
@@ -323,6 +398,7 @@ impl<'field> FieldGenerator<'field> {
                     &self.reader_var,
                     &self.endian_var,
                     &self.args_var,
+                    &map_err,
                 )
                 .finish();
 
@@ -366,6 +442,26 @@ impl<'field> FieldGenerator<'field> {
         self
     }
 
+    fn wrap_checksum(mut self) -> Self {
+        if let Some(checksum) = &self.field.checksum {
+            let reader_var = &self.reader_var;
+            let function = &checksum.function;
+            let range = checksum
+                .range
+                .as_ref()
+                .map_or_else(|| quote! { None }, |range| quote! { Some(#range) });
+            let value = self.out;
+            self.out = quote! {{
+                let #CHECKSUM_POS = #SEEK_TRAIT::stream_position(#reader_var)?;
+                let #TEMP = #value;
+                #VERIFY_CHECKSUM(#reader_var, #POS, #CHECKSUM_POS, #range, &#TEMP, #function)?;
+                #TEMP
+            }};
+        }
+
+        self
+    }
+
     fn prefix_map_function(mut self) -> Self {
         let map_func = make_ident(&self.field.ident, "map_func");
         let ty = &self.field.ty;
@@ -407,8 +503,22 @@ impl<'field> FieldGenerator<'field> {
             let rest = self.out;
             let reader_var = &self.reader_var;
             let outer_reader_var = &self.outer_reader_var;
+            let reader_ty = &self.reader_ty;
             self.out = quote_spanned_any! { map_stream.span()=>
-                let #reader_var = &mut #MAP_READER_TYPE_HINT::<R, _, _>(#map_stream)(#outer_reader_var);
+                let #reader_var = &mut #MAP_READER_TYPE_HINT::<#reader_ty, _, _>(#map_stream)(#outer_reader_var);
+                #rest
+            };
+        }
+
+        self
+    }
+
+    fn prefix_field_stream(mut self) -> Self {
+        if let Some(stream) = &self.field.stream {
+            let rest = self.out;
+            let outer_reader_var = &self.outer_reader_var;
+            self.out = quote_spanned_any! { stream.span()=>
+                let #outer_reader_var = &mut *(#stream);
                 #rest
             };
         }
@@ -417,15 +527,28 @@ impl<'field> FieldGenerator<'field> {
     }
 
     fn prefix_read_function(mut self) -> Self {
-        let read_function = match &self.field.field_mode {
-            FieldMode::Function(parser) => {
+        let count_bytes = self
+            .field
+            .count_bytes_of
+            .as_ref()
+            .map(|count| get_count_bytes(&self.reader_var, count));
+        let read_function = match (&self.field.field_mode, count_bytes) {
+            (FieldMode::Function(parser, _), None) => {
                 quote_spanned_any! { parser.span()=>
                     let #READ_FUNCTION = #PARSE_FN_TYPE_HINT(#parser);
                 }
             }
-            FieldMode::Normal => quote! {
+            (FieldMode::Function(parser, _), Some(count_bytes)) => {
+                quote_spanned_any! { parser.span()=>
+                    let #READ_FUNCTION = #PARSE_FN_TYPE_HINT(#COUNT_BYTES_WITH(#count_bytes, #parser));
+                }
+            }
+            (FieldMode::Normal, None) => quote! {
                 let #READ_FUNCTION = #READ_METHOD;
             },
+            (FieldMode::Normal, Some(count_bytes)) => quote! {
+                let #READ_FUNCTION = #PARSE_FN_TYPE_HINT(#COUNT_BYTES(#count_bytes));
+            },
             _ => return self,
         };
 
@@ -440,13 +563,19 @@ impl<'field> FieldGenerator<'field> {
 
     fn prefix_args_and_options(mut self) -> Self {
         let args = self.args_var.as_ref().map(|args_var| {
+            let args_var = if self.field.offset_base.as_deref() == Some(&OffsetBase::Current) {
+                quote! { mut #args_var }
+            } else {
+                args_var.to_token_stream()
+            };
             let map_func = make_ident(&self.field.ident, "map_func");
             let args = get_passed_args(self.field, READER);
             let ty = &self.field.ty;
+            let reader_ty = &self.reader_ty;
 
-            if let FieldMode::Function(_) = &self.field.field_mode {
+            if self.field.has_parse_fn() {
                 quote_spanned! {ty.span()=>
-                    let #args_var = #ARGS_TYPE_HINT::<R, #ty, _, _>(#READ_FUNCTION, #args);
+                    let #args_var = #ARGS_TYPE_HINT::<#reader_ty, #ty, _, _>(&#READ_FUNCTION, #args);
                 }
             } else {
                 match &self.field.map {
@@ -482,7 +611,8 @@ impl<'field> FieldGenerator<'field> {
     }
 
     fn prefix_magic(mut self) -> Self {
-        if let Some(magic) = get_magic(&self.field.magic, &self.reader_var, &self.endian_var) {
+        if let Some(magic) = get_magic(&self.field.magic, &self.reader_var, &self.endian_var, false)
+        {
             let tail = self.out;
             self.out = quote! {
                 #magic
@@ -498,7 +628,7 @@ impl<'field> FieldGenerator<'field> {
             FieldMode::Default => quote! { <_>::default() },
             FieldMode::Calc(calc) => quote! { #calc },
             FieldMode::TryCalc(calc) => get_try_calc(POS, &self.field.ty, calc),
-            read_mode @ (FieldMode::Normal | FieldMode::Function(_)) => {
+            read_mode @ (FieldMode::Normal | FieldMode::Function(..)) => {
                 let args_arg = get_args_argument(
                     self.field,
                     &self.args_var,
@@ -507,7 +637,7 @@ impl<'field> FieldGenerator<'field> {
                 let reader_var = &self.reader_var;
                 let endian_var = &self.endian_var;
 
-                if let FieldMode::Function(f) = read_mode {
+                let value = if let FieldMode::Function(f, _) = read_mode {
                     let ty = &self.field.ty;
                     // Adding a closure suppresses mentions of the generated
                     // READ_FUNCTION variable in errors; mapping the value with
@@ -522,6 +652,14 @@ impl<'field> FieldGenerator<'field> {
                     quote! {
                         #READ_FUNCTION(#reader_var, #endian_var, #args_arg)
                     }
+                };
+
+                match (self.field.offset_base.as_deref(), &self.args_var) {
+                    (Some(OffsetBase::Current), Some(args_var)) => quote! {{
+                        #args_var.offset = #SEEK_TRAIT::stream_position(#reader_var)?;
+                        #value
+                    }},
+                    _ => value,
                 }
             }
         };
@@ -529,13 +667,31 @@ impl<'field> FieldGenerator<'field> {
         self
     }
 
+    fn check_float(mut self) -> Self {
+        let finite = self.field.finite.is_some();
+        let no_subnormal = self.field.no_subnormal.is_some();
+        if finite || no_subnormal {
+            let reader_var = &self.reader_var;
+            let value = self.out;
+            self.out = quote! {{
+                let #FLOAT_POS = #SEEK_TRAIT::stream_position(#reader_var)?;
+                #value.and_then(|v| #CHECK_FLOAT(v, #FLOAT_POS, #finite, #no_subnormal))
+            }};
+        }
+
+        self
+    }
+
     fn try_conversion(mut self, name: Option<&Ident>, variant_name: Option<&str>) -> Self {
-        if !self.field.generated_value() {
+        if !matches!(
+            self.field.field_mode,
+            FieldMode::Calc(_) | FieldMode::Default
+        ) {
             let result = self.out;
             self.out = if self.field.do_try.is_some() {
                 quote! { #result.unwrap_or_default() }
             } else {
-                let map_err = get_err_context(self.field, name, variant_name);
+                let map_err = get_err_context(&self.reader_var, self.field, name, variant_name);
                 quote! { #result #map_err ? }
             };
         }
@@ -543,6 +699,22 @@ impl<'field> FieldGenerator<'field> {
         self
     }
 
+    fn wrap_eof(mut self) -> Self {
+        if self.field.if_eof_none.is_some() {
+            let reader_var = &self.reader_var;
+            let value = self.out;
+            self.out = quote! {
+                if #IS_EOF(#reader_var)? {
+                    ::core::option::Option::None
+                } else {
+                    #value
+                }
+            };
+        }
+
+        self
+    }
+
     fn wrap_condition(mut self) -> Self {
         if let Some(cond) = &self.field.if_cond {
             let condition = &cond.condition;
@@ -563,6 +735,18 @@ impl<'field> FieldGenerator<'field> {
         self
     }
 
+    fn prefix_save_position(mut self) -> Self {
+        if let Some(save_position) = get_save_position(&self.reader_var, self.field) {
+            let out = self.out;
+            self.out = quote! {
+                #save_position
+                #out
+            };
+        }
+
+        self
+    }
+
     fn wrap_restore_position(mut self) -> Self {
         if self.field.restore_position.is_some() {
             self.out = wrap_save_restore(&self.reader_var, self.out);
@@ -605,61 +789,57 @@ fn get_args_argument(
     )
 }
 
+fn get_count_bytes(reader_var: &TokenStream, count: &TokenStream) -> TokenStream {
+    quote_spanned_any! { count.span()=> {
+        let #TEMP = #count;
+        #[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+        u64::try_from(#TEMP).map_err(|_| {
+            extern crate alloc;
+            #BIN_ERROR::AssertFail {
+                pos: #SEEK_TRAIT::stream_position(#reader_var).unwrap_or_default(),
+                message: alloc::format!("count_bytes_of {:?} out of range of u64", #TEMP).into()
+            }
+        })?
+    }}
+}
+
 fn get_err_context(
+    reader_var: &TokenStream,
     field: &StructField,
     name: Option<&Ident>,
     variant_name: Option<&str>,
 ) -> TokenStream {
-    let backtrace = if let Some(ErrContext::Context(expr)) = &field.err_context {
-        quote_spanned! {field.ident.span()=>
-            #BACKTRACE_FRAME::Custom(Box::new(#expr) as _)
-        }
-    } else {
-        #[cfg(feature = "verbose-backtrace")]
-        let code = {
-            let code = BacktraceFrame::from_field(field).to_string();
-            if code.is_empty() {
-                quote! { None }
-            } else {
-                quote! { Some(#code) }
-            }
-        };
-        #[cfg(not(feature = "verbose-backtrace"))]
-        let code = quote!(None);
+    let pos = quote! { #SEEK_TRAIT::stream_position(#reader_var).ok() };
+    let backtrace = get_err_context_at(&pos, field, name, variant_name);
+    quote! {
+        .map_err(|err| #WITH_CONTEXT(err, #backtrace))
+    }
+}
 
-        let message = if let Some(ErrContext::Format(fmt, exprs)) = &field.err_context {
-            if exprs.is_empty() {
-                quote! { (#fmt) }
-            } else {
-                quote! {
-                    {
-                        extern crate alloc;
-                        alloc::format!(#fmt, #(#exprs),*)
-                    }
-                }
-            }
-        } else {
-            format!(
-                "While parsing field '{}' in {}",
-                field.ident,
-                name.map_or_else(|| variant_name.unwrap().into(), ToString::to_string)
-            )
-            .into_token_stream()
-        };
+/// Returns the backtrace frame which is added to errors from a field. `pos`
+/// is an expression for the position of the error.
+pub(super) fn get_err_context_at(
+    pos: &TokenStream,
+    field: &StructField,
+    name: Option<&Ident>,
+    variant_name: Option<&str>,
+) -> TokenStream {
+    get_err_context_frame(field).unwrap_or_else(|| {
+        let type_name = name.map_or_else(|| variant_name.unwrap().into(), ToString::to_string);
+        let field_name = field.ident.to_string();
+        let code = get_backtrace_code(field);
 
         quote_spanned! {field.ident.span()=>
-            #BACKTRACE_FRAME::Full {
-                message: #message.into(),
+            #BACKTRACE_FRAME::Field {
+                type_name: #type_name,
+                field: #field_name,
+                pos: #pos,
                 line: ::core::line!(),
                 file: ::core::file!(),
                 code: #code,
             }
         }
-    };
-
-    quote! {
-        .map_err(|err| #WITH_CONTEXT(err, #backtrace))
-    }
+    })
 }
 
 fn get_prelude(input: &Input, name: Option<&Ident>) -> TokenStream {
@@ -673,31 +853,44 @@ fn get_prelude(input: &Input, name: Option<&Ident>) -> TokenStream {
 
 fn generate_seek_after(reader_var: &TokenStream, field: &StructField) -> TokenStream {
     let pad_size_to = field.pad_size_to.as_ref().map(|pad| {
+        let size = if field.untrusted {
+            quote! { #UNTRUSTED_SIZE(#SEEK_TRAIT::stream_position(#reader_var)?, #POS)? }
+        } else {
+            quote! { (#SEEK_TRAIT::stream_position(#reader_var)? - #POS) as i64 }
+        };
+        let skip_padding = skip_padding(reader_var, field, &quote! { pad - size });
         quote! {{
             let pad = (#pad) as i64;
-            let size = (#SEEK_TRAIT::stream_position(#reader_var)? - #POS) as i64;
+            let size = #size;
             if size < pad {
-                #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Current(pad - size))?;
+                #skip_padding
             }
         }}
     });
     let pad_after = field
         .pad_after
         .as_ref()
-        .map(|value| map_pad(reader_var, value));
+        .map(|value| map_pad(reader_var, field, value));
     let align_after = field
         .align_after
         .as_ref()
-        .map(|value| map_align(reader_var, value));
+        .map(|value| map_align(reader_var, field, value));
+    let seek_after = field.seek_after.as_ref().map(|seek| {
+        quote! {
+            #SEEK_TRAIT::seek(#reader_var, #seek)?;
+        }
+    });
 
     quote! {
         #pad_size_to
         #pad_after
         #align_after
+        #seek_after
     }
 }
 
 fn generate_seek_before(reader_var: &TokenStream, field: &StructField) -> TokenStream {
+    let seek_to_saved = get_seek_to_saved(reader_var, field);
     let seek_before = field.seek_before.as_ref().map(|seek| {
         quote! {
             #SEEK_TRAIT::seek(#reader_var, #seek)?;
@@ -706,11 +899,11 @@ fn generate_seek_before(reader_var: &TokenStream, field: &StructField) -> TokenS
     let pad_before = field
         .pad_before
         .as_ref()
-        .map(|value| map_pad(reader_var, value));
+        .map(|value| map_pad(reader_var, field, value));
     let align_before = field
         .align_before
         .as_ref()
-        .map(|value| map_align(reader_var, value));
+        .map(|value| map_align(reader_var, field, value));
     let pad_size_to_before = field.pad_size_to.as_ref().map(|_| {
         quote! {
             let #POS = #SEEK_TRAIT::stream_position(#reader_var)?;
@@ -718,6 +911,7 @@ fn generate_seek_before(reader_var: &TokenStream, field: &StructField) -> TokenS
     });
 
     quote! {
+        #seek_to_saved
         #seek_before
         #pad_before
         #align_before
@@ -725,10 +919,54 @@ fn generate_seek_before(reader_var: &TokenStream, field: &StructField) -> TokenS
     }
 }
 
+fn get_size_region(input: &Input, st: &Struct) -> (TokenStream, TokenStream) {
+    st.size.as_ref().map_or_else(<_>::default, |size| {
+        let reader_var = input.stream_ident_or(READER);
+        let region_end = match st.trailing.as_deref() {
+            Some(Trailing::Capture(field)) => quote! {
+                #[allow(unused_variables)]
+                let (#reader_var, #field) = #SIZE_REGION_CAPTURE(#SIZE_REGION_VAR)?;
+            },
+            trailing => {
+                let error = matches!(trailing, Some(Trailing::Error));
+                quote! {
+                    #[allow(unused_variables)]
+                    let #reader_var = #SIZE_REGION_END(#SIZE_REGION_VAR, #error)?;
+                }
+            }
+        };
+        (
+            quote_spanned_any! { size.span()=>
+                let mut #SIZE_REGION_VAR = #SIZE_REGION(&mut *#reader_var, #size)?;
+                let #reader_var = &mut #SIZE_REGION_VAR;
+            },
+            region_end,
+        )
+    })
+}
+
 fn get_after_parse_handler(field: &StructField) -> Option<IdentStr> {
     field.can_call_after_parse().then_some(AFTER_PARSE)
 }
 
+/// Returns the value of a struct or variant, after running its `finally`
+/// hook.
+fn get_return_value(input: &Input, st: &Struct, value: &TokenStream) -> TokenStream {
+    if let Some(finally) = &st.finally {
+        let reader_var = input.stream_ident_or(READER);
+        quote_spanned_any! { finally.span()=>
+            let mut #TEMP = #value;
+            #FINALLY_FN_TYPE_HINT::<Self, _>(#finally)(
+                &mut #TEMP,
+                #SEEK_TRAIT::stream_position(#reader_var)?,
+            )?;
+            Ok(#TEMP)
+        }
+    } else {
+        quote! { Ok(#value) }
+    }
+}
+
 fn get_return_type(variant_ident: Option<&Ident>) -> TokenStream {
     variant_ident.map_or_else(|| quote! { Self }, |ident| quote! { Self::#ident })
 }
@@ -739,6 +977,8 @@ fn make_field_vars(
 ) -> (TokenStream, TokenStream, Option<Ident>) {
     let reader_var = if field.map_stream.is_some() {
         make_ident(&field.ident, "reader").into_token_stream()
+    } else if field.stream.is_some() {
+        make_ident(&field.ident, "stream").into_token_stream()
     } else {
         input.stream_ident_or(READER)
     };
@@ -758,17 +998,44 @@ fn make_field_vars(
     (reader_var, endian_var, args_var)
 }
 
-fn map_align(reader_var: &TokenStream, align: &TokenStream) -> TokenStream {
+fn map_align(reader_var: &TokenStream, field: &StructField, align: &TokenStream) -> TokenStream {
+    if field.untrusted {
+        let skip_padding = skip_padding(
+            reader_var,
+            field,
+            &quote! { #UNTRUSTED_ALIGN(pos, (#align) as i64)? },
+        );
+        return quote! {{
+            let pos = #SEEK_TRAIT::stream_position(#reader_var)?;
+            #skip_padding
+        }};
+    }
+
+    let skip_padding = skip_padding(
+        reader_var,
+        field,
+        &quote! { (align - (pos % align)) % align },
+    );
     quote! {{
         let align = (#align) as i64;
         let pos = #SEEK_TRAIT::stream_position(#reader_var)? as i64;
-        #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Current((align - (pos % align)) % align))?;
+        #skip_padding
     }}
 }
 
-fn map_pad(reader_var: &TokenStream, pad: &TokenStream) -> TokenStream {
-    quote! {
-        #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Current((#pad) as i64))?;
+fn map_pad(reader_var: &TokenStream, field: &StructField, pad: &TokenStream) -> TokenStream {
+    skip_padding(reader_var, field, &quote! { (#pad) as i64 })
+}
+
+fn skip_padding(reader_var: &TokenStream, field: &StructField, count: &TokenStream) -> TokenStream {
+    if let Some(fill) = &field.pad_with {
+        quote! {
+            #SKIP_FILL(#reader_var, #count, #fill)?;
+        }
+    } else {
+        quote! {
+            #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Current(#count))?;
+        }
     }
 }
 
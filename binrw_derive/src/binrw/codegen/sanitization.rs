@@ -29,6 +29,10 @@ ident_str! {
     pub(crate) READ_TRAIT = from_crate!(io::Read);
     pub(crate) WRITE_TRAIT = from_crate!(io::Write);
     pub(crate) SEEK_TRAIT = from_crate!(io::Seek);
+    pub(crate) ASYNC_BINREAD_TRAIT = from_crate!(AsyncBinRead);
+    pub(crate) ASYNC_READ_TRAIT = from_crate!(__private::AsyncRead);
+    pub(crate) ASYNC_SEEK_TRAIT = from_crate!(__private::AsyncSeek);
+    pub(crate) ASYNC_SEEK_EXT = from_crate!(__private::AsyncSeekExt);
     pub(crate) SEEK_FROM = from_crate!(io::SeekFrom);
     pub(crate) BIN_RESULT = from_crate!(BinResult);
     pub(crate) ENDIAN_ENUM = from_crate!(Endian);
@@ -41,9 +45,20 @@ ident_str! {
     pub(crate) ARGS = "__binrw_generated_var_arguments";
     pub(crate) SAVED_POSITION = "__binrw_generated_saved_position";
     pub(crate) ASSERT_MAGIC = from_crate!(__private::magic);
+    pub(crate) ASSERT_MAGIC_ASYNC = from_crate!(__private::magic_async);
+    pub(crate) AFTER_PARSE_ASYNC = from_crate!(__private::after_parse_async);
+    pub(crate) ASSERT_MAGIC_ANY = from_crate!(__private::magic_any);
+    pub(crate) ASSERT_MAGIC_MASKED = from_crate!(__private::magic_masked);
+    pub(crate) BAD_MAGIC = from_crate!(__private::bad_magic);
     pub(crate) ASSERT = from_crate!(__private::assert);
     pub(crate) ASSERT_ERROR_FN = from_crate!(__private::AssertErrorFn);
+    pub(crate) ASSERT_MESSAGE = from_crate!(__private::assert_message);
+    pub(crate) ASSERT_VALUE = from_crate!(__private::AssertValue);
+    pub(crate) ASSERT_VALUE_DEBUG = from_crate!(__private::AssertValueDebug);
+    pub(crate) ASSERT_VALUE_NO_DEBUG = from_crate!(__private::AssertValueNoDebug);
     pub(crate) COERCE_FN = from_crate!(__private::coerce_fn);
+    pub(crate) IS_EOF = from_crate!(__private::is_eof);
+    pub(crate) FINALLY_FN_TYPE_HINT = from_crate!(__private::finally_fn_type_hint);
     pub(crate) ARGS_TYPE_HINT = from_crate!(__private::parse_function_args_type_hint);
     pub(crate) MAP_ARGS_TYPE_HINT = from_crate!(__private::map_args_type_hint);
     pub(crate) REQUIRED_ARG_TRAIT = from_crate!(__private::Required);
@@ -58,13 +73,49 @@ ident_str! {
     pub(crate) WRITE_FN_MAP_OUTPUT_TYPE_HINT = from_crate!(__private::write_fn_map_output_type_hint);
     pub(crate) WRITE_FN_TRY_MAP_OUTPUT_TYPE_HINT = from_crate!(__private::write_fn_try_map_output_type_hint);
     pub(crate) WRITE_ZEROES = from_crate!(__private::write_zeroes);
+    pub(crate) WRITE_FILL = from_crate!(__private::write_fill);
+    pub(crate) SKIP_FILL = from_crate!(__private::skip_fill);
+    pub(crate) WRITE_DYN = from_crate!(__private::write_dyn);
+    pub(crate) READ_REST = from_crate!(__private::read_rest);
+    pub(crate) READ_CONTEXT = from_crate!(__private::read_context);
+    pub(crate) COUNT_OF = from_crate!(__private::count_of);
+    pub(crate) CHECK_FLOAT = from_crate!(__private::check_float);
+    pub(crate) FLOAT_FIELD_TRAIT = from_crate!(__private::FloatField);
+    pub(crate) CRC = from_crate!(crc);
+    pub(crate) VERIFY_CHECKSUM = from_crate!(__private::verify_checksum);
+    pub(crate) CHECKSUM_WRITER = from_crate!(__private::ChecksumWriter);
+    pub(crate) WRITE_CHECKSUM = from_crate!(__private::write_checksum);
+    pub(crate) SIZE_OF_VALUE = from_crate!(__private::size_of_value);
+    pub(crate) SIZE_REGION = from_crate!(__private::size_region);
+    pub(crate) SIZE_REGION_CAPTURE = from_crate!(__private::size_region_capture);
+    pub(crate) SIZE_REGION_END = from_crate!(__private::size_region_end);
+    pub(crate) TAKE_SEEK = from_crate!(io::TakeSeek);
+    pub(crate) SIZE_HINT_ADD = from_crate!(__private::size_hint_add);
+    pub(crate) SIZE_HINT_EITHER = from_crate!(__private::size_hint_either);
+    pub(crate) UNTRUSTED_ALIGN = from_crate!(__private::untrusted_align);
+    pub(crate) UNTRUSTED_COUNT = from_crate!(__private::untrusted_count);
+    pub(crate) UNTRUSTED_ENTER = from_crate!(__private::untrusted_enter);
+    pub(crate) UNTRUSTED_SIZE = from_crate!(__private::untrusted_size);
     pub(crate) ARGS_MACRO = from_crate!(args);
+    pub(crate) COUNT_BYTES = from_crate!(helpers::count_bytes);
+    pub(crate) COUNT_BYTES_WITH = from_crate!(helpers::count_bytes_with);
+    pub(crate) UNTIL_EOF = from_crate!(helpers::until_eof);
     pub(crate) META_ENDIAN_KIND = from_crate!(meta::EndianKind);
+    pub(crate) READ_AUDIT = from_crate!(meta::ReadAudit);
     pub(crate) READ_ENDIAN = from_crate!(meta::ReadEndian);
+    pub(crate) READ_ERROR = from_crate!(error::ReadError);
     pub(crate) READ_MAGIC = from_crate!(meta::ReadMagic);
+    pub(crate) READ_SCHEMA = from_crate!(schema::ReadSchema);
+    pub(crate) SCHEMA = from_crate!(schema::Schema);
+    pub(crate) SCHEMA_ENDIANNESS = from_crate!(schema::Endianness);
+    pub(crate) SCHEMA_FIELD = from_crate!(schema::Field);
+    pub(crate) SCHEMA_LAYOUT = from_crate!(schema::Layout);
+    pub(crate) SCHEMA_VARIANT = from_crate!(schema::Variant);
+    pub(crate) WRITE_AUDIT = from_crate!(meta::WriteAudit);
     pub(crate) WRITE_ENDIAN = from_crate!(meta::WriteEndian);
     pub(crate) WRITE_MAGIC = from_crate!(meta::WriteMagic);
-    pub(crate) WITH_CONTEXT = from_crate!(error::ContextExt::with_context);
+    pub(crate) WRITE_SCHEMA = from_crate!(schema::WriteSchema);
+    pub(crate) WITH_CONTEXT = from_crate!(__private::with_context);
     pub(crate) BACKTRACE_FRAME = from_crate!(error::BacktraceFrame);
     pub(crate) TEMP = "__binrw_temp";
     pub(crate) POS = "__binrw_generated_position_temp";
@@ -72,9 +123,27 @@ ident_str! {
     pub(crate) READ_FUNCTION = "__binrw_generated_read_function";
     pub(crate) WRITE_FUNCTION = "__binrw_generated_write_function";
     pub(crate) BEFORE_POS = "__binrw_generated_before_pos";
+    pub(crate) UNTRUSTED_GUARD = "__binrw_generated_untrusted_guard";
+    pub(crate) CHECKSUM_POS = "__binrw_generated_checksum_pos";
+    pub(crate) COUNT_POS = "__binrw_generated_count_pos";
+    pub(crate) FLOAT_POS = "__binrw_generated_float_pos";
+    pub(crate) PARENT_POS = "__binrw_generated_parent_pos";
+    pub(crate) SIZE_OF_END = "__binrw_generated_size_of_end";
+    pub(crate) SIZE_REGION_VAR = "__binrw_generated_size_region";
+    pub(crate) TAG = "__binrw_generated_tag";
+    pub(crate) MAGIC_VALUE = "__binrw_generated_magic_value";
+    pub(crate) TAG_MATCHED = "__binrw_generated_tag_matched";
+    pub(crate) MATCHED_VARIANT = "__binrw_generated_matched_variant";
     pub(crate) DBG_EPRINTLN = from_crate!(__private::eprintln);
 }
 
+#[cfg(feature = "tracing")]
+ident_str! {
+    pub(crate) TRACING = from_crate!(__private::tracing);
+    pub(crate) TRACE_SPAN = "__binrw_generated_trace_span";
+    pub(crate) TRACE_START = "__binrw_generated_trace_start";
+}
+
 pub(crate) fn make_ident(ident: &Ident, kind: &str) -> Ident {
     format_ident!("__binrw_generated_{}_{}", kind, ident)
 }
@@ -0,0 +1,265 @@
+use super::sanitization::{
+    READ_SCHEMA, SCHEMA, SCHEMA_ENDIANNESS, SCHEMA_FIELD, SCHEMA_LAYOUT, SCHEMA_VARIANT,
+    WRITE_SCHEMA,
+};
+use crate::binrw::parser::{
+    CondEndian, EnumVariant, FieldMode, Input, Magic, Map, Struct, StructField,
+};
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+
+pub(crate) fn generate<const WRITE: bool>(
+    input: &Input,
+    derive_input: &syn::DeriveInput,
+) -> TokenStream {
+    let name = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+    let schema_trait = if WRITE { WRITE_SCHEMA } else { READ_SCHEMA };
+
+    let type_name = name.to_string();
+    let endian = endianness(input.endian());
+    let magic = magic(input.magic());
+    let repr = match input.map() {
+        Map::Repr(repr) => {
+            let repr = source_text(repr.clone(), true);
+            quote! { Some(#repr) }
+        }
+        Map::None | Map::Map(_) | Map::Try(_) => quote! { None },
+    };
+
+    let layout = match (input, input.map()) {
+        (Input::UnitOnlyEnum(en), _) => {
+            let variants = en
+                .fields
+                .iter()
+                .map(|field| variant(&field.ident, &CondEndian::Inherited, &field.magic, &[]));
+            quote! { #SCHEMA_LAYOUT::Enum(&[#(#variants),*]) }
+        }
+        (_, Map::Map(_) | Map::Try(_) | Map::Repr(_)) => quote! { #SCHEMA_LAYOUT::Mapped },
+        (Input::Struct(st) | Input::UnitStruct(st), Map::None) => {
+            let fields = fields::<WRITE>(st);
+            quote! { #SCHEMA_LAYOUT::Struct(&[#(#fields),*]) }
+        }
+        (Input::Enum(en), Map::None) => {
+            let variants = en.variants.iter().map(|v| match v {
+                EnumVariant::Variant { ident, options } => {
+                    let fields = fields::<WRITE>(options).collect::<Vec<_>>();
+                    variant(ident, &options.endian, &options.magic, &fields)
+                }
+                EnumVariant::Unit(field) => {
+                    variant(&field.ident, &CondEndian::Inherited, &field.magic, &[])
+                }
+            });
+            quote! { #SCHEMA_LAYOUT::Enum(&[#(#variants),*]) }
+        }
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #schema_trait for #name #ty_generics #where_clause {
+            const SCHEMA: #SCHEMA = #SCHEMA {
+                name: #type_name,
+                endian: #endian,
+                magic: #magic,
+                repr: #repr,
+                layout: #layout,
+            };
+        }
+    }
+}
+
+/// Returns the fields of a struct which are actually read or written.
+fn fields<const WRITE: bool>(st: &Struct) -> impl Iterator<Item = TokenStream> + '_ {
+    st.fields
+        .iter()
+        .filter(|field| {
+            if WRITE {
+                field.is_written()
+            } else {
+                matches!(
+                    field.field_mode,
+                    FieldMode::Normal | FieldMode::Function(..)
+                )
+            }
+        })
+        .map(|field| self::field(field, st.for_write))
+}
+
+fn field(field: &StructField, for_write: bool) -> TokenStream {
+    let name = field.ident.to_string();
+    let ty = source_text(field.ty.to_token_stream(), true);
+    let endian = endianness(&field.endian);
+    let magic = magic(&field.magic);
+    let count = optional_text(field.count.as_ref());
+    let condition = optional_text(field.if_cond.as_ref().map(|cond| &cond.condition));
+    let pad_before = optional_text(field.pad_before.as_ref());
+    let pad_after = optional_text(field.pad_after.as_ref());
+    let align_before = optional_text(field.align_before.as_ref());
+    let align_after = optional_text(field.align_after.as_ref());
+    let pad_size_to = optional_text(field.pad_size_to.as_ref());
+    let temp = field.is_temp(for_write);
+    quote! {
+        #SCHEMA_FIELD {
+            name: #name,
+            ty: #ty,
+            endian: #endian,
+            magic: #magic,
+            count: #count,
+            condition: #condition,
+            pad_before: #pad_before,
+            pad_after: #pad_after,
+            align_before: #align_before,
+            align_after: #align_after,
+            pad_size_to: #pad_size_to,
+            temp: #temp,
+        }
+    }
+}
+
+fn variant(
+    ident: &syn::Ident,
+    endian: &CondEndian,
+    magic: &Magic,
+    fields: &[TokenStream],
+) -> TokenStream {
+    let name = ident.to_string();
+    let endian = endianness(endian);
+    let magic = self::magic(magic);
+    quote! {
+        #SCHEMA_VARIANT {
+            name: #name,
+            endian: #endian,
+            magic: #magic,
+            fields: &[#(#fields),*],
+        }
+    }
+}
+
+fn endianness(endian: &CondEndian) -> TokenStream {
+    match endian {
+        CondEndian::Inherited => quote! { #SCHEMA_ENDIANNESS::Inherited },
+        CondEndian::Fixed(endian) => quote! { #SCHEMA_ENDIANNESS::Fixed(#endian) },
+        CondEndian::Cond(endian, condition) => {
+            let condition = source_text(condition.clone(), false);
+            quote! { #SCHEMA_ENDIANNESS::Runtime(#endian, #condition) }
+        }
+    }
+}
+
+fn magic(magic: &Magic) -> TokenStream {
+    optional_text(magic.as_ref().map(|magic| magic.match_value()).as_ref())
+}
+
+fn optional_text(tokens: Option<&TokenStream>) -> TokenStream {
+    tokens.map_or_else(
+        || quote! { None },
+        |tokens| {
+            let text = source_text(tokens.clone(), false);
+            quote! { Some(#text) }
+        },
+    )
+}
+
+/// Converts tokens back to source code with conventional spacing, since the
+/// `Display` implementation of `TokenStream` puts spaces between all tokens.
+///
+/// In a type, `<` and `>` are treated as brackets instead of operators.
+fn source_text(tokens: TokenStream, is_type: bool) -> String {
+    let mut text = String::new();
+    write_source_text(&mut text, tokens, is_type);
+    text
+}
+
+fn write_source_text(text: &mut String, tokens: TokenStream, is_type: bool) {
+    // Whether the next token is attached to the previous one
+    let mut glue = true;
+    // Whether the previous token ends an operand, so that a following
+    // operator is binary instead of unary
+    let mut after_operand = false;
+    let mut prev_punct = None;
+    for token in tokens {
+        let (space_before, next_glue, is_operand) = match &token {
+            TokenTree::Punct(punct) => {
+                let ch = punct.as_char();
+                let continues = matches!(prev_punct, Some((_, Spacing::Joint)));
+                let is_path_sep = ch == ':' && prev_punct == Some((':', Spacing::Joint));
+                let is_unary = !after_operand && !continues && matches!(ch, '&' | '*' | '-' | '!');
+                let is_bracket = is_type && matches!(ch, '<' | '>');
+                let space_before =
+                    !(matches!(ch, ',' | ';' | '.' | ':' | '?') || is_path_sep || is_bracket);
+                let next_glue = punct.spacing() == Spacing::Joint
+                    || matches!(ch, '.' | '\'')
+                    || (ch == ':' && is_path_sep)
+                    || is_unary
+                    || (is_bracket && ch == '<');
+                prev_punct = Some((ch, punct.spacing()));
+                (space_before, next_glue, ch == '>' && is_type || ch == '?')
+            }
+            TokenTree::Group(group) => {
+                let is_call = after_operand && group.delimiter() != Delimiter::Brace;
+                prev_punct = None;
+                (!is_call, false, true)
+            }
+            TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                prev_punct = None;
+                (true, false, true)
+            }
+        };
+
+        if space_before && !glue {
+            text.push(' ');
+        }
+
+        if let TokenTree::Group(group) = token {
+            let (open, close) = match group.delimiter() {
+                Delimiter::Parenthesis => ("(", ")"),
+                Delimiter::Bracket => ("[", "]"),
+                Delimiter::Brace => ("{ ", " }"),
+                Delimiter::None => ("", ""),
+            };
+            text.push_str(open);
+            write_source_text(text, group.stream(), is_type);
+            text.push_str(close);
+        } else {
+            text.push_str(&token.to_string());
+        }
+
+        glue = next_glue;
+        after_operand = is_operand;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::source_text;
+    use quote::quote;
+
+    #[test]
+    fn source_text_spacing() {
+        for (tokens, is_type, expected) in [
+            (quote! { Vec<u8> }, true, "Vec<u8>"),
+            (quote! { [u8; 4] }, true, "[u8; 4]"),
+            (
+                quote! { Option<(u8, &'a str)> },
+                true,
+                "Option<(u8, &'a str)>",
+            ),
+            (
+                quote! { core::num::NonZeroU8 },
+                true,
+                "core::num::NonZeroU8",
+            ),
+            (quote! { len as usize * 2 }, false, "len as usize * 2"),
+            (
+                quote! { self.header.len() - 1 },
+                false,
+                "self.header.len() - 1",
+            ),
+            (quote! { kind == 1 && !flag }, false, "kind == 1 && !flag"),
+            (quote! { f(-1, *x) }, false, "f(-1, *x)"),
+            (quote! { b"ABC" | b"DEF" }, false, "b\"ABC\" | b\"DEF\""),
+        ] {
+            assert_eq!(source_text(tokens, is_type), expected);
+        }
+    }
+}
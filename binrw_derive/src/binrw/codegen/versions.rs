@@ -0,0 +1,361 @@
+//! Checks that the `if` conditions of fields which depend on a version number
+//! do not leave gaps or overlap by a single value.
+//!
+//! Proc macros cannot emit warnings directly, so each problem is reported by
+//! generating a use of a deprecated constant. This means the warnings can be
+//! silenced or denied with the `deprecated` lint, either for the whole crate or
+//! on the type itself.
+
+use crate::binrw::parser::{EnumVariant, Input, StructField};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote_spanned, ToTokens};
+use syn::{spanned::Spanned, BinOp, Expr, Lit, UnOp};
+
+pub(crate) fn generate(input: &Input, derive_input: &syn::DeriveInput) -> TokenStream {
+    let warnings = match input {
+        Input::Struct(st) => check_fields(&st.fields),
+        Input::Enum(en) => en
+            .variants
+            .iter()
+            .flat_map(|variant| match variant {
+                EnumVariant::Variant { options, .. } => check_fields(&options.fields),
+                EnumVariant::Unit(_) => Vec::new(),
+            })
+            .collect(),
+        Input::UnitStruct(_) | Input::UnitOnlyEnum(_) => Vec::new(),
+    };
+
+    // Lint levels set on the type do not apply to the generated items, so they
+    // are copied over
+    let lint_attrs = derive_input
+        .attrs
+        .iter()
+        .filter(|attr| {
+            ["allow", "warn", "deny", "forbid"]
+                .iter()
+                .any(|level| attr.path.is_ident(level))
+        })
+        .collect::<Vec<_>>();
+
+    warnings
+        .into_iter()
+        .map(|(span, message)| {
+            quote_spanned! {span=>
+                #(#lint_attrs)*
+                const _: () = {
+                    #[deprecated(note = #message)]
+                    #[allow(non_upper_case_globals)]
+                    const binrw_version_check: () = ();
+                    binrw_version_check
+                };
+            }
+        })
+        .collect()
+}
+
+/// An inclusive range of values.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Range {
+    start: i128,
+    end: i128,
+}
+
+impl Range {
+    const FULL: Self = Self {
+        start: i128::MIN,
+        end: i128::MAX,
+    };
+
+    fn intersect(self, other: Self) -> Option<Self> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        (start <= end).then_some(Self { start, end })
+    }
+
+    fn contains(self, other: Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    fn describe(self, var: &str) -> String {
+        if self.start == self.end {
+            format!("`{var} == {}`", self.start)
+        } else {
+            format!("`{} <= {var} <= {}`", self.start, self.end)
+        }
+    }
+}
+
+struct VersionedField {
+    name: String,
+    span: Span,
+    var: String,
+    range: Range,
+}
+
+fn check_fields(fields: &[StructField]) -> Vec<(Span, String)> {
+    let fields = fields
+        .iter()
+        .filter_map(|field| {
+            let condition = syn::parse2::<Expr>(field.if_cond.as_ref()?.condition.clone()).ok()?;
+            let (var, range) = condition_range(&condition)?;
+            Some(VersionedField {
+                name: field.ident.to_string(),
+                span: field.if_cond.as_ref()?.condition.span(),
+                var,
+                range,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut warnings = Vec::new();
+    let mut vars = fields.iter().map(|field| &field.var).collect::<Vec<_>>();
+    vars.sort();
+    vars.dedup();
+
+    for var in vars {
+        let group = fields
+            .iter()
+            .filter(|field| &field.var == var)
+            .collect::<Vec<_>>();
+        warnings.extend(find_gaps(&group));
+        warnings.extend(find_overlaps(&group));
+    }
+
+    warnings
+}
+
+/// Finds values between the conditions of a group of fields which are not
+/// covered by any of them.
+fn find_gaps(group: &[&VersionedField]) -> Vec<(Span, String)> {
+    let mut sorted = group.to_vec();
+    sorted.sort_by_key(|field| field.range.start);
+
+    let mut warnings = Vec::new();
+    let mut covered_end = None::<i128>;
+    for field in sorted {
+        if let Some(end) = covered_end {
+            if end < field.range.start.saturating_sub(1) {
+                let gap = Range {
+                    start: end + 1,
+                    end: field.range.start - 1,
+                };
+                warnings.push((
+                    field.span,
+                    format!(
+                        "`if` conditions on `{}` do not cover {}; check for a missing or misnumbered field",
+                        field.var,
+                        gap.describe(&field.var)
+                    ),
+                ));
+            }
+        }
+        covered_end = Some(covered_end.map_or(field.range.end, |end| end.max(field.range.end)));
+        if covered_end == Some(i128::MAX) {
+            break;
+        }
+    }
+
+    warnings
+}
+
+/// Finds pairs of conditions which overlap at exactly one value without one
+/// containing the other, which is usually an off-by-one mistake like using
+/// `<=` instead of `<`.
+fn find_overlaps(group: &[&VersionedField]) -> Vec<(Span, String)> {
+    let mut warnings = Vec::new();
+    for (index, later) in group.iter().enumerate() {
+        for earlier in &group[..index] {
+            let overlap = match earlier.range.intersect(later.range) {
+                Some(overlap) if overlap.start == overlap.end => overlap,
+                _ => continue,
+            };
+
+            if !earlier.range.contains(later.range) && !later.range.contains(earlier.range) {
+                warnings.push((
+                    later.span,
+                    format!(
+                        "`if` conditions of `{}` and `{}` both match {}; check for `<=` that should be `<`",
+                        earlier.name,
+                        later.name,
+                        overlap.describe(&later.var)
+                    ),
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
+/// Converts a condition which compares one variable to integer literals into
+/// the name of the variable and the range of values for which the condition is
+/// true.
+fn condition_range(expr: &Expr) -> Option<(String, Range)> {
+    match expr {
+        Expr::Paren(expr) => condition_range(&expr.expr),
+        Expr::Group(expr) => condition_range(&expr.expr),
+        Expr::Binary(expr) => match expr.op {
+            BinOp::And(_) => {
+                let (left_var, left) = condition_range(&expr.left)?;
+                let (right_var, right) = condition_range(&expr.right)?;
+                (left_var == right_var)
+                    .then(|| left.intersect(right))
+                    .flatten()
+                    .map(|range| (left_var, range))
+            }
+            op => {
+                if let Some(value) = int_value(&expr.right) {
+                    Some((var_name(&expr.left)?, compare_range(op, value)?))
+                } else {
+                    let value = int_value(&expr.left)?;
+                    Some((var_name(&expr.right)?, compare_range(flip(op)?, value)?))
+                }
+            }
+        },
+        _ => None,
+    }
+}
+
+fn compare_range(op: BinOp, value: i128) -> Option<Range> {
+    let full = Range::FULL;
+    match op {
+        BinOp::Eq(_) => Some(Range {
+            start: value,
+            end: value,
+        }),
+        BinOp::Lt(_) => Some(Range {
+            end: value.checked_sub(1)?,
+            ..full
+        }),
+        BinOp::Le(_) => Some(Range { end: value, ..full }),
+        BinOp::Gt(_) => Some(Range {
+            start: value.checked_add(1)?,
+            ..full
+        }),
+        BinOp::Ge(_) => Some(Range {
+            start: value,
+            ..full
+        }),
+        _ => None,
+    }
+}
+
+fn flip(op: BinOp) -> Option<BinOp> {
+    Some(match op {
+        BinOp::Eq(token) => BinOp::Eq(token),
+        BinOp::Lt(token) => BinOp::Gt(syn::Token![>](token.span)),
+        BinOp::Le(token) => BinOp::Ge(syn::Token![>=](token.spans)),
+        BinOp::Gt(token) => BinOp::Lt(syn::Token![<](token.span)),
+        BinOp::Ge(token) => BinOp::Le(syn::Token![<=](token.spans)),
+        _ => return None,
+    })
+}
+
+fn int_value(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(expr) => match &expr.lit {
+            Lit::Int(lit) => lit.base10_parse().ok(),
+            _ => None,
+        },
+        Expr::Unary(expr) if matches!(expr.op, UnOp::Neg(_)) => {
+            int_value(&expr.expr)?.checked_neg()
+        }
+        Expr::Paren(expr) => int_value(&expr.expr),
+        Expr::Group(expr) => int_value(&expr.expr),
+        _ => None,
+    }
+}
+
+fn var_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(expr) if expr.qself.is_none() => expr.path.get_ident().map(ToString::to_string),
+        Expr::Field(expr) => Some(format!(
+            "{}.{}",
+            var_name(&expr.base)?,
+            expr.member.to_token_stream()
+        )),
+        Expr::Unary(expr) if matches!(expr.op, UnOp::Deref(_)) => var_name(&expr.expr),
+        Expr::Paren(expr) => var_name(&expr.expr),
+        Expr::Group(expr) => var_name(&expr.expr),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binrw::parser::ParseResult;
+
+    fn warnings(input: TokenStream) -> Vec<String> {
+        let input = syn::parse2::<syn::DeriveInput>(input).unwrap();
+        let options = crate::binrw::Options {
+            derive: true,
+            write: false,
+        };
+        let ParseResult::Ok(input) = Input::from_input(&input, options) else {
+            panic!("invalid input");
+        };
+        match &input {
+            Input::Struct(st) => check_fields(&st.fields)
+                .into_iter()
+                .map(|(_, message)| message)
+                .collect(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn complete() {
+        assert!(warnings(quote::quote! {
+            #[br(import(version: u8))]
+            struct Foo {
+                #[br(if(version < 3))]
+                a: Option<u8>,
+                #[br(if(version >= 3 && version <= 5))]
+                b: Option<u16>,
+                #[br(if(6 <= version))]
+                c: Option<u32>,
+                #[br(if(version >= 4))]
+                d: Option<u8>,
+                #[br(if(flag))]
+                e: Option<u8>,
+            }
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn gap() {
+        let warnings = warnings(quote::quote! {
+            #[br(import(version: u8))]
+            struct Foo {
+                #[br(if(version < 3))]
+                a: Option<u8>,
+                #[br(if(version > 3))]
+                b: Option<u16>,
+                #[br(if(header.version == 1))]
+                c: Option<u8>,
+                #[br(if(header.version >= 4))]
+                d: Option<u8>,
+            }
+        });
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("do not cover `2 <= header.version <= 3`"));
+        assert!(warnings[1].contains("do not cover `version == 3`"));
+    }
+
+    #[test]
+    fn overlap() {
+        let warnings = warnings(quote::quote! {
+            #[br(import(version: u8))]
+            struct Foo {
+                #[br(if(version <= 3))]
+                a: Option<u8>,
+                #[br(if(version >= 3))]
+                b: Option<u16>,
+            }
+        });
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("of `a` and `b` both match `version == 3`"));
+    }
+}
@@ -1,11 +1,14 @@
 mod r#enum;
 mod prelude;
+pub(super) mod size_hint;
 mod r#struct;
 mod struct_field;
 
 use super::get_map_err;
 use crate::binrw::{
-    codegen::sanitization::{OPT, POS, SEEK_TRAIT, WRITER, WRITE_METHOD},
+    codegen::sanitization::{
+        CHECKSUM_WRITER, OPT, POS, SEEK_TRAIT, WRITER, WRITE_METHOD, WRITE_TRAIT,
+    },
     parser::{Input, Map},
 };
 use proc_macro2::TokenStream;
@@ -30,16 +33,31 @@ pub(crate) fn generate(input: &Input, derive_input: &syn::DeriveInput) -> TokenS
     };
 
     let writer_var = input.stream_ident_or(WRITER);
+    let flush_after = input.flush_after().then(|| {
+        quote! {
+            #WRITE_TRAIT::flush(#writer_var)?;
+        }
+    });
 
     quote! {
         let #writer_var = #WRITER;
         let #POS = #SEEK_TRAIT::stream_position(#writer_var)?;
         #inner
+        #flush_after
 
         Ok(())
     }
 }
 
+/// Returns the type of the writer used by fields of the given input.
+fn writer_type(input: &Input) -> TokenStream {
+    if input.has_checksum() {
+        quote! { #CHECKSUM_WRITER<'_, W> }
+    } else {
+        quote! { W }
+    }
+}
+
 fn generate_map(input: &Input, name: Option<&Ident>, map: &TokenStream) -> TokenStream {
     let map_try = input.map().is_try().then(|| {
         let map_err = get_map_err(POS, map.span());
@@ -63,7 +81,7 @@ fn generate_map(input: &Input, name: Option<&Ident>, map: &TokenStream) -> Token
     let magic = input.magic();
     let endian = input.endian();
     prelude::PreludeGenerator::new(write_data, Some(input), name, &writer_var)
-        .prefix_magic(magic)
+        .prefix_magic(magic, input.tag_endian())
         .prefix_endian(endian)
         .prefix_imports()
         .finish()
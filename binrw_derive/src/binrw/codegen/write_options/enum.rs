@@ -1,6 +1,9 @@
-use super::{prelude::PreludeGenerator, r#struct::StructGenerator};
+use super::{prelude::PreludeGenerator, r#struct::StructGenerator, writer_type};
 use crate::binrw::{
-    codegen::sanitization::{OPT, WRITER, WRITE_METHOD},
+    codegen::{
+        get_tag_endian,
+        sanitization::{WRITER, WRITE_METHOD},
+    },
     parser::{Enum, EnumVariant, Input, UnitEnumField, UnitOnlyEnum},
 };
 use proc_macro2::{Ident, TokenStream};
@@ -12,13 +15,14 @@ pub(crate) fn generate_unit_enum(
     en: &UnitOnlyEnum,
 ) -> TokenStream {
     let writer_var = input.stream_ident_or(WRITER);
+    let tag_endian = get_tag_endian(en.tag_endian);
     let write = match en.map.as_repr() {
-        Some(repr) => generate_unit_enum_repr(&writer_var, repr, &en.fields),
-        None => generate_unit_enum_magic(&writer_var, &en.fields),
+        Some(repr) => generate_unit_enum_repr(&writer_var, &tag_endian, repr, &en.fields),
+        None => generate_unit_enum_magic(&writer_var, &tag_endian, &en.fields),
     };
 
     PreludeGenerator::new(write, Some(input), name, &writer_var)
-        .prefix_magic(&en.magic)
+        .prefix_magic(&en.magic, en.tag_endian)
         .prefix_endian(&en.endian)
         .prefix_imports()
         .prefix_map_stream()
@@ -65,9 +69,10 @@ impl<'a> EnumGenerator<'a> {
             };
 
             let writer_var = &self.writer_var;
+            let writer_ty = writer_type(self.input);
             let writing = match variant {
                 EnumVariant::Variant { options, .. } => {
-                    StructGenerator::new(None, options, None, &self.writer_var)
+                    StructGenerator::new(None, options, None, &self.writer_var, &writer_ty)
                         .write_fields()
                         .prefix_prelude()
                         .finish()
@@ -76,12 +81,13 @@ impl<'a> EnumGenerator<'a> {
                     .magic
                     .as_ref()
                     .map(|magic| {
-                        let magic = magic.match_value();
+                        let magic = magic.value();
+                        let endian = get_tag_endian(self.en.tag_endian);
                         quote! {
                             #WRITE_METHOD (
                                 &#magic,
                                 #writer_var,
-                                #OPT,
+                                #endian,
                                 ()
                             )?;
                         }
@@ -109,9 +115,10 @@ impl<'a> EnumGenerator<'a> {
         let out = self.out;
 
         self.out = PreludeGenerator::new(out, Some(self.input), self.name, &self.writer_var)
-            .prefix_magic(&self.en.magic)
+            .prefix_magic(&self.en.magic, self.en.tag_endian)
             .prefix_endian(&self.en.endian)
             .prefix_imports()
+            .prefix_checksum_writer()
             .prefix_map_stream()
             .finish();
 
@@ -125,6 +132,7 @@ impl<'a> EnumGenerator<'a> {
 
 fn generate_unit_enum_repr(
     writer_var: &TokenStream,
+    endian: &TokenStream,
     repr: &TokenStream,
     variants: &[UnitEnumField],
 ) -> TokenStream {
@@ -141,23 +149,27 @@ fn generate_unit_enum_repr(
                 #(#branches),*
             } as #repr),
             #writer_var,
-            #OPT,
+            #endian,
             (),
         )?;
     }
 }
 
-fn generate_unit_enum_magic(writer_var: &TokenStream, variants: &[UnitEnumField]) -> TokenStream {
+fn generate_unit_enum_magic(
+    writer_var: &TokenStream,
+    endian: &TokenStream,
+    variants: &[UnitEnumField],
+) -> TokenStream {
     let branches = variants.iter().map(|variant| {
         let name = &variant.ident;
         let magic = variant.magic.as_ref().map(|magic| {
-            let magic = magic.match_value();
+            let magic = magic.value();
 
             quote! {
                 #WRITE_METHOD (
                     &#magic,
                     #writer_var,
-                    #OPT,
+                    #endian,
                     (),
                 )?;
             }
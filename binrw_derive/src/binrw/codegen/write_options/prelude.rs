@@ -1,10 +1,12 @@
 use crate::{
     binrw::{
         codegen::{
-            get_destructured_imports, get_endian,
-            sanitization::{ARGS, MAP_WRITER_TYPE_HINT, OPT, WRITER, WRITE_METHOD},
+            get_destructured_imports, get_endian, get_tag_endian,
+            sanitization::{
+                ARGS, CHECKSUM_WRITER, MAP_WRITER_TYPE_HINT, OPT, POS, WRITER, WRITE_METHOD,
+            },
         },
-        parser::{CondEndian, Input, Magic},
+        parser::{CondEndian, Endian, Input, Magic, Prepare},
     },
     util::quote_spanned_any,
 };
@@ -49,16 +51,17 @@ impl<'a> PreludeGenerator<'a> {
         self
     }
 
-    pub(crate) fn prefix_magic(mut self, magic: &Magic) -> Self {
+    pub(crate) fn prefix_magic(mut self, magic: &Magic, tag_endian: Option<Endian>) -> Self {
         if let Some(magic) = magic {
             let writer_var = &self.writer_var;
-            let magic = magic.match_value();
+            let magic = magic.value();
+            let endian = get_tag_endian(tag_endian);
             let out = self.out;
             self.out = quote! {
                 #WRITE_METHOD (
                     &#magic,
                     #writer_var,
-                    #OPT,
+                    #endian,
                     ()
                 )?;
 
@@ -69,6 +72,26 @@ impl<'a> PreludeGenerator<'a> {
         self
     }
 
+    pub(crate) fn prefix_prepare(mut self, prepare: Option<&Prepare>) -> Self {
+        if let Some(prepare) = prepare {
+            let expr = &prepare.expr;
+            let out = self.out;
+            self.out = if let Some(ident) = &prepare.ident {
+                quote_spanned_any! { expr.span()=>
+                    let #ident = #expr;
+                    #out
+                }
+            } else {
+                quote_spanned_any! { expr.span()=>
+                    let () = #expr;
+                    #out
+                }
+            };
+        }
+
+        self
+    }
+
     pub(crate) fn prefix_endian(mut self, endian: &CondEndian) -> Self {
         let endian = get_endian(endian);
         let out = self.out;
@@ -80,6 +103,19 @@ impl<'a> PreludeGenerator<'a> {
         self
     }
 
+    pub(crate) fn prefix_checksum_writer(mut self) -> Self {
+        if self.input.is_some_and(Input::has_checksum) {
+            let writer_var = &self.writer_var;
+            let out = self.out;
+            self.out = quote! {
+                let #writer_var = &mut #CHECKSUM_WRITER::new(#writer_var, #POS);
+                #out
+            };
+        }
+
+        self
+    }
+
     pub(crate) fn prefix_map_stream(mut self) -> Self {
         if let Some(input) = self.input {
             if let Some(map_stream) = input.map_stream() {
@@ -0,0 +1,154 @@
+use crate::binrw::{
+    codegen::sanitization::{BINWRITE_TRAIT, SIZE_HINT_ADD, SIZE_HINT_EITHER},
+    parser::{
+        Enum, EnumVariant, FieldMode, Input, Magic, Map, PassedArgs, Struct, StructField,
+        UnitOnlyEnum,
+    },
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(crate) fn generate(input: &Input) -> Option<TokenStream> {
+    if !matches!(input.map(), Map::None) || input.map_stream().is_some() {
+        return None;
+    }
+
+    let hint = match input {
+        Input::UnitStruct(st) | Input::Struct(st) => {
+            let pattern = st.fields_pattern();
+            let hint = struct_hint(&st.magic, st);
+            quote! {
+                let Self #pattern = self;
+                #hint
+            }
+        }
+        Input::Enum(en) => add(&magic_hint(&en.magic), &data_enum_hint(en)),
+        Input::UnitOnlyEnum(en) => add(&magic_hint(&en.magic), &unit_enum_hint(en)),
+    };
+
+    Some(quote! {
+        #[allow(unused_variables)]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            #hint
+        }
+    })
+}
+
+fn data_enum_hint(en: &Enum) -> TokenStream {
+    let variants = en.variants.iter().map(|variant| {
+        let name = variant.ident();
+        match variant {
+            EnumVariant::Variant { options, .. } => {
+                let pattern = options.fields_pattern();
+                let hint = struct_hint(&options.magic, options);
+                quote! { Self::#name #pattern => #hint, }
+            }
+            EnumVariant::Unit(variant) => {
+                let hint = magic_hint(&variant.magic);
+                quote! { Self::#name => #hint, }
+            }
+        }
+    });
+
+    quote! {
+        match self {
+            #(#variants)*
+        }
+    }
+}
+
+fn unit_enum_hint(en: &UnitOnlyEnum) -> TokenStream {
+    let variants = en.fields.iter().map(|variant| {
+        let name = &variant.ident;
+        let hint = magic_hint(&variant.magic);
+        quote! { Self::#name => #hint, }
+    });
+
+    quote! {
+        match self {
+            #(#variants)*
+        }
+    }
+}
+
+fn struct_hint(magic: &Magic, st: &Struct) -> TokenStream {
+    // Once the writer is moved, later fields may overwrite earlier ones or
+    // leave gaps, so nothing is known about the size
+    if st.fields.iter().any(moves_writer) {
+        return quote! { (0, None) };
+    }
+
+    st.fields
+        .iter()
+        .map(field_hint)
+        .fold(magic_hint(magic), |hint, field| add(&hint, &field))
+}
+
+fn field_hint(field: &StructField) -> TokenStream {
+    // Fields written to another stream do not add anything to this one
+    if !field.is_written() || field.stream.is_some() {
+        return exact_zero();
+    }
+
+    let hint = if has_fixed_layout(field) {
+        let ident = &field.ident;
+        add(
+            &magic_hint(&field.magic),
+            &quote! { #BINWRITE_TRAIT::size_hint(#ident) },
+        )
+    } else {
+        // The written data depends on something other than the value of the
+        // field, so only the magic is known
+        add(&magic_hint(&field.magic), &quote! { (0, None) })
+    };
+
+    match &field.if_cond {
+        Some(cond) if cond.alternate.is_none() => quote! {
+            #SIZE_HINT_EITHER((0, Some(0)), #hint)
+        },
+        _ => hint,
+    }
+}
+
+fn moves_writer(field: &StructField) -> bool {
+    field.is_written()
+        && (field.seek_before.is_some()
+            || field.seek_after.is_some()
+            || field.seek_to_saved.is_some()
+            || field.restore_position.is_some())
+}
+
+fn has_fixed_layout(field: &StructField) -> bool {
+    matches!(field.field_mode, FieldMode::Normal)
+        && field.map.is_none()
+        && field.map_stream.is_none()
+        && field.checksum.is_none()
+        && matches!(field.args, PassedArgs::None)
+        && field.restore_position.is_none()
+        && field.seek_before.is_none()
+        && field.seek_after.is_none()
+        && field.seek_to_saved.is_none()
+        && field.pad_before.is_none()
+        && field.pad_after.is_none()
+        && field.align_before.is_none()
+        && field.align_after.is_none()
+        && field.pad_size_to.is_none()
+}
+
+fn magic_hint(magic: &Magic) -> TokenStream {
+    match magic {
+        Some(magic) => {
+            let magic = magic.value();
+            quote! { #BINWRITE_TRAIT::size_hint(&#magic) }
+        }
+        None => exact_zero(),
+    }
+}
+
+fn add(a: &TokenStream, b: &TokenStream) -> TokenStream {
+    quote! { #SIZE_HINT_ADD(#a, #b) }
+}
+
+fn exact_zero() -> TokenStream {
+    quote! { (0, Some(0)) }
+}
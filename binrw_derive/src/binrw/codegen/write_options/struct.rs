@@ -1,4 +1,4 @@
-use super::{prelude::PreludeGenerator, struct_field::write_field};
+use super::{prelude::PreludeGenerator, struct_field::write_field, writer_type};
 use crate::binrw::{
     codegen::{get_assertions, sanitization::WRITER},
     parser::{Input, Struct},
@@ -8,12 +8,18 @@ use quote::quote;
 use syn::Ident;
 
 pub(super) fn generate_struct(input: &Input, name: Option<&Ident>, st: &Struct) -> TokenStream {
-    StructGenerator::new(Some(input), st, name, &input.stream_ident_or(WRITER))
-        .write_fields()
-        .prefix_assertions()
-        .prefix_prelude()
-        .prefix_borrow_fields()
-        .finish()
+    StructGenerator::new(
+        Some(input),
+        st,
+        name,
+        &input.stream_ident_or(WRITER),
+        &writer_type(input),
+    )
+    .write_fields()
+    .prefix_assertions()
+    .prefix_prelude()
+    .prefix_borrow_fields()
+    .finish()
 }
 
 pub(crate) struct StructGenerator<'input> {
@@ -21,6 +27,7 @@ pub(crate) struct StructGenerator<'input> {
     st: &'input Struct,
     name: Option<&'input Ident>,
     writer_var: &'input TokenStream,
+    writer_ty: &'input TokenStream,
     out: TokenStream,
 }
 
@@ -30,21 +37,25 @@ impl<'input> StructGenerator<'input> {
         st: &'input Struct,
         name: Option<&'input Ident>,
         writer_var: &'input TokenStream,
+        writer_ty: &'input TokenStream,
     ) -> Self {
         Self {
             input,
             st,
             name,
             writer_var,
+            writer_ty,
             out: TokenStream::new(),
         }
     }
 
     pub(crate) fn prefix_prelude(mut self) -> Self {
         self.out = PreludeGenerator::new(self.out, self.input, self.name, self.writer_var)
-            .prefix_magic(&self.st.magic)
+            .prefix_magic(&self.st.magic, self.st.tag_endian)
+            .prefix_prepare(self.st.prepare.as_ref())
             .prefix_endian(&self.st.endian)
             .prefix_imports()
+            .prefix_checksum_writer()
             .prefix_map_stream()
             .finish();
 
@@ -68,7 +79,7 @@ impl<'input> StructGenerator<'input> {
             .st
             .fields
             .iter()
-            .map(|field| write_field(self.writer_var, field));
+            .map(|field| write_field(self.writer_var, self.writer_ty, field, &self.st.fields));
 
         self.out = quote! {
             #(#write_fields)*
@@ -1,13 +1,16 @@
 use crate::{
     binrw::{
         codegen::{
-            get_assertions, get_endian, get_map_err, get_passed_args, get_try_calc,
+            get_assertions, get_debug_line, get_endian, get_err_context_frame, get_map_err,
+            get_passed_args, get_save_position, get_seek_to_saved, get_trace_field, get_try_calc,
             sanitization::{
-                make_ident, BEFORE_POS, BINWRITE_TRAIT, MAP_WRITER_TYPE_HINT, POS, SAVED_POSITION,
-                SEEK_FROM, SEEK_TRAIT, WRITER, WRITE_ARGS_TYPE_HINT, WRITE_FN_MAP_OUTPUT_TYPE_HINT,
-                WRITE_FN_TRY_MAP_OUTPUT_TYPE_HINT, WRITE_FN_TYPE_HINT, WRITE_FUNCTION,
-                WRITE_MAP_ARGS_TYPE_HINT, WRITE_MAP_INPUT_TYPE_HINT, WRITE_METHOD,
-                WRITE_TRY_MAP_ARGS_TYPE_HINT, WRITE_ZEROES,
+                make_ident, BEFORE_POS, BINWRITE_TRAIT, DBG_EPRINTLN, FLOAT_FIELD_TRAIT,
+                MAP_WRITER_TYPE_HINT, POS, SAVED_POSITION, SEEK_FROM, SEEK_TRAIT, SIZE_OF_END,
+                SIZE_OF_VALUE, WITH_CONTEXT, WRITER, WRITE_ARGS_TYPE_HINT, WRITE_CHECKSUM,
+                WRITE_FILL, WRITE_FN_MAP_OUTPUT_TYPE_HINT, WRITE_FN_TRY_MAP_OUTPUT_TYPE_HINT,
+                WRITE_FN_TYPE_HINT, WRITE_FUNCTION, WRITE_MAP_ARGS_TYPE_HINT,
+                WRITE_MAP_INPUT_TYPE_HINT, WRITE_METHOD, WRITE_TRAIT, WRITE_TRY_MAP_ARGS_TYPE_HINT,
+                WRITE_ZEROES,
             },
         },
         parser::{FieldMode, Map, StructField},
@@ -20,9 +23,16 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::{spanned::Spanned, Ident};
 
-pub(crate) fn write_field(writer_var: &TokenStream, field: &StructField) -> TokenStream {
-    StructFieldGenerator::new(field, writer_var)
+pub(crate) fn write_field(
+    writer_var: &TokenStream,
+    writer_ty: &TokenStream,
+    field: &StructField,
+    fields: &[StructField],
+) -> TokenStream {
+    StructFieldGenerator::new(field, writer_var, writer_ty)
         .write_field()
+        .wrap_debug()
+        .wrap_trace()
         .wrap_padding()
         .prefix_args()
         .prefix_write_fn()
@@ -30,27 +40,44 @@ pub(crate) fn write_field(writer_var: &TokenStream, field: &StructField) -> Toke
         .prefix_magic()
         .wrap_condition()
         .prefix_assertions()
+        .prefix_save_position()
         .prefix_map_stream()
+        .prefix_field_stream()
+        .wrap_size_of(writer_var, fields)
         .finish()
 }
 
 struct StructFieldGenerator<'input> {
     field: &'input StructField,
-    outer_writer_var: &'input TokenStream,
+    outer_writer_var: Cow<'input, TokenStream>,
     writer_var: Cow<'input, TokenStream>,
+    writer_ty: &'input TokenStream,
+    size_of_patch: Option<TokenStream>,
     out: TokenStream,
 }
 
 impl<'a> StructFieldGenerator<'a> {
-    fn new(field: &'a StructField, outer_writer_var: &'a TokenStream) -> Self {
+    fn new(
+        field: &'a StructField,
+        outer_writer_var: &'a TokenStream,
+        writer_ty: &'a TokenStream,
+    ) -> Self {
+        let outer_writer_var = if field.stream.is_some() {
+            Cow::Owned(make_ident(&field.ident, "stream").into_token_stream())
+        } else {
+            Cow::Borrowed(outer_writer_var)
+        };
+
         Self {
             field,
-            outer_writer_var,
             writer_var: if field.map_stream.is_some() {
                 Cow::Owned(make_ident(&field.ident, "reader").into_token_stream())
             } else {
-                Cow::Borrowed(outer_writer_var)
+                outer_writer_var.clone()
             },
+            outer_writer_var,
+            writer_ty,
+            size_of_patch: None,
             out: TokenStream::new(),
         }
     }
@@ -67,13 +94,81 @@ impl<'a> StructFieldGenerator<'a> {
         self
     }
 
+    fn prefix_save_position(mut self) -> Self {
+        if let Some(save_position) = get_save_position(&self.writer_var, self.field) {
+            let out = self.out;
+            self.out = quote! {
+                #save_position
+                #out
+            };
+        }
+
+        self
+    }
+
+    fn wrap_size_of(mut self, writer_var: &TokenStream, fields: &[StructField]) -> Self {
+        let start = make_ident(&self.field.ident, "size_of_start");
+        let patches = fields
+            .iter()
+            .filter(|field| field.size_of.as_ref() == Some(&self.field.ident))
+            .map(|field| {
+                let pos = size_of_pos_ident(&field.ident);
+                let mut patch = StructFieldGenerator::new(field, writer_var, self.writer_ty);
+                patch.size_of_patch = Some(quote! { #SIZE_OF_END.saturating_sub(#start) });
+                let patch = patch
+                    .write_field()
+                    .prefix_args()
+                    .prefix_write_fn()
+                    .prefix_map_fn()
+                    .finish();
+                quote! {
+                    #SEEK_TRAIT::seek(#writer_var, #SEEK_FROM::Start(#pos))?;
+                    { #patch }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if !patches.is_empty() {
+            let out = self.out;
+            self.out = quote! {
+                let #start = #SEEK_TRAIT::stream_position(#writer_var)?;
+                #out
+                {
+                    let #SIZE_OF_END = #SEEK_TRAIT::stream_position(#writer_var)?;
+                    #(#patches)*
+                    #SEEK_TRAIT::seek(#writer_var, #SEEK_FROM::Start(#SIZE_OF_END))?;
+                }
+            };
+        }
+
+        self
+    }
+
     fn prefix_map_stream(mut self) -> Self {
         if let Some(map_stream) = &self.field.map_stream {
             let rest = self.out;
             let writer_var = &self.writer_var;
             let outer_writer_var = &self.outer_writer_var;
+            let writer_ty = if self.field.stream.is_some() {
+                &quote! { _ }
+            } else {
+                self.writer_ty
+            };
             self.out = quote_spanned_any! { map_stream.span()=>
-                let #writer_var = &mut #MAP_WRITER_TYPE_HINT::<W, _, _>(#map_stream)(#outer_writer_var);
+                let #writer_var = &mut #MAP_WRITER_TYPE_HINT::<#writer_ty, _, _>(#map_stream)(#outer_writer_var);
+                #rest
+            };
+        }
+
+        self
+    }
+
+    fn prefix_field_stream(mut self) -> Self {
+        if let Some(stream) = &self.field.stream {
+            let rest = self.out;
+            let outer_writer_var = &self.outer_writer_var;
+            self.out = quote_spanned_any! { stream.span()=>
+                let #outer_writer_var = &mut *(#stream);
                 #rest
             };
         }
@@ -90,7 +185,7 @@ impl<'a> StructFieldGenerator<'a> {
             FieldMode::Normal | FieldMode::Calc(_) | FieldMode::TryCalc(_) => {
                 quote! { #WRITE_METHOD }
             }
-            FieldMode::Function(write_fn) => write_fn.clone(),
+            FieldMode::Function(write_fn, _) => write_fn.clone(),
             FieldMode::Default => unreachable!("Ignored fields are not written"),
         };
 
@@ -141,6 +236,9 @@ impl<'a> StructFieldGenerator<'a> {
         let writer_var = &self.writer_var;
 
         let initialize = match &self.field.field_mode {
+            FieldMode::Normal | FieldMode::Function(..) if self.field.size_of.is_some() => {
+                Some(self.size_of_value())
+            }
             FieldMode::Calc(expr) => Some({
                 let ty = &self.field.ty;
                 quote! {
@@ -150,13 +248,29 @@ impl<'a> StructFieldGenerator<'a> {
             FieldMode::TryCalc(expr) => Some({
                 let ty = &self.field.ty;
                 let expr = get_try_calc(POS, &self.field.ty, expr);
+                let map_err = get_err_context_frame(self.field).map(|backtrace| {
+                    quote! {
+                        .map_err(|err| #WITH_CONTEXT(err, #backtrace))
+                    }
+                });
                 quote! {
-                    let #name: #ty = #expr;
+                    let #name: #ty = #expr #map_err ?;
                 }
             }),
             // If ignored, just skip this now
             FieldMode::Default => return self,
-            _ => None,
+            FieldMode::Normal => self.field.checksum.as_ref().map(|checksum| {
+                let ty = &self.field.ty;
+                let function = &checksum.function;
+                let range = checksum
+                    .range
+                    .as_ref()
+                    .map_or_else(|| quote! { None }, |range| quote! { Some(#range) });
+                quote! {
+                    let #name: #ty = #WRITE_CHECKSUM(#writer_var, #range, #function)?;
+                }
+            }),
+            FieldMode::Function(..) => None,
         };
 
         let map_fn = self.field.map.is_some().then(|| map_fn_ident(name));
@@ -187,20 +301,110 @@ impl<'a> StructFieldGenerator<'a> {
             })
             .unwrap_or_else(|| quote::ToTokens::to_token_stream(name));
 
+        let value = quote! { (#map_fn (#name) #map_try) };
+        let value = if self.field.canonical_nan.is_some() {
+            // Method call syntax is used so that the value is auto-dereferenced
+            // regardless of whether the field is borrowed or calculated
+            quote! {{
+                use #FLOAT_FIELD_TRAIT as _;
+                #value.canonical_nan()
+            }}
+        } else {
+            value
+        };
+
+        let map_err = get_err_context_frame(self.field).map(|backtrace| {
+            quote! {
+                .map_err(|err| #WITH_CONTEXT(err, #backtrace))
+            }
+        });
+
         self.out = quote! {
             #initialize
 
             #WRITE_FUNCTION (
-                { #store_position &(#map_fn (#name) #map_try) },
+                { #store_position &#value },
                 #writer_var,
                 #endian,
                 #args
-            )?;
+            )#map_err?;
         };
 
         self
     }
 
+    fn size_of_value(&self) -> TokenStream {
+        let name = &self.field.ident;
+        let ty = &self.field.ty;
+        let pos = size_of_pos_ident(name);
+        if let Some(size) = &self.size_of_patch {
+            quote! {
+                let #name: &#ty = &#SIZE_OF_VALUE(#pos, #size)?;
+            }
+        } else {
+            let writer_var = &self.writer_var;
+            quote! {
+                let #pos = #SEEK_TRAIT::stream_position(#writer_var)?;
+                let #name: &#ty = &#SIZE_OF_VALUE(#pos, 0)?;
+            }
+        }
+    }
+
+    fn wrap_debug(mut self) -> Self {
+        if let Some(debug) = self.field.debug {
+            let out = self.out;
+            let writer_var = &self.writer_var;
+            let ident = &self.field.ident;
+            let start = make_ident(ident, "dbg_start");
+            let at = get_debug_line(ident.span());
+
+            self.out = if debug.range {
+                quote! {
+                    let #start = #SEEK_TRAIT::stream_position(#writer_var)?;
+                    #out
+                    #DBG_EPRINTLN!(
+                        "[{}:{} | offset {:#x}..{:#x}] {} = {:#x?}",
+                        ::core::file!(),
+                        #at,
+                        #start,
+                        #SEEK_TRAIT::stream_position(#writer_var)?,
+                        ::core::stringify!(#ident),
+                        &#ident
+                    );
+                }
+            } else {
+                quote! {
+                    let #start = #SEEK_TRAIT::stream_position(#writer_var)?;
+                    #out
+                    #DBG_EPRINTLN!(
+                        "[{}:{} | offset {:#x}] {} = {:#x?}",
+                        ::core::file!(), #at, #start, ::core::stringify!(#ident), &#ident
+                    );
+                }
+            };
+        }
+
+        self
+    }
+
+    fn wrap_trace(mut self) -> Self {
+        if matches!(self.field.field_mode, FieldMode::Default) {
+            return self;
+        }
+
+        let ident = &self.field.ident;
+        if let Some((before, after)) = get_trace_field(&self.writer_var, ident, &quote!(&#ident)) {
+            let out = self.out;
+            self.out = quote! {
+                #before
+                #out
+                #after
+            };
+        }
+
+        self
+    }
+
     fn wrap_condition(mut self) -> Self {
         if let Some(cond) = &self.field.if_cond {
             if cond.alternate.is_none() {
@@ -268,11 +472,12 @@ impl<'a> StructFieldGenerator<'a> {
                 let #args = ();
                 #out
             },
-            FieldMode::Function(_) => {
+            FieldMode::Function(..) => {
                 let ty = &self.field.ty;
+                let writer_ty = self.writer_ty;
                 quote! {
-                    let #args = #WRITE_ARGS_TYPE_HINT::<#ty, W, _, _>(
-                        #WRITE_FUNCTION, #args_val
+                    let #args = #WRITE_ARGS_TYPE_HINT::<#ty, #writer_ty, _, _>(
+                        &#WRITE_FUNCTION, #args_val
                     );
                     #out
                 }
@@ -285,7 +490,7 @@ impl<'a> StructFieldGenerator<'a> {
 
     fn prefix_magic(mut self) -> Self {
         if let Some(magic) = &self.field.magic {
-            let magic = magic.match_value();
+            let magic = magic.value();
             let endian = get_endian(&self.field.endian);
             let writer_var = &self.writer_var;
             let out = self.out;
@@ -325,48 +530,69 @@ fn map_fn_ident(ident: &Ident) -> Ident {
     make_ident(ident, "map_fn")
 }
 
+fn size_of_pos_ident(ident: &Ident) -> Ident {
+    make_ident(ident, "size_of_pos")
+}
+
 fn pad_after(writer_var: &TokenStream, field: &StructField) -> TokenStream {
     let pad_size_to = field.pad_size_to.as_ref().map(|size| {
+        let fill_padding = write_padding(writer_var, field, &quote! { padding });
         quote! {{
             let pad_to_size = (#size) as u64;
             let after_pos = #SEEK_TRAIT::seek(#writer_var, #SEEK_FROM::Current(0))?;
             if let Some(size) = after_pos.checked_sub(#BEFORE_POS) {
                 if let Some(padding) = pad_to_size.checked_sub(size) {
-                    #WRITE_ZEROES(#writer_var, padding)?;
+                    #fill_padding
                 }
             }
         }}
     });
-    let pad_after = field.pad_after.as_ref().map(|padding| {
-        quote! {
-            #WRITE_ZEROES(#writer_var, (#padding) as u64)?;
-        }
-    });
+    let pad_after = field
+        .pad_after
+        .as_ref()
+        .map(|padding| write_padding(writer_var, field, &quote! { (#padding) as u64 }));
     let align_after = field.align_after.as_ref().map(|alignment| {
+        let fill_padding = write_padding(writer_var, field, &quote! { align - rem });
         quote! {{
             let pos = #SEEK_TRAIT::seek(#writer_var, #SEEK_FROM::Current(0))?;
             let align = ((#alignment) as u64);
             let rem = pos % align;
             if rem != 0 {
-                #WRITE_ZEROES(#writer_var, align - rem)?;
+                #fill_padding
             }
         }}
     });
+    let seek_after = field.seek_after.as_ref().map(|seek| {
+        quote! {
+            #SEEK_TRAIT::seek(
+                #writer_var,
+                #seek,
+            )?;
+        }
+    });
     let restore_position = field.restore_position.map(|_| {
         quote! {
             #SEEK_TRAIT::seek(#writer_var, #SEEK_FROM::Start(#SAVED_POSITION))?;
         }
     });
+    let flush_after = field.flush_after.map(|()| {
+        quote! {
+            #WRITE_TRAIT::flush(#writer_var)?;
+        }
+    });
 
     quote! {
         #pad_size_to
         #pad_after
         #align_after
+        #seek_after
         #restore_position
+        #flush_after
     }
 }
 
 fn pad_before(writer_var: &TokenStream, field: &StructField) -> TokenStream {
+    let seek_to_saved = get_seek_to_saved(writer_var, field);
     let seek_before = field.seek_before.as_ref().map(|seek| {
         quote! {
             #SEEK_TRAIT::seek(
@@ -375,18 +601,18 @@ fn pad_before(writer_var: &TokenStream, field: &StructField) -> TokenStream {
             )?;
         }
     });
-    let pad_before = field.pad_before.as_ref().map(|padding| {
-        quote! {
-            #WRITE_ZEROES(#writer_var, (#padding) as u64)?;
-        }
-    });
+    let pad_before = field
+        .pad_before
+        .as_ref()
+        .map(|padding| write_padding(writer_var, field, &quote! { (#padding) as u64 }));
     let align_before = field.align_before.as_ref().map(|alignment| {
+        let fill_padding = write_padding(writer_var, field, &quote! { align - rem });
         quote! {{
             let pos = #SEEK_TRAIT::seek(#writer_var, #SEEK_FROM::Current(0))?;
             let align = ((#alignment) as u64);
             let rem = pos % align;
             if rem != 0 {
-                #WRITE_ZEROES(#writer_var, align - rem)?;
+                #fill_padding
             }
         }}
     });
@@ -403,9 +629,26 @@ fn pad_before(writer_var: &TokenStream, field: &StructField) -> TokenStream {
 
     quote! {
         #store_position
+        #seek_to_saved
         #seek_before
         #pad_before
         #align_before
         #pad_size_to_before
     }
 }
+
+fn write_padding(
+    writer_var: &TokenStream,
+    field: &StructField,
+    count: &TokenStream,
+) -> TokenStream {
+    if let Some(fill) = &field.pad_with {
+        quote! {
+            #WRITE_FILL(#writer_var, #count, #fill)?;
+        }
+    } else {
+        quote! {
+            #WRITE_ZEROES(#writer_var, #count)?;
+        }
+    }
+}
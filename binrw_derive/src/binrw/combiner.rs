@@ -118,7 +118,24 @@ fn apply_temp_crossover_struct(
     // Iterate the fields again and set temp flags
     set_fields_temporary(&mut binread_struct.fields, &write_temporary);
     set_fields_temporary(&mut binwrite_struct.fields, &read_temporary);
-    None
+
+    set_fields_read_only(&binread_struct.fields, &mut binwrite_struct.fields)
+}
+
+/// Skips writing fields which were marked `read_only` on the read side.
+fn set_fields_read_only(
+    read_fields: &[StructField],
+    write_fields: &mut [StructField],
+) -> Option<syn::Error> {
+    let mut all_errors = None::<syn::Error>;
+    for (read_field, write_field) in read_fields.iter().zip(write_fields) {
+        if let (Some(read_only), None) = (&read_field.read_only, &write_field.read_only) {
+            if let Err(error) = write_field.force_read_only(read_only.span()) {
+                combine_error(&mut all_errors, error);
+            }
+        }
+    }
+    all_errors
 }
 
 fn validate_fields_temporary(
@@ -1,7 +1,10 @@
-use super::keywords as kw;
+use super::{
+    keywords as kw,
+    types::{CrcArg, MagicValues},
+};
 use crate::meta_types::{
-    IdentPatType, IdentTypeMaybeDefault, MetaEnclosedList, MetaExpr, MetaIdent, MetaList, MetaLit,
-    MetaType, MetaValue, MetaVoid,
+    IdentPatType, IdentTypeMaybeDefault, MetaEnclosedList, MetaExpr, MetaIdent, MetaLabel,
+    MetaList, MetaOptionalIdent, MetaType, MetaValue, MetaVoid,
 };
 use syn::{Expr, FieldValue, Token};
 
@@ -11,39 +14,75 @@ pub(super) type Args = MetaEnclosedList<kw::args, Expr, FieldValue>;
 pub(super) type ArgsRaw = MetaExpr<kw::args_raw>;
 pub(super) type AssertLike<Keyword> = MetaList<Keyword, Expr>;
 pub(super) type Assert = AssertLike<kw::assert>;
+pub(super) type Async = MetaVoid<Token![async]>;
+pub(super) type Audit = MetaVoid<kw::audit>;
 pub(super) type Big = MetaVoid<kw::big>;
 pub(super) type Calc = MetaExpr<kw::calc>;
+pub(super) type CalcCountOf = MetaExpr<kw::calc_count_of>;
+pub(super) type CanonicalNan = MetaVoid<kw::canonical_nan>;
+pub(super) type CatchAll = MetaVoid<kw::catch_all>;
+pub(super) type Checksum = MetaList<kw::checksum, Expr>;
+pub(super) type Context = MetaVoid<kw::context>;
+pub(super) type Crc = MetaList<kw::crc, CrcArg>;
 pub(super) type Count = MetaExpr<kw::count>;
-pub(super) type Debug = MetaVoid<kw::dbg>;
+pub(super) type CountBytesOf = MetaExpr<kw::count_bytes_of>;
+pub(super) type Debug = MetaOptionalIdent<kw::dbg>;
 pub(super) type Default = MetaVoid<kw::default>;
 pub(super) type DerefNow = MetaVoid<kw::deref_now>;
+pub(super) type DynWith = MetaExpr<kw::dyn_with>;
+pub(super) type DynWrite = MetaVoid<kw::dyn_with>;
 pub(super) type ErrContext = MetaList<kw::err_context, Expr>;
+pub(super) type ErrorType = MetaType<kw::error>;
+pub(super) type Finally = MetaExpr<kw::finally>;
+pub(super) type Finite = MetaVoid<kw::finite>;
+pub(super) type FieldStream = MetaExpr<kw::stream>;
+pub(super) type FlushAfter = MetaVoid<kw::flush_after>;
 pub(super) type If = MetaList<Token![if], Expr>;
+pub(super) type IfEofNone = MetaVoid<kw::if_eof_none>;
 pub(super) type Ignore = MetaVoid<kw::ignore>;
 pub(super) type Import = MetaEnclosedList<kw::import, IdentPatType, IdentTypeMaybeDefault>;
 pub(super) type ImportRaw = MetaValue<kw::import_raw, IdentPatType>;
 pub(super) type IsBig = MetaExpr<kw::is_big>;
 pub(super) type IsLittle = MetaExpr<kw::is_little>;
 pub(super) type Little = MetaVoid<kw::little>;
-pub(super) type Magic = MetaLit<kw::magic>;
+pub(super) type Magic = MetaValue<kw::magic, MagicValues>;
 pub(super) type Map = MetaExpr<kw::map>;
 pub(super) type MapStream = MetaExpr<kw::map_stream>;
+pub(super) type NoSubnormal = MetaVoid<kw::no_subnormal>;
 pub(super) type Offset = MetaExpr<kw::offset>;
 pub(super) type OffsetAfter = MetaExpr<kw::offset_after>;
+pub(super) type OffsetBase = MetaIdent<kw::offset_base>;
 pub(super) type PadAfter = MetaExpr<kw::pad_after>;
 pub(super) type PadBefore = MetaExpr<kw::pad_before>;
 pub(super) type PadSizeTo = MetaExpr<kw::pad_size_to>;
+pub(super) type PadWith = MetaExpr<kw::pad_with>;
 pub(super) type ParseWith = MetaExpr<kw::parse_with>;
 pub(super) type PostProcessNow = MetaVoid<kw::postprocess_now>;
 pub(super) type PreAssert = AssertLike<kw::pre_assert>;
+pub(super) type Prepare = MetaExpr<kw::prepare>;
+pub(super) type ReadOnly = MetaVoid<kw::read_only>;
 pub(super) type Repr = MetaType<kw::repr>;
+pub(super) type Rest = MetaVoid<kw::rest>;
 pub(super) type RestorePosition = MetaVoid<kw::restore_position>;
 pub(super) type ReturnAllErrors = MetaVoid<kw::return_all_errors>;
 pub(super) type ReturnUnexpectedError = MetaVoid<kw::return_unexpected_error>;
+pub(super) type SavePosition = MetaLabel<kw::save_position>;
+pub(super) type Schema = MetaVoid<kw::schema>;
+pub(super) type SeekAfter = MetaExpr<kw::seek_after>;
 pub(super) type SeekBefore = MetaExpr<kw::seek_before>;
+pub(super) type SeekToSaved = MetaLabel<kw::seek_to_saved>;
+pub(super) type Size = MetaExpr<kw::size>;
+pub(super) type SizeOf = MetaIdent<kw::size_of>;
 pub(super) type Stream = MetaIdent<kw::stream>;
+pub(super) type Tag = MetaExpr<kw::tag>;
+pub(super) type TagEndian = MetaIdent<kw::tag_endian>;
+pub(super) type TagInto = MetaIdent<kw::tag_into>;
+pub(super) type TagValue = MetaExpr<kw::tag_value>;
 pub(super) type Temp = MetaVoid<kw::temp>;
+pub(super) type Trailing = MetaExpr<kw::trailing>;
 pub(super) type Try = MetaVoid<Token![try]>;
 pub(super) type TryCalc = MetaExpr<kw::try_calc>;
 pub(super) type TryMap = MetaExpr<kw::try_map>;
+pub(super) type Untrusted = MetaVoid<kw::untrusted>;
+pub(super) type Verify = MetaList<kw::verify, Expr>;
 pub(super) type WriteWith = MetaExpr<kw::write_with>;
@@ -1,7 +1,10 @@
 use super::{
     attr_struct,
     top_level_attrs::StructAttr,
-    types::{Assert, CondEndian, Condition, ErrContext, FieldMode, Magic, Map, PassedArgs},
+    types::{
+        Assert, Checksum, CondEndian, Condition, DebugMode, ErrContext, FieldMode, Magic, Map,
+        OffsetBase, PassedArgs,
+    },
     FromAttrs, FromField, FromInput, ParseResult, SpannedValue, Struct, TrySet,
 };
 use crate::{binrw::Options, combine_error};
@@ -22,31 +25,53 @@ attr_struct! {
         pub(crate) map: Map,
         #[from(RW:MapStream)]
         pub(crate) map_stream: Option<TokenStream>,
+        #[from(RW:FieldStream)]
+        pub(crate) stream: Option<TokenStream>,
         #[from(RW:Magic)]
         pub(crate) magic: Magic,
         #[from(RW:Args, RW:ArgsRaw)]
         pub(crate) args: PassedArgs,
-        #[from(RW:Calc, RW:TryCalc, RO:Default, RW:Ignore, RO:ParseWith, WO:WriteWith)]
+        #[from(RW:Calc, RW:TryCalc, WO:CalcCountOf, RO:Default, RW:Ignore, RO:ParseWith, WO:WriteWith, RO:DynWith, WO:DynWrite, RO:Rest, RO:Context)]
         pub(crate) field_mode: FieldMode,
         #[from(RO:Count)]
         pub(crate) count: Option<TokenStream>,
+        #[from(RO:CountBytesOf)]
+        pub(crate) count_bytes_of: Option<TokenStream>,
         #[from(RO:Offset)]
         pub(crate) offset: Option<TokenStream>,
         #[from(RO:OffsetAfter)]
         pub(crate) offset_after: Option<SpannedValue<TokenStream>>,
+        #[from(RO:OffsetBase)]
+        pub(crate) offset_base: Option<SpannedValue<OffsetBase>>,
         #[from(RW:If)]
         pub(crate) if_cond: Option<Condition>,
         #[from(RO:DerefNow, RO:PostProcessNow)]
         pub(crate) deref_now: Option<SpannedValue<()>>,
         #[from(RW:RestorePosition)]
         pub(crate) restore_position: Option<()>,
+        #[from(RW:SavePosition)]
+        pub(crate) save_position: Option<syn::Ident>,
+        #[from(RW:SeekToSaved)]
+        pub(crate) seek_to_saved: Option<syn::Ident>,
         #[from(RO:Try)]
         pub(crate) do_try: Option<SpannedValue<()>>,
+        #[from(RO:IfEofNone)]
+        pub(crate) if_eof_none: Option<SpannedValue<()>>,
+        #[from(RO:Finite)]
+        pub(crate) finite: Option<SpannedValue<()>>,
+        #[from(RO:NoSubnormal)]
+        pub(crate) no_subnormal: Option<SpannedValue<()>>,
+        #[from(WO:CanonicalNan)]
+        pub(crate) canonical_nan: Option<()>,
         #[from(RO:Temp)]
         pub(crate) temp: Option<()>,
+        #[from(RW:ReadOnly)]
+        pub(crate) read_only: Option<SpannedValue<()>>,
         #[from(RW:Assert)]
         pub(crate) assertions: Vec<Assert>,
-        #[from(RO:ErrContext)]
+        #[from(RO:Verify, WO:Checksum, RW:Crc)]
+        pub(crate) checksum: Option<Checksum>,
+        #[from(RW:ErrContext)]
         pub(crate) err_context: Option<ErrContext>,
         #[from(RW:PadBefore)]
         pub(crate) pad_before: Option<TokenStream>,
@@ -58,10 +83,19 @@ attr_struct! {
         pub(crate) align_after: Option<TokenStream>,
         #[from(RW:SeekBefore)]
         pub(crate) seek_before: Option<TokenStream>,
+        #[from(RW:SeekAfter)]
+        pub(crate) seek_after: Option<TokenStream>,
         #[from(RW:PadSizeTo)]
         pub(crate) pad_size_to: Option<TokenStream>,
-        #[from(RO:Debug)] // TODO is this really RO?
-        pub(crate) debug: Option<()>,
+        #[from(RW:PadWith)]
+        pub(crate) pad_with: Option<TokenStream>,
+        #[from(WO:SizeOf)]
+        pub(crate) size_of: Option<syn::Ident>,
+        #[from(WO:FlushAfter)]
+        pub(crate) flush_after: Option<()>,
+        #[from(RW:Debug)]
+        pub(crate) debug: Option<DebugMode>,
+        pub(crate) untrusted: bool,
     }
 }
 
@@ -69,7 +103,9 @@ impl StructField {
     /// Returns true if this field is read from a parser with an `after_parse`
     /// method.
     pub(crate) fn can_call_after_parse(&self) -> bool {
-        matches!(self.field_mode, FieldMode::Normal) && self.map.is_none()
+        matches!(self.field_mode, FieldMode::Normal)
+            && self.map.is_none()
+            && self.count_bytes_of.is_none()
     }
 
     /// Returns true if the code generator should emit `BinRead::after_parse()`
@@ -99,9 +135,18 @@ impl StructField {
         !matches!(self.field_mode, FieldMode::Default)
     }
 
+    /// Returns true if the field is read using a parser function instead of
+    /// `BinRead::read_options`.
+    pub(crate) fn has_parse_fn(&self) -> bool {
+        matches!(self.field_mode, FieldMode::Function(..)) || self.count_bytes_of.is_some()
+    }
+
     /// Returns true if the field requires arguments.
     pub(crate) fn needs_args(&self) -> bool {
-        self.args.is_some() || self.count.is_some() || self.offset.is_some()
+        self.args.is_some()
+            || self.count.is_some()
+            || self.offset.is_some()
+            || self.offset_base.is_some()
     }
 
     /// Returns true if the field overrides endianness.
@@ -112,7 +157,10 @@ impl StructField {
     /// Returns true if the field is using shorthand directives that are
     /// converted into named arguments.
     pub(crate) fn has_named_arg_directives(&self) -> bool {
-        self.count.is_some() || self.offset.is_some() || self.offset_after.is_some()
+        self.count.is_some()
+            || self.offset.is_some()
+            || self.offset_after.is_some()
+            || self.offset_base.is_some()
     }
 
     /// Returns true if the only field-level attributes are asserts
@@ -133,23 +181,74 @@ impl StructField {
             && matches!(self.field_mode, FieldMode::Normal)
             && all_fields_none!(
                 count,
+                count_bytes_of,
                 offset,
                 offset_after,
+                offset_base,
                 if_cond,
                 deref_now,
                 restore_position,
+                save_position,
+                seek_to_saved,
                 do_try,
+                if_eof_none,
+                finite,
+                no_subnormal,
+                canonical_nan,
                 temp,
+                read_only,
                 pad_before,
                 pad_after,
                 align_before,
                 align_after,
                 seek_before,
+                seek_after,
                 pad_size_to,
-                magic
+                pad_with,
+                magic,
+                checksum,
+                size_of,
+                flush_after
             )
     }
 
+    /// Returns the first directive which cannot be used on a field of a
+    /// struct using `async`.
+    pub(crate) fn async_conflict(&self) -> Option<&'static str> {
+        if let FieldMode::Function(_, directive) = self.field_mode {
+            return Some(directive);
+        }
+
+        [
+            ("`map`", self.map.is_some()),
+            ("`map_stream`", self.map_stream.is_some()),
+            ("`stream`", self.stream.is_some()),
+            ("`count_bytes_of`", self.count_bytes_of.is_some()),
+            ("`offset`", self.offset.is_some()),
+            ("`offset_after`", self.offset_after.is_some()),
+            ("`offset_base`", self.offset_base.is_some()),
+            ("`deref_now`", self.deref_now.is_some()),
+            ("`restore_position`", self.restore_position.is_some()),
+            ("`save_position`", self.save_position.is_some()),
+            ("`seek_to_saved`", self.seek_to_saved.is_some()),
+            ("`try`", self.do_try.is_some()),
+            ("`if_eof_none`", self.if_eof_none.is_some()),
+            ("`finite`", self.finite.is_some()),
+            ("`no_subnormal`", self.no_subnormal.is_some()),
+            ("`pad_before`", self.pad_before.is_some()),
+            ("`pad_after`", self.pad_after.is_some()),
+            ("`align_before`", self.align_before.is_some()),
+            ("`align_after`", self.align_after.is_some()),
+            ("`seek_before`", self.seek_before.is_some()),
+            ("`seek_after`", self.seek_after.is_some()),
+            ("`pad_size_to`", self.pad_size_to.is_some()),
+            ("`dbg`", self.debug.is_some()),
+        ]
+        .into_iter()
+        .find_map(|(directive, is_set)| is_set.then_some(directive))
+        .or_else(|| self.checksum.as_ref().map(|checksum| checksum.keyword))
+    }
+
     /// Forces the field to be treated as a temporary variable even if it was
     /// not explicitly specified by a directive.
     ///
@@ -160,7 +259,138 @@ impl StructField {
         self.temp = Some(());
     }
 
-    fn validate(&self, _: Options) -> syn::Result<()> {
+    /// Forces the field to be read-only even if it was not explicitly
+    /// specified by a directive.
+    ///
+    /// This is used to ensure that, when combining read and write on a single
+    /// type, a field specified as read-only on the read side is also skipped
+    /// on the write side.
+    pub(crate) fn force_read_only(&mut self, span: proc_macro2::Span) -> syn::Result<()> {
+        self.read_only = Some(SpannedValue::new((), span));
+        self.apply_read_only(Options {
+            derive: false,
+            write: true,
+        });
+        self.read_only_error(true).map_or(Ok(()), Err)
+    }
+
+    fn apply_read_only(&mut self, options: Options) {
+        if options.write && self.read_only.is_some() && matches!(self.field_mode, FieldMode::Normal)
+        {
+            self.field_mode = FieldMode::Default;
+        }
+    }
+
+    fn read_only_error(&self, write: bool) -> Option<syn::Error> {
+        let read_only = self.read_only.as_ref()?;
+        let conflict = if self.temp.is_some() {
+            "`temp`"
+        } else if write && !matches!(self.field_mode, FieldMode::Default) {
+            "`calc`, `try_calc`, and `write_with`"
+        } else {
+            return None;
+        };
+        Some(syn::Error::new(
+            read_only.span(),
+            format!("`read_only` is incompatible with {conflict}"),
+        ))
+    }
+
+    fn checksum_error(&self, options: Options) -> Option<syn::Error> {
+        let checksum = self.checksum.as_ref()?;
+        let conflict = if self.map.is_some() {
+            "`map`, `try_map`, and `repr`"
+        } else if self.map_stream.is_some() {
+            "`map_stream`"
+        } else if self.stream.is_some() {
+            "`stream`"
+        } else if options.write && !matches!(self.field_mode, FieldMode::Normal) {
+            "`calc`, `try_calc`, `ignore`, and `write_with`"
+        } else if !matches!(self.field_mode, FieldMode::Normal | FieldMode::Function(..)) {
+            "`calc`, `try_calc`, `default`, and `ignore`"
+        } else {
+            return None;
+        };
+        Some(syn::Error::new(
+            checksum.kw_span,
+            format!("{} is incompatible with {conflict}", checksum.keyword),
+        ))
+    }
+
+    fn size_of_error(&self) -> Option<syn::Error> {
+        let size_of = self.size_of.as_ref()?;
+        let conflict = if !matches!(self.field_mode, FieldMode::Normal | FieldMode::Function(..)) {
+            "`calc`, `try_calc`, and `ignore`"
+        } else if self.if_cond.is_some() {
+            "`if`"
+        } else if self.map_stream.is_some() {
+            "`map_stream`"
+        } else if self.stream.is_some() {
+            "`stream`"
+        } else if self.restore_position.is_some() {
+            "`restore_position`"
+        } else if self.checksum.is_some() {
+            "`checksum` and `crc`"
+        } else {
+            return None;
+        };
+        Some(syn::Error::new(
+            size_of.span(),
+            format!("`size_of` is incompatible with {conflict}"),
+        ))
+    }
+
+    fn offset_base_error(&self) -> Option<syn::Error> {
+        let offset_base = self.offset_base.as_ref()?;
+        let conflict = if self.offset.is_some() || self.offset_after.is_some() {
+            "`offset` and `offset_after`"
+        } else if self.generated_value() {
+            "`default`, `calc`, and `try_calc`"
+        } else {
+            return None;
+        };
+        Some(syn::Error::new(
+            offset_base.span(),
+            format!("`offset_base` is incompatible with {conflict}"),
+        ))
+    }
+
+    fn named_args_error(&self) -> Option<syn::Error> {
+        if !self.has_named_arg_directives() {
+            return None;
+        }
+
+        let (span, repr) = match &self.args {
+            PassedArgs::Named(_) | PassedArgs::None => return None,
+            PassedArgs::List(list) => (
+                list.span(),
+                format!(
+                    "({},{})",
+                    list.first().map_or_else(<_>::default, ToString::to_string),
+                    if list.len() > 1 { " ..." } else { "" }
+                ),
+            ),
+            PassedArgs::Tuple(raw) => (raw.span(), raw.to_string()),
+        };
+
+        let mut all_errors = None::<syn::Error>;
+        for (used, name) in [
+            (self.count.is_some(), "count"),
+            (self.offset.is_some(), "offset"),
+            (self.offset_after.is_some(), "offset_after"),
+            (self.offset_base.is_some(), "offset_base"),
+        ] {
+            if used {
+                combine_error(&mut all_errors, syn::Error::new(
+                    span,
+                    format!("`{name}` can only be used with named args; did you mean `args {{ inner: {repr} }}`?")
+                ));
+            }
+        }
+        all_errors
+    }
+
+    fn validate(&self, options: Options) -> syn::Result<()> {
         let mut all_errors = None::<syn::Error>;
 
         if let (Some(offset_after), Some(deref_now)) = (&self.offset_after, &self.deref_now) {
@@ -177,6 +407,27 @@ impl StructField {
             );
         }
 
+        if let (Some(count), Some(count_bytes_of)) = (&self.count, &self.count_bytes_of) {
+            let span = count
+                .span()
+                .join(count_bytes_of.span())
+                .unwrap_or_else(|| count.span());
+            combine_error(
+                &mut all_errors,
+                syn::Error::new(span, "`count` and `count_bytes_of` are mutually exclusive"),
+            );
+        }
+
+        if let (Some(seek_after), Some(())) = (&self.seek_after, self.restore_position) {
+            combine_error(
+                &mut all_errors,
+                syn::Error::new(
+                    seek_after.span(),
+                    "`seek_after` is incompatible with `restore_position`",
+                ),
+            );
+        }
+
         if self.do_try.is_some() && self.generated_value() {
             //TODO: join with span of read mode somehow
             let span = self.do_try.as_ref().unwrap().span();
@@ -189,6 +440,18 @@ impl StructField {
             );
         }
 
+        for (directive, name) in [
+            (&self.if_eof_none, "if_eof_none"),
+            (&self.finite, "finite"),
+            (&self.no_subnormal, "no_subnormal"),
+        ] {
+            if let Some(directive) = directive.as_ref().filter(|_| self.generated_value()) {
+                let message =
+                    format!("`{name}` is incompatible with `default`, `calc`, and `try_calc`");
+                combine_error(&mut all_errors, syn::Error::new(directive.span(), message));
+            }
+        }
+
         if matches!(self.field_mode, FieldMode::TryCalc(_) | FieldMode::Calc(_))
             && self.args.is_some()
         {
@@ -202,34 +465,17 @@ impl StructField {
             );
         }
 
-        if self.has_named_arg_directives()
-            && !matches!(self.args, PassedArgs::None | PassedArgs::Named(..))
+        for error in [
+            self.read_only_error(options.write),
+            self.checksum_error(options),
+            self.size_of_error(),
+            self.offset_base_error(),
+            self.named_args_error(),
+        ]
+        .into_iter()
+        .flatten()
         {
-            let (span, repr) = match &self.args {
-                PassedArgs::Named(_) | PassedArgs::None => unreachable!(),
-                PassedArgs::List(list) => (
-                    list.span(),
-                    format!(
-                        "({},{})",
-                        list.first().map_or_else(<_>::default, ToString::to_string),
-                        if list.len() > 1 { " ..." } else { "" }
-                    ),
-                ),
-                PassedArgs::Tuple(raw) => (raw.span(), raw.to_string()),
-            };
-
-            for (used, name) in [
-                (self.count.is_some(), "count"),
-                (self.offset.is_some(), "offset"),
-                (self.offset_after.is_some(), "offset_after"),
-            ] {
-                if used {
-                    combine_error(&mut all_errors, syn::Error::new(
-                        span,
-                        format!("`{name}` can only be used with named args; did you mean `args {{ inner: {repr} }}`?")
-                    ));
-                }
-            }
+            combine_error(&mut all_errors, error);
         }
 
         if let Some(error) = all_errors {
@@ -255,28 +501,44 @@ impl FromField for StructField {
             endian: <_>::default(),
             map: <_>::default(),
             map_stream: <_>::default(),
+            stream: <_>::default(),
             magic: <_>::default(),
             args: <_>::default(),
             field_mode: <_>::default(),
             count: <_>::default(),
+            count_bytes_of: <_>::default(),
             offset: <_>::default(),
             offset_after: <_>::default(),
+            offset_base: <_>::default(),
             if_cond: <_>::default(),
             deref_now: <_>::default(),
             restore_position: <_>::default(),
+            save_position: <_>::default(),
+            seek_to_saved: <_>::default(),
             do_try: <_>::default(),
+            if_eof_none: <_>::default(),
+            finite: <_>::default(),
+            no_subnormal: <_>::default(),
+            canonical_nan: <_>::default(),
             temp: <_>::default(),
+            read_only: <_>::default(),
             assertions: <_>::default(),
+            checksum: <_>::default(),
             pad_before: <_>::default(),
             pad_after: <_>::default(),
             align_before: <_>::default(),
             align_after: <_>::default(),
             seek_before: <_>::default(),
+            seek_after: <_>::default(),
             pad_size_to: <_>::default(),
+            pad_with: <_>::default(),
+            size_of: <_>::default(),
+            flush_after: <_>::default(),
             #[cfg(feature = "verbose-backtrace")]
             keyword_spans: <_>::default(),
             err_context: <_>::default(),
             debug: <_>::default(),
+            untrusted: false,
         };
 
         let result = if options.write {
@@ -286,14 +548,16 @@ impl FromField for StructField {
         };
 
         match result {
-            ParseResult::Ok(this) => {
+            ParseResult::Ok(mut this) => {
+                this.apply_read_only(options);
                 if let Err(error) = this.validate(options) {
                     ParseResult::Partial(this, error)
                 } else {
                     ParseResult::Ok(this)
                 }
             }
-            ParseResult::Partial(this, mut parse_error) => {
+            ParseResult::Partial(mut this, mut parse_error) => {
+                this.apply_read_only(options);
                 if let Err(error) = this.validate(options) {
                     parse_error.combine(error);
                 }
@@ -313,6 +577,8 @@ attr_struct! {
         pub(crate) magic: Magic,
         #[from(RO:PreAssert)]
         pub(crate) pre_assertions: Vec<Assert>,
+        #[from(RO:TagValue)]
+        pub(crate) tag_value: Option<TokenStream>,
     }
 }
 
@@ -321,6 +587,7 @@ impl From<UnitEnumField> for Struct {
         Self {
             magic: value.magic,
             pre_assertions: value.pre_assertions,
+            tag_value: value.tag_value,
             ..<_>::default()
         }
     }
@@ -334,6 +601,7 @@ impl FromField for UnitEnumField {
             ident: field.ident.clone(),
             magic: <_>::default(),
             pre_assertions: <_>::default(),
+            tag_value: <_>::default(),
             #[cfg(feature = "verbose-backtrace")]
             keyword_spans: <_>::default(),
         };
@@ -377,6 +645,27 @@ impl EnumVariant {
             Self::Unit(_) => true,
         }
     }
+
+    pub(crate) fn is_catch_all(&self) -> bool {
+        match self {
+            Self::Variant { options, .. } => options.catch_all.is_some(),
+            Self::Unit(_) => false,
+        }
+    }
+
+    pub(crate) fn tag_value(&self) -> Option<&TokenStream> {
+        match self {
+            Self::Variant { options, .. } => options.tag_value.as_ref(),
+            Self::Unit(field) => field.tag_value.as_ref(),
+        }
+    }
+
+    pub(crate) fn magic_mut(&mut self) -> &mut Magic {
+        match self {
+            EnumVariant::Variant { options, .. } => &mut options.magic,
+            EnumVariant::Unit(field) => &mut field.magic,
+        }
+    }
 }
 
 impl From<EnumVariant> for Struct {
@@ -7,11 +7,13 @@ macro_rules! define_keywords {
 }
 
 define_keywords! {
+    algorithm,
     align_after,
     align_before,
     args,
     args_raw,
     assert,
+    audit,
     big,
     binread,
     br,
@@ -19,11 +21,24 @@ define_keywords! {
     binwrite,
     bw,
     calc,
+    calc_count_of,
+    canonical_nan,
+    catch_all,
+    checksum,
+    context,
     count,
+    count_bytes_of,
+    crc,
     dbg,
     default,
     deref_now,
+    dyn_with,
     err_context,
+    error,
+    finally,
+    finite,
+    flush_after,
+    if_eof_none,
     ignore,
     import,
     import_raw,
@@ -33,22 +48,43 @@ define_keywords! {
     magic,
     map,
     map_stream,
+    mask,
+    no_subnormal,
     offset,
     offset_after,
+    offset_base,
+    over,
     pad_after,
     pad_before,
     pad_size_to,
+    pad_with,
     parse_with,
     postprocess_now,
     pre_assert,
+    prepare,
+    read_only,
     repr,
+    rest,
     restore_position,
     return_all_errors,
     return_unexpected_error,
+    save_position,
+    schema,
+    seek_after,
     seek_before,
+    seek_to_saved,
+    size,
+    size_of,
     stream,
+    tag,
+    tag_endian,
+    tag_into,
+    tag_value,
     temp,
+    trailing,
     try_calc,
     try_map,
+    untrusted,
+    verify,
     write_with,
 }
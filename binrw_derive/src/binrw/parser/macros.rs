@@ -6,6 +6,9 @@ macro_rules! parse_any {
         ),*
         $(,)?
     }) => {
+        // Lint: Values of these types only exist briefly while attributes are
+        // parsed, so boxing the larger directives is not worth it
+        #[allow(clippy::large_enum_variant)]
         $vis enum $enum<const WRITE: bool> {
             $(
                 $variant($ty)
@@ -139,6 +139,28 @@ mod tests {
         )
     }
 
+    #[cfg_attr(coverage_nightly, no_coverage)]
+    fn try_write_input(input: TokenStream) -> ParseResult<Input> {
+        Input::from_input(
+            &syn::parse2::<DeriveInput>(input).unwrap(),
+            Options {
+                derive: false,
+                write: true,
+            },
+        )
+    }
+
+    macro_rules! try_write_error (
+        ($name:ident: $message:literal $tt:tt) => {
+            #[test]
+            #[cfg_attr(coverage_nightly, no_coverage)]
+            #[should_panic(expected = $message)]
+            fn $name() {
+                try_write_input(quote::quote! $tt).unwrap();
+            }
+        };
+    );
+
     macro_rules! try_error (
         ($name:ident: $message:literal $tt:tt) => {
             #[test]
@@ -172,6 +194,14 @@ mod tests {
         }
     });
 
+    try_error!(conflicting_keyword_count_count_bytes_of: "`count` and `count_bytes_of` are mutually exclusive" {
+        struct Foo {
+            a: u8,
+            #[br(count = a, count_bytes_of = a)]
+            b: Vec<u8>,
+        }
+    });
+
     try_error!(conflicting_keyword_count_args_list: "did you mean `args { inner: (a,) }`" {
         struct Foo {
             a: u8,
@@ -245,6 +275,53 @@ mod tests {
         }
     });
 
+    try_error!(conflicting_keyword_rest: "conflicting read mode keyword" {
+        struct Foo {
+            #[br(parse_with = until_eof, rest)]
+            a: Vec<u8>,
+        }
+    });
+
+    try_error!(dbg_invalid: "expected `range`" {
+        struct Foo {
+            #[br(dbg(end))]
+            a: u8,
+        }
+    });
+
+    try_error!(conflicting_keyword_context: "conflicting read mode keyword" {
+        struct Foo {
+            #[br(calc = 1, context)]
+            a: u8,
+        }
+    });
+
+    try_error!(catch_all_magic: "cannot have a `magic`" {
+        enum Foo {
+            #[br(magic = 0u8)] A(u8),
+            #[br(catch_all, magic = 1u8)] B(u8),
+        }
+    });
+
+    try_error!(catch_all_not_last: "must be used on the last variant" {
+        enum Foo {
+            #[br(catch_all)] A(u8),
+            #[br(magic = 0u8)] B(u8),
+        }
+    });
+
+    try_error!(catch_all_struct: "can only be used on enum variants" {
+        #[br(catch_all)]
+        struct Foo(u8);
+    });
+
+    try_error!(conflicting_keyword_dyn_with: "conflicting read mode keyword" {
+        struct Foo {
+            #[br(parse_with = u8, dyn_with = u8)]
+            a: i32,
+        }
+    });
+
     try_error!(enum_missing_magic_repr {
         enum UnitEnum {
             A,
@@ -343,6 +420,28 @@ mod tests {
         struct Foo;
     });
 
+    try_error!(magic_alternatives_mismatch: "every alternative of `magic` must have the same type" {
+        #[br(magic = b"AB" | b"ABC")]
+        struct Foo;
+    });
+
+    try_error!(magic_mask_float: "`mask` requires an integer or byte string" {
+        #[br(magic(1.0f32, mask = 0.0f32))]
+        struct Foo;
+    });
+
+    try_error!(magic_mask_mismatch: "`mask` must have the same type" {
+        #[br(magic(b"AB", mask = b"\xff"))]
+        struct Foo;
+    });
+
+    try_error!(unit_enum_magic_mask: "`mask` cannot be used on the variants of a unit-like enum" {
+        enum Foo {
+            #[br(magic(1u8, mask = 0xfu8))]
+            A,
+        }
+    });
+
     try_error!(invalid_magic_type: "expected byte string, byte, float, or int" {
         #[br(magic = "invalid_type")]
         struct Foo;
@@ -362,6 +461,290 @@ mod tests {
         }
     });
 
+    try_error!(verify_calc_conflict: "`verify` is incompatible" {
+        struct Foo {
+            #[br(verify(sum), calc(0))]
+            a: u8,
+        }
+    });
+
+    try_error!(crc_missing_algorithm: "requires an `algorithm`" {
+        struct Foo {
+            #[br(crc(over = 0..4))]
+            a: u32,
+        }
+    });
+
+    try_error!(crc_unknown_algorithm: "unknown CRC algorithm" {
+        struct Foo {
+            #[br(crc(algorithm = "md5"))]
+            a: u32,
+        }
+    });
+
+    try_error!(async_on_variant: "`async` is only supported on structs" {
+        enum Foo {
+            #[br(async)]
+            A(u8),
+        }
+    });
+
+    try_error!(async_field_conflict: "`async` is incompatible with `pad_before`" {
+        #[br(async)]
+        struct Foo {
+            #[br(pad_before = 1)]
+            a: u8,
+        }
+    });
+
+    try_error!(async_rest_conflict: "`async` is incompatible with `rest`" {
+        #[br(async)]
+        struct Foo {
+            #[br(rest)]
+            a: Vec<u8>,
+        }
+    });
+
+    try_error!(audit_on_variant: "`audit` applies to the whole enum" {
+        enum Foo {
+            #[br(audit)]
+            A(u8),
+        }
+    });
+
+    try_error!(schema_on_variant: "`schema` applies to the whole enum" {
+        enum Foo {
+            #[br(schema)]
+            A(u8),
+        }
+    });
+
+    try_error!(if_eof_none_calc_conflict: "`if_eof_none` is incompatible" {
+        struct Foo {
+            #[br(if_eof_none, calc(None))]
+            a: Option<u8>,
+        }
+    });
+
+    try_error!(finite_calc_conflict: "`finite` is incompatible" {
+        struct Foo {
+            #[br(finite, calc(1.0))]
+            a: f32,
+        }
+    });
+
+    try_error!(offset_base_invalid: "expected `absolute`, `parent`, or `current`" {
+        struct Foo {
+            #[br(offset_base = start)]
+            a: FilePtr8<u8>,
+        }
+    });
+
+    try_error!(offset_base_offset_conflict: "`offset_base` is incompatible with `offset`" {
+        struct Foo {
+            #[br(offset_base = parent, offset = 4)]
+            a: FilePtr8<u8>,
+        }
+    });
+
+    try_error!(seek_after_restore_position_conflict: "`seek_after` is incompatible with `restore_position`" {
+        struct Foo {
+            #[br(seek_after = SeekFrom::Current(1), restore_position)]
+            a: u8,
+        }
+    });
+
+    try_error!(read_only_temp_conflict: "`read_only` is incompatible with `temp`" {
+        struct Foo {
+            #[br(read_only, temp)]
+            a: u8,
+        }
+    });
+
+    try_error!(error_on_variant: "`error` applies to the whole enum" {
+        enum Foo {
+            #[br(error = MyError)]
+            A(u8),
+        }
+    });
+
+    try_error!(invalid_tag_endian: "expected `big` or `little`" {
+        #[br(tag_endian = middle)]
+        enum Foo {
+            A(u8),
+        }
+    });
+
+    try_error!(seek_to_saved_unknown_slot: "`seek_to_saved` must refer to a position saved by `save_position` on this or an earlier field" {
+        struct Foo {
+            #[br(seek_to_saved = start)]
+            a: u8,
+            #[br(save_position = start)]
+            b: u8,
+        }
+    });
+
+    try_error!(save_position_bad_label: "expected a label which is a valid identifier" {
+        struct Foo {
+            #[br(save_position = "table of contents")]
+            a: u8,
+        }
+    });
+
+    try_error!(size_on_variant: "`size` cannot be used on an enum variant" {
+        enum Foo {
+            #[br(size = 4)]
+            A(u8),
+        }
+    });
+
+    try_error!(finally_with_map: "`finally` cannot be used with a struct-level `map`" {
+        #[br(map = |a: u8| Foo(a), finally = |_, _| Ok(()))]
+        struct Foo(u8);
+    });
+
+    try_error!(trailing_invalid: "expected `skip`, `error`, or `capture(field)`" {
+        #[br(size = 4, trailing = ignore)]
+        struct Foo(u8);
+    });
+
+    try_error!(trailing_capture_not_last: "`trailing = capture(..)` must refer to the last field" {
+        #[br(size = 4, trailing = capture(a))]
+        struct Foo {
+            a: Vec<u8>,
+            b: u8,
+        }
+    });
+
+    try_error!(trailing_capture_directives: "a field captured by `trailing` cannot use other read directives" {
+        #[br(size = 4, trailing = capture(a))]
+        struct Foo {
+            #[br(count = 2)]
+            a: Vec<u8>,
+        }
+    });
+
+    try_error!(trailing_without_size: "`trailing` requires `size`" {
+        #[br(trailing = error)]
+        struct Foo(u8);
+    });
+
+    try_write_error!(calc_count_of_calc_conflict: "conflicting read mode keyword" {
+        struct Foo {
+            #[bw(calc_count_of = b, calc = 1)]
+            a: u8,
+            b: Vec<u8>,
+        }
+    });
+
+    try_write_error!(size_of_calc_conflict: "`size_of` is incompatible with `calc`" {
+        struct Foo {
+            #[bw(size_of = b, calc = 1)]
+            a: u8,
+            b: Vec<u8>,
+        }
+    });
+
+    try_write_error!(size_of_earlier_field: "`size_of` must refer to a field which comes after it" {
+        struct Foo {
+            a: Vec<u8>,
+            #[bw(size_of = a)]
+            b: u8,
+        }
+    });
+
+    try_write_error!(size_of_if_conflict: "`size_of` is incompatible with `if`" {
+        struct Foo {
+            #[bw(size_of = b, if(true))]
+            a: u8,
+            b: Vec<u8>,
+        }
+    });
+
+    try_write_error!(prepare_with_map: "`prepare` cannot be used with a struct-level `map`" {
+        #[bw(map = |a: &Foo| a.0, prepare = ())]
+        struct Foo(u8);
+    });
+
+    try_write_error!(prepare_invalid_name: "expected a name for the result of `prepare`" {
+        #[bw(prepare(self.0 = 1))]
+        struct Foo(u8);
+    });
+
+    try_write_error!(flush_after_on_variant: "`flush_after` applies to the whole enum" {
+        enum Foo {
+            #[bw(flush_after)]
+            A(u8),
+        }
+    });
+
+    try_error!(tag_value_struct: "can only be used on enum variants" {
+        #[br(tag_value = 1)]
+        struct Foo(u8);
+    });
+
+    try_error!(tag_value_without_tag: "requires a `tag` on the enum" {
+        enum Foo {
+            #[br(tag_value = 1)]
+            A(u8),
+        }
+    });
+
+    try_error!(tag_into_struct_without_magic: "requires a `magic` on the struct" {
+        #[br(tag_into = a)]
+        struct Foo {
+            a: u8,
+        }
+    });
+
+    try_error!(tag_into_struct_missing_field: "must refer to a field of this struct" {
+        #[br(magic = 1u8, tag_into = b)]
+        struct Foo {
+            a: u8,
+        }
+    });
+
+    try_error!(tag_into_without_tag: "requires a `tag` on the enum or a `magic`" {
+        enum Foo {
+            #[br(tag_into = a)]
+            A { a: u8 },
+        }
+    });
+
+    try_error!(tag_into_missing_field: "must refer to a field of this variant" {
+        #[br(tag = 1u8)]
+        enum Foo {
+            #[br(tag_into = b)]
+            A { a: u8 },
+        }
+    });
+
+    try_error!(tag_into_calc_conflict: "cannot also use `calc`" {
+        #[br(tag = 1u8)]
+        enum Foo {
+            #[br(tag_into = a)]
+            A {
+                #[br(calc = 2)]
+                a: u8,
+            },
+        }
+    });
+
+    try_error!(unit_enum_tag_magic: "`magic` cannot be used" {
+        #[br(tag = 1u8)]
+        enum Foo {
+            #[br(magic = 1u8)]
+            A,
+        }
+    });
+
+    try_error!(unit_enum_tag_repr: "`tag` and `repr` are mutually exclusive" {
+        #[br(tag = 1u8, repr = u8)]
+        enum Foo {
+            A = 1,
+        }
+    });
+
     // Errors on one field should not prevent the parser from surfacing errors
     // on other fields
     #[test]
@@ -381,10 +764,45 @@ mod tests {
         assert_eq!(error.into_iter().count(), 3);
     }
 
-    try_error!(repr_magic_conflict: "mutually exclusive" {
+    try_error!(repr_magic_not_integer: "expected integer magic number" {
         #[br(repr = u8)]
         enum Foo {
-            #[br(magic = 0u8)] A,
+            #[br(magic = b"A")] A,
+        }
+    });
+
+    try_error!(repr_magic_not_primitive: "must be a primitive integer" {
+        #[br(repr = Foo)]
+        enum Foo {
+            #[br(magic = 0)] A,
+        }
+    });
+
+    try_error!(repr_magic_out_of_range: "out of range for `repr = u8`" {
+        #[br(repr = u8)]
+        enum Foo {
+            #[br(magic = 0)] A,
+            #[br(magic = 0x100)] B,
+        }
+    });
+
+    try_error!(repr_magic_out_of_range_data: "out of range for `repr = i16`" {
+        #[br(repr = i16)]
+        enum Foo {
+            #[br(magic = 0x8000)] A(u8),
+        }
+    });
+
+    try_error!(repr_magic_type_mismatch: "type `u16` does not match `repr = u8`" {
+        #[br(repr = u8)]
+        enum Foo {
+            #[br(magic = 0u16)] A,
+        }
+    });
+
+    try_error!(untyped_magic_variant: "expected explicit type suffix" {
+        enum Foo {
+            #[br(magic = 0)] A(u8),
         }
     });
 
@@ -1,9 +1,18 @@
 use super::{
     attr_struct,
-    types::{Assert, CondEndian, EnumErrorMode, Imports, Magic, Map},
-    EnumVariant, FromInput, ParseResult, StructField, TrySet, UnitEnumField,
+    types::{
+        Assert, CondEndian, Endian, EnumErrorMode, FieldMode, Imports, Magic, Map, Prepare,
+        Trailing,
+    },
+    EnumVariant, FromInput, ParseResult, SpannedValue, StructField, TrySet, UnitEnumField,
+};
+use crate::{
+    binrw::{
+        codegen::sanitization::{MAGIC_VALUE, TAG, UNTIL_EOF},
+        Options,
+    },
+    combine_error,
 };
-use crate::binrw::Options;
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{spanned::Spanned, Ident};
@@ -23,6 +32,22 @@ pub(crate) enum Input {
 impl Input {
     /// Tries parsing the binrw attributes on a data structure.
     pub(crate) fn from_input(input: &syn::DeriveInput, options: Options) -> ParseResult<Self> {
+        match Self::parse_input(input, options) {
+            ParseResult::Ok(this) => match this.validate() {
+                Ok(()) => ParseResult::Ok(this),
+                Err(error) => ParseResult::Partial(this, error),
+            },
+            ParseResult::Partial(this, mut error) => {
+                if let Err(validation_error) = this.validate() {
+                    error.combine(validation_error);
+                }
+                ParseResult::Partial(this, error)
+            }
+            ParseResult::Err(error) => ParseResult::Err(error),
+        }
+    }
+
+    fn parse_input(input: &syn::DeriveInput, options: Options) -> ParseResult<Self> {
         let attrs = &input.attrs;
         let ident = Some(&input.ident);
         match &input.data {
@@ -41,6 +66,7 @@ impl Input {
                     )
                 };
 
+                let read_struct = prepare_struct_tag_into(&input.ident, read_struct);
                 if matches!(st.fields, syn::Fields::Unit) {
                     read_struct.map(Self::UnitStruct)
                 } else {
@@ -98,6 +124,59 @@ impl Input {
         }
     }
 
+    /// Checks the parts of the input which can only be validated once every
+    /// variant is parsed.
+    fn validate(&self) -> syn::Result<()> {
+        if let Input::Struct(s) | Input::UnitStruct(s) = self {
+            for (directive, is_set) in [
+                ("catch_all", s.catch_all.is_some()),
+                ("tag_value", s.tag_value.is_some()),
+            ] {
+                if is_set {
+                    return Err(syn::Error::new(
+                        proc_macro2::Span::call_site(),
+                        format!("`{directive}` can only be used on enum variants"),
+                    ));
+                }
+            }
+
+            if let (Some(is_async), false) = (&s.is_async, cfg!(feature = "tokio")) {
+                return Err(syn::Error::new(
+                    is_async.span(),
+                    "`async` requires the `tokio` feature of binrw",
+                ));
+            }
+        }
+
+        self.check_magic_types()
+    }
+
+    /// Checks that every integer magic number has a type, either from its
+    /// suffix or from the `repr` of its enum.
+    fn check_magic_types(&self) -> syn::Result<()> {
+        fn struct_magic(st: &Struct) -> impl Iterator<Item = &Magic> {
+            core::iter::once(&st.magic).chain(st.fields.iter().map(|field| &field.magic))
+        }
+
+        let magics = match self {
+            Input::Struct(s) | Input::UnitStruct(s) => struct_magic(s).collect::<Vec<_>>(),
+            Input::Enum(e) => core::iter::once(&e.magic)
+                .chain(e.variants.iter().flat_map(|variant| match variant {
+                    EnumVariant::Variant { options, .. } => struct_magic(options).collect(),
+                    EnumVariant::Unit(field) => vec![&field.magic],
+                }))
+                .collect(),
+            Input::UnitOnlyEnum(e) => core::iter::once(&e.magic)
+                .chain(e.fields.iter().map(|field| &field.magic))
+                .collect(),
+        };
+
+        magics
+            .into_iter()
+            .flatten()
+            .try_for_each(|magic| magic.check_typed())
+    }
+
     pub(crate) fn endian(&self) -> &CondEndian {
         match self {
             Input::Struct(s) | Input::UnitStruct(s) => &s.endian,
@@ -106,6 +185,14 @@ impl Input {
         }
     }
 
+    pub(crate) fn tag_endian(&self) -> Option<Endian> {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.tag_endian,
+            Input::Enum(e) => e.tag_endian,
+            Input::UnitOnlyEnum(e) => e.tag_endian,
+        }
+    }
+
     pub(crate) fn imports(&self) -> &Imports {
         match self {
             Input::Struct(s) | Input::UnitStruct(s) => &s.imports,
@@ -143,6 +230,18 @@ impl Input {
         }
     }
 
+    /// Returns true if any field of the input matches the predicate.
+    pub(crate) fn any_field(&self, mut predicate: impl FnMut(&StructField) -> bool) -> bool {
+        match self {
+            Input::Struct(s) => s.fields.iter().any(predicate),
+            Input::Enum(e) => e.variants.iter().any(|variant| {
+                matches!(variant, EnumVariant::Variant { options, .. }
+                    if options.fields.iter().any(&mut predicate))
+            }),
+            Input::UnitStruct(_) | Input::UnitOnlyEnum(_) => false,
+        }
+    }
+
     pub(crate) fn map(&self) -> &Map {
         match self {
             Input::Struct(s) | Input::UnitStruct(s) => &s.map,
@@ -167,6 +266,65 @@ impl Input {
         }
     }
 
+    pub(crate) fn untrusted(&self) -> bool {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.untrusted.is_some(),
+            Input::Enum(e) => e.untrusted.is_some(),
+            Input::UnitOnlyEnum(e) => e.untrusted.is_some(),
+        }
+    }
+
+    pub(crate) fn audit(&self) -> bool {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.audit.is_some(),
+            Input::Enum(e) => e.audit.is_some(),
+            Input::UnitOnlyEnum(e) => e.audit.is_some(),
+        }
+    }
+
+    pub(crate) fn schema(&self) -> bool {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.schema.is_some(),
+            Input::Enum(e) => e.schema.is_some(),
+            Input::UnitOnlyEnum(e) => e.schema.is_some(),
+        }
+    }
+
+    pub(crate) fn is_async(&self) -> bool {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.is_async.is_some(),
+            Input::Enum(_) | Input::UnitOnlyEnum(_) => false,
+        }
+    }
+
+    pub(crate) fn flush_after(&self) -> bool {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.flush_after.is_some(),
+            Input::Enum(e) => e.flush_after.is_some(),
+            Input::UnitOnlyEnum(e) => e.flush_after.is_some(),
+        }
+    }
+
+    pub(crate) fn error_type(&self) -> Option<&TokenStream> {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.error_type.as_ref(),
+            Input::Enum(e) => e.error_type.as_ref(),
+            Input::UnitOnlyEnum(e) => e.error_type.as_ref(),
+        }
+    }
+
+    pub(crate) fn has_checksum(&self) -> bool {
+        let has_checksum = |st: &Struct| st.fields.iter().any(|field| field.checksum.is_some());
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => has_checksum(s),
+            Input::Enum(e) => e.variants.iter().any(|variant| match variant {
+                EnumVariant::Variant { options, .. } => has_checksum(options),
+                EnumVariant::Unit(_) => false,
+            }),
+            Input::UnitOnlyEnum(_) => false,
+        }
+    }
+
     pub(crate) fn pre_assertions(&self) -> &[Assert] {
         match self {
             Input::Struct(s) | Input::UnitStruct(s) => &s.pre_assertions,
@@ -205,6 +363,8 @@ attr_struct! {
         pub(crate) stream_ident: Option<Ident>,
         #[from(RW:Big, RW:Little, RW:IsBig, RW:IsLittle)]
         pub(crate) endian: CondEndian,
+        #[from(RW:TagEndian)]
+        pub(crate) tag_endian: Option<Endian>,
         #[from(RW:Map, RW:TryMap, RW:Repr)]
         pub(crate) map: Map,
         #[from(RW:MapStream)]
@@ -217,6 +377,34 @@ attr_struct! {
         pub(crate) assertions: Vec<Assert>,
         #[from(RO:PreAssert)]
         pub(crate) pre_assertions: Vec<Assert>,
+        #[from(RO:Untrusted)]
+        pub(crate) untrusted: Option<()>,
+        #[from(RW:PadWith)]
+        pub(crate) pad_with: Option<TokenStream>,
+        #[from(RW:Audit)]
+        pub(crate) audit: Option<()>,
+        #[from(RW:Schema)]
+        pub(crate) schema: Option<()>,
+        #[from(RO:Async)]
+        pub(crate) is_async: Option<SpannedValue<()>>,
+        #[from(WO:FlushAfter)]
+        pub(crate) flush_after: Option<()>,
+        #[from(RO:ErrorType)]
+        pub(crate) error_type: Option<TokenStream>,
+        #[from(RO:CatchAll)]
+        pub(crate) catch_all: Option<()>,
+        #[from(RO:TagValue)]
+        pub(crate) tag_value: Option<TokenStream>,
+        #[from(RO:TagInto)]
+        pub(crate) tag_into: Option<Ident>,
+        #[from(RO:Size)]
+        pub(crate) size: Option<TokenStream>,
+        #[from(RO:Trailing)]
+        pub(crate) trailing: Option<SpannedValue<Trailing>>,
+        #[from(RO:Finally)]
+        pub(crate) finally: Option<TokenStream>,
+        #[from(WO:Prepare)]
+        pub(crate) prepare: Option<Prepare>,
         pub(crate) fields: Vec<StructField>,
         pub(crate) for_write: bool,
     }
@@ -229,6 +417,12 @@ impl Struct {
             .map_or(false, |field| field.generated_ident)
     }
 
+    /// Returns whether the given field receives the unread bytes of a `size`
+    /// region instead of being read.
+    pub(crate) fn is_trailing_capture(&self, field: &StructField) -> bool {
+        matches!(self.trailing.as_deref(), Some(Trailing::Capture(target)) if field.ident == *target)
+    }
+
     pub(crate) fn iter_permanent_idents(&self) -> impl Iterator<Item = &syn::Ident> + '_ {
         self.fields.iter().filter_map(move |field| {
             if field.is_temp(self.for_write) {
@@ -241,12 +435,58 @@ impl Struct {
 
     pub(crate) fn has_no_attrs(&self) -> bool {
         matches!(self.endian, CondEndian::Inherited)
+            && self.tag_endian.is_none()
             && matches!(self.map, Map::None)
             && self.magic.is_none()
+            && self.catch_all.is_none()
+            && self.tag_value.is_none()
+            && self.tag_into.is_none()
+            && self.pad_with.is_none()
+            && self.size.is_none()
+            && self.finally.is_none()
+            && self.prepare.is_none()
             && matches!(self.imports, Imports::None)
             && self.fields.iter().all(StructField::has_no_attrs)
     }
 
+    fn async_error(&self) -> Option<syn::Error> {
+        let span = self.is_async.as_ref()?.span();
+        let conflict = if self.map.is_some() {
+            "`map`, `try_map`, and `repr`"
+        } else if self.map_stream.is_some() {
+            "`map_stream`"
+        } else if self.stream_ident.is_some() {
+            "`stream`"
+        } else if self.magic.as_ref().is_some_and(|magic| !magic.is_exact()) {
+            "multiple or masked `magic` values"
+        } else if !self.pre_assertions.is_empty() {
+            "`pre_assert`"
+        } else if self.untrusted.is_some() {
+            "`untrusted`"
+        } else if self.pad_with.is_some() {
+            "`pad_with`"
+        } else if self.size.is_some() {
+            "`size`"
+        } else if self.finally.is_some() {
+            "`finally`"
+        } else if let Some((field, directive)) = self
+            .fields
+            .iter()
+            .find_map(|field| Some((field, field.async_conflict()?)))
+        {
+            return Some(syn::Error::new(
+                field.field.span(),
+                format!("`async` is incompatible with {directive}"),
+            ));
+        } else {
+            return None;
+        };
+        Some(syn::Error::new(
+            span,
+            format!("`async` is incompatible with {conflict}"),
+        ))
+    }
+
     pub(crate) fn fields_pattern(&self) -> TokenStream {
         let fields = self.iter_permanent_idents();
 
@@ -265,7 +505,11 @@ impl Struct {
 impl<const WRITE: bool> FromInput<StructAttr<WRITE>> for Struct {
     type Field = StructField;
 
-    fn push_field(&mut self, field: Self::Field) -> syn::Result<()> {
+    fn push_field(&mut self, mut field: Self::Field) -> syn::Result<()> {
+        field.untrusted |= self.untrusted.is_some();
+        if field.pad_with.is_none() {
+            field.pad_with.clone_from(&self.pad_with);
+        }
         self.fields.push(field);
         Ok(())
     }
@@ -275,6 +519,83 @@ impl<const WRITE: bool> FromInput<StructAttr<WRITE>> for Struct {
     }
 
     fn validate(&self, options: Options) -> syn::Result<()> {
+        if let Some(error) = self.async_error() {
+            return Err(error);
+        }
+
+        if let (Some(trailing), None) = (&self.trailing, &self.size) {
+            return Err(syn::Error::new(
+                trailing.span(),
+                "`trailing` requires `size`",
+            ));
+        }
+
+        if let Some(Trailing::Capture(target)) = self.trailing.as_deref() {
+            match self.fields.last() {
+                Some(field) if field.ident == *target && !field.generated_ident => {
+                    if !field.has_no_attrs() {
+                        return Err(syn::Error::new(
+                            field.field.span(),
+                            "a field captured by `trailing` cannot use other read directives",
+                        ));
+                    }
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        target.span(),
+                        "`trailing = capture(..)` must refer to the last field",
+                    ))
+                }
+            }
+        }
+
+        if let (Some(size), Some(_)) = (&self.size, &self.map_stream) {
+            return Err(syn::Error::new(
+                size.span(),
+                "`size` is incompatible with `map_stream`",
+            ));
+        }
+
+        for (index, field) in self.fields.iter().enumerate() {
+            if let Some(target) = &field.size_of {
+                if !self.fields[index + 1..]
+                    .iter()
+                    .any(|later| later.ident == *target)
+                {
+                    return Err(syn::Error::new(
+                        target.span(),
+                        "`size_of` must refer to a field which comes after it",
+                    ));
+                }
+            }
+
+            if let Some(slot) = &field.seek_to_saved {
+                if !self.fields[..=index]
+                    .iter()
+                    .any(|earlier| earlier.save_position.as_ref() == Some(slot))
+                {
+                    return Err(syn::Error::new(
+                        slot.span(),
+                        "`seek_to_saved` must refer to a position saved by `save_position` on this or an earlier field",
+                    ));
+                }
+            }
+        }
+
+        if let (Some(finally), true) = (&self.finally, self.map.is_some()) {
+            return Err(syn::Error::new(
+                finally.span(),
+                "`finally` cannot be used with a struct-level `map`",
+            ));
+        }
+
+        if let (Some(prepare), true) = (&self.prepare, self.map.is_some()) {
+            return Err(syn::Error::new(
+                prepare.expr.span(),
+                "`prepare` cannot be used with a struct-level `map`",
+            ));
+        }
+
         if self.map.is_none() && !options.derive {
             return Ok(());
         }
@@ -312,6 +633,8 @@ attr_struct! {
         pub(crate) stream_ident: Option<Ident>,
         #[from(RW:Big, RW:Little, RW:IsBig, RW:IsLittle)]
         pub(crate) endian: CondEndian,
+        #[from(RW:TagEndian)]
+        pub(crate) tag_endian: Option<Endian>,
         #[from(RW:Map, RW:TryMap, RW:Repr)]
         pub(crate) map: Map,
         #[from(RW:MapStream)]
@@ -331,14 +654,114 @@ attr_struct! {
         pub(crate) pre_assertions: Vec<Assert>,
         #[from(RO:ReturnAllErrors, RO:ReturnUnexpectedError)]
         pub(crate) error_mode: EnumErrorMode,
+        #[from(RO:Untrusted)]
+        pub(crate) untrusted: Option<()>,
+        #[from(RW:PadWith)]
+        pub(crate) pad_with: Option<TokenStream>,
+        #[from(RW:Audit)]
+        pub(crate) audit: Option<()>,
+        #[from(RW:Schema)]
+        pub(crate) schema: Option<()>,
+        #[from(WO:FlushAfter)]
+        pub(crate) flush_after: Option<()>,
+        #[from(RO:ErrorType)]
+        pub(crate) error_type: Option<TokenStream>,
+        #[from(RO:Tag)]
+        pub(crate) tag: Option<TokenStream>,
         pub(crate) variants: Vec<EnumVariant>,
+        pub(crate) tag_repr: Option<TokenStream>,
     }
 }
 
 impl<const WRITE: bool> FromInput<EnumAttr<WRITE>> for Enum {
     type Field = EnumVariant;
 
-    fn push_field(&mut self, field: Self::Field) -> syn::Result<()> {
+    fn push_field(&mut self, mut field: Self::Field) -> syn::Result<()> {
+        if let EnumVariant::Variant { ident, options } = &field {
+            if let Some(size) = &options.size {
+                return Err(syn::Error::new(
+                    size.span(),
+                    "`size` cannot be used on an enum variant",
+                ));
+            }
+
+            if let Some(is_async) = &options.is_async {
+                return Err(syn::Error::new(
+                    is_async.span(),
+                    "`async` is only supported on structs",
+                ));
+            }
+
+            for (directive, is_set) in [
+                ("audit", options.audit.is_some()),
+                ("error", options.error_type.is_some()),
+                ("flush_after", options.flush_after.is_some()),
+                ("schema", options.schema.is_some()),
+            ] {
+                if is_set {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!("`{directive}` applies to the whole enum and cannot be used on a variant"),
+                    ));
+                }
+            }
+        }
+
+        if self.tag.is_none() && field.tag_value().is_some() {
+            return Err(syn::Error::new(
+                field.ident().span(),
+                "`tag_value` requires a `tag` on the enum",
+            ));
+        }
+
+        if let Some(catch_all) = self
+            .variants
+            .last()
+            .filter(|variant| variant.is_catch_all())
+        {
+            return Err(syn::Error::new(
+                catch_all.ident().span(),
+                "`catch_all` must be used on the last variant",
+            ));
+        }
+
+        if let EnumVariant::Variant { ident, options } = &mut field {
+            if options.catch_all.is_some() {
+                prepare_catch_all(ident, options)?;
+            }
+        }
+
+        apply_tag_repr(&mut self.map, &mut self.tag_repr, field.magic_mut())?;
+
+        if let EnumVariant::Variant { ident, options } = &mut field {
+            if options.tag_into.is_some() {
+                prepare_tag_into(ident, options, Some(self.tag.is_some()))?;
+            }
+        }
+
+        if let (Some(tag_endian), EnumVariant::Variant { options, .. }) =
+            (self.tag_endian, &mut field)
+        {
+            options.tag_endian.get_or_insert(tag_endian);
+        }
+
+        if let (Some(()), EnumVariant::Variant { options, .. }) = (self.untrusted, &mut field) {
+            options.untrusted = Some(());
+            for field in &mut options.fields {
+                field.untrusted = true;
+            }
+        }
+
+        if let (Some(pad_with), EnumVariant::Variant { options, .. }) = (&self.pad_with, &mut field)
+        {
+            for field in options
+                .fields
+                .iter_mut()
+                .filter(|field| field.pad_with.is_none())
+            {
+                field.pad_with = Some(pad_with.clone());
+            }
+        }
         self.variants.push(field);
         Ok(())
     }
@@ -364,6 +787,8 @@ attr_struct! {
         pub(crate) stream_ident: Option<Ident>,
         #[from(RW:Big, RW:Little, RW:IsBig, RW:IsLittle)]
         pub(crate) endian: CondEndian,
+        #[from(RW:TagEndian)]
+        pub(crate) tag_endian: Option<Endian>,
         #[from(RW:Map, RW:TryMap, RW:Repr)]
         pub(crate) map: Map,
         #[from(RW:MapStream)]
@@ -372,8 +797,21 @@ attr_struct! {
         pub(crate) magic: Magic,
         #[from(RW:Import, RW:ImportRaw)]
         pub(crate) imports: Imports,
+        #[from(RO:Untrusted)]
+        pub(crate) untrusted: Option<()>,
+        #[from(RW:Audit)]
+        pub(crate) audit: Option<()>,
+        #[from(RW:Schema)]
+        pub(crate) schema: Option<()>,
+        #[from(WO:FlushAfter)]
+        pub(crate) flush_after: Option<()>,
+        #[from(RO:ErrorType)]
+        pub(crate) error_type: Option<TokenStream>,
+        #[from(RO:Tag)]
+        pub(crate) tag: Option<TokenStream>,
         pub(crate) fields: Vec<UnitEnumField>,
         pub(crate) is_magic_enum: bool,
+        pub(crate) tag_repr: Option<TokenStream>,
     }
 }
 
@@ -386,23 +824,37 @@ impl UnitOnlyEnum {
 impl<const WRITE: bool> FromInput<UnitEnumAttr<WRITE>> for UnitOnlyEnum {
     type Field = UnitEnumField;
 
-    fn push_field(&mut self, field: Self::Field) -> syn::Result<()> {
-        if let (Some(repr), Some(magic)) = (self.map.as_repr(), field.magic.as_ref()) {
-            let magic_span = magic.span();
-            let span = magic_span.join(repr.span()).unwrap_or(magic_span);
-            Err(syn::Error::new(
-                span,
-                "`repr` and `magic` are mutually exclusive",
-            ))
-        } else {
-            self.is_magic_enum |= field.magic.is_some();
-            self.fields.push(field);
-            Ok(())
+    fn push_field(&mut self, mut field: Self::Field) -> syn::Result<()> {
+        if self.tag.is_none() && field.tag_value.is_some() {
+            return Err(syn::Error::new(
+                field.ident.span(),
+                "`tag_value` requires a `tag` on the enum",
+            ));
+        } else if self.tag.is_some() && field.magic.is_some() {
+            return Err(syn::Error::new(
+                field.ident.span(),
+                "`magic` cannot be used on the variants of a unit enum with a `tag`",
+            ));
+        } else if let Some(magic) = field.magic.as_ref().filter(|magic| magic.has_mask()) {
+            return Err(syn::Error::new(
+                magic.span(),
+                "`mask` cannot be used on the variants of a unit-like enum",
+            ));
         }
+
+        apply_tag_repr(&mut self.map, &mut self.tag_repr, &mut field.magic)?;
+        self.is_magic_enum |= field.magic.is_some();
+        self.fields.push(field);
+        Ok(())
     }
 
     fn validate(&self, options: Options) -> syn::Result<()> {
-        if self.map.as_repr().is_some() || self.is_magic_enum() {
+        if self.tag.is_some() && self.map.as_repr().is_some() {
+            Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "`tag` and `repr` are mutually exclusive",
+            ))
+        } else if self.map.as_repr().is_some() || self.is_magic_enum() || self.tag.is_some() {
             Ok(())
         } else if options.write {
             Err(syn::Error::new(proc_macro2::Span::call_site(), "BinWrite on unit-like enums requires either `#[bw(repr = ...)]` on the enum or `#[bw(magic = ...)]` on at least one variant"))
@@ -411,3 +863,125 @@ impl<const WRITE: bool> FromInput<UnitEnumAttr<WRITE>> for UnitOnlyEnum {
         }
     }
 }
+
+/// Checks a `catch_all` variant and makes its last field consume the rest of
+/// the data, unless it already has its own directives.
+fn prepare_catch_all(ident: &Ident, options: &mut Struct) -> syn::Result<()> {
+    if options.magic.is_some() {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`catch_all` variants cannot have a `magic`",
+        ));
+    }
+
+    if let Some(field) = options
+        .fields
+        .last_mut()
+        .filter(|field| field.has_no_attrs())
+    {
+        field.field_mode = FieldMode::Function(UNTIL_EOF.to_token_stream(), "`catch_all`");
+    }
+
+    Ok(())
+}
+
+/// Makes the field named by `tag_into` take its value from the tag or magic
+/// number which selected the variant, or from the magic number of a struct,
+/// instead of reading it. `has_tag` is `None` for a struct, and otherwise is
+/// whether the enum has a `tag`.
+fn prepare_tag_into(ident: &Ident, options: &mut Struct, has_tag: Option<bool>) -> syn::Result<()> {
+    let value = if has_tag == Some(true) {
+        quote::quote! { ::core::clone::Clone::clone(#TAG) }
+    } else if let Some(magic) = &options.magic {
+        if magic.is_exact() {
+            magic.deref_value()
+        } else {
+            MAGIC_VALUE.to_token_stream()
+        }
+    } else {
+        return Err(syn::Error::new(
+            ident.span(),
+            if has_tag.is_some() {
+                "`tag_into` requires a `tag` on the enum or a `magic` on the variant"
+            } else {
+                "`tag_into` requires a `magic` on the struct"
+            },
+        ));
+    };
+
+    let Some(target) = &options.tag_into else {
+        return Ok(());
+    };
+
+    let Some(field) = options
+        .fields
+        .iter_mut()
+        .find(|field| field.ident == *target)
+    else {
+        return Err(syn::Error::new(
+            target.span(),
+            if has_tag.is_some() {
+                "`tag_into` must refer to a field of this variant"
+            } else {
+                "`tag_into` must refer to a field of this struct"
+            },
+        ));
+    };
+
+    if !matches!(field.field_mode, FieldMode::Normal) {
+        return Err(syn::Error::new(
+            target.span(),
+            "the field named by `tag_into` cannot also use `calc`, `try_calc`, `default`, `ignore`, or `parse_with`",
+        ));
+    }
+
+    field.field_mode = FieldMode::Calc(quote::quote! {
+        ::core::convert::Into::into(#value)
+    });
+
+    Ok(())
+}
+
+/// Applies `tag_into` to a parsed struct.
+fn prepare_struct_tag_into(ident: &Ident, result: ParseResult<Struct>) -> ParseResult<Struct> {
+    let (mut st, mut error) = match result {
+        ParseResult::Ok(st) => (st, None),
+        ParseResult::Partial(st, error) => (st, Some(error)),
+        ParseResult::Err(error) => return ParseResult::Err(error),
+    };
+
+    if st.tag_into.is_some() {
+        if let Err(tag_into_error) = prepare_tag_into(ident, &mut st, None) {
+            combine_error(&mut error, tag_into_error);
+        }
+    }
+
+    match error {
+        Some(error) => ParseResult::Partial(st, error),
+        None => ParseResult::Ok(st),
+    }
+}
+
+/// Gives the integer magic numbers of enum variants the type from the `repr`
+/// of the enum, so that every variant reads a tag of the same size.
+///
+/// Once a variant has a magic number, `repr` is no longer used as a map for
+/// the whole enum.
+fn apply_tag_repr(
+    map: &mut Map,
+    tag_repr: &mut Option<TokenStream>,
+    magic: &mut Magic,
+) -> syn::Result<()> {
+    if let Some(magic) = magic {
+        if let Map::Repr(repr) = map {
+            *tag_repr = Some(core::mem::take(repr));
+            *map = Map::None;
+        }
+
+        if let Some(repr) = tag_repr {
+            *magic = magic.with_repr(repr)?;
+        }
+    }
+
+    Ok(())
+}
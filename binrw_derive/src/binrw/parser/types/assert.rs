@@ -1,7 +1,7 @@
 use crate::{binrw::parser::attrs, meta_types::KeywordToken};
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
-use syn::{parse::Parse, spanned::Spanned, token::Token, Expr, ExprLit, Lit};
+use syn::{parse::Parse, spanned::Spanned, token::Token};
 
 #[derive(Debug, Clone)]
 pub(crate) enum Error {
@@ -35,15 +35,20 @@ impl<K: Parse + Spanned + Token> TryFrom<attrs::AssertLike<K>> for Assert {
             ));
         };
 
-        let consequent = match args.next() {
-            Some(Expr::Lit(ExprLit {
-                lit: Lit::Str(message),
-                ..
-            })) => Some(Error::Message(quote! {
-                extern crate alloc;
-                alloc::format!(#message #(, #args)*)
-            })),
-            Some(error) => {
+        let consequent = match args.next().map(|arg| (arg, super::lit_str(arg))) {
+            Some((_, Some(message))) => Some(Error::Message(
+                // Messages without formatting are passed through as-is so that
+                // they do not need to be allocated
+                if args.len() == 0 && !message.value().contains(['{', '}']) {
+                    message.to_token_stream()
+                } else {
+                    quote! {
+                        extern crate alloc;
+                        alloc::format!(#message #(, #args)*)
+                    }
+                },
+            )),
+            Some((error, None)) => {
                 super::assert_all_args_consumed(args, value.keyword_span())?;
                 Some(Error::Error(error.to_token_stream()))
             }
@@ -0,0 +1,134 @@
+use crate::{
+    binrw::{
+        codegen::sanitization::CRC,
+        parser::{attrs, keywords as kw},
+    },
+    meta_types::{KeywordToken, MetaExpr, MetaList, MetaLit},
+};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote_spanned, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    token::Token,
+    Expr, Lit,
+};
+
+/// The names of the algorithms in `binrw::crc` which can be used with the
+/// `crc` directive.
+const CRC_ALGORITHMS: &[&str] = &["crc16", "crc32", "crc32c"];
+
+#[derive(Debug, Clone)]
+pub(crate) struct Checksum {
+    pub(crate) keyword: &'static str,
+    pub(crate) kw_span: Span,
+    pub(crate) function: TokenStream,
+    pub(crate) range: Option<TokenStream>,
+}
+
+impl<K: Parse + Spanned + Token> TryFrom<MetaList<K, Expr>> for Checksum {
+    type Error = syn::Error;
+
+    fn try_from(value: MetaList<K, Expr>) -> Result<Self, Self::Error> {
+        let kw_span = value.keyword_span();
+        let mut args = value.fields.iter();
+
+        let function = if let Some(function) = args.next() {
+            function.into_token_stream()
+        } else {
+            return Err(Self::Error::new(
+                kw_span,
+                format!(
+                    "{} requires a checksum function as an argument",
+                    value.dyn_display()
+                ),
+            ));
+        };
+
+        let range = args.next().map(ToTokens::into_token_stream);
+
+        super::assert_all_args_consumed(args, kw_span)?;
+
+        Ok(Self {
+            keyword: value.dyn_display(),
+            kw_span,
+            function,
+            range,
+        })
+    }
+}
+
+impl TryFrom<attrs::Crc> for Checksum {
+    type Error = syn::Error;
+
+    fn try_from(value: attrs::Crc) -> Result<Self, Self::Error> {
+        let kw_span = value.keyword_span();
+        let mut algorithm = None;
+        let mut range = None;
+
+        for arg in value.fields {
+            let (span, is_duplicate) = match arg {
+                CrcArg::Algorithm(arg) => {
+                    (arg.ident.span(), algorithm.replace(arg.value).is_some())
+                }
+                CrcArg::Over(arg) => (
+                    arg.ident.span(),
+                    range.replace(arg.value.into_token_stream()).is_some(),
+                ),
+            };
+
+            if is_duplicate {
+                return Err(Self::Error::new(span, "conflicting `crc` argument"));
+            }
+        }
+
+        let algorithm = algorithm
+            .ok_or_else(|| Self::Error::new(kw_span, "crc requires an `algorithm` argument"))?;
+
+        let function = match &algorithm {
+            Lit::Str(name) if CRC_ALGORITHMS.contains(&name.value().as_str()) => {
+                let name = format_ident!("{}", name.value(), span = name.span());
+                quote_spanned! { algorithm.span()=> #CRC::#name }
+            }
+            _ => {
+                return Err(Self::Error::new(
+                    algorithm.span(),
+                    format!(
+                        "unknown CRC algorithm; expected one of: {}",
+                        CRC_ALGORITHMS
+                            .iter()
+                            .map(|name| format!("\"{name}\""))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                ))
+            }
+        };
+
+        Ok(Self {
+            keyword: "`crc`",
+            kw_span,
+            function,
+            range,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum CrcArg {
+    Algorithm(MetaLit<kw::algorithm>),
+    Over(Box<MetaExpr<kw::over>>),
+}
+
+impl Parse for CrcArg {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::algorithm) {
+            input.parse().map(Self::Algorithm)
+        } else if lookahead.peek(kw::over) {
+            input.parse().map(|arg| Self::Over(Box::new(arg)))
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens, TokenStreamExt};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) enum Endian {
     Big,
     Little,
@@ -23,6 +23,23 @@ impl Endian {
     }
 }
 
+impl TryFrom<attrs::TagEndian> for Endian {
+    type Error = syn::Error;
+
+    fn try_from(tag_endian: attrs::TagEndian) -> Result<Self, Self::Error> {
+        if tag_endian.value == "big" {
+            Ok(Self::Big)
+        } else if tag_endian.value == "little" {
+            Ok(Self::Little)
+        } else {
+            Err(syn::Error::new(
+                tag_endian.value.span(),
+                "expected `big` or `little`",
+            ))
+        }
+    }
+}
+
 impl ToTokens for Endian {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
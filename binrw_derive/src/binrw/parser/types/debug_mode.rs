@@ -0,0 +1,21 @@
+use crate::binrw::parser::attrs;
+
+/// What the `dbg` directive prints about a field.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct DebugMode {
+    /// Whether the end offset of the field is printed along with its start
+    /// offset.
+    pub(crate) range: bool,
+}
+
+impl TryFrom<attrs::Debug> for DebugMode {
+    type Error = syn::Error;
+
+    fn try_from(debug: attrs::Debug) -> Result<Self, Self::Error> {
+        match debug.value {
+            None => Ok(Self { range: false }),
+            Some(value) if value == "range" => Ok(Self { range: true }),
+            Some(value) => Err(syn::Error::new(value.span(), "expected `range`")),
+        }
+    }
+}
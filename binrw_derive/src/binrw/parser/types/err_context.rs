@@ -1,3 +1,4 @@
+use super::lit_str;
 use crate::{binrw::parser::keywords, meta_types::MetaList};
 
 #[derive(Debug, Clone)]
@@ -30,15 +31,3 @@ impl TryFrom<MetaList<keywords::err_context, syn::Expr>> for ErrContext {
         }
     }
 }
-
-fn lit_str(expr: &syn::Expr) -> Option<&syn::LitStr> {
-    if let syn::Expr::Lit(syn::ExprLit {
-        lit: syn::Lit::Str(lit_str),
-        ..
-    }) = expr
-    {
-        Some(lit_str)
-    } else {
-        None
-    }
-}
@@ -1,9 +1,13 @@
 use crate::{
-    binrw::parser::{attrs, TrySet},
+    binrw::{
+        codegen::sanitization::{COUNT_OF, READ_CONTEXT, READ_REST, WRITE_DYN},
+        parser::{attrs, TrySet},
+    },
     meta_types::KeywordToken,
 };
 use proc_macro2::TokenStream;
-use quote::ToTokens;
+use quote::{quote, ToTokens};
+use syn::{parse_quote, Expr};
 
 #[derive(Clone, Debug)]
 pub(crate) enum FieldMode {
@@ -11,7 +15,8 @@ pub(crate) enum FieldMode {
     Default,
     Calc(TokenStream),
     TryCalc(TokenStream),
-    Function(TokenStream),
+    /// A custom parser or writer, and the directive which it came from.
+    Function(TokenStream, &'static str),
 }
 
 impl Default for FieldMode {
@@ -44,16 +49,65 @@ impl From<attrs::TryCalc> for FieldMode {
     }
 }
 
+impl From<attrs::CalcCountOf> for FieldMode {
+    fn from(calc_count_of: attrs::CalcCountOf) -> Self {
+        let collection = calc_count_of.into_token_stream();
+        Self::TryCalc(quote! { #COUNT_OF((#collection).len()) })
+    }
+}
+
 impl From<attrs::ParseWith> for FieldMode {
     fn from(parse_with: attrs::ParseWith) -> Self {
-        Self::Function(parse_with.into_token_stream())
+        Self::Function(
+            with_args_param(parse_with.value, 3),
+            attrs::ParseWith::display(),
+        )
     }
 }
 
 impl From<attrs::WriteWith> for FieldMode {
     fn from(write_with: attrs::WriteWith) -> Self {
-        Self::Function(write_with.into_token_stream())
+        Self::Function(
+            with_args_param(write_with.value, 4),
+            attrs::WriteWith::display(),
+        )
+    }
+}
+
+impl From<attrs::DynWith> for FieldMode {
+    fn from(dyn_with: attrs::DynWith) -> Self {
+        Self::Function(dyn_with.into_token_stream(), attrs::DynWith::display())
+    }
+}
+
+impl From<attrs::DynWrite> for FieldMode {
+    fn from(_: attrs::DynWrite) -> Self {
+        Self::Function(WRITE_DYN.to_token_stream(), attrs::DynWrite::display())
+    }
+}
+
+impl From<attrs::Rest> for FieldMode {
+    fn from(_: attrs::Rest) -> Self {
+        Self::Function(READ_REST.to_token_stream(), attrs::Rest::display())
+    }
+}
+
+impl From<attrs::Context> for FieldMode {
+    fn from(_: attrs::Context) -> Self {
+        Self::Function(READ_CONTEXT.to_token_stream(), attrs::Context::display())
+    }
+}
+
+/// Adds a unit arguments parameter to an inline closure which omits it, so that
+/// closures for fields without arguments can use the same calling convention
+/// as other custom parsers and writers.
+fn with_args_param(mut expr: Expr, arity: usize) -> TokenStream {
+    if let Expr::Closure(closure) = &mut expr {
+        if closure.inputs.len() + 1 == arity {
+            closure.inputs.push(parse_quote!(()));
+        }
     }
+    expr.into_token_stream()
 }
 
 impl<T: Into<FieldMode> + KeywordToken> TrySet<FieldMode> for T {
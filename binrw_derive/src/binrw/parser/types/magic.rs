@@ -1,13 +1,30 @@
 use super::SpannedValue;
-use crate::{binrw::parser::attrs, meta_types::KeywordToken};
+use crate::{
+    binrw::parser::{attrs, keywords as kw},
+    meta_types::{KeywordToken, MetaLit},
+};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::Lit;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Lit, LitInt, Token,
+};
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug, PartialOrd, Ord)]
 pub(crate) enum Kind {
     Numeric(String),
     ByteStr(String),
+    /// An integer literal without a type suffix. These get their type from the
+    /// `repr` of the enclosing enum.
+    Untyped,
+}
+
+impl Kind {
+    fn is_float(&self) -> bool {
+        matches!(self, Kind::Numeric(ty) if ty.starts_with('f'))
+    }
 }
 
 impl From<&Kind> for TokenStream {
@@ -17,44 +34,210 @@ impl From<&Kind> for TokenStream {
                 let ty: TokenStream = ty.parse().unwrap();
                 quote! { #ty }
             }
+            // This is only reachable when the input already has an error, so
+            // use the same type as the compiler would
+            Kind::Untyped => quote! { i32 },
         }
     }
 }
 
 pub(crate) type Magic = Option<SpannedValue<Inner>>;
 
+/// The alternative magic numbers of a `magic` directive, separated by `|`,
+/// and an optional mask.
 #[derive(Clone, Debug)]
-pub(crate) struct Inner(Kind, TokenStream);
+pub(crate) struct Values(Vec<Lit>, Option<MetaLit<kw::mask>>);
+
+impl Parse for Values {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let values = Punctuated::<Lit, Token![|]>::parse_separated_nonempty(input)?;
+        let mask = if input.peek(Token![,]) && input.peek2(kw::mask) {
+            input.parse::<Token![,]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self(values.into_iter().collect(), mask))
+    }
+}
+
+/// A magic number, or several alternative magic numbers of the same type, and
+/// an optional mask of the bits which are compared. The first alternative is
+/// the one which is written.
+#[derive(Clone, Debug)]
+pub(crate) struct Inner(Kind, Vec<TokenStream>, Option<TokenStream>);
 
 impl Inner {
     pub(crate) fn add_ref(&self) -> TokenStream {
         match &self.0 {
             Kind::ByteStr(_) => quote! { & },
-            Kind::Numeric(_) => TokenStream::new(),
+            Kind::Numeric(_) | Kind::Untyped => TokenStream::new(),
         }
     }
 
     pub(crate) fn deref_value(&self) -> TokenStream {
+        self.deref(self.value())
+    }
+
+    /// Returns every alternative, dereferenced like [`Self::deref_value`].
+    pub(crate) fn deref_values(&self) -> impl Iterator<Item = TokenStream> + '_ {
+        self.1.iter().map(|value| self.deref(value))
+    }
+
+    fn deref(&self, value: &TokenStream) -> TokenStream {
         match self.0 {
-            Kind::ByteStr(_) => {
-                let value = &self.1;
-                quote! { *#value }
-            }
-            Kind::Numeric(_) => self.1.clone(),
+            Kind::ByteStr(_) => quote! { *#value },
+            Kind::Numeric(_) | Kind::Untyped => value.clone(),
+        }
+    }
+
+    /// Returns the mask, dereferenced like [`Self::deref_value`].
+    pub(crate) fn deref_mask(&self) -> Option<TokenStream> {
+        self.2.as_ref().map(|mask| self.deref(mask))
+    }
+
+    /// Returns `true` if reading the magic number does not depend on the
+    /// byte order.
+    pub(crate) fn is_endian_independent(&self) -> bool {
+        match &self.0 {
+            Kind::ByteStr(_) => true,
+            Kind::Numeric(ty) => ty == "u8" || ty == "i8",
+            Kind::Untyped => false,
         }
     }
 
+    pub(crate) fn is_float(&self) -> bool {
+        self.0.is_float()
+    }
+
+    pub(crate) fn has_mask(&self) -> bool {
+        self.2.is_some()
+    }
+
+    /// Returns the value of each alternative in a form which can be compared
+    /// with the alternatives of other magic numbers of the same kind.
+    pub(crate) fn value_keys(&self) -> impl Iterator<Item = String> + '_ {
+        self.1
+            .iter()
+            .map(|value| match syn::parse2::<Lit>(value.clone()) {
+                Ok(Lit::ByteStr(bytes)) => format!("{:?}", bytes.value()),
+                Ok(Lit::Byte(byte)) => byte.value().to_string(),
+                Ok(Lit::Int(int)) => int.base10_digits().to_owned(),
+                _ => value.to_string(),
+            })
+    }
+
+    /// Returns `true` if only one value is accepted when reading.
+    pub(crate) fn is_exact(&self) -> bool {
+        self.1.len() == 1 && self.2.is_none()
+    }
+
     pub(crate) fn kind(&self) -> &Kind {
         &self.0
     }
 
-    pub(crate) fn match_value(&self) -> &TokenStream {
-        &self.1
+    /// Returns a pattern which matches any of the alternatives.
+    pub(crate) fn match_value(&self) -> TokenStream {
+        let values = &self.1;
+        quote! { #(#values)|* }
+    }
+
+    /// Returns the magic number which is written.
+    pub(crate) fn value(&self) -> &TokenStream {
+        &self.1[0]
+    }
+
+    /// Returns an error if this is an integer literal without a type suffix
+    /// which did not get a type from an enum `repr`.
+    pub(crate) fn check_typed(&self) -> syn::Result<()> {
+        if self.0 == Kind::Untyped {
+            let value = self.value();
+            Err(syn::Error::new(
+                value.span(),
+                format!("expected explicit type suffix for integer literal\ne.g {value}u64"),
+            ))
+        } else {
+            Ok(())
+        }
     }
 
     #[cfg(feature = "verbose-backtrace")]
     pub(crate) fn into_match_value(self) -> TokenStream {
-        self.1
+        self.match_value()
+    }
+}
+
+impl SpannedValue<Inner> {
+    /// Converts an integer magic number to the `repr` type of its enum.
+    ///
+    /// Returns an error if the magic number is not an integer, has a type
+    /// suffix which is different from `repr`, or is out of range for `repr`.
+    pub(crate) fn with_repr(&self, repr: &TokenStream) -> syn::Result<Self> {
+        let ty = repr.to_string();
+        let max = match ty.as_str() {
+            "u8" => u8::MAX.into(),
+            "u16" => u16::MAX.into(),
+            "u32" => u32::MAX.into(),
+            "u64" => u64::MAX.into(),
+            "u128" => u128::MAX,
+            "i8" => i8::MAX.unsigned_abs().into(),
+            "i16" => i16::MAX.unsigned_abs().into(),
+            "i32" => i32::MAX.unsigned_abs().into(),
+            "i64" => i64::MAX.unsigned_abs().into(),
+            "i128" => i128::MAX.unsigned_abs(),
+            _ => {
+                return Err(syn::Error::new(
+                    repr.span(),
+                    "`repr` must be a primitive integer type when used with `magic`",
+                ))
+            }
+        };
+
+        let values = self
+            .1
+            .iter()
+            .map(|value| repr_value(value, &ty, max))
+            .collect::<syn::Result<_>>()?;
+        let mask = self
+            .2
+            .as_ref()
+            .map(|mask| repr_value(mask, &ty, max))
+            .transpose()?;
+        Ok(Self::new(
+            Inner(Kind::Numeric(ty), values, mask),
+            self.span(),
+        ))
+    }
+}
+
+/// Converts one integer magic number to the `repr` type `ty`, whose maximum
+/// value is `max`.
+fn repr_value(value: &TokenStream, ty: &str, max: u128) -> syn::Result<TokenStream> {
+    let lit = syn::parse2::<Lit>(value.clone())?;
+    let (value, suffix) = match &lit {
+        Lit::Int(int) => (int.base10_parse::<u128>()?, int.suffix()),
+        Lit::Byte(byte) => (byte.value().into(), "u8"),
+        _ => {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("expected integer magic number for `repr = {ty}`"),
+            ))
+        }
+    };
+
+    if !suffix.is_empty() && suffix != ty {
+        Err(syn::Error::new(
+            lit.span(),
+            format!("magic number type `{suffix}` does not match `repr = {ty}`"),
+        ))
+    } else if value > max {
+        Err(syn::Error::new(
+            lit.span(),
+            format!("magic number `{value}` is out of range for `repr = {ty}`"),
+        ))
+    } else {
+        let value = LitInt::new(&format!("{value}{ty}"), lit.span());
+        Ok(value.to_token_stream())
     }
 }
 
@@ -62,42 +245,86 @@ impl TryFrom<attrs::Magic> for SpannedValue<Inner> {
     type Error = syn::Error;
 
     fn try_from(magic: attrs::Magic) -> Result<Self, Self::Error> {
-        let value = &magic.value;
+        let mut values = magic.value.0.iter();
+        let first = values.next().expect("`magic` parses at least one value");
+        let kind = lit_kind(first)?;
+        for value in values {
+            if lit_kind(value)? != kind {
+                return Err(syn::Error::new(
+                    value.span(),
+                    "every alternative of `magic` must have the same type",
+                ));
+            }
+        }
 
-        let kind = match &value {
-            Lit::ByteStr(bytes) => Kind::ByteStr(format!("[u8; {}]", bytes.value().len())),
-            Lit::Byte(_) => Kind::Numeric("u8".to_owned()),
-            Lit::Int(i) => {
-                if i.suffix().is_empty() {
+        let mask = magic
+            .value
+            .1
+            .as_ref()
+            .map(|mask| {
+                if kind.is_float() {
                     return Err(syn::Error::new(
-                        value.span(),
-                        format!("expected explicit type suffix for integer literal\ne.g {i}u64",),
+                        mask.ident.span(),
+                        "`mask` requires an integer or byte string magic number",
                     ));
                 }
-                Kind::Numeric(i.suffix().to_owned())
-            }
-            Lit::Float(f) => {
-                if f.suffix().is_empty() {
-                    return Err(syn::Error::new(
-                        value.span(),
-                        format!(
-                            "expected explicit type suffix for float literal\nvalid values are {f}f32 or {f}f64",
-                        ),
-                    ));
+
+                // An integer mask without a type suffix gets the type of the
+                // magic number
+                let mask_kind = lit_kind(&mask.value)?;
+                if mask_kind == kind
+                    || (mask_kind == Kind::Untyped && !matches!(kind, Kind::ByteStr(_)))
+                {
+                    Ok(mask.value.to_token_stream())
+                } else {
+                    Err(syn::Error::new(
+                        mask.value.span(),
+                        "`mask` must have the same type as the magic number",
+                    ))
                 }
-                Kind::Numeric(f.suffix().to_owned())
-            }
-            Lit::Char(_) | Lit::Str(_) | Lit::Bool(_) | Lit::Verbatim(_) => {
-                return Err(syn::Error::new(
-                    value.span(),
-                    "expected byte string, byte, float, or int",
-                ))
-            }
-        };
+            })
+            .transpose()?;
 
         Ok(Self::new(
-            Inner(kind, value.to_token_stream()),
+            Inner(
+                kind,
+                magic
+                    .value
+                    .0
+                    .iter()
+                    .map(ToTokens::to_token_stream)
+                    .collect(),
+                mask,
+            ),
             magic.keyword_span(),
         ))
     }
 }
+
+fn lit_kind(value: &Lit) -> syn::Result<Kind> {
+    Ok(match value {
+        Lit::ByteStr(bytes) => Kind::ByteStr(format!("[u8; {}]", bytes.value().len())),
+        Lit::Byte(_) => Kind::Numeric("u8".to_owned()),
+        // An unsuffixed integer may still get its type from an enum
+        // `repr`, so it is checked after the whole input is parsed
+        Lit::Int(i) if i.suffix().is_empty() => Kind::Untyped,
+        Lit::Int(i) => Kind::Numeric(i.suffix().to_owned()),
+        Lit::Float(f) => {
+            if f.suffix().is_empty() {
+                return Err(syn::Error::new(
+                        value.span(),
+                        format!(
+                            "expected explicit type suffix for float literal\nvalid values are {f}f32 or {f}f64",
+                        ),
+                    ));
+            }
+            Kind::Numeric(f.suffix().to_owned())
+        }
+        Lit::Char(_) | Lit::Str(_) | Lit::Bool(_) | Lit::Verbatim(_) => {
+            return Err(syn::Error::new(
+                value.span(),
+                "expected byte string, byte, float, or int",
+            ))
+        }
+    })
+}
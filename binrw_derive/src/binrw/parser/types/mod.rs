@@ -1,26 +1,36 @@
 mod assert;
+mod checksum;
 mod cond_endian;
 mod condition;
+mod debug_mode;
 mod enum_error_mode;
 mod err_context;
 mod field_mode;
 mod imports;
 mod magic;
 mod map;
+mod offset_base;
 mod passed_args;
+mod prepare;
 mod spanned_value;
+mod trailing;
 
 pub(crate) use assert::{Assert, Error as AssertionError};
-pub(crate) use cond_endian::CondEndian;
+pub(crate) use checksum::{Checksum, CrcArg};
+pub(crate) use cond_endian::{CondEndian, Endian};
 pub(crate) use condition::Condition;
+pub(crate) use debug_mode::DebugMode;
 pub(crate) use enum_error_mode::EnumErrorMode;
 pub(crate) use err_context::ErrContext;
 pub(crate) use field_mode::FieldMode;
 pub(crate) use imports::Imports;
-pub(crate) use magic::Magic;
+pub(crate) use magic::{Magic, Values as MagicValues};
 pub(crate) use map::Map;
+pub(crate) use offset_base::OffsetBase;
 pub(crate) use passed_args::PassedArgs;
+pub(crate) use prepare::Prepare;
 pub(crate) use spanned_value::SpannedValue;
+pub(crate) use trailing::Trailing;
 
 fn assert_all_args_consumed<Iter, IterItem>(
     args: Iter,
@@ -52,3 +62,16 @@ where
         Err(syn::Error::new(span, "too many arguments"))
     })
 }
+
+/// Returns the string literal in `expr`, looking through the invisible groups
+/// which wrap fragments passed through a `macro_rules!` macro.
+fn lit_str(expr: &syn::Expr) -> Option<&syn::LitStr> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit_str),
+            ..
+        }) => Some(lit_str),
+        syn::Expr::Group(group) => lit_str(&group.expr),
+        _ => None,
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{
+    binrw::parser::{attrs, SpannedValue},
+    meta_types::KeywordToken,
+};
+
+/// The position which the `offset` argument of a field is relative to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum OffsetBase {
+    /// The start of the stream.
+    Absolute,
+    /// The start of the parent struct or enum.
+    Parent,
+    /// The position of the field.
+    Current,
+}
+
+impl TryFrom<attrs::OffsetBase> for SpannedValue<OffsetBase> {
+    type Error = syn::Error;
+
+    fn try_from(offset_base: attrs::OffsetBase) -> Result<Self, Self::Error> {
+        let base = if offset_base.value == "absolute" {
+            OffsetBase::Absolute
+        } else if offset_base.value == "parent" {
+            OffsetBase::Parent
+        } else if offset_base.value == "current" {
+            OffsetBase::Current
+        } else {
+            return Err(syn::Error::new(
+                offset_base.value.span(),
+                "expected `absolute`, `parent`, or `current`",
+            ));
+        };
+        Ok(SpannedValue::new(base, offset_base.keyword_span()))
+    }
+}
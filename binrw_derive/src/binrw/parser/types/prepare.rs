@@ -0,0 +1,42 @@
+use crate::binrw::parser::attrs;
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{spanned::Spanned, Expr, Ident};
+
+/// An expression which runs before a struct or variant is written.
+#[derive(Clone, Debug)]
+pub(crate) struct Prepare {
+    /// The name which the result of the expression is bound to, if any.
+    pub(crate) ident: Option<Ident>,
+    pub(crate) expr: TokenStream,
+}
+
+impl TryFrom<attrs::Prepare> for Prepare {
+    type Error = syn::Error;
+
+    fn try_from(prepare: attrs::Prepare) -> Result<Self, Self::Error> {
+        if let Expr::Assign(assign) = prepare.value {
+            match &*assign.left {
+                Expr::Path(path) if path.attrs.is_empty() && path.qself.is_none() => {
+                    if let Some(ident) = path.path.get_ident() {
+                        return Ok(Self {
+                            ident: Some(ident.clone()),
+                            expr: assign.right.into_token_stream(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+
+            Err(syn::Error::new(
+                assign.left.span(),
+                "expected a name for the result of `prepare`",
+            ))
+        } else {
+            Ok(Self {
+                ident: None,
+                expr: prepare.value.into_token_stream(),
+            })
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{
+    binrw::parser::{attrs, SpannedValue},
+    meta_types::KeywordToken,
+};
+use syn::{spanned::Spanned, Expr, Ident};
+
+/// What to do with the bytes at the end of a `size` region which were not read
+/// by any field.
+#[derive(Clone, Debug)]
+pub(crate) enum Trailing {
+    /// Unread bytes are skipped.
+    Skip,
+    /// Unread bytes are an error.
+    Error,
+    /// Unread bytes are stored in the named field.
+    Capture(Ident),
+}
+
+impl TryFrom<attrs::Trailing> for SpannedValue<Trailing> {
+    type Error = syn::Error;
+
+    fn try_from(trailing: attrs::Trailing) -> Result<Self, Self::Error> {
+        let policy = match &trailing.value {
+            Expr::Path(path) if path.path.is_ident("skip") => Some(Trailing::Skip),
+            Expr::Path(path) if path.path.is_ident("error") => Some(Trailing::Error),
+            Expr::Call(call) => match (&*call.func, call.args.len()) {
+                (Expr::Path(func), 1) if func.path.is_ident("capture") => match &call.args[0] {
+                    Expr::Path(field) => field.path.get_ident().cloned().map(Trailing::Capture),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        };
+
+        policy
+            .map(|policy| SpannedValue::new(policy, trailing.keyword_span()))
+            .ok_or_else(|| {
+                syn::Error::new(
+                    trailing.value.span(),
+                    "expected `skip`, `error`, or `capture(field)`",
+                )
+            })
+    }
+}
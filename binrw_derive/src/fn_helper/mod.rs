@@ -4,6 +4,7 @@ use crate::{
     util::{from_crate, ident_str},
 };
 use proc_macro::TokenStream;
+use proc_macro2::Span;
 use quote::{quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
@@ -36,7 +37,11 @@ pub(crate) fn derive_from_attribute<const WRITE: bool>(
 }
 
 fn generate<const WRITE: bool>(
-    Options { stream, endian }: Options<WRITE>,
+    Options {
+        stream,
+        endian,
+        args_raw,
+    }: Options<WRITE>,
     mut func: ItemFn,
 ) -> PartialResult<ItemFn, Error> {
     // Since these functions are written to match the binrw API, args must be
@@ -44,7 +49,12 @@ fn generate<const WRITE: bool>(
     func.attrs
         .push(parse_quote!(#[allow(clippy::needless_pass_by_value)]));
 
-    let raw_args_span = func.sig.variadic.take().map(|variadic| variadic.span());
+    let raw_args_span = func
+        .sig
+        .variadic
+        .take()
+        .map(|variadic| variadic.span())
+        .or(args_raw);
 
     func.sig.generics.params.push({
         let stream_trait = if WRITE { WRITE_TRAIT } else { READ_TRAIT };
@@ -126,6 +136,7 @@ ident_str! {
 struct Options<const WRITE: bool> {
     stream: Pat,
     endian: Pat,
+    args_raw: Option<Span>,
 }
 
 impl<const WRITE: bool> Parse for Options<WRITE> {
@@ -148,6 +159,7 @@ impl<const WRITE: bool> Parse for Options<WRITE> {
 
         let mut stream = None;
         let mut endian = None;
+        let mut args_raw = None;
 
         let mut all_errors = None;
 
@@ -160,6 +172,7 @@ impl<const WRITE: bool> Parse for Options<WRITE> {
                     &mut all_errors,
                 ),
                 Arg::Endian(ident) => try_set("endian", ident, &mut endian, &mut all_errors),
+                Arg::ArgsRaw(ident) => try_set("args_raw", ident, &mut args_raw, &mut all_errors),
             }
         }
 
@@ -169,6 +182,7 @@ impl<const WRITE: bool> Parse for Options<WRITE> {
             Ok(Self {
                 stream: stream.map_or_else(|| parse_quote!(_), |ident| parse_quote!(#ident)),
                 endian: endian.map_or_else(|| parse_quote!(_), |ident| parse_quote!(#ident)),
+                args_raw: args_raw.map(|ident| ident.span()),
             })
         }
     }
@@ -177,6 +191,7 @@ impl<const WRITE: bool> Parse for Options<WRITE> {
 enum Arg<const WRITE: bool> {
     Stream(Ident),
     Endian(Ident),
+    ArgsRaw(Ident),
 }
 
 impl<const WRITE: bool> Parse for Arg<WRITE> {
@@ -202,6 +217,8 @@ impl<const WRITE: bool> Parse for Arg<WRITE> {
         } else if kw.peek(kw::endian) {
             let kw = input.parse::<Ident>()?;
             Ok(Arg::Endian(maybe_ident(kw, input)?))
+        } else if kw.peek(kw::args_raw) {
+            Ok(Arg::ArgsRaw(input.parse()?))
         } else {
             Err(kw.error())
         }
@@ -209,6 +226,7 @@ impl<const WRITE: bool> Parse for Arg<WRITE> {
 }
 
 mod kw {
+    syn::custom_keyword!(args_raw);
     syn::custom_keyword!(endian);
     syn::custom_keyword!(reader);
     syn::custom_keyword!(value);
@@ -258,11 +276,11 @@ mod tests {
         [reader = invalid] ()
     );
 
-    try_error!(read fn_helper_invalid_reader: "expected `reader` or `endian`"
+    try_error!(read fn_helper_invalid_reader: "expected one of: `reader`, `endian`, `args_raw`"
         [invalid] ()
     );
 
-    try_error!(write fn_helper_invalid_writer: "expected `writer` or `endian`"
+    try_error!(write fn_helper_invalid_writer: "expected one of: `writer`, `endian`, `args_raw`"
         [invalid] ()
     );
 
@@ -301,4 +319,12 @@ mod tests {
     try_error!(write fn_helper_missing_args_writer: "missing raw arguments"
         [] (obj: &(), ...)
     );
+
+    try_error!(read fn_helper_conflicting_args_raw: "conflicting `args_raw`"
+        [args_raw, args_raw] (args: ())
+    );
+
+    try_error!(read fn_helper_extra_args_raw: "unexpected extra parameter"
+        [args_raw] (arg0: (), arg1: ())
+    );
 }
@@ -6,7 +6,7 @@ use syn::{
     punctuated::Punctuated,
     spanned::Spanned,
     token::{self, Token},
-    Expr, Ident, Lit, Token, Type,
+    Expr, Ident, Lit, LitStr, Token, Type,
 };
 
 pub(crate) trait KeywordToken {
@@ -46,6 +46,14 @@ pub(crate) type MetaType<Keyword> = MetaValue<Keyword, Type>;
 /// both are always allowed
 pub(crate) type MetaIdent<Keyword> = MetaValue<Keyword, Ident>;
 
+/// `MetaLabel` represents a key/label pair, where the label is an identifier
+/// which may also be written as a string literal
+/// Takes two forms:
+/// * ident(label)
+/// * ident = label
+/// both are always allowed
+pub(crate) type MetaLabel<Keyword> = MetaValue<Keyword, Label>;
+
 /// `MetaLit` represents a key/lit pair
 /// Takes two forms:
 /// * ident(lit)
@@ -95,6 +103,12 @@ impl<Keyword> From<MetaValue<Keyword, Ident>> for Ident {
     }
 }
 
+impl<Keyword> From<MetaValue<Keyword, Label>> for Ident {
+    fn from(value: MetaValue<Keyword, Label>) -> Self {
+        value.value.0
+    }
+}
+
 impl<Keyword, Value: ToTokens> ToTokens for MetaValue<Keyword, Value> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         self.value.to_tokens(tokens);
@@ -109,6 +123,29 @@ impl<Keyword: Token + Spanned, Value> KeywordToken for MetaValue<Keyword, Value>
     }
 }
 
+/// An identifier written either bare (`toc`) or as a string literal (`"toc"`).
+#[derive(Debug, Clone)]
+pub(crate) struct Label(Ident);
+
+impl Parse for Label {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            let label = input.parse::<LitStr>()?;
+            label.parse().map(Self).map_err(|_| {
+                syn::Error::new(label.span(), "expected a label which is a valid identifier")
+            })
+        } else {
+            input.parse().map(Self)
+        }
+    }
+}
+
+impl ToTokens for Label {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.0.to_tokens(tokens);
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct MetaVoid<Keyword> {
     pub(crate) ident: Keyword,
@@ -126,6 +163,39 @@ impl<Keyword> From<MetaVoid<Keyword>> for () {
     fn from(_: MetaVoid<Keyword>) -> Self {}
 }
 
+/// `MetaOptionalIdent` represents a keyword which may be followed by an ident
+/// Takes two forms:
+/// * ident
+/// * ident(ident)
+#[derive(Debug, Clone)]
+pub(crate) struct MetaOptionalIdent<Keyword> {
+    pub(crate) ident: Keyword,
+    pub(crate) value: Option<Ident>,
+}
+
+impl<Keyword: Parse> Parse for MetaOptionalIdent<Keyword> {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        let value = if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Some(content.parse()?)
+        } else {
+            None
+        };
+
+        Ok(MetaOptionalIdent { ident, value })
+    }
+}
+
+impl<Keyword: Token + Spanned> KeywordToken for MetaOptionalIdent<Keyword> {
+    type Token = Keyword;
+
+    fn keyword_span(&self) -> Span {
+        self.ident.span()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct MetaList<Keyword, ItemType> {
     pub(crate) ident: Keyword,
@@ -34,7 +34,6 @@ impl<'a> Builder<'a> {
         };
         let vis = self.vis;
         let user_generic_args = self.user_generic_args();
-        let fields = self.generate_result_fields();
         let builder_fields = self.generate_builder_fields();
         let initial = self.generate_builder_initial();
         let generics = self.generate_generics();
@@ -51,33 +50,7 @@ impl<'a> Builder<'a> {
         let optional_finalizers = self.optional_finalizers();
         let generics = quote! { #( #generics ),* };
 
-        let res_struct = if define_result {
-            let docs = self.owner_name.map(|owner_name| {
-                let (impl_name, impl_fn) = if self.is_write {
-                    ("BinWrite", "write_options")
-                } else {
-                    ("BinRead", "read_options")
-                };
-                format!(
-                    "Named arguments for the [`{impl_name}::{impl_fn}`](::binrw::{impl_name}::{impl_fn}) implementation of [`{owner_name}`].",
-                )
-            });
-
-            let derives = if self.are_all_fields_optional() {
-                quote!(#[derive(Clone, Default)])
-            } else {
-                quote!(#[derive(Clone)])
-            };
-            Some(quote!(
-                #derives
-                #[doc = #docs]
-                #vis struct #name < #user_bounds > {
-                    #fields
-                }
-            ))
-        } else {
-            None
-        };
+        let res_struct = define_result.then(|| self.generate_result_struct());
 
         let builder_docs = format!(
             "A builder for [`{name}`] objects. Compatible with [`binrw::args!`](::binrw::args)."
@@ -138,6 +111,49 @@ impl<'a> Builder<'a> {
         )
     }
 
+    fn generate_result_struct(&self) -> TokenStream {
+        let name = self.result_name;
+        let user_bounds = {
+            let generics = self.generics;
+            quote! { #( #generics, )* }
+        };
+        let user_generic_args = self.user_generic_args();
+        let vis = self.vis;
+        let fields = self.generate_result_fields();
+        let docs = self.owner_name.map(|owner_name| {
+            let (impl_name, impl_fn) = if self.is_write {
+                ("BinWrite", "write_options")
+            } else {
+                ("BinRead", "read_options")
+            };
+            format!(
+                "Named arguments for the [`{impl_name}::{impl_fn}`](::binrw::{impl_name}::{impl_fn}) implementation of [`{owner_name}`].",
+            )
+        });
+
+        // `Default` is implemented using the builder instead of derived so
+        // that the default values of the fields are used
+        let default_impl = self.are_all_fields_optional().then(|| {
+            quote!(
+                impl< #user_bounds > ::core::default::Default for #name < #user_generic_args > {
+                    fn default() -> Self {
+                        Self::builder().finalize()
+                    }
+                }
+            )
+        });
+
+        quote!(
+            #[derive(Clone)]
+            #[doc = #docs]
+            #vis struct #name < #user_bounds > {
+                #fields
+            }
+
+            #default_impl
+        )
+    }
+
     fn user_generic_args(&self) -> TokenStream {
         let args = self.generics.iter().map(|generic| match generic {
             GenericParam::Type(ty) => GenericArgument::Type(Type::Path(syn::TypePath {
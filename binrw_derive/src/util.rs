@@ -66,6 +66,11 @@ impl ToSpannedTokens for IdentStr {
 /// Currently, for laziness/YAGNI reasons, repetitions fall back to
 /// `quote::quote_spanned!`, so interpolated tokens inside repetitions will
 /// not have overridden spans.
+///
+/// The span is only used for its location. Generated tokens always resolve at
+/// the call site so that they can refer to variables created by other
+/// generated code even when the span comes from tokens which were passed
+/// through a `macro_rules!` macro.
 macro_rules! quote_spanned_any {
     (@group $ts:ident $span:ident $delimiter:ident $($tt:tt)*) => {
         let mut _inner_ts = proc_macro2::TokenStream::new();
@@ -123,7 +128,7 @@ macro_rules! quote_spanned_any {
         #[allow(unused_imports)]
         use $crate::util::ToSpannedTokens;
         let mut _ts = proc_macro2::TokenStream::new();
-        let _span = $span;
+        let _span = proc_macro2::Span::call_site().located_at($span);
         $crate::util::quote_spanned_any!(@tt _ts _span $($tt)*);
         _ts
     } }